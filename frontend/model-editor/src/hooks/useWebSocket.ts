@@ -16,15 +16,27 @@ export interface ModelChangeEvent {
 }
 
 export interface ModelEventMessage {
+  /** Position in the server's event log, used to resume after reconnecting */
+  seq?: number;
   event: ModelChangeEvent;
   exclude_client?: string;
 }
 
+/** Stream control messages sent by the server alongside events */
+interface StreamControlMessage {
+  type: 'connected' | 'replayComplete' | 'resyncRequired' | string;
+  latestSeq?: number;
+  lastSeq?: number;
+  reason?: string;
+}
+
 interface UseWebSocketOptions {
   url: string;
   clientId?: string;
   modelFilter?: string;
   onEvent?: (event: ModelChangeEvent) => void;
+  /** Called when missed events cannot be replayed and state has to be reloaded */
+  onResync?: () => void;
   autoReconnect?: boolean;
   reconnectInterval?: number;
 }
@@ -43,6 +55,7 @@ export const useWebSocket = (options: UseWebSocketOptions): UseWebSocketReturn =
     clientId,
     modelFilter,
     onEvent,
+    onResync,
     autoReconnect = true,
     reconnectInterval = 5000,
   } = options;
@@ -53,6 +66,8 @@ export const useWebSocket = (options: UseWebSocketOptions): UseWebSocketReturn =
   const reconnectTimeoutRef = useRef<number | null>(null);
   const lastNotificationRef = useRef<string | null>(null);
   const notificationTimeoutRef = useRef<number | null>(null);
+  // Last event sequence number seen, sent on reconnect so the server replays missed events
+  const lastSeqRef = useRef<number | null>(null);
 
   const wsUrl = useMemo(() => {
     const socketUrl = new URL(url);
@@ -75,9 +90,13 @@ export const useWebSocket = (options: UseWebSocketOptions): UseWebSocketReturn =
     }
 
     try {
-      console.log('Connecting to WebSocket:', wsUrl);
+      const socketUrl = new URL(wsUrl);
+      if (lastSeqRef.current !== null) {
+        socketUrl.searchParams.set('last_seq', String(lastSeqRef.current));
+      }
+      console.log('Connecting to WebSocket:', socketUrl.toString());
       
-      socketRef.current = new WebSocket(wsUrl);
+      socketRef.current = new WebSocket(socketUrl.toString());
 
       socketRef.current.onopen = () => {
         console.log('WebSocket connected');
@@ -92,8 +111,18 @@ export const useWebSocket = (options: UseWebSocketOptions): UseWebSocketReturn =
 
       socketRef.current.onmessage = (event) => {
         try {
-          const message: ModelEventMessage = JSON.parse(event.data);
+          const parsed = JSON.parse(event.data);
+          if (!parsed.event) {
+            handleControlMessage(parsed as StreamControlMessage);
+            return;
+          }
+
+          const message: ModelEventMessage = parsed;
           console.log('Received WebSocket event:', message);
+          if (message.seq !== undefined) {
+            // Events relayed from other server nodes can arrive out of order
+            lastSeqRef.current = Math.max(lastSeqRef.current ?? 0, message.seq);
+          }
           
           setLastEvent(message.event);
           
@@ -162,7 +191,25 @@ export const useWebSocket = (options: UseWebSocketOptions): UseWebSocketReturn =
     } catch (error) {
       console.error('Failed to create WebSocket connection:', error);
     }
-  }, [wsUrl, onEvent, autoReconnect, reconnectInterval]);
+  }, [wsUrl, onEvent, onResync, autoReconnect, reconnectInterval]);
+
+  const handleControlMessage = (message: StreamControlMessage) => {
+    switch (message.type) {
+      case 'connected':
+        // A fresh stream starts at the current end of the log
+        if (lastSeqRef.current === null && message.latestSeq !== undefined) {
+          lastSeqRef.current = message.latestSeq;
+        }
+        break;
+      case 'resyncRequired':
+        console.warn('WebSocket event stream needs a resync:', message.reason);
+        lastSeqRef.current = message.latestSeq ?? lastSeqRef.current;
+        onResync?.();
+        break;
+      default:
+        break;
+    }
+  };
 
   const disconnect = useCallback(() => {
     if (reconnectTimeoutRef.current) {
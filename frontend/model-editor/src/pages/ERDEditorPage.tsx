@@ -6,6 +6,7 @@ import { notifications } from '@mantine/notifications';
 import { VisualERDEditor } from '../components/VisualERDEditor';
 import { GET_MODEL, GET_ENTITIES } from '../graphql/queries';
 import { CREATE_ENTITY, UPDATE_ENTITY, CREATE_RELATIONSHIP, UPDATE_RELATIONSHIP } from '../graphql/mutations';
+import { toFieldTypeNotation } from '../utils/fieldDefinition';
 
 interface RouteParams extends Record<string, string | undefined> {
   id: string;
@@ -120,7 +121,7 @@ export const ERDEditorPage: React.FC = () => {
               id: field.id,
               name: field.name,
               displayName: field.displayName,
-              fieldType: field.fieldType,
+              fieldType: toFieldTypeNotation(field, entitiesData?.entities),
               required: field.required
             }))
           }
@@ -159,7 +160,7 @@ export const ERDEditorPage: React.FC = () => {
             fields: entityData.fields.map(field => ({
               name: field.name,
               displayName: field.displayName,
-              fieldType: field.fieldType,
+              fieldType: toFieldTypeNotation(field, entitiesData?.entities),
               required: field.required
             }))
           }
@@ -21,6 +21,7 @@ import { GET_MODEL } from '../graphql/queries'
 import { CREATE_ENTITY, UPDATE_ENTITY } from '../graphql/mutations'
 import { EntityFieldsEditor } from '../components/EntityEditor'
 import { Model, Field } from '../types/model'
+import { toFieldTypeNotation, toServerValidation } from '../utils/fieldDefinition'
 
 export function EntityEditorPage() {
   const { id: modelId, entityId } = useParams<{ id: string; entityId?: string }>()
@@ -129,9 +130,9 @@ export function EntityEditorPage() {
             fields: values.fields.map(field => ({
               name: field.name,
               displayName: field.displayName,
-              fieldType: field.fieldType,
+              fieldType: toFieldTypeNotation(field, model?.entities),
               required: field.required,
-              validation: field.validation || [],
+              validation: toServerValidation(field.validation),
               defaultValue: field.defaultValue,
             })),
           },
@@ -148,9 +149,9 @@ export function EntityEditorPage() {
             fields: values.fields.map(field => ({
               name: field.name,
               displayName: field.displayName,
-              fieldType: field.fieldType,
+              fieldType: toFieldTypeNotation(field, model?.entities),
               required: field.required,
-              validation: field.validation || [],
+              validation: toServerValidation(field.validation),
               defaultValue: field.defaultValue,
             })),
           },
@@ -2,6 +2,7 @@ import { useState, useEffect } from 'react'
 import { useParams, useNavigate } from 'react-router-dom'
 import { useQuery, useMutation } from '@apollo/client'
 import { generateId } from '../utils/idGenerator'
+import { toFieldTypeNotation, toServerValidation } from '../utils/fieldDefinition'
 import {
   Container,
   Paper,
@@ -125,15 +126,16 @@ export function RelationshipEditorPage() {
   })) || []
 
   // Helper function to determine the appropriate field type for a relationship
-  const getRelationshipFieldType = (): FieldType => {
+  const getRelationshipFieldType = (targetEntityId: string): string => {
     // For relationships, we use Reference type which points to the ID of the target entity
-    return FieldType.Reference
+    return `${FieldType.Reference}(${targetEntityId})`
   }
 
   // Helper function to validate if a field type is valid for a relationship
-  const isValidRelationshipField = (fieldType: FieldType): boolean => {
+  const isValidRelationshipField = (fieldType: FieldType | string): boolean => {
     // For relationships, we accept Reference, String (for ID storage), or Integer types
-    return [FieldType.Reference, FieldType.String, FieldType.Integer].includes(fieldType)
+    const baseType = String(fieldType).split(/[([<]/)[0] as FieldType
+    return [FieldType.Reference, FieldType.String, FieldType.Integer].includes(baseType)
   }
 
   // Get field options for selected entities (only show valid field types)
@@ -244,7 +246,7 @@ export function RelationshipEditorPage() {
             id: newFieldId,
             name: fromFieldSelection.newFieldName,
             displayName: fromFieldSelection.newFieldName,
-            fieldType: getRelationshipFieldType(),
+            fieldType: getRelationshipFieldType(values.toEntity),
             required: false,
             validation: [],
           }
@@ -259,9 +261,9 @@ export function RelationshipEditorPage() {
                 fields: [...(fromEntity.fields || []), newField].map(f => ({
                   name: f.name,
                   displayName: f.displayName,
-                  fieldType: f.fieldType,
+                  fieldType: toFieldTypeNotation(f, model?.entities),
                   required: f.required,
-                  validation: f.validation || [],
+                  validation: toServerValidation(f.validation),
                   defaultValue: 'defaultValue' in f ? f.defaultValue : undefined,
                   description: 'description' in f ? f.description : undefined,
                 })),
@@ -280,7 +282,7 @@ export function RelationshipEditorPage() {
             id: newFieldId,
             name: toFieldSelection.newFieldName,
             displayName: toFieldSelection.newFieldName,
-            fieldType: getRelationshipFieldType(),
+            fieldType: getRelationshipFieldType(values.fromEntity),
             required: false,
             validation: [],
           }
@@ -295,9 +297,9 @@ export function RelationshipEditorPage() {
                 fields: [...(toEntity.fields || []), newField].map(f => ({
                   name: f.name,
                   displayName: f.displayName,
-                  fieldType: f.fieldType,
+                  fieldType: toFieldTypeNotation(f, model?.entities),
                   required: f.required,
-                  validation: f.validation || [],
+                  validation: toServerValidation(f.validation),
                   defaultValue: 'defaultValue' in f ? f.defaultValue : undefined,
                   description: 'description' in f ? f.description : undefined,
                 })),
@@ -492,7 +494,7 @@ export function RelationshipEditorPage() {
                           })
                         }}
                         error={fromFieldSelection.newFieldName && !/^[a-zA-Z][a-zA-Z0-9_]*$/.test(fromFieldSelection.newFieldName) && 'Field name must start with a letter and contain only letters, numbers, and underscores'}
-                        description={`Will create a ${getRelationshipFieldType()} field`}
+                        description={`Will create a ${FieldType.Reference} field`}
                       />
                     )}
                   </Box>
@@ -570,7 +572,7 @@ export function RelationshipEditorPage() {
                           })
                         }}
                         error={toFieldSelection.newFieldName && !/^[a-zA-Z][a-zA-Z0-9_]*$/.test(toFieldSelection.newFieldName) && 'Field name must start with a letter and contain only letters, numbers, and underscores'}
-                        description={`Will create a ${getRelationshipFieldType()} field`}
+                        description={`Will create a ${FieldType.Reference} field`}
                       />
                     )}
                   </Box>
@@ -0,0 +1,89 @@
+import { FieldType } from '../types/model'
+
+interface FieldLike {
+  fieldType: FieldType | string
+  uiConfig?: Record<string, any>
+}
+
+interface EntityRef {
+  id: string
+  name: string
+}
+
+/**
+ * Build the field type notation the server parses, e.g. `String(100)`, `Enum["a","b"]`,
+ * `Reference(<entity id>)`. Types already in server notation are passed through.
+ */
+export function toFieldTypeNotation(field: FieldLike, entities: EntityRef[] = []): string {
+  const fieldType = String(field.fieldType)
+  if (/[([<]/.test(fieldType)) {
+    return fieldType
+  }
+
+  const uiConfig = field.uiConfig || {}
+  switch (fieldType) {
+    case FieldType.String:
+      return uiConfig.maxLength ? `String(${uiConfig.maxLength})` : fieldType
+    case FieldType.Enum:
+      return `Enum${JSON.stringify(uiConfig.enumValues || [])}`
+    case FieldType.Reference: {
+      const target = entities.find(e => e.id === uiConfig.referenceEntity || e.name === uiConfig.referenceEntity)
+      return target ? `Reference(${target.id})` : fieldType
+    }
+    case FieldType.Array:
+      return 'Array<String>'
+    default:
+      return fieldType
+  }
+}
+
+/**
+ * Convert validation rules from the editor shape (`{ validationType: { type, value } }`) to the
+ * shape the server stores. Rules loaded from the server are passed through.
+ */
+export function toServerValidation(rules: any): any[] {
+  if (!Array.isArray(rules)) {
+    return []
+  }
+
+  return rules.map(rule => {
+    const editorType = rule?.validationType?.type
+    if (!editorType) {
+      return rule
+    }
+
+    const { value, pattern, expression, min, max } = rule.validationType
+    const convert = (): any => {
+      switch (editorType) {
+        case 'required':
+          return 'Required'
+        case 'minLength':
+          return { MinLength: value ?? 0 }
+        case 'maxLength':
+          return { MaxLength: value ?? 0 }
+        case 'pattern':
+          return { Pattern: pattern ?? '' }
+        case 'min':
+        case 'minDate':
+          return { Range: { min: value ?? null, max: null } }
+        case 'max':
+        case 'maxDate':
+          return { Range: { min: null, max: value ?? null } }
+        case 'range':
+        case 'dateRange':
+          return { Range: { min: min ?? null, max: max ?? null } }
+        case 'custom':
+          return { Custom: expression ?? '' }
+        default:
+          // Formats such as `email` or `url` are checked by named custom validators
+          return { Custom: editorType }
+      }
+    }
+
+    return {
+      validationType: convert(),
+      message: rule.message || '',
+      severity: rule.severity || 'Error',
+    }
+  })
+}
@@ -35,6 +35,7 @@ import {
   IconFileImport
 } from '@tabler/icons-react'
 import { useLoadEntityData, useJsonRpcMutation, useFormDefinition } from '../../hooks/useJsonRpc'
+import { useEntityDataEvents } from '../../hooks/useEntityDataEvents'
 import type { DataGridColumn, DataGridFilter, DataGridSort } from '../../types/jsonrpc'
 import { ImportWizard } from './ImportWizard'
 
@@ -97,6 +98,9 @@ export const DataGrid = memo(function DataGrid({
     apiBaseUrl
   )
 
+  // Reload when other users create, update or delete records of this entity
+  useEntityDataEvents(modelId, entityName, () => refetch(), apiBaseUrl)
+
   const entityData = useMemo(() => data?.data || [], [data?.data])
   const pagination = data?.pagination
   
@@ -0,0 +1,85 @@
+import { useEffect, useRef } from 'react'
+
+export interface EntityDataChange {
+  model_id: string
+  entity_type: string
+  record_id: string
+  action: 'created' | 'updated' | 'deleted'
+  /** The record after the change, when small enough to send */
+  record?: Record<string, any>
+  /** Changed fields of an update whose record is too large to send */
+  diff?: { set: Record<string, any>; removed: string[] }
+  timestamp: string
+}
+
+/** Records of one entity type changed together, e.g. removed by the retention job */
+export interface EntityDataBulkChange {
+  model_id: string
+  entity_type: string
+  action: 'created' | 'updated' | 'deleted'
+  count: number
+  /** IDs of the changed records, when few enough to send; otherwise reload the data */
+  record_ids?: string[]
+  timestamp: string
+}
+
+const RECONNECT_INTERVAL = 5000
+
+/**
+ * Subscribe to created, updated and deleted records of one entity type, as
+ * broadcast by the server over its `/ws` event stream. Bulk changes arrive as
+ * one `EntityDataBulkChange`.
+ */
+export function useEntityDataEvents(
+  modelId: string,
+  entityName: string,
+  onChange: (change: EntityDataChange | EntityDataBulkChange) => void,
+  apiBaseUrl: string = 'http://localhost:8081'
+) {
+  // Keep the latest callback without reconnecting when it changes
+  const onChangeRef = useRef(onChange)
+  onChangeRef.current = onChange
+
+  useEffect(() => {
+    if (!modelId || !entityName || typeof WebSocket === 'undefined') {
+      return
+    }
+
+    const url = new URL('/ws', apiBaseUrl)
+    url.protocol = url.protocol === 'https:' ? 'wss:' : 'ws:'
+    url.searchParams.set('model_filter', modelId)
+    url.searchParams.set('entity_filter', entityName)
+
+    let socket: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | undefined
+    let closed = false
+
+    const connect = () => {
+      socket = new WebSocket(url.toString())
+      socket.onmessage = (message) => {
+        try {
+          const parsed = JSON.parse(message.data)
+          if (parsed.event?.type === 'EntityDataChanged') {
+            onChangeRef.current(parsed.event.data as EntityDataChange)
+          } else if (parsed.event?.type === 'EntityDataBulkChanged') {
+            onChangeRef.current(parsed.event.data as EntityDataBulkChange)
+          }
+        } catch (error) {
+          console.error('[useEntityDataEvents] Failed to parse event:', error)
+        }
+      }
+      socket.onclose = () => {
+        if (!closed) {
+          reconnectTimeout = setTimeout(connect, RECONNECT_INTERVAL)
+        }
+      }
+    }
+    connect()
+
+    return () => {
+      closed = true
+      clearTimeout(reconnectTimeout)
+      socket?.close()
+    }
+  }, [modelId, entityName, apiBaseUrl])
+}
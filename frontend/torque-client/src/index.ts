@@ -23,4 +23,6 @@ export {
   useModelMetadata,
   useCapabilities,
   useJsonRpcMutation
-} from './hooks/useJsonRpc'
\ No newline at end of file
+} from './hooks/useJsonRpc'
+export { useEntityDataEvents } from './hooks/useEntityDataEvents'
+export type { EntityDataChange, EntityDataBulkChange } from './hooks/useEntityDataEvents'
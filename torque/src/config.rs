@@ -12,6 +12,14 @@ pub struct Config {
     pub jsonrpc: JsonRpcConfig,
     pub mcp: McpConfig,
     pub frontend: FrontendConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub retention: RetentionJobConfig,
+    #[serde(default)]
+    pub rest: RestApiConfig,
+    #[serde(default)]
+    pub events: EventLogConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +71,13 @@ pub struct GraphQLConfig {
     pub enable_introspection: bool,
     pub max_query_complexity: usize,
     pub max_query_depth: usize,
+    /// Automatic Persisted Queries kept by hash; 0 disables persisted queries
+    #[serde(default = "default_persisted_query_cache_size")]
+    pub persisted_query_cache_size: usize,
+}
+
+fn default_persisted_query_cache_size() -> usize {
+    1000
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +102,122 @@ pub struct FrontendConfig {
     pub enable_pwa: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// Blob store backend: "local" or "s3"
+    pub backend: String,
+    pub local_path: PathBuf,
+    pub max_upload_size: usize,
+    /// Allowed MIME types; entries ending in "/*" match a whole family. Empty allows all.
+    pub allowed_content_types: Vec<String>,
+    /// Minutes an upload may stay unreferenced by any record before it is removed; 0 keeps them
+    #[serde(default = "default_unreferenced_blob_minutes")]
+    pub unreferenced_blob_minutes: u64,
+    pub s3: Option<S3StorageConfig>,
+}
+
+fn default_unreferenced_blob_minutes() -> u64 {
+    60
+}
+
+/// S3-compatible object store; requests are signed with AWS Signature Version 4
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3StorageConfig {
+    pub endpoint: String,
+    pub bucket: String,
+    /// Region requests are signed for, e.g. "us-east-1"
+    pub region: String,
+    /// Falls back to the AWS_ACCESS_KEY_ID environment variable
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    /// Falls back to the AWS_SECRET_ACCESS_KEY environment variable
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// Token for temporary credentials; falls back to the AWS_SESSION_TOKEN environment variable
+    #[serde(default)]
+    pub session_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionJobConfig {
+    pub enabled: bool,
+    pub interval_seconds: u64,
+    /// Maximum rows processed per entity type in a single run
+    pub batch_size: u64,
+    /// Days archived rows are kept before they and their blobs are deleted; 0 keeps them
+    #[serde(default)]
+    pub archive_retention_days: u32,
+}
+
+/// Generated REST API under `/api/models/:model/:entity`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestApiConfig {
+    pub enabled: bool,
+    pub default_page_size: u64,
+    pub max_page_size: u64,
+}
+
+/// Event log backing resumable WebSocket event streams
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLogConfig {
+    /// Number of most recent events kept for replay
+    pub retained_events: u64,
+    /// Maximum events replayed to a reconnecting client before it is told to resync
+    pub max_replay: u64,
+    /// Largest serialized app data record sent with a change event; larger updates send a diff
+    pub max_record_payload_bytes: usize,
+}
+
+impl Default for EventLogConfig {
+    fn default() -> Self {
+        Self {
+            retained_events: 10000,
+            max_replay: 1000,
+            max_record_payload_bytes: 16384,
+        }
+    }
+}
+
+impl Default for RestApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_page_size: 50,
+            max_page_size: 500,
+        }
+    }
+}
+
+impl Default for RetentionJobConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_seconds: 3600,
+            batch_size: 1000,
+            archive_retention_days: 0,
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            backend: "local".to_string(),
+            local_path: PathBuf::from("data/blobs"),
+            max_upload_size: 10 * 1024 * 1024,
+            allowed_content_types: vec![
+                "image/*".to_string(),
+                "application/pdf".to_string(),
+                "text/plain".to_string(),
+                "text/csv".to_string(),
+                "application/octet-stream".to_string(),
+            ],
+            unreferenced_blob_minutes: default_unreferenced_blob_minutes(),
+            s3: None,
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -128,6 +259,7 @@ impl Default for Config {
                 enable_introspection: true,
                 max_query_complexity: 1000,
                 max_query_depth: 20,
+                persisted_query_cache_size: default_persisted_query_cache_size(),
             },
             jsonrpc: JsonRpcConfig {
                 enable_batch_requests: true,
@@ -145,6 +277,10 @@ impl Default for Config {
                 bundle_optimization: true,
                 enable_pwa: true,
             },
+            storage: StorageConfig::default(),
+            retention: RetentionJobConfig::default(),
+            rest: RestApiConfig::default(),
+            events: EventLogConfig::default(),
         }
     }
 }
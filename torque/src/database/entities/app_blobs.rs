@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// AppBlobs table tracking binary attachments for `FieldType::Binary` fields
+/// The bytes themselves live in the configured blob store under `storage_key`
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_blobs")]
+pub struct Model {
+    /// Unique identifier for this blob
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// UUID of the Torque Model this blob belongs to
+    #[sea_orm(indexed)]
+    pub model_id: String,
+
+    /// ID of the owning app entity instance, if attached
+    #[sea_orm(indexed)]
+    pub entity_id: Option<String>,
+
+    /// Name of the Binary field on the owning entity
+    pub field_name: Option<String>,
+
+    /// Original file name supplied by the uploader
+    pub filename: String,
+
+    /// MIME type of the stored content
+    pub content_type: String,
+
+    /// Size of the stored content in bytes
+    pub size_bytes: i64,
+
+    /// Key of the object in the blob store
+    pub storage_key: String,
+
+    /// When this blob was uploaded
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// AppEntitiesArchive table holding app entity rows moved out of `app_entities`
+/// by the retention job when a model's cleanup strategy is `Archive`
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "app_entities_archive")]
+pub struct Model {
+    /// Original entity instance identifier
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// UUID of the Torque Model this entity belonged to
+    #[sea_orm(indexed)]
+    pub model_id: String,
+
+    /// Entity type name as defined in the Torque Model
+    pub entity_type: String,
+
+    /// JSON blob with the entity data at the time of archiving
+    pub data: Json,
+
+    /// When the original entity instance was created
+    pub created_at: DateTime,
+
+    /// When the original entity instance was last updated
+    pub updated_at: DateTime,
+
+    /// When the row was moved to the archive
+    pub archived_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
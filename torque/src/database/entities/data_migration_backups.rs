@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// DataMigrationBackups table holding the pre-migration state of every
+/// app entity row touched by a data migration job, used for rollback
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "data_migration_backups")]
+pub struct Model {
+    /// Data migration job that touched the row
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub job_id: String,
+
+    /// Original app entity instance identifier
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub entity_id: String,
+
+    /// UUID of the Torque Model the row belongs to
+    pub model_id: String,
+
+    /// Entity type name before the migration
+    pub entity_type: String,
+
+    /// JSON data before the migration
+    pub data: Json,
+
+    /// Original creation timestamp
+    pub created_at: DateTime,
+
+    /// Original update timestamp
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,60 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// DataMigrationJobs table tracking runs of app data migrations
+/// produced when a model's schema changes between versions
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "data_migration_jobs")]
+pub struct Model {
+    /// Job identifier
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// UUID of the Torque Model whose app data is migrated
+    #[sea_orm(indexed)]
+    pub model_id: String,
+
+    /// Model version the app data was shaped for before the job
+    pub from_version: i32,
+
+    /// Model version the app data is migrated to
+    pub to_version: i32,
+
+    /// Job status: pending, running, completed, failed or rolled_back
+    pub status: String,
+
+    /// Serialized migration plan
+    pub plan: Json,
+
+    /// Number of rows rewritten in place
+    pub rows_updated: i64,
+
+    /// Number of orphaned rows moved to `app_entities_archive`
+    pub rows_archived: i64,
+
+    /// Number of orphaned rows deleted
+    pub rows_deleted: i64,
+
+    /// Number of values that could not be coerced and were set to null
+    pub coercion_failures: i64,
+
+    /// Error message if the job failed
+    pub error: Option<String>,
+
+    /// When the job was created
+    pub created_at: DateTime,
+
+    /// When the job finished
+    pub completed_at: Option<DateTime>,
+
+    /// When the job was rolled back
+    pub rolled_back_at: Option<DateTime>,
+
+    /// Last time the node running the job reported it alive
+    pub heartbeat_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// EventLog table recording every broadcast model event under a monotonically
+/// increasing sequence number, so WebSocket clients can resume after reconnecting
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "event_log")]
+pub struct Model {
+    /// Event sequence number, assigned by the database
+    #[sea_orm(primary_key)]
+    pub seq: i64,
+
+    /// UUID of the Torque Model the event belongs to
+    #[sea_orm(indexed)]
+    pub model_id: String,
+
+    /// Serialized ModelChangeEvent
+    pub event: Json,
+
+    /// Client that caused the event and should not receive it back
+    pub exclude_client: Option<String>,
+
+    /// Node that published the event
+    pub node_id: Option<String>,
+
+    /// When the event was recorded
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
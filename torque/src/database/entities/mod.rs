@@ -1,4 +1,11 @@
 pub mod torque_models;
 pub mod app_entities;
+pub mod app_blobs;
+pub mod app_entities_archive;
+pub mod torque_model_versions;
+pub mod torque_model_pins;
+pub mod data_migration_jobs;
+pub mod data_migration_backups;
+pub mod event_log;
 
 pub use torque_models::*;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// TorqueModelPins table recording which snapshot running apps should
+/// serve for a model instead of the latest saved definition
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "torque_model_pins")]
+pub struct Model {
+    /// UUID of the pinned Torque Model
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub model_id: String,
+
+    /// Version number from `torque_model_versions` to serve
+    pub version_number: i32,
+
+    /// Who set the pin
+    pub pinned_by: String,
+
+    /// When the pin was set
+    pub pinned_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// TorqueModelVersions table holding an immutable snapshot of a model
+/// for every save, numbered sequentially per model
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "torque_model_versions")]
+pub struct Model {
+    /// Snapshot identifier
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+
+    /// UUID of the Torque Model this snapshot belongs to
+    #[sea_orm(indexed)]
+    pub model_id: String,
+
+    /// Sequential version number, starting at 1 for each model
+    pub version_number: i32,
+
+    /// Model name at the time of the snapshot
+    pub name: String,
+
+    /// Free-form `TorqueModel.version` label at the time of the snapshot
+    pub version: String,
+
+    /// Who made the change that produced this snapshot
+    pub author: String,
+
+    /// Optional description of the change
+    pub message: Option<String>,
+
+    /// Full serialized TorqueModel
+    pub model_json: Json,
+
+    /// When the snapshot was taken
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,71 +1,622 @@
-use sea_orm::{DatabaseConnection, Statement, ConnectionTrait};
-use crate::Result;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement, TransactionTrait};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::{Error, Result};
 
+/// A versioned, reversible schema migration.
+///
+/// Applied migrations are recorded in the `schema_migrations` ledger together with
+/// a checksum of the SQL that was run, so edits to an applied migration are detected.
+/// A database created before the ledger existed starts with an empty ledger, so every
+/// migration is replayed on it. The table and index steps use `IF NOT EXISTS` and leave its
+/// schema as it is; the later column additions were written after the ledger, so they only
+/// ever run on databases that don't have the column yet.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    sqlite_up: fn() -> String,
+    postgres_up: fn() -> String,
+    /// Down steps only drop objects, so the same SQL serves both backends
+    down: &'static str,
+    /// Replaces `down` on SQLite, for steps it can't express directly. Dropping a column
+    /// needs SQLite 3.35, so those tables are rebuilt instead.
+    sqlite_down: Option<&'static str>,
+}
+
+impl Migration {
+    fn up_sql(&self, backend: DatabaseBackend) -> Result<String> {
+        match backend {
+            DatabaseBackend::Sqlite => Ok((self.sqlite_up)()),
+            DatabaseBackend::Postgres => Ok((self.postgres_up)()),
+            _ => Err(Error::Configuration("Unsupported database backend".to_string())),
+        }
+    }
+
+    fn down_sql(&self, backend: DatabaseBackend) -> &'static str {
+        match (backend, self.sqlite_down) {
+            (DatabaseBackend::Sqlite, Some(sqlite_down)) => sqlite_down,
+            _ => self.down,
+        }
+    }
+
+    /// SHA-256 of the up SQL for the given backend
+    pub fn checksum(&self, backend: DatabaseBackend) -> Result<String> {
+        Ok(format!("{:x}", Sha256::digest(self.up_sql(backend)?.as_bytes())))
+    }
+}
+
+/// All migrations, in version order
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            name: "create_core_schema",
+            sqlite_up: core_schema_sqlite,
+            postgres_up: core_schema_postgres,
+            down: r#"
+            DROP TABLE IF EXISTS performance_metrics;
+            DROP TABLE IF EXISTS system_config;
+            DROP TABLE IF EXISTS xflow_executions;
+            DROP TABLE IF EXISTS xflows;
+            DROP TABLE IF EXISTS entity_relationships;
+            DROP TABLE IF EXISTS entities;
+            DROP TABLE IF EXISTS torque_applications;
+            DROP TABLE IF EXISTS torque_models;
+            "#,
+            sqlite_down: None,
+        },
+        Migration {
+            version: 2,
+            name: "create_app_entities",
+            sqlite_up: app_entities_sqlite,
+            postgres_up: app_entities_postgres,
+            down: "DROP TABLE IF EXISTS app_entities",
+            sqlite_down: None,
+        },
+        Migration {
+            version: 3,
+            name: "create_app_blobs",
+            sqlite_up: app_blobs_sqlite,
+            postgres_up: app_blobs_postgres,
+            down: "DROP TABLE IF EXISTS app_blobs",
+            sqlite_down: None,
+        },
+        Migration {
+            version: 4,
+            name: "create_app_entities_archive",
+            sqlite_up: app_entities_archive_sqlite,
+            postgres_up: app_entities_archive_postgres,
+            down: "DROP TABLE IF EXISTS app_entities_archive",
+            sqlite_down: None,
+        },
+        Migration {
+            version: 5,
+            name: "create_model_versions",
+            sqlite_up: model_versions_sqlite,
+            postgres_up: model_versions_postgres,
+            down: r#"
+            DROP TABLE IF EXISTS torque_model_pins;
+            DROP TABLE IF EXISTS torque_model_versions;
+            "#,
+            sqlite_down: None,
+        },
+        Migration {
+            version: 6,
+            name: "create_data_migration_jobs",
+            sqlite_up: data_migration_jobs_sqlite,
+            postgres_up: data_migration_jobs_postgres,
+            down: r#"
+            DROP TABLE IF EXISTS data_migration_backups;
+            DROP TABLE IF EXISTS data_migration_jobs;
+            "#,
+            sqlite_down: None,
+        },
+        Migration {
+            version: 7,
+            name: "create_model_search_index",
+            sqlite_up: create_model_search_index_sqlite,
+            postgres_up: model_search_index_postgres,
+            down: "DROP TABLE IF EXISTS model_search_index",
+            sqlite_down: None,
+        },
+        Migration {
+            version: 8,
+            name: "create_event_log",
+            sqlite_up: create_event_log_sqlite,
+            postgres_up: create_event_log_postgres,
+            down: "DROP TABLE IF EXISTS event_log",
+            sqlite_down: None,
+        },
+        Migration {
+            version: 9,
+            name: "add_data_migration_jobs_active_index",
+            sqlite_up: data_migration_jobs_active_index_sqlite,
+            postgres_up: data_migration_jobs_active_index_postgres,
+            down: "DROP INDEX IF EXISTS idx_data_migration_jobs_active",
+            sqlite_down: None,
+        },
+        Migration {
+            version: 10,
+            name: "add_event_log_node_id",
+            sqlite_up: event_log_node_id_sqlite,
+            postgres_up: event_log_node_id_postgres,
+            down: "ALTER TABLE event_log DROP COLUMN node_id",
+            sqlite_down: Some(r#"
+            CREATE TABLE event_log_rebuild (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                model_id TEXT NOT NULL,
+                event JSON NOT NULL,
+                exclude_client TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            INSERT INTO event_log_rebuild (seq, model_id, event, exclude_client, created_at)
+                SELECT seq, model_id, event, exclude_client, created_at FROM event_log;
+            DROP TABLE event_log;
+            ALTER TABLE event_log_rebuild RENAME TO event_log;
+            "#),
+        },
+        Migration {
+            version: 11,
+            name: "add_data_migration_jobs_heartbeat",
+            sqlite_up: data_migration_jobs_heartbeat_sqlite,
+            postgres_up: data_migration_jobs_heartbeat_postgres,
+            down: "ALTER TABLE data_migration_jobs DROP COLUMN heartbeat_at",
+            sqlite_down: Some(r#"
+            CREATE TABLE data_migration_jobs_rebuild (
+                id TEXT PRIMARY KEY,
+                model_id TEXT NOT NULL,
+                from_version INTEGER NOT NULL,
+                to_version INTEGER NOT NULL,
+                status VARCHAR(50) NOT NULL DEFAULT 'pending',
+                plan JSON NOT NULL,
+                rows_updated INTEGER NOT NULL DEFAULT 0,
+                rows_archived INTEGER NOT NULL DEFAULT 0,
+                rows_deleted INTEGER NOT NULL DEFAULT 0,
+                coercion_failures INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                completed_at DATETIME,
+                rolled_back_at DATETIME
+            );
+            INSERT INTO data_migration_jobs_rebuild
+                SELECT id, model_id, from_version, to_version, status, plan, rows_updated, rows_archived,
+                       rows_deleted, coercion_failures, error, created_at, completed_at, rolled_back_at
+                FROM data_migration_jobs;
+            DROP TABLE data_migration_jobs;
+            ALTER TABLE data_migration_jobs_rebuild RENAME TO data_migration_jobs;
+            CREATE INDEX idx_data_migration_jobs_model_id ON data_migration_jobs(model_id, created_at DESC);
+            CREATE UNIQUE INDEX idx_data_migration_jobs_active
+                ON data_migration_jobs(model_id) WHERE status IN ('pending', 'running');
+            "#),
+        },
+    ]
+}
+
+/// Where a migration stands against the ledger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Applied, but the SQL has changed since
+    ChecksumMismatch,
+    /// Recorded in the ledger but unknown to this build
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<String>,
+}
+
+struct LedgerEntry {
+    version: i64,
+    name: String,
+    checksum: String,
+    applied_at: String,
+}
+
+/// Apply all pending migrations. Called on every startup.
 pub async fn run_migrations(db: &DatabaseConnection) -> Result<()> {
     tracing::info!("Running database migrations");
     
-    // Check if we're using SQLite or PostgreSQL
-    let db_backend = db.get_database_backend();
+    let applied = migrate_up(db).await?;
+    
+    tracing::info!("Database migrations completed ({} applied)", applied.len());
+    Ok(())
+}
+
+/// Status of every known migration plus any unknown ledger entries, ordered by version
+pub async fn migration_status(db: &DatabaseConnection) -> Result<Vec<MigrationStatus>> {
+    let backend = db.get_database_backend();
+    let ledger = read_ledger(db).await?;
+    let migrations = migrations();
+    
+    let mut statuses = Vec::with_capacity(migrations.len());
+    for migration in &migrations {
+        let entry = ledger.iter().find(|e| e.version == migration.version);
+        let state = match entry {
+            None => MigrationState::Pending,
+            Some(entry) if entry.checksum != migration.checksum(backend)? => MigrationState::ChecksumMismatch,
+            Some(_) => MigrationState::Applied,
+        };
+        statuses.push(MigrationStatus {
+            version: migration.version,
+            name: migration.name.to_string(),
+            state,
+            applied_at: entry.map(|e| e.applied_at.clone()),
+        });
+    }
+    
+    for entry in ledger.iter().filter(|e| !migrations.iter().any(|m| m.version == e.version)) {
+        statuses.push(MigrationStatus {
+            version: entry.version,
+            name: entry.name.clone(),
+            state: MigrationState::Unknown,
+            applied_at: Some(entry.applied_at.clone()),
+        });
+    }
+    
+    statuses.sort_by_key(|s| s.version);
+    Ok(statuses)
+}
+
+/// Apply pending migrations in order, each in its own transaction.
+/// Refuses to run when an applied migration no longer matches its checksum.
+pub async fn migrate_up(db: &DatabaseConnection) -> Result<Vec<MigrationStatus>> {
+    let backend = db.get_database_backend();
+    let statuses = migration_status(db).await?;
     
-    match db_backend {
-        sea_orm::DatabaseBackend::Sqlite => {
-            run_sqlite_migrations(db).await?;
+    if let Some(modified) = statuses.iter().find(|s| s.state == MigrationState::ChecksumMismatch) {
+        return Err(Error::Configuration(format!(
+            "Migration {} ({}) was modified after it was applied (checksum mismatch)",
+            modified.version, modified.name
+        )));
+    }
+    for unknown in statuses.iter().filter(|s| s.state == MigrationState::Unknown) {
+        tracing::warn!("Database has migration {} ({}) that this build does not know about", unknown.version, unknown.name);
+    }
+    
+    let mut applied = Vec::new();
+    for migration in migrations() {
+        let pending = statuses.iter()
+            .any(|s| s.version == migration.version && s.state == MigrationState::Pending);
+        if !pending {
+            continue;
+        }
+        
+        let applied_at = chrono::Utc::now().to_rfc3339();
+        let txn = db.begin().await?;
+        for statement in split_statements(&migration.up_sql(backend)?) {
+            txn.execute(Statement::from_string(backend, statement.to_string())).await?;
         }
-        sea_orm::DatabaseBackend::Postgres => {
-            run_postgres_migrations(db).await?;
+        let insert = match backend {
+            DatabaseBackend::Postgres => "INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES ($1, $2, $3, $4)",
+            _ => "INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES (?, ?, ?, ?)",
+        };
+        txn.execute(Statement::from_sql_and_values(backend, insert, vec![
+            migration.version.into(),
+            migration.name.into(),
+            migration.checksum(backend)?.into(),
+            applied_at.clone().into(),
+        ])).await?;
+        txn.commit().await?;
+        
+        tracing::info!("Applied migration {} ({})", migration.version, migration.name);
+        applied.push(MigrationStatus {
+            version: migration.version,
+            name: migration.name.to_string(),
+            state: MigrationState::Applied,
+            applied_at: Some(applied_at),
+        });
+    }
+    
+    Ok(applied)
+}
+
+/// Roll back the most recently applied migration. Returns `None` when nothing is applied.
+pub async fn migrate_down(db: &DatabaseConnection) -> Result<Option<MigrationStatus>> {
+    let backend = db.get_database_backend();
+    let statuses = migration_status(db).await?;
+    
+    let Some(last) = statuses.iter().rev().find(|s| s.state != MigrationState::Pending) else {
+        return Ok(None);
+    };
+    match last.state {
+        MigrationState::Unknown => {
+            return Err(Error::Configuration(format!(
+                "Cannot roll back migration {} ({}): it is not known to this build",
+                last.version, last.name
+            )));
         }
-        _ => {
-            return Err(crate::Error::Configuration("Unsupported database backend".to_string()));
+        MigrationState::ChecksumMismatch => {
+            return Err(Error::Configuration(format!(
+                "Cannot roll back migration {} ({}): it was modified after it was applied",
+                last.version, last.name
+            )));
         }
+        _ => {}
     }
     
-    tracing::info!("Database migrations completed");
-    Ok(())
+    let migration = migrations().into_iter()
+        .find(|m| m.version == last.version)
+        .ok_or_else(|| Error::Internal(format!("Migration {} disappeared", last.version)))?;
+    
+    let txn = db.begin().await?;
+    for statement in split_statements(migration.down_sql(backend)) {
+        txn.execute(Statement::from_string(backend, statement.to_string())).await?;
+    }
+    let delete = match backend {
+        DatabaseBackend::Postgres => "DELETE FROM schema_migrations WHERE version = $1",
+        _ => "DELETE FROM schema_migrations WHERE version = ?",
+    };
+    txn.execute(Statement::from_sql_and_values(backend, delete, vec![migration.version.into()])).await?;
+    txn.commit().await?;
+    
+    tracing::info!("Rolled back migration {} ({})", migration.version, migration.name);
+    Ok(Some(MigrationStatus {
+        version: migration.version,
+        name: migration.name.to_string(),
+        state: MigrationState::Pending,
+        applied_at: None,
+    }))
 }
 
-async fn run_sqlite_migrations(db: &DatabaseConnection) -> Result<()> {
-    // SQLite-specific schema with performance optimizations
-    let migrations = vec![
+async fn read_ledger(db: &DatabaseConnection) -> Result<Vec<LedgerEntry>> {
+    let backend = db.get_database_backend();
+    if !matches!(backend, DatabaseBackend::Sqlite | DatabaseBackend::Postgres) {
+        return Err(Error::Configuration("Unsupported database backend".to_string()));
+    }
+    
+    db.execute(Statement::from_string(backend, create_schema_migrations())).await?;
+    
+    let rows = db.query_all(Statement::from_string(
+        backend,
+        "SELECT version, name, checksum, applied_at FROM schema_migrations ORDER BY version".to_string(),
+    )).await?;
+    
+    let mut entries = Vec::with_capacity(rows.len());
+    for row in rows {
+        entries.push(LedgerEntry {
+            version: row.try_get("", "version")?,
+            name: row.try_get("", "name")?,
+            checksum: row.try_get("", "checksum")?,
+            applied_at: row.try_get("", "applied_at")?,
+        });
+    }
+    Ok(entries)
+}
+
+/// Split a migration script into single statements. Migration SQL never contains
+/// semicolons inside literals, so a plain split is enough.
+fn split_statements(sql: &str) -> impl Iterator<Item = &str> {
+    sql.split(';').map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn create_schema_migrations() -> String {
+    // Same DDL on both backends; applied_at is stored as RFC 3339 text for that reason
+    r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version BIGINT PRIMARY KEY,
+        name VARCHAR(255) NOT NULL,
+        checksum VARCHAR(64) NOT NULL,
+        applied_at VARCHAR(64) NOT NULL
+    )
+    "#.to_string()
+}
+
+fn join_sql(parts: &[String]) -> String {
+    parts.iter().map(|p| p.trim()).collect::<Vec<_>>().join(";\n")
+}
+
+fn core_schema_sqlite() -> String {
+    join_sql(&[
         create_torque_models_sqlite(),
         create_torque_applications_sqlite(),
         create_entities_sqlite(),
         create_entity_relationships_sqlite(),
-        create_app_entities_sqlite(),
         create_xflows_sqlite(),
         create_xflow_executions_sqlite(),
         create_system_config_sqlite(),
         create_performance_metrics_sqlite(),
-        create_indexes_sqlite(),
-    ];
-    
-    for migration in migrations {
-        db.execute(Statement::from_string(db.get_database_backend(), migration)).await?;
-    }
-    
-    Ok(())
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_torque_models_name ON torque_models(name);
+        CREATE INDEX IF NOT EXISTS idx_torque_models_created_at ON torque_models(created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_torque_applications_status ON torque_applications(status);
+        CREATE INDEX IF NOT EXISTS idx_torque_applications_model_id ON torque_applications(model_id);
+        CREATE INDEX IF NOT EXISTS idx_entities_app_type ON entities(application_id, entity_type);
+        CREATE INDEX IF NOT EXISTS idx_entities_updated_at ON entities(updated_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_entity_relationships_source ON entity_relationships(source_entity_id, relationship_type);
+        CREATE INDEX IF NOT EXISTS idx_entity_relationships_target ON entity_relationships(target_entity_id, relationship_type);
+        CREATE INDEX IF NOT EXISTS idx_xflows_enabled ON xflows(enabled);
+        CREATE INDEX IF NOT EXISTS idx_xflows_priority ON xflows(priority DESC);
+        CREATE INDEX IF NOT EXISTS idx_xflows_application_id ON xflows(application_id);
+        CREATE INDEX IF NOT EXISTS idx_xflow_executions_status ON xflow_executions(status);
+        CREATE INDEX IF NOT EXISTS idx_xflow_executions_xflow_id ON xflow_executions(xflow_id);
+        CREATE INDEX IF NOT EXISTS idx_xflow_executions_started_at ON xflow_executions(started_at DESC)
+        "#.to_string(),
+    ])
 }
 
-async fn run_postgres_migrations(db: &DatabaseConnection) -> Result<()> {
-    // PostgreSQL-specific schema with partitioning and advanced features
-    let migrations = vec![
+fn core_schema_postgres() -> String {
+    // Migrations run inside a transaction, so GIN indexes are built without CONCURRENTLY
+    join_sql(&[
         create_torque_models_postgres(),
         create_torque_applications_postgres(),
         create_entities_postgres(),
         create_entity_relationships_postgres(),
-        create_app_entities_postgres(),
         create_xflows_postgres(),
         create_xflow_executions_postgres(),
         create_system_config_postgres(),
         create_performance_metrics_postgres(),
         create_partitions_postgres(),
-        create_indexes_postgres(),
-    ];
-    
-    for migration in migrations {
-        db.execute(Statement::from_string(db.get_database_backend(), migration)).await?;
-    }
-    
-    Ok(())
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_torque_models_name ON torque_models(name);
+        CREATE INDEX IF NOT EXISTS idx_torque_models_created_at ON torque_models(created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_torque_models_model_json_gin ON torque_models USING GIN (model_json);
+        CREATE INDEX IF NOT EXISTS idx_torque_applications_status ON torque_applications(status) WHERE status = 'active';
+        CREATE INDEX IF NOT EXISTS idx_torque_applications_model_id ON torque_applications(model_id);
+        CREATE INDEX IF NOT EXISTS idx_entities_app_type ON entities(application_id, entity_type);
+        CREATE INDEX IF NOT EXISTS idx_entities_data_gin ON entities USING GIN (data);
+        CREATE INDEX IF NOT EXISTS idx_entities_updated_at ON entities(updated_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_entity_relationships_source ON entity_relationships(source_entity_id, relationship_type);
+        CREATE INDEX IF NOT EXISTS idx_entity_relationships_target ON entity_relationships(target_entity_id, relationship_type);
+        CREATE INDEX IF NOT EXISTS idx_xflows_enabled ON xflows(enabled) WHERE enabled = true;
+        CREATE INDEX IF NOT EXISTS idx_xflows_priority ON xflows(priority DESC) WHERE enabled = true;
+        CREATE INDEX IF NOT EXISTS idx_xflows_application_id ON xflows(application_id);
+        CREATE INDEX IF NOT EXISTS idx_xflow_executions_status ON xflow_executions(status);
+        CREATE INDEX IF NOT EXISTS idx_xflow_executions_xflow_id ON xflow_executions(xflow_id);
+        CREATE INDEX IF NOT EXISTS idx_xflow_executions_started_at ON xflow_executions(started_at DESC)
+        "#.to_string(),
+    ])
+}
+
+fn app_entities_sqlite() -> String {
+    join_sql(&[
+        create_app_entities_sqlite(),
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_app_entities_model_id ON app_entities(model_id);
+        CREATE INDEX IF NOT EXISTS idx_app_entities_model_entity ON app_entities(model_id, entity_type);
+        CREATE INDEX IF NOT EXISTS idx_app_entities_created_at ON app_entities(created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_app_entities_updated_at ON app_entities(model_id, entity_type, updated_at)
+        "#.to_string(),
+    ])
+}
+
+fn app_entities_postgres() -> String {
+    join_sql(&[
+        create_app_entities_postgres(),
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_app_entities_model_id ON app_entities(model_id);
+        CREATE INDEX IF NOT EXISTS idx_app_entities_model_entity ON app_entities(model_id, entity_type);
+        CREATE INDEX IF NOT EXISTS idx_app_entities_created_at ON app_entities(created_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_app_entities_data_gin ON app_entities USING GIN (data);
+        CREATE INDEX IF NOT EXISTS idx_app_entities_updated_at ON app_entities(model_id, entity_type, updated_at)
+        "#.to_string(),
+    ])
+}
+
+fn app_blobs_sqlite() -> String {
+    join_sql(&[create_app_blobs_sqlite(), app_blobs_indexes()])
+}
+
+fn app_blobs_postgres() -> String {
+    join_sql(&[create_app_blobs_postgres(), app_blobs_indexes()])
+}
+
+fn app_blobs_indexes() -> String {
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_app_blobs_model_id ON app_blobs(model_id);
+    CREATE INDEX IF NOT EXISTS idx_app_blobs_entity_id ON app_blobs(entity_id)
+    "#.to_string()
+}
+
+fn app_entities_archive_sqlite() -> String {
+    join_sql(&[create_app_entities_archive_sqlite(), app_entities_archive_indexes()])
+}
+
+fn app_entities_archive_postgres() -> String {
+    join_sql(&[create_app_entities_archive_postgres(), app_entities_archive_indexes()])
+}
+
+fn app_entities_archive_indexes() -> String {
+    "CREATE INDEX IF NOT EXISTS idx_app_entities_archive_model_entity ON app_entities_archive(model_id, entity_type)".to_string()
+}
+
+fn model_versions_sqlite() -> String {
+    join_sql(&[
+        create_torque_model_versions_sqlite(),
+        create_torque_model_pins_sqlite(),
+        model_versions_indexes(),
+    ])
+}
+
+fn model_versions_postgres() -> String {
+    join_sql(&[
+        create_torque_model_versions_postgres(),
+        create_torque_model_pins_postgres(),
+        model_versions_indexes(),
+    ])
+}
+
+fn model_versions_indexes() -> String {
+    r#"
+    CREATE INDEX IF NOT EXISTS idx_torque_model_versions_model_id ON torque_model_versions(model_id, version_number DESC);
+    CREATE INDEX IF NOT EXISTS idx_torque_model_versions_name_version ON torque_model_versions(name, version)
+    "#.to_string()
+}
+
+fn data_migration_jobs_sqlite() -> String {
+    join_sql(&[
+        create_data_migration_jobs_sqlite(),
+        create_data_migration_backups_sqlite(),
+        data_migration_jobs_indexes(),
+    ])
+}
+
+fn data_migration_jobs_postgres() -> String {
+    join_sql(&[
+        create_data_migration_jobs_postgres(),
+        create_data_migration_backups_postgres(),
+        data_migration_jobs_indexes(),
+    ])
+}
+
+fn data_migration_jobs_indexes() -> String {
+    "CREATE INDEX IF NOT EXISTS idx_data_migration_jobs_model_id ON data_migration_jobs(model_id, created_at DESC)".to_string()
+}
+
+/// Fails all but the newest active job of each model, so the unique index can be built
+fn data_migration_jobs_active_index_sqlite() -> String {
+    join_sql(&[
+        r#"
+        UPDATE data_migration_jobs SET status = 'failed', error = 'Superseded by a newer job', completed_at = CURRENT_TIMESTAMP
+        WHERE status IN ('pending', 'running') AND EXISTS (
+            SELECT 1 FROM data_migration_jobs AS newer
+            WHERE newer.model_id = data_migration_jobs.model_id
+              AND newer.status IN ('pending', 'running')
+              AND (newer.created_at > data_migration_jobs.created_at
+                   OR (newer.created_at = data_migration_jobs.created_at AND newer.id > data_migration_jobs.id))
+        )
+        "#.to_string(),
+        data_migration_jobs_active_index(),
+    ])
+}
+
+fn data_migration_jobs_active_index_postgres() -> String {
+    join_sql(&[
+        r#"
+        UPDATE data_migration_jobs AS job SET status = 'failed', error = 'Superseded by a newer job', completed_at = NOW()
+        FROM data_migration_jobs AS newer
+        WHERE job.status IN ('pending', 'running')
+          AND newer.model_id = job.model_id
+          AND newer.status IN ('pending', 'running')
+          AND (newer.created_at > job.created_at OR (newer.created_at = job.created_at AND newer.id > job.id))
+        "#.to_string(),
+        data_migration_jobs_active_index(),
+    ])
+}
+
+/// The partial unique index allows at most one pending or running job per model
+fn data_migration_jobs_active_index() -> String {
+    r#"
+    CREATE UNIQUE INDEX IF NOT EXISTS idx_data_migration_jobs_active
+        ON data_migration_jobs(model_id) WHERE status IN ('pending', 'running')
+    "#.to_string()
+}
+
+fn model_search_index_postgres() -> String {
+    join_sql(&[
+        create_model_search_index_postgres(),
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_model_search_index_model_id ON model_search_index(model_id);
+        CREATE INDEX IF NOT EXISTS idx_model_search_index_document ON model_search_index USING GIN(document)
+        "#.to_string(),
+    ])
 }
 
 fn create_torque_models_sqlite() -> String {
@@ -100,6 +651,62 @@ fn create_torque_models_postgres() -> String {
     "#.to_string()
 }
 
+fn create_torque_model_versions_sqlite() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS torque_model_versions (
+        id TEXT PRIMARY KEY DEFAULT (hex(randomblob(16))),
+        model_id TEXT NOT NULL,
+        version_number INTEGER NOT NULL,
+        name VARCHAR(255) NOT NULL,
+        version VARCHAR(50) NOT NULL,
+        author VARCHAR(255) NOT NULL,
+        message TEXT,
+        model_json JSON NOT NULL,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        UNIQUE(model_id, version_number)
+    )
+    "#.to_string()
+}
+
+fn create_torque_model_versions_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS torque_model_versions (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        model_id UUID NOT NULL,
+        version_number INTEGER NOT NULL,
+        name VARCHAR(255) NOT NULL,
+        version VARCHAR(50) NOT NULL,
+        author VARCHAR(255) NOT NULL,
+        message TEXT,
+        model_json JSONB NOT NULL,
+        created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+        UNIQUE(model_id, version_number)
+    )
+    "#.to_string()
+}
+
+fn create_torque_model_pins_sqlite() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS torque_model_pins (
+        model_id TEXT PRIMARY KEY,
+        version_number INTEGER NOT NULL,
+        pinned_by VARCHAR(255) NOT NULL,
+        pinned_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )
+    "#.to_string()
+}
+
+fn create_torque_model_pins_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS torque_model_pins (
+        model_id UUID PRIMARY KEY,
+        version_number INTEGER NOT NULL,
+        pinned_by VARCHAR(255) NOT NULL,
+        pinned_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+    )
+    "#.to_string()
+}
+
 fn create_torque_applications_sqlite() -> String {
     r#"
     CREATE TABLE IF NOT EXISTS torque_applications (
@@ -328,28 +935,6 @@ fn create_partitions_postgres() -> String {
     "#.to_string()
 }
 
-fn create_indexes_sqlite() -> String {
-    r#"
-    CREATE INDEX IF NOT EXISTS idx_torque_models_name ON torque_models(name);
-    CREATE INDEX IF NOT EXISTS idx_torque_models_created_at ON torque_models(created_at DESC);
-    CREATE INDEX IF NOT EXISTS idx_torque_applications_status ON torque_applications(status);
-    CREATE INDEX IF NOT EXISTS idx_torque_applications_model_id ON torque_applications(model_id);
-    CREATE INDEX IF NOT EXISTS idx_entities_app_type ON entities(application_id, entity_type);
-    CREATE INDEX IF NOT EXISTS idx_entities_updated_at ON entities(updated_at DESC);
-    CREATE INDEX IF NOT EXISTS idx_entity_relationships_source ON entity_relationships(source_entity_id, relationship_type);
-    CREATE INDEX IF NOT EXISTS idx_entity_relationships_target ON entity_relationships(target_entity_id, relationship_type);
-    CREATE INDEX IF NOT EXISTS idx_app_entities_model_id ON app_entities(model_id);
-    CREATE INDEX IF NOT EXISTS idx_app_entities_model_entity ON app_entities(model_id, entity_type);
-    CREATE INDEX IF NOT EXISTS idx_app_entities_created_at ON app_entities(created_at DESC);
-    CREATE INDEX IF NOT EXISTS idx_xflows_enabled ON xflows(enabled);
-    CREATE INDEX IF NOT EXISTS idx_xflows_priority ON xflows(priority DESC);
-    CREATE INDEX IF NOT EXISTS idx_xflows_application_id ON xflows(application_id);
-    CREATE INDEX IF NOT EXISTS idx_xflow_executions_status ON xflow_executions(status);
-    CREATE INDEX IF NOT EXISTS idx_xflow_executions_xflow_id ON xflow_executions(xflow_id);
-    CREATE INDEX IF NOT EXISTS idx_xflow_executions_started_at ON xflow_executions(started_at DESC);
-    "#.to_string()
-}
-
 fn create_app_entities_sqlite() -> String {
     r#"
     CREATE TABLE IF NOT EXISTS app_entities (
@@ -376,27 +961,230 @@ fn create_app_entities_postgres() -> String {
     "#.to_string()
 }
 
-fn create_indexes_postgres() -> String {
-    r#"
-    CREATE INDEX IF NOT EXISTS idx_torque_models_name ON torque_models(name);
-    CREATE INDEX IF NOT EXISTS idx_torque_models_created_at ON torque_models(created_at DESC);
-    CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_torque_models_model_json_gin ON torque_models USING GIN (model_json);
-    CREATE INDEX IF NOT EXISTS idx_torque_applications_status ON torque_applications(status) WHERE status = 'active';
-    CREATE INDEX IF NOT EXISTS idx_torque_applications_model_id ON torque_applications(model_id);
-    CREATE INDEX IF NOT EXISTS idx_entities_app_type ON entities(application_id, entity_type);
-    CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_entities_data_gin ON entities USING GIN (data);
-    CREATE INDEX IF NOT EXISTS idx_entities_updated_at ON entities(updated_at DESC);
-    CREATE INDEX IF NOT EXISTS idx_entity_relationships_source ON entity_relationships(source_entity_id, relationship_type);
-    CREATE INDEX IF NOT EXISTS idx_entity_relationships_target ON entity_relationships(target_entity_id, relationship_type);
-    CREATE INDEX IF NOT EXISTS idx_app_entities_model_id ON app_entities(model_id);
-    CREATE INDEX IF NOT EXISTS idx_app_entities_model_entity ON app_entities(model_id, entity_type);
-    CREATE INDEX IF NOT EXISTS idx_app_entities_created_at ON app_entities(created_at DESC);
-    CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_app_entities_data_gin ON app_entities USING GIN (data);
-    CREATE INDEX IF NOT EXISTS idx_xflows_enabled ON xflows(enabled) WHERE enabled = true;
-    CREATE INDEX IF NOT EXISTS idx_xflows_priority ON xflows(priority DESC) WHERE enabled = true;
-    CREATE INDEX IF NOT EXISTS idx_xflows_application_id ON xflows(application_id);
-    CREATE INDEX IF NOT EXISTS idx_xflow_executions_status ON xflow_executions(status);
-    CREATE INDEX IF NOT EXISTS idx_xflow_executions_xflow_id ON xflow_executions(xflow_id);
-    CREATE INDEX IF NOT EXISTS idx_xflow_executions_started_at ON xflow_executions(started_at DESC);
-    "#.to_string()
-}
\ No newline at end of file
+fn create_app_blobs_sqlite() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS app_blobs (
+        id TEXT PRIMARY KEY DEFAULT (hex(randomblob(16))),
+        model_id TEXT NOT NULL,
+        entity_id TEXT,
+        field_name VARCHAR(255),
+        filename VARCHAR(255) NOT NULL,
+        content_type VARCHAR(255) NOT NULL,
+        size_bytes INTEGER NOT NULL,
+        storage_key TEXT NOT NULL,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )
+    "#.to_string()
+}
+
+fn create_app_blobs_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS app_blobs (
+        id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+        model_id UUID NOT NULL,
+        entity_id UUID,
+        field_name VARCHAR(255),
+        filename VARCHAR(255) NOT NULL,
+        content_type VARCHAR(255) NOT NULL,
+        size_bytes BIGINT NOT NULL,
+        storage_key TEXT NOT NULL,
+        created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+    )
+    "#.to_string()
+}
+
+fn create_app_entities_archive_sqlite() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS app_entities_archive (
+        id TEXT PRIMARY KEY,
+        model_id TEXT NOT NULL,
+        entity_type VARCHAR(255) NOT NULL,
+        data JSON NOT NULL,
+        created_at DATETIME,
+        updated_at DATETIME,
+        archived_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )
+    "#.to_string()
+}
+
+fn create_app_entities_archive_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS app_entities_archive (
+        id UUID PRIMARY KEY,
+        model_id UUID NOT NULL,
+        entity_type VARCHAR(255) NOT NULL,
+        data JSONB NOT NULL,
+        created_at TIMESTAMP WITH TIME ZONE,
+        updated_at TIMESTAMP WITH TIME ZONE,
+        archived_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+    )
+    "#.to_string()
+}
+
+fn create_model_search_index_sqlite() -> String {
+    r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS model_search_index USING fts5(
+        model_id UNINDEXED,
+        model_name UNINDEXED,
+        path UNINDEXED,
+        kind UNINDEXED,
+        name,
+        content,
+        tokenize = 'unicode61'
+    )
+    "#.to_string()
+}
+
+fn create_model_search_index_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS model_search_index (
+        id BIGSERIAL PRIMARY KEY,
+        model_id UUID NOT NULL,
+        model_name VARCHAR(255) NOT NULL,
+        path TEXT NOT NULL,
+        kind VARCHAR(50) NOT NULL,
+        name TEXT NOT NULL,
+        content TEXT NOT NULL,
+        document TSVECTOR GENERATED ALWAYS AS (
+            setweight(to_tsvector('simple', name), 'A') || setweight(to_tsvector('simple', content), 'B')
+        ) STORED
+    )
+    "#.to_string()
+}
+
+fn create_data_migration_jobs_sqlite() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS data_migration_jobs (
+        id TEXT PRIMARY KEY,
+        model_id TEXT NOT NULL,
+        from_version INTEGER NOT NULL,
+        to_version INTEGER NOT NULL,
+        status VARCHAR(50) NOT NULL DEFAULT 'pending',
+        plan JSON NOT NULL,
+        rows_updated INTEGER NOT NULL DEFAULT 0,
+        rows_archived INTEGER NOT NULL DEFAULT 0,
+        rows_deleted INTEGER NOT NULL DEFAULT 0,
+        coercion_failures INTEGER NOT NULL DEFAULT 0,
+        error TEXT,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+        completed_at DATETIME,
+        rolled_back_at DATETIME
+    )
+    "#.to_string()
+}
+
+fn create_data_migration_jobs_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS data_migration_jobs (
+        id UUID PRIMARY KEY,
+        model_id UUID NOT NULL,
+        from_version INTEGER NOT NULL,
+        to_version INTEGER NOT NULL,
+        status VARCHAR(50) NOT NULL DEFAULT 'pending',
+        plan JSONB NOT NULL,
+        rows_updated BIGINT NOT NULL DEFAULT 0,
+        rows_archived BIGINT NOT NULL DEFAULT 0,
+        rows_deleted BIGINT NOT NULL DEFAULT 0,
+        coercion_failures BIGINT NOT NULL DEFAULT 0,
+        error TEXT,
+        created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+        completed_at TIMESTAMP WITH TIME ZONE,
+        rolled_back_at TIMESTAMP WITH TIME ZONE
+    )
+    "#.to_string()
+}
+
+fn create_data_migration_backups_sqlite() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS data_migration_backups (
+        job_id TEXT NOT NULL,
+        entity_id TEXT NOT NULL,
+        model_id TEXT NOT NULL,
+        entity_type VARCHAR(255) NOT NULL,
+        data JSON NOT NULL,
+        created_at DATETIME,
+        updated_at DATETIME,
+        PRIMARY KEY (job_id, entity_id)
+    )
+    "#.to_string()
+}
+
+fn create_data_migration_backups_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS data_migration_backups (
+        job_id UUID NOT NULL,
+        entity_id UUID NOT NULL,
+        model_id UUID NOT NULL,
+        entity_type VARCHAR(255) NOT NULL,
+        data JSONB NOT NULL,
+        created_at TIMESTAMP WITH TIME ZONE,
+        updated_at TIMESTAMP WITH TIME ZONE,
+        PRIMARY KEY (job_id, entity_id)
+    )
+    "#.to_string()
+}
+
+fn create_event_log_sqlite() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS event_log (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        model_id TEXT NOT NULL,
+        event JSON NOT NULL,
+        exclude_client TEXT,
+        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+    )
+    "#.to_string()
+}
+
+fn create_event_log_postgres() -> String {
+    r#"
+    CREATE TABLE IF NOT EXISTS event_log (
+        seq BIGSERIAL PRIMARY KEY,
+        model_id UUID NOT NULL,
+        event JSONB NOT NULL,
+        exclude_client TEXT,
+        created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+    )
+    "#.to_string()
+}
+
+/// SQLite has no `ADD COLUMN IF NOT EXISTS`; the ledger keeps this from running twice
+fn event_log_node_id_sqlite() -> String {
+    "ALTER TABLE event_log ADD COLUMN node_id TEXT".to_string()
+}
+
+fn event_log_node_id_postgres() -> String {
+    "ALTER TABLE event_log ADD COLUMN IF NOT EXISTS node_id TEXT".to_string()
+}
+
+fn data_migration_jobs_heartbeat_sqlite() -> String {
+    "ALTER TABLE data_migration_jobs ADD COLUMN heartbeat_at DATETIME".to_string()
+}
+
+fn data_migration_jobs_heartbeat_postgres() -> String {
+    "ALTER TABLE data_migration_jobs ADD COLUMN IF NOT EXISTS heartbeat_at TIMESTAMP WITH TIME ZONE".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_versions_are_ordered() {
+        let migrations = migrations();
+        assert!(migrations.windows(2).all(|w| w[0].version < w[1].version));
+        for migration in &migrations {
+            assert_ne!(
+                migration.checksum(DatabaseBackend::Sqlite).unwrap(),
+                migration.checksum(DatabaseBackend::Postgres).unwrap(),
+                "migration {} should have backend specific SQL", migration.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_statements() {
+        let statements: Vec<_> = split_statements("CREATE TABLE a (id INT);\n  ;\nDROP TABLE b\n").collect();
+        assert_eq!(statements, vec!["CREATE TABLE a (id INT)", "DROP TABLE b"]);
+    }
+}
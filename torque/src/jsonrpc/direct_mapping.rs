@@ -1,7 +1,7 @@
 use serde_json::{Value, Map};
 use crate::common::{Uuid, UtcDateTime};
 use crate::services::entity::{Entity, CreateEntityRequest, UpdateEntityRequest};
-use crate::model::types::{ModelEntity, FieldType};
+use crate::model::types::{EntityField, ModelEntity, FieldType};
 
 /// Direct JSONB mapping for TorqueApp - no transformations
 /// Entity data is stored exactly as it will be consumed by frontend components
@@ -193,6 +193,44 @@ impl DirectMapping {
         })
     }
     
+    /// DataGrid column data type for a field type
+    pub fn column_data_type(field_type: &FieldType) -> &'static str {
+        match field_type {
+            FieldType::String { .. } => "string",
+            FieldType::Integer { .. } | FieldType::Float { .. } => "number",
+            FieldType::Boolean => "boolean",
+            FieldType::DateTime | FieldType::Date | FieldType::Time => "date",
+            FieldType::Json => "json",
+            FieldType::Binary => "binary",
+            FieldType::Enum { .. } => "enum",
+            FieldType::Array { .. } => "array",
+            FieldType::Reference { .. } => "reference",
+        }
+    }
+
+    /// Form input type for a field
+    pub fn form_input_type(field: &EntityField) -> &'static str {
+        match &field.field_type {
+            FieldType::String { .. } => {
+                if field.ui_config.custom_props.get("multiline").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    "textarea"
+                } else {
+                    "text"
+                }
+            },
+            FieldType::Integer { .. } | FieldType::Float { .. } => "number",
+            FieldType::Boolean => "checkbox",
+            FieldType::DateTime => "datetime-local",
+            FieldType::Date => "date",
+            FieldType::Time => "time",
+            FieldType::Binary => "file",
+            FieldType::Enum { .. } => "select",
+            FieldType::Array { .. } => "multiselect",
+            FieldType::Reference { .. } => "select",
+            FieldType::Json => "textarea",
+        }
+    }
+
     /// Generate DataGrid columns from entity definition
     pub fn generate_datagrid_columns(entity: &ModelEntity) -> Vec<Value> {
         let mut columns = vec![];
@@ -210,17 +248,7 @@ impl DirectMapping {
         
         // Add field columns
         for field in &entity.fields {
-            let data_type = match &field.field_type {
-                FieldType::String { .. } => "string",
-                FieldType::Integer { .. } | FieldType::Float { .. } => "number",
-                FieldType::Boolean => "boolean",
-                FieldType::DateTime | FieldType::Date | FieldType::Time => "date",
-                FieldType::Json => "json",
-                FieldType::Binary => "binary",
-                FieldType::Enum { .. } => "enum",
-                FieldType::Array { .. } => "array",
-                FieldType::Reference { .. } => "reference",
-            };
+            let data_type = Self::column_data_type(&field.field_type);
             
             columns.push(serde_json::json!({
                 "key": field.name,
@@ -259,25 +287,7 @@ impl DirectMapping {
     /// Generate form fields from entity definition
     pub fn generate_form_fields(entity: &ModelEntity) -> Vec<Value> {
         entity.fields.iter().map(|field| {
-            let field_type = match &field.field_type {
-                FieldType::String { .. } => {
-                    if field.ui_config.custom_props.get("multiline").and_then(|v| v.as_bool()).unwrap_or(false) {
-                        "textarea"
-                    } else {
-                        "text"
-                    }
-                },
-                FieldType::Integer { .. } | FieldType::Float { .. } => "number",
-                FieldType::Boolean => "checkbox",
-                FieldType::DateTime => "datetime-local",
-                FieldType::Date => "date",
-                FieldType::Time => "time",
-                FieldType::Binary => "file",
-                FieldType::Enum { .. } => "select",
-                FieldType::Array { .. } => "multiselect",
-                FieldType::Reference { .. } => "select",
-                FieldType::Json => "textarea",
-            };
+            let field_type = Self::form_input_type(field);
             
             serde_json::json!({
                 "id": field.id,
@@ -1,6 +1,8 @@
 use crate::server::AppState;
 // Model types imported as needed in specific handlers
 use crate::jsonrpc::direct_mapping::DirectMapping;
+use crate::database::entities::app_entities::Model as AppEntity;
+use crate::services::app_database::{entity_record, EntityFilter, EntityListQuery, FilterOp};
 use axum::{
     extract::State,
     http::StatusCode,
@@ -36,6 +38,7 @@ impl ConsoleSession {
             capabilities: vec![
                 "listProjects".to_string(),
                 "createProject".to_string(),
+                "listProjectTemplates".to_string(),
                 "deleteProject".to_string(),
                 "getProjectInfo".to_string(),
             ],
@@ -55,21 +58,18 @@ pub async fn jsonrpc_handler(
     Json(request): Json<Value>,
 ) -> Result<Json<Value>, StatusCode> {
     tracing::debug!("JSON-RPC request received: {}", request);
-    
+    Ok(Json(handle_request(&state, &request).await))
+}
+
+/// Validate and dispatch a single JSON-RPC request, returning its response.
+/// Shared by the HTTP endpoint and the WebSocket command channel.
+pub async fn handle_request(state: &AppState, request: &Value) -> Value {
     // Extract request ID for proper JSON-RPC response format
     let request_id = request.get("id").cloned().unwrap_or(json!(null));
     
     // Validate JSON-RPC request format
-    if let Err((code, message)) = validate_jsonrpc_request(&request) {
-        let response = json!({
-            "jsonrpc": "2.0",
-            "id": request_id,
-            "error": {
-                "code": code,
-                "message": message
-            }
-        });
-        return Ok(Json(response));
+    if let Err((code, message)) = validate_jsonrpc_request(request) {
+        return error_response(request_id, code, message);
     }
     
     // Extract method and params
@@ -78,29 +78,28 @@ pub async fn jsonrpc_handler(
     let params = request.get("params").unwrap_or(&default_params);
     
     // Dispatch to appropriate method handler
-    match dispatch_method(&state, method, params).await {
-        Ok(result) => {
-            let response = json!({
-                "jsonrpc": "2.0",
-                "id": request_id,
-                "result": result
-            });
-            Ok(Json(response))
-        },
-        Err((code, message)) => {
-            let response = json!({
-                "jsonrpc": "2.0",
-                "id": request_id,
-                "error": {
-                    "code": code,
-                    "message": message
-                }
-            });
-            Ok(Json(response))
-        }
+    match dispatch_method(state, method, params).await {
+        Ok(result) => json!({
+            "jsonrpc": "2.0",
+            "id": request_id,
+            "result": result
+        }),
+        Err((code, message)) => error_response(request_id, code, message),
     }
 }
 
+/// JSON-RPC error response
+pub fn error_response(request_id: Value, code: i32, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": request_id,
+        "error": {
+            "code": code,
+            "message": message
+        }
+    })
+}
+
 /// Non-console JSON-RPC method dispatcher to avoid recursion
 async fn dispatch_non_console_method(
     state: &AppState,
@@ -121,6 +120,7 @@ async fn dispatch_non_console_method(
         // Layout and UI methods
         "getComponentConfig" => get_component_config(state, params).await,
         "getLayoutConfig" => get_layout_config(state, params).await,
+        "generateLayouts" => generate_layouts(state, params).await,
         "getModelMetadata" => get_model_metadata(state, params).await,
         
         // Health and introspection
@@ -130,6 +130,7 @@ async fn dispatch_non_console_method(
         // Console/Project management methods (also exposed as MCP tools)
         "listProjects" => list_projects(state, params).await,
         "createProject" => create_project(state, params).await,
+        "listProjectTemplates" => list_project_templates().await,
         "deleteProject" => delete_project(state, params).await,
         "getProjectInfo" => get_project_info(state, params).await,
         
@@ -147,6 +148,22 @@ async fn dispatch_non_console_method(
         "getRemediationStrategies" => get_remediation_strategies(state, params).await,
         "executeAutoRemediation" => execute_auto_remediation(state, params).await,
         
+        // Model version history
+        "listModelVersions" => list_model_versions(state, params).await,
+        "getModelVersion" => get_model_version(state, params).await,
+        "rollbackModel" => rollback_model(state, params).await,
+        "pinModelVersion" => pin_model_version(state, params).await,
+        "unpinModelVersion" => unpin_model_version(state, params).await,
+        "diffModel" => diff_model(state, params).await,
+        "mergeModel" => merge_model(state, params).await,
+        
+        // App data migrations
+        "previewDataMigration" => preview_data_migration(state, params).await,
+        "runDataMigration" => run_data_migration(state, params).await,
+        "getDataMigrationJob" => get_data_migration_job(state, params).await,
+        "listDataMigrationJobs" => list_data_migration_jobs(state, params).await,
+        "rollbackDataMigration" => rollback_data_migration(state, params).await,
+        
         // Explicitly exclude executeConsoleCommand to prevent recursion
         "executeConsoleCommand" => Err((-32603, "Recursive console command execution not allowed".to_string())),
         
@@ -174,6 +191,7 @@ async fn dispatch_method(
         // Layout and UI methods
         "getComponentConfig" => get_component_config(state, params).await,
         "getLayoutConfig" => get_layout_config(state, params).await,
+        "generateLayouts" => generate_layouts(state, params).await,
         "getModelMetadata" => get_model_metadata(state, params).await,
         
         // Health and introspection
@@ -183,6 +201,7 @@ async fn dispatch_method(
         // Console/Project management methods (also exposed as MCP tools)
         "listProjects" => list_projects(state, params).await,
         "createProject" => create_project(state, params).await,
+        "listProjectTemplates" => list_project_templates().await,
         "deleteProject" => delete_project(state, params).await,
         "getProjectInfo" => get_project_info(state, params).await,
         
@@ -200,6 +219,22 @@ async fn dispatch_method(
         "getRemediationStrategies" => get_remediation_strategies(state, params).await,
         "executeAutoRemediation" => execute_auto_remediation(state, params).await,
         
+        // Model version history
+        "listModelVersions" => list_model_versions(state, params).await,
+        "getModelVersion" => get_model_version(state, params).await,
+        "rollbackModel" => rollback_model(state, params).await,
+        "pinModelVersion" => pin_model_version(state, params).await,
+        "unpinModelVersion" => unpin_model_version(state, params).await,
+        "diffModel" => diff_model(state, params).await,
+        "mergeModel" => merge_model(state, params).await,
+        
+        // App data migrations
+        "previewDataMigration" => preview_data_migration(state, params).await,
+        "runDataMigration" => run_data_migration(state, params).await,
+        "getDataMigrationJob" => get_data_migration_job(state, params).await,
+        "listDataMigrationJobs" => list_data_migration_jobs(state, params).await,
+        "rollbackDataMigration" => rollback_data_migration(state, params).await,
+        
         // Console command execution
         "executeConsoleCommand" => execute_console_command(state, params).await,
         
@@ -207,6 +242,27 @@ async fn dispatch_method(
     }
 }
 
+/// Resolve the model definition for a runtime request.
+/// An optional `modelVersion` param selects a version snapshot; otherwise the
+/// model's pinned version (if any) or the latest definition is used.
+async fn load_runtime_model(state: &AppState, model_uuid: Uuid, params: &Value) -> Result<crate::model::types::TorqueModel, (i32, String)> {
+    let requested_version = match params.get("modelVersion") {
+        None | Some(Value::Null) => None,
+        Some(v) => Some(
+            v.as_i64()
+                .and_then(|n| i32::try_from(n).ok())
+                .ok_or((-32602, "Invalid modelVersion: expected a version number".to_string()))?,
+        ),
+    };
+
+    state.services.model_service.get_runtime_model(model_uuid, requested_version).await
+        .map_err(|e| match e {
+            crate::Error::NotFound(msg) => (-32604, msg),
+            e => (-32603, format!("Failed to load model: {}", e)),
+        })?
+        .ok_or((-32604, "Model not found".to_string()))
+}
+
 /// Load page layout and configuration for a TorqueApp
 async fn load_page(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let model_id = params.get("modelId")
@@ -221,9 +277,7 @@ async fn load_page(state: &AppState, params: &Value) -> Result<Value, (i32, Stri
         .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
     
     // Get model from service
-    let model = state.services.model_service.get_model(model_uuid).await
-        .map_err(|e| (-32603, format!("Failed to load model: {}", e)))?
-        .ok_or((-32604, "Model not found".to_string()))?;
+    let model = load_runtime_model(state, model_uuid.clone(), params).await?;
     
     // Determine which layout to load
     let layout_id = if let Some(page_name) = page_name {
@@ -307,38 +361,42 @@ async fn load_entity_data(state: &AppState, params: &Value) -> Result<Value, (i3
         .and_then(|v| v.as_u64())
         .unwrap_or(20);
     
-    let _filters = params.get("filters").cloned();
-    
     // Parse model ID
     let model_uuid = Uuid::parse(model_id)
         .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
     
     // Get model to find entity definition
-    let model = state.services.model_service.get_model(model_uuid.clone()).await
-        .map_err(|e| (-32603, format!("Failed to load model: {}", e)))?
-        .ok_or((-32604, "Model not found".to_string()))?;
+    let model = load_runtime_model(state, model_uuid.clone(), params).await?;
     
     // Find entity definition
     let entity_def = model.entities.iter()
         .find(|e| e.name == entity_name)
         .ok_or((-32605, format!("Entity '{}' not found in model", entity_name)))?;
     
-    // Note: Filtering will be implemented in a future iteration
-    // For now, we return all entities with basic pagination
+    // Equality filters on top-level data fields, e.g. {"project_id": 1}
+    let mut filters = Vec::new();
+    if let Some(filter_params) = params.get("filters").and_then(|v| v.as_object()) {
+        for (field, value) in filter_params {
+            if !entity_def.fields.iter().any(|f| &f.name == field) {
+                return Err((-32602, format!("Entity '{}' has no field '{}'", entity_name, field)));
+            }
+            filters.push(EntityFilter { field: field.clone(), op: FilterOp::Eq, value: value.clone() });
+        }
+    }
     
     // Query entities using the app database service (where sample data is stored)
-    let offset = (page - 1) * limit;
+    let query = EntityListQuery {
+        filters,
+        limit,
+        offset: (page.max(1) - 1) * limit,
+        ..Default::default()
+    };
     
-    let entities = state.services.app_database_service
-        .get_entities(model_id, entity_name, limit, offset)
+    let (entities, total) = state.services.app_database_service
+        .query_entities(model_id, entity_name, &query)
         .await
         .map_err(|e| (-32603, format!("Failed to query entities: {}", e)))?;
     
-    let total = state.services.app_database_service
-        .get_entity_count(model_id, entity_name)
-        .await
-        .map_err(|e| (-32603, format!("Failed to get entity count: {}", e)))?;
-    
     // Format response directly since we already have JSON data
     let response = serde_json::json!({
         "data": entities,
@@ -376,9 +434,7 @@ async fn get_form_definition(state: &AppState, params: &Value) -> Result<Value,
         .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
     
     // Get model from service
-    let model = state.services.model_service.get_model(model_uuid).await
-        .map_err(|e| (-32603, format!("Failed to load model: {}", e)))?
-        .ok_or((-32604, "Model not found".to_string()))?;
+    let model = load_runtime_model(state, model_uuid.clone(), params).await?;
     
     // Find entity definition
     let entity_def = model.entities.iter()
@@ -404,7 +460,8 @@ async fn get_form_definition(state: &AppState, params: &Value) -> Result<Value,
     }))
 }
 
-/// Create a new entity instance with direct JSONB mapping
+/// Create a new app data record. The record is validated against the entity's
+/// JSON Schema and subscribers receive an `EntityDataChanged` event.
 async fn create_entity(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let model_id = params.get("modelId")
         .and_then(|v| v.as_str())
@@ -418,89 +475,86 @@ async fn create_entity(state: &AppState, params: &Value) -> Result<Value, (i32,
         .ok_or((-32602, "Missing required parameter: data".to_string()))?
         .clone();
     
-    // Parse model ID
-    let model_uuid = Uuid::parse(model_id)
+    Uuid::parse(model_id)
         .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
     
-    // Get model to find entity definition
-    let model = state.services.model_service.get_model(model_uuid.clone()).await
-        .map_err(|e| (-32603, format!("Failed to load model: {}", e)))?
-        .ok_or((-32604, "Model not found".to_string()))?;
-    
-    // Find entity definition
-    let entity_def = model.entities.iter()
-        .find(|e| e.name == entity_name);
-    
-    // Create entity with direct mapping
-    let request = DirectMapping::create_entity_request(
-        model_uuid,
-        entity_name.to_string(),
-        entity_data,
-        entity_def
-    );
-    
-    let entity = state.services.entity_service.create_entity(request).await
-        .map_err(|e| (-32603, format!("Failed to create entity: {}", e)))?;
+    let entity = state.services.app_database_service
+        .create_entity(model_id, entity_name, entity_data)
+        .await
+        .map_err(|e| app_data_error("create entity", e))?;
     
-    // Return the entity data directly
     Ok(json!({
         "id": entity.id,
         "modelId": model_id,
         "entityName": entity_name,
-        "data": DirectMapping::extract_for_frontend(&entity),
-        "createdAt": entity.created_at
+        "createdAt": entity.created_at.to_string(),
+        "data": entity_record(entity),
     }))
 }
 
-/// Update an existing entity instance with direct JSONB mapping
+/// Update an existing app data record. Only the top-level fields present in `data`
+/// are changed; the merged record must still be valid.
 async fn update_entity(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let entity_id = params.get("entityId")
         .and_then(|v| v.as_str())
         .ok_or((-32602, "Missing required parameter: entityId".to_string()))?;
     
-    let entity_data = params.get("data")
+    let changes = params.get("data")
         .ok_or((-32602, "Missing required parameter: data".to_string()))?
+        .as_object()
+        .ok_or((-32602, "Invalid data: expected an object".to_string()))?
         .clone();
     
-    // Parse entity ID
-    let entity_uuid = Uuid::parse(entity_id)
-        .map_err(|_| (-32602, "Invalid entityId format".to_string()))?;
-    
-    // Create update request with direct mapping
-    let request = DirectMapping::update_entity_request(entity_data, false);
-    
-    let entity = state.services.entity_service.update_entity(entity_uuid, request).await
-        .map_err(|e| (-32603, format!("Failed to update entity: {}", e)))?
-        .ok_or((-32604, "Entity not found".to_string()))?;
+    let existing = find_record(state, entity_id).await?;
+    let entity = state.services.app_database_service
+        .patch_entity(&existing.model_id, None, entity_id, changes)
+        .await
+        .map_err(|e| app_data_error("update entity", e))?;
     
-    // Return the updated entity data directly
     Ok(json!({
         "id": entity.id,
-        "data": DirectMapping::extract_for_frontend(&entity),
-        "updatedAt": entity.updated_at
+        "updatedAt": entity.updated_at.to_string(),
+        "data": entity_record(entity),
     }))
 }
 
-/// Delete an entity instance
+/// Delete an app data record and its attachments
 async fn delete_entity(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let entity_id = params.get("entityId")
         .and_then(|v| v.as_str())
         .ok_or((-32602, "Missing required parameter: entityId".to_string()))?;
     
-    // Parse entity ID
-    let entity_uuid = Uuid::parse(entity_id)
-        .map_err(|_| (-32602, "Invalid entityId format".to_string()))?;
-    
-    let deleted = state.services.entity_service.delete_entity(entity_uuid).await
-        .map_err(|e| (-32603, format!("Failed to delete entity: {}", e)))?;
+    let existing = find_record(state, entity_id).await?;
+    state.services.app_database_service
+        .delete_entity(&existing.model_id, entity_id)
+        .await
+        .map_err(|e| app_data_error("delete entity", e))?;
     
     Ok(json!({
         "id": entity_id,
-        "deleted": deleted,
+        "deleted": true,
         "deletedAt": UtcDateTime::now()
     }))
 }
 
+/// Look up an app data record by ID alone; the frontend doesn't send its model
+async fn find_record(state: &AppState, entity_id: &str) -> Result<AppEntity, (i32, String)> {
+    state.services.app_database_service
+        .find_entity(entity_id)
+        .await
+        .map_err(|e| app_data_error("load entity", e))?
+        .ok_or((-32604, "Entity not found".to_string()))
+}
+
+/// Map app data errors
+fn app_data_error(action: &str, e: crate::Error) -> (i32, String) {
+    match e {
+        crate::Error::NotFound(msg) => (-32604, msg),
+        crate::Error::Validation(msg) => (-32602, msg),
+        e => (-32603, format!("Failed to {}: {}", action, e)),
+    }
+}
+
 /// Get component configuration for UI rendering
 async fn get_component_config(_state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let component_type = params.get("componentType")
@@ -574,6 +628,42 @@ async fn get_layout_config(_state: &AppState, params: &Value) -> Result<Value, (
     Ok(config)
 }
 
+/// Generate list, detail and form layouts for an entity from its UI configuration
+async fn generate_layouts(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let entity = params.get("entityId")
+        .or_else(|| params.get("entityName"))
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: entityId or entityName".to_string()))?;
+
+    let kinds = match params.get("kinds").and_then(|v| v.as_array()) {
+        Some(kinds) => kinds.iter()
+            .map(|k| k.as_str()
+                .ok_or((-32602, "kinds must be an array of strings".to_string()))
+                .and_then(|k| crate::model::layouts::LayoutKind::parse(k).map_err(|e| (-32602, e.to_string()))))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => crate::model::layouts::LayoutKind::ALL.to_vec(),
+    };
+
+    let layouts = state.services.model_service
+        .generate_layouts(model_uuid, entity, &kinds).await
+        .map_err(|e| match e {
+            crate::Error::NotFound(msg) | crate::Error::EntityNotFound(msg) => (-32604, msg),
+            e => (-32603, format!("Failed to generate layouts: {}", e)),
+        })?;
+
+    Ok(json!({
+        "modelId": model_id,
+        "layouts": layouts
+    }))
+}
+
 /// Get model metadata
 async fn get_model_metadata(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let model_id = params.get("modelId")
@@ -585,9 +675,7 @@ async fn get_model_metadata(state: &AppState, params: &Value) -> Result<Value, (
         .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
     
     // Get model from service
-    let model = state.services.model_service.get_model(model_uuid).await
-        .map_err(|e| (-32603, format!("Failed to load model: {}", e)))?
-        .ok_or((-32604, "Model not found".to_string()))?;
+    let model = load_runtime_model(state, model_uuid.clone(), params).await?;
     
     Ok(json!({
         "id": model.id,
@@ -624,7 +712,11 @@ async fn get_capabilities() -> Result<Value, (i32, String)> {
             "flows",
             "layouts",
             "console-session-management",
-            "project-management"
+            "project-management",
+            "model-versioning",
+            "data-migrations",
+            "project-templates",
+            "layout-generation"
         ],
         "supportedComponents": [
             "DataGrid",
@@ -667,7 +759,7 @@ async fn list_projects(state: &AppState, _params: &Value) -> Result<Value, (i32,
     }))
 }
 
-/// Create a new project/model
+/// Create a new project/model, optionally from a model template
 async fn create_project(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let name = params.get("name")
         .and_then(|v| v.as_str())
@@ -676,26 +768,66 @@ async fn create_project(state: &AppState, params: &Value) -> Result<Value, (i32,
     let description = params.get("description")
         .and_then(|v| v.as_str());
     
-    use crate::services::model::CreateModelInput;
-    let input = CreateModelInput {
-        name: name.to_string(),
-        description: description.map(|s| s.to_string()),
-        config: None,
+    let template = params.get("template")
+        .and_then(|v| v.as_str());
+    
+    let model = match template {
+        Some(template_id) => {
+            if crate::model::templates::find_template(template_id).is_none() {
+                return Err((-32602, format!("Unknown project template: {}", template_id)));
+            }
+            state.services.model_service
+                .create_model_from_template(template_id, Some(name.to_string()), description.map(|s| s.to_string()))
+                .await
+                .map_err(|e| (-32603, format!("Failed to create project: {}", e)))?
+        }
+        None => {
+            use crate::services::model::CreateModelInput;
+            let input = CreateModelInput {
+                name: name.to_string(),
+                description: description.map(|s| s.to_string()),
+                config: None,
+            };
+            state.services.model_service.create_model(input).await
+                .map_err(|e| (-32603, format!("Failed to create project: {}", e)))?
+        }
     };
     
-    let model = state.services.model_service.create_model(input).await
-        .map_err(|e| (-32603, format!("Failed to create project: {}", e)))?;
+    // Templates come with seed data, which is loaded unless the caller opts out
+    let load_sample_data = params.get("loadSampleData")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    let mut sample_records = 0;
+    if let (Some(template_id), true) = (template, load_sample_data) {
+        sample_records = state.services.app_database_service
+            .load_template_sample_data(model.id.as_str(), template_id)
+            .await
+            .map_err(|e| (-32603, format!("Failed to load sample data: {}", e)))?;
+    }
     
     Ok(json!({
         "id": model.id,
         "name": model.name,
         "description": model.description,
         "version": model.version,
+        "template": template,
+        "entityCount": model.entities.len(),
+        "sampleRecords": sample_records,
         "createdAt": model.created_at,
         "updatedAt": model.updated_at
     }))
 }
 
+/// List the model templates a project can be created from
+async fn list_project_templates() -> Result<Value, (i32, String)> {
+    let templates = crate::model::templates::template_catalogue();
+    
+    Ok(json!({
+        "templates": templates,
+        "total": templates.len()
+    }))
+}
+
 /// Delete a project/model
 async fn delete_project(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
     let id_str = params.get("id")
@@ -799,6 +931,7 @@ async fn set_project_context(state: &AppState, params: &Value) -> Result<Value,
         let capabilities = vec![
             "listProjects".to_string(),
             "createProject".to_string(),
+            "listProjectTemplates".to_string(),
             "deleteProject".to_string(),
             "getProjectInfo".to_string(),
             "loadPage".to_string(),
@@ -1211,6 +1344,297 @@ async fn execute_auto_remediation(state: &AppState, params: &Value) -> Result<Va
     }))
 }
 
+// === Model Version History ===
+
+/// Parse the required `modelId` and `versionNumber` params
+fn parse_model_version_params(params: &Value) -> Result<(Uuid, i32), (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let version_number = params.get("versionNumber")
+        .and_then(|v| v.as_i64())
+        .and_then(|n| i32::try_from(n).ok())
+        .ok_or((-32602, "Missing required parameter: versionNumber".to_string()))?;
+
+    Ok((model_uuid, version_number))
+}
+
+/// Map model service errors for version operations
+fn version_error(action: &str, e: crate::Error) -> (i32, String) {
+    match e {
+        crate::Error::NotFound(msg) => (-32604, msg),
+        e => (-32603, format!("Failed to {}: {}", action, e)),
+    }
+}
+
+/// List the version history of a model
+async fn list_model_versions(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let versions = state.services.model_service.list_model_versions(model_uuid.clone()).await
+        .map_err(|e| version_error("list model versions", e))?;
+
+    let pin = state.services.model_service.get_model_version_pin(model_uuid).await
+        .map_err(|e| version_error("load version pin", e))?;
+
+    Ok(json!({
+        "modelId": model_id,
+        "versions": versions,
+        "total": versions.len(),
+        "pinnedVersion": pin.map(|p| p.version_number)
+    }))
+}
+
+/// Get a specific version snapshot of a model
+async fn get_model_version(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let (model_uuid, version_number) = parse_model_version_params(params)?;
+
+    let version = state.services.model_service.get_model_version(model_uuid, version_number).await
+        .map_err(|e| version_error("load model version", e))?;
+
+    serde_json::to_value(version)
+        .map_err(|e| (-32603, format!("Failed to serialize model version: {}", e)))
+}
+
+/// Roll a model back to a previous version
+async fn rollback_model(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let (model_uuid, version_number) = parse_model_version_params(params)?;
+
+    let author = params.get("author").and_then(|v| v.as_str()).map(|s| s.to_string());
+    let message = params.get("message").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    let model = state.services.model_service
+        .rollback_model(model_uuid, version_number, author, message).await
+        .map_err(|e| version_error("roll back model", e))?;
+
+    Ok(json!({
+        "id": model.id,
+        "name": model.name,
+        "version": model.version,
+        "updatedAt": model.updated_at,
+        "restoredFrom": version_number
+    }))
+}
+
+/// Pin running apps of a model to a specific version
+async fn pin_model_version(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let (model_uuid, version_number) = parse_model_version_params(params)?;
+
+    let pinned_by = params.get("pinnedBy").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    let pin = state.services.model_service
+        .pin_model_version(model_uuid, version_number, pinned_by).await
+        .map_err(|e| version_error("pin model version", e))?;
+
+    serde_json::to_value(pin)
+        .map_err(|e| (-32603, format!("Failed to serialize version pin: {}", e)))
+}
+
+/// Remove a version pin so running apps follow the latest definition
+async fn unpin_model_version(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let removed = state.services.model_service.unpin_model_version(model_uuid).await
+        .map_err(|e| version_error("unpin model version", e))?;
+
+    Ok(json!({
+        "modelId": model_id,
+        "unpinned": removed
+    }))
+}
+
+/// Structural diff between two versions of a model.
+/// `fromVersion` / `toVersion` default to the current definition when omitted.
+async fn diff_model(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let from_version = optional_version_param(params, "fromVersion")?;
+    let to_version = optional_version_param(params, "toVersion")?;
+
+    let diff = state.services.model_service.diff_model_versions(model_uuid, from_version, to_version).await
+        .map_err(|e| version_error("diff model", e))?;
+
+    Ok(json!({
+        "modelId": model_id,
+        "fromVersion": from_version,
+        "toVersion": to_version,
+        "changes": diff.changes,
+        "summary": {
+            "added": diff.count(crate::model::diff::ChangeKind::Added),
+            "removed": diff.count(crate::model::diff::ChangeKind::Removed),
+            "modified": diff.count(crate::model::diff::ChangeKind::Modified)
+        }
+    }))
+}
+
+/// Three-way merge an edited model into the current definition.
+/// The merge is only saved when it has no conflicts.
+async fn merge_model(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let base_version = optional_version_param(params, "baseVersion")?
+        .ok_or((-32602, "Missing required parameter: baseVersion".to_string()))?;
+
+    let incoming: crate::model::types::TorqueModel = params.get("model")
+        .cloned()
+        .ok_or((-32602, "Missing required parameter: model".to_string()))
+        .and_then(|v| serde_json::from_value(v)
+            .map_err(|e| (-32602, format!("Invalid model definition: {}", e))))?;
+
+    let author = params.get("author").and_then(|v| v.as_str()).map(|s| s.to_string());
+    let message = params.get("message").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    let result = state.services.model_service
+        .merge_model(model_uuid, base_version, incoming, author, message).await
+        .map_err(|e| version_error("merge model", e))?;
+
+    Ok(json!({
+        "modelId": model_id,
+        "applied": result.is_clean(),
+        "conflicts": result.conflicts,
+        "model": result.merged
+    }))
+}
+
+// === App Data Migrations ===
+
+/// Model, from and to versions, and orphan strategy of a data migration request
+type DataMigrationParams = (Uuid, Option<i32>, Option<i32>, crate::model::data_migration::OrphanStrategy);
+
+/// Parse the model and version range params shared by preview and run
+fn parse_data_migration_params(params: &Value) -> Result<DataMigrationParams, (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let from_version = optional_version_param(params, "fromVersion")?;
+    let to_version = optional_version_param(params, "toVersion")?;
+
+    let orphan_strategy = match params.get("orphanStrategy") {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|_| (-32602, "Invalid orphanStrategy: expected 'archive' or 'delete'".to_string()))?,
+        None => Default::default(),
+    };
+
+    Ok((model_uuid, from_version, to_version, orphan_strategy))
+}
+
+/// Read an optional version number param, rejecting values that don't fit a version
+fn optional_version_param(params: &Value, key: &str) -> Result<Option<i32>, (i32, String)> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v.as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(Some)
+            .ok_or((-32602, format!("Invalid {}: expected a version number", key))),
+    }
+}
+
+/// Map data migration errors
+fn data_migration_error(action: &str, e: crate::Error) -> (i32, String) {
+    match e {
+        crate::Error::NotFound(msg) => (-32604, msg),
+        crate::Error::Validation(msg) => (-32602, msg),
+        e => (-32603, format!("Failed to {}: {}", action, e)),
+    }
+}
+
+/// Dry run the data migration between two model versions
+async fn preview_data_migration(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let (model_uuid, from_version, to_version, orphan_strategy) = parse_data_migration_params(params)?;
+
+    let preview = state.services.data_migration_service
+        .preview(&model_uuid, from_version, to_version, orphan_strategy).await
+        .map_err(|e| data_migration_error("preview data migration", e))?;
+
+    serde_json::to_value(preview)
+        .map_err(|e| (-32603, format!("Failed to serialize data migration preview: {}", e)))
+}
+
+/// Start a tracked data migration job
+async fn run_data_migration(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let (model_uuid, from_version, to_version, orphan_strategy) = parse_data_migration_params(params)?;
+
+    let job = state.services.data_migration_service
+        .start_job(&model_uuid, from_version, to_version, orphan_strategy).await
+        .map_err(|e| data_migration_error("start data migration", e))?;
+
+    serde_json::to_value(job)
+        .map_err(|e| (-32603, format!("Failed to serialize data migration job: {}", e)))
+}
+
+/// Get the status of a data migration job
+async fn get_data_migration_job(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let job_id = params.get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: jobId".to_string()))?;
+
+    let job = state.services.data_migration_service.get_job(job_id).await
+        .map_err(|e| data_migration_error("load data migration job", e))?;
+
+    serde_json::to_value(job)
+        .map_err(|e| (-32603, format!("Failed to serialize data migration job: {}", e)))
+}
+
+/// List the data migration jobs of a model, newest first
+async fn list_data_migration_jobs(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let model_id = params.get("modelId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: modelId".to_string()))?;
+
+    let model_uuid = Uuid::parse(model_id)
+        .map_err(|_| (-32602, "Invalid modelId format".to_string()))?;
+
+    let jobs = state.services.data_migration_service.list_jobs(&model_uuid).await
+        .map_err(|e| data_migration_error("list data migration jobs", e))?;
+
+    Ok(json!({
+        "modelId": model_id,
+        "jobs": jobs,
+        "total": jobs.len()
+    }))
+}
+
+/// Restore the rows touched by a completed data migration job
+async fn rollback_data_migration(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let job_id = params.get("jobId")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: jobId".to_string()))?;
+
+    let job = state.services.data_migration_service.rollback_job(job_id).await
+        .map_err(|e| data_migration_error("roll back data migration", e))?;
+
+    serde_json::to_value(job)
+        .map_err(|e| (-32603, format!("Failed to serialize data migration job: {}", e)))
+}
+
 /// Format command history for display
 fn format_command_history(history: &[String]) -> String {
     if history.is_empty() {
@@ -1230,7 +1654,7 @@ fn format_command_history(history: &[String]) -> String {
 }
 
 /// Validate JSON-RPC request format
-fn validate_jsonrpc_request(request: &Value) -> Result<(), (i32, String)> {
+pub fn validate_jsonrpc_request(request: &Value) -> Result<(), (i32, String)> {
     // Check jsonrpc version
     if request.get("jsonrpc").and_then(|v| v.as_str()) != Some("2.0") {
         return Err((-32600, "Invalid Request: missing or invalid jsonrpc field".to_string()));
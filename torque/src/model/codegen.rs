@@ -0,0 +1,654 @@
+use crate::error::Error;
+use crate::model::types::{EntityField, FieldType, ModelEntity, ModelRelationship, TorqueModel};
+use std::fmt::Write;
+
+/// Target language of generated client code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodegenLanguage {
+    TypeScript,
+    Rust,
+}
+
+impl CodegenLanguage {
+    pub fn parse(lang: &str) -> Result<Self, Error> {
+        match lang.to_lowercase().as_str() {
+            "ts" | "typescript" => Ok(Self::TypeScript),
+            "rs" | "rust" => Ok(Self::Rust),
+            other => Err(Error::InvalidInput(format!("Unsupported codegen language '{}' (expected ts or rust)", other))),
+        }
+    }
+
+    /// Conventional file extension for generated sources
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::TypeScript => "ts",
+            Self::Rust => "rs",
+        }
+    }
+}
+
+/// Generate a typed client for a model: entity types, CRUD methods over JSON-RPC
+/// and helpers that follow relationships
+pub fn generate_client(model: &TorqueModel, lang: CodegenLanguage) -> String {
+    match lang {
+        CodegenLanguage::TypeScript => typescript_client(model),
+        CodegenLanguage::Rust => rust_client(model),
+    }
+}
+
+fn header(model: &TorqueModel, comment: &str) -> String {
+    format!(
+        "{c} Generated by `torque model codegen` from model \"{}\" ({}, version {}).\n{c} Do not edit by hand; regenerate after changing the model.\n",
+        model.name, model.id, model.version, c = comment
+    )
+}
+
+/// Relationships whose entities both exist in the model
+pub(crate) fn relationships(model: &TorqueModel) -> impl Iterator<Item = (&ModelRelationship, &ModelEntity, &ModelEntity)> {
+    model.relationships.iter().filter_map(|r| {
+        let from = model.entities.iter().find(|e| e.id == r.from_entity)?;
+        let to = model.entities.iter().find(|e| e.id == r.to_entity)?;
+        Some((r, from, to))
+    })
+}
+
+// TypeScript
+
+fn typescript_client(model: &TorqueModel) -> String {
+    let mut out = header(model, "//");
+    out.push_str(TS_PRELUDE);
+
+    for entity in &model.entities {
+        let type_name = pascal_case(&entity.name);
+
+        for field in &entity.fields {
+            if let Some(values) = enum_values(&field.field_type) {
+                let union = values.iter().map(|v| ts_string(v)).collect::<Vec<_>>().join(" | ");
+                let _ = writeln!(out, "\nexport type {} = {}", enum_type_name(entity, field), union);
+            }
+        }
+
+        let _ = writeln!(out, "\n/** {} */", entity.description.as_deref().unwrap_or(&entity.display_name));
+        let _ = writeln!(out, "export interface {} {{", type_name);
+        for field in &entity.fields {
+            let ty = ts_type(entity, field, &field.field_type);
+            if field.required {
+                let _ = writeln!(out, "  {}: {}", ts_property(&field.name), ty);
+            } else {
+                let _ = writeln!(out, "  {}?: {} | null", ts_property(&field.name), ty);
+            }
+        }
+        out.push_str("}\n");
+        let _ = writeln!(out, "\nexport type {0}Record = {0} & RecordMeta", type_name);
+    }
+
+    let client_name = format!("{}Client", pascal_case(&model.name));
+    let _ = writeln!(out, "\nexport class {} {{", client_name);
+    let _ = writeln!(
+        out,
+        "  constructor(private readonly rpc: RpcTransport, readonly modelId: string = {}) {{}}",
+        ts_string(model.id.as_str())
+    );
+
+    for entity in &model.entities {
+        let type_name = pascal_case(&entity.name);
+        let entity_name = ts_string(&entity.name);
+        let _ = write!(
+            out,
+            r#"
+  list{t}(page = 1, limit = 20, filters: Filters<{t}> = {{}}): Promise<Page<{t}Record>> {{
+    return this.rpc.call('loadEntityData', {{ modelId: this.modelId, entityName: {n}, page, limit, filters }})
+  }}
+
+  create{t}(data: {t}): Promise<Mutation<{t}>> {{
+    return this.rpc.call('createEntity', {{ modelId: this.modelId, entityName: {n}, data }})
+  }}
+
+  /** Only fields present in `data` are changed */
+  update{t}(entityId: string, data: Partial<{t}>): Promise<Mutation<{t}>> {{
+    return this.rpc.call('updateEntity', {{ entityId, data }})
+  }}
+
+  delete{t}(entityId: string): Promise<Deletion> {{
+    return this.rpc.call('deleteEntity', {{ entityId }})
+  }}
+"#,
+            t = type_name,
+            n = entity_name,
+        );
+    }
+
+    for (relationship, from, to) in relationships(model) {
+        let method = camel_case(&relationship.name);
+        let (from_type, to_type) = (pascal_case(&from.name), pascal_case(&to.name));
+        let _ = write!(
+            out,
+            r#"
+  /** {to_display} records referenced by a {from_display} through `{rel}` */
+  async {m}(record: {f}Record): Promise<{t}Record[]> {{
+    const filters = {{ {to_field}: record{from_access} }} as Filters<{t}>
+    return collectPages(page => this.list{t}(page, RELATIONSHIP_PAGE_SIZE, filters))
+  }}
+
+  /** {from_display} records that reference a {to_display} through `{rel}` */
+  async {m}Inverse(record: {t}Record): Promise<{f}Record[]> {{
+    const filters = {{ {from_field}: record{to_access} }} as Filters<{f}>
+    return collectPages(page => this.list{f}(page, RELATIONSHIP_PAGE_SIZE, filters))
+  }}
+"#,
+            rel = relationship.name,
+            m = method,
+            f = from_type,
+            t = to_type,
+            from_display = from.display_name,
+            to_display = to.display_name,
+            to_field = ts_property(&relationship.to_field),
+            from_field = ts_property(&relationship.from_field),
+            from_access = ts_access(&relationship.from_field),
+            to_access = ts_access(&relationship.to_field),
+        );
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+const TS_PRELUDE: &str = r#"
+/** Anything that can make a JSON-RPC call, e.g. the torque-client `JsonRpcClient` */
+export interface RpcTransport {
+  call<T = any>(method: string, params?: Record<string, any>): Promise<T>
+}
+
+/** Records are referenced by ID; sample data uses numeric IDs */
+export type RecordId = string | number
+
+export interface RecordMeta {
+  _id: string
+  _created_at: string
+  _updated_at: string
+}
+
+export interface Page<T> {
+  data: T[]
+  pagination: {
+    page: number
+    limit: number
+    total: number
+    totalPages: number
+    hasNextPage: boolean
+    hasPreviousPage: boolean
+  }
+}
+
+export interface Mutation<T> {
+  id: string
+  data: T
+}
+
+export interface Deletion {
+  id: string
+  deleted: boolean
+}
+
+/** Equality filters on top-level fields */
+export type Filters<T> = { [K in keyof T]?: T[K] }
+
+/** Records fetched per request when following a relationship */
+const RELATIONSHIP_PAGE_SIZE = 100
+
+/** Fetch every page of a listing, following `hasNextPage` */
+async function collectPages<T>(list: (page: number) => Promise<Page<T>>): Promise<T[]> {
+  const records: T[] = []
+  for (let page = 1; ; page++) {
+    const result = await list(page)
+    records.push(...result.data)
+    if (!result.pagination.hasNextPage || result.data.length === 0) {
+      return records
+    }
+  }
+}
+"#;
+
+fn ts_type(entity: &ModelEntity, field: &EntityField, field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::String { .. } | FieldType::DateTime | FieldType::Date | FieldType::Time | FieldType::Binary => {
+            "string".to_string()
+        }
+        FieldType::Integer { .. } | FieldType::Float { .. } => "number".to_string(),
+        FieldType::Boolean => "boolean".to_string(),
+        FieldType::Json => "unknown".to_string(),
+        FieldType::Enum { .. } => enum_type_name(entity, field),
+        FieldType::Reference { .. } => "RecordId".to_string(),
+        FieldType::Array { element_type } => {
+            let element = ts_type(entity, field, element_type);
+            if element.contains(' ') { format!("Array<{}>", element) } else { format!("{}[]", element) }
+        }
+    }
+}
+
+fn ts_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn ts_property(name: &str) -> String {
+    if is_identifier(name) { name.to_string() } else { ts_string(name) }
+}
+
+fn ts_access(name: &str) -> String {
+    if is_identifier(name) { format!(".{}", name) } else { format!("[{}]", ts_string(name)) }
+}
+
+// Rust
+
+fn rust_client(model: &TorqueModel) -> String {
+    let mut out = header(model, "//");
+    out.push_str(RUST_PRELUDE);
+
+    for entity in &model.entities {
+        let type_name = pascal_case(&entity.name);
+
+        for field in &entity.fields {
+            if let Some(values) = enum_values(&field.field_type) {
+                let _ = writeln!(out, "\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]");
+                let _ = writeln!(out, "pub enum {} {{", enum_type_name(entity, field));
+                for (variant, value) in enum_variants(values) {
+                    if variant != *value {
+                        let _ = writeln!(out, "    #[serde(rename = {:?})]", value);
+                    }
+                    let _ = writeln!(out, "    {},", variant);
+                }
+                out.push_str("}\n");
+            }
+        }
+
+        let _ = writeln!(out, "\n/// {}", entity.description.as_deref().unwrap_or(&entity.display_name));
+        out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        let _ = writeln!(out, "pub struct {} {{", type_name);
+        for field in &entity.fields {
+            let ident = rust_field_ident(&field.name);
+            if ident.trim_start_matches("r#") != field.name {
+                let _ = writeln!(out, "    #[serde(rename = {:?})]", field.name);
+            }
+            let ty = rust_type(entity, field, &field.field_type);
+            if field.required {
+                let _ = writeln!(out, "    pub {}: {},", ident, ty);
+            } else {
+                out.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+                let _ = writeln!(out, "    pub {}: Option<{}>,", ident, ty);
+            }
+        }
+        out.push_str("}\n");
+    }
+
+    let client_name = format!("{}Client", pascal_case(&model.name));
+    let _ = write!(
+        out,
+        r#"
+pub struct {c}<T: RpcTransport> {{
+    transport: T,
+    model_id: String,
+}}
+
+impl<T: RpcTransport> {c}<T> {{
+    pub fn new(transport: T) -> Self {{
+        Self::with_model_id(transport, {id:?})
+    }}
+
+    pub fn with_model_id(transport: T, model_id: impl Into<String>) -> Self {{
+        Self {{ transport, model_id: model_id.into() }}
+    }}
+
+    async fn call<R: DeserializeOwned>(&self, method: &str, params: Value) -> Result<R, RpcError> {{
+        let result = self.transport.call(method, params).await?;
+        serde_json::from_value(result).map_err(|e| RpcError::Decode(e.to_string()))
+    }}
+"#,
+        c = client_name,
+        id = model.id.to_string(),
+    );
+
+    for entity in &model.entities {
+        let type_name = pascal_case(&entity.name);
+        let fn_name = snake_case(&entity.name);
+        let _ = write!(
+            out,
+            r#"
+    pub async fn list_{f}(&self, page: u64, limit: u64, filters: Map<String, Value>) -> Result<Page<{t}>, RpcError> {{
+        self.call("loadEntityData", json!({{ "modelId": self.model_id, "entityName": {n:?}, "page": page, "limit": limit, "filters": filters }})).await
+    }}
+
+    pub async fn create_{f}(&self, data: &{t}) -> Result<Mutation<{t}>, RpcError> {{
+        self.call("createEntity", json!({{ "modelId": self.model_id, "entityName": {n:?}, "data": data }})).await
+    }}
+
+    /// Only fields present in `data` are changed
+    pub async fn update_{f}(&self, entity_id: &str, data: Value) -> Result<Mutation<{t}>, RpcError> {{
+        self.call("updateEntity", json!({{ "entityId": entity_id, "data": data }})).await
+    }}
+
+    pub async fn delete_{f}(&self, entity_id: &str) -> Result<Deletion, RpcError> {{
+        self.call("deleteEntity", json!({{ "entityId": entity_id }})).await
+    }}
+"#,
+            f = fn_name,
+            t = type_name,
+            n = entity.name,
+        );
+    }
+
+    for (relationship, from, to) in relationships(model) {
+        let method = snake_case(&relationship.name);
+        let _ = write!(
+            out,
+            r#"
+    /// {to_display} records referenced by a {from_display} through `{rel}`
+    pub async fn {m}(&self, record: &Record<{f}>) -> Result<Vec<Record<{t}>>, RpcError> {{
+        let filters = relationship_filter({to_field:?}, &record.data, {from_field:?})?;
+        let mut records = Vec::new();
+        for page in 1.. {{
+            let result = self.list_{t_fn}(page, RELATIONSHIP_PAGE_SIZE, filters.clone()).await?;
+            let done = !result.pagination.has_next_page || result.data.is_empty();
+            records.extend(result.data);
+            if done {{
+                break;
+            }}
+        }}
+        Ok(records)
+    }}
+
+    /// {from_display} records that reference a {to_display} through `{rel}`
+    pub async fn {m}_inverse(&self, record: &Record<{t}>) -> Result<Vec<Record<{f}>>, RpcError> {{
+        let filters = relationship_filter({from_field:?}, &record.data, {to_field:?})?;
+        let mut records = Vec::new();
+        for page in 1.. {{
+            let result = self.list_{f_fn}(page, RELATIONSHIP_PAGE_SIZE, filters.clone()).await?;
+            let done = !result.pagination.has_next_page || result.data.is_empty();
+            records.extend(result.data);
+            if done {{
+                break;
+            }}
+        }}
+        Ok(records)
+    }}
+"#,
+            rel = relationship.name,
+            m = method,
+            f = pascal_case(&from.name),
+            t = pascal_case(&to.name),
+            f_fn = snake_case(&from.name),
+            t_fn = snake_case(&to.name),
+            from_display = from.display_name,
+            to_display = to.display_name,
+            from_field = relationship.from_field,
+            to_field = relationship.to_field,
+        );
+    }
+
+    out.push_str("}\n");
+    out.push_str(RUST_HELPERS);
+    out
+}
+
+const RUST_PRELUDE: &str = r#"
+#![allow(dead_code)]
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::future::Future;
+
+#[derive(Debug)]
+pub enum RpcError {
+    /// Error returned by the server
+    Rpc { code: i64, message: String },
+    /// Failure to reach the server
+    Transport(String),
+    /// Response didn't match the generated types
+    Decode(String),
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rpc { code, message } => write!(f, "JSON-RPC error {}: {}", code, message),
+            Self::Transport(message) => write!(f, "Transport error: {}", message),
+            Self::Decode(message) => write!(f, "Invalid response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// Sends a JSON-RPC request and returns its `result`
+pub trait RpcTransport {
+    fn call(&self, method: &str, params: Value) -> impl Future<Output = Result<Value, RpcError>> + Send;
+}
+
+/// Records are referenced by ID; sample data uses numeric IDs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RecordId {
+    Number(i64),
+    String(String),
+}
+
+/// Stored record with its metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record<T> {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_created_at")]
+    pub created_at: String,
+    #[serde(rename = "_updated_at")]
+    pub updated_at: String,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pagination {
+    pub page: u64,
+    pub limit: u64,
+    pub total: u64,
+    pub total_pages: u64,
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub data: Vec<Record<T>>,
+    pub pagination: Pagination,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mutation<T> {
+    pub id: String,
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deletion {
+    pub id: String,
+    pub deleted: bool,
+}
+"#;
+
+const RUST_HELPERS: &str = r#"
+/// Records fetched per request when following a relationship
+const RELATIONSHIP_PAGE_SIZE: u64 = 100;
+
+/// Equality filter matching `field` against `source_field` of a record
+fn relationship_filter<S: Serialize>(field: &str, record: &S, source_field: &str) -> Result<Map<String, Value>, RpcError> {
+    let record = serde_json::to_value(record).map_err(|e| RpcError::Decode(e.to_string()))?;
+    let mut filters = Map::new();
+    filters.insert(field.to_string(), record.get(source_field).cloned().unwrap_or(Value::Null));
+    Ok(filters)
+}
+"#;
+
+fn rust_type(entity: &ModelEntity, field: &EntityField, field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::String { .. } | FieldType::DateTime | FieldType::Date | FieldType::Time | FieldType::Binary => {
+            "String".to_string()
+        }
+        FieldType::Integer { .. } => "i64".to_string(),
+        FieldType::Float { .. } => "f64".to_string(),
+        FieldType::Boolean => "bool".to_string(),
+        FieldType::Json => "Value".to_string(),
+        FieldType::Enum { .. } => enum_type_name(entity, field),
+        FieldType::Reference { .. } => "RecordId".to_string(),
+        FieldType::Array { element_type } => format!("Vec<{}>", rust_type(entity, field, element_type)),
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+fn rust_field_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    if RUST_KEYWORDS.contains(&ident.as_str()) { format!("r#{}", ident) } else { ident }
+}
+
+/// Variant names for enum values, kept unique
+fn enum_variants(values: &[String]) -> Vec<(String, &String)> {
+    let mut variants: Vec<(String, &String)> = Vec::with_capacity(values.len());
+    for value in values {
+        let mut variant = pascal_case(value);
+        if variant.is_empty() || variant.starts_with(|c: char| c.is_ascii_digit()) {
+            variant = format!("V{}", variant);
+        }
+        let base = variant.clone();
+        let mut n = 2;
+        while variants.iter().any(|(v, _)| *v == variant) {
+            variant = format!("{}{}", base, n);
+            n += 1;
+        }
+        variants.push((variant, value));
+    }
+    variants
+}
+
+// Shared naming helpers
+
+fn enum_values(field_type: &FieldType) -> Option<&[String]> {
+    match field_type {
+        FieldType::Enum { values } => Some(values),
+        FieldType::Array { element_type } => enum_values(element_type),
+        _ => None,
+    }
+}
+
+fn enum_type_name(entity: &ModelEntity, field: &EntityField) -> String {
+    format!("{}{}", pascal_case(&entity.name), pascal_case(&field.name))
+}
+
+/// Lowercase ASCII words of an identifier or label, split on other characters and
+/// lower-to-upper case changes. A name without any becomes `field` and its code points.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    if words.is_empty() && !name.is_empty() {
+        let code_points: String = name.chars().map(|c| format!("{:x}", c as u32)).collect();
+        words = vec!["field".to_string(), code_points];
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+pub(crate) fn pascal_case(name: &str) -> String {
+    words(name).iter().map(|w| capitalize(w)).collect()
+}
+
+pub(crate) fn camel_case(name: &str) -> String {
+    let words = words(name);
+    let mut out = String::new();
+    for (i, word) in words.iter().enumerate() {
+        if i == 0 { out.push_str(word) } else { out.push_str(&capitalize(word)) }
+    }
+    if out.starts_with(|c: char| c.is_ascii_digit()) { format!("_{}", out) } else { out }
+}
+
+fn snake_case(name: &str) -> String {
+    let out = words(name).join("_");
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) { format!("_{}", out) } else { out }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naming() {
+        assert_eq!(pascal_case("order_items"), "OrderItems");
+        assert_eq!(pascal_case("In Progress"), "InProgress");
+        assert_eq!(camel_case("task_project"), "taskProject");
+        assert_eq!(snake_case("customerEmail"), "customer_email");
+        assert_eq!(rust_field_ident("type"), "r#type");
+        assert_eq!(ts_property("first-name"), "'first-name'");
+        assert_eq!(ts_access("id"), ".id");
+    }
+
+    #[test]
+    fn test_non_ascii_names() {
+        assert_eq!(pascal_case("Straße"), "StraE");
+        assert_eq!(pascal_case("名前"), "Field540d524d");
+        assert_eq!(camel_case("名前"), "field540d524d");
+        assert_eq!(rust_field_ident("名前"), "field_540d524d");
+        assert_ne!(rust_field_ident("名前"), rust_field_ident("住所"));
+
+        let values = vec!["高".to_string()];
+        assert_eq!(enum_variants(&values)[0].0, "Field9ad8");
+    }
+
+    #[test]
+    fn test_enum_variants_are_unique() {
+        let values = vec!["In Progress".to_string(), "in_progress".to_string(), "1st".to_string()];
+        let variants: Vec<String> = enum_variants(&values).into_iter().map(|(v, _)| v).collect();
+        assert_eq!(variants, vec!["InProgress", "InProgress2", "V1st"]);
+    }
+
+    #[test]
+    fn test_parse_language() {
+        assert_eq!(CodegenLanguage::parse("ts").unwrap(), CodegenLanguage::TypeScript);
+        assert_eq!(CodegenLanguage::parse("Rust").unwrap(), CodegenLanguage::Rust);
+        assert!(CodegenLanguage::parse("go").is_err());
+    }
+}
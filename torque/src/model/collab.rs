@@ -0,0 +1,418 @@
+use crate::common::{Uuid, UtcDateTime};
+use crate::error::Error;
+use crate::model::types::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Model properties that can be changed with `setModelProperty`
+const MODEL_PROPERTIES: &[&str] = &["name", "description", "version", "config"];
+
+/// Fine-grained edit of a model, sent by collaborative editors.
+///
+/// `update*` operations carry only the changed top-level properties of the item so edits of
+/// different properties by different editors can be applied concurrently.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum EditOperation {
+    SetModelProperty { property: String, value: Value },
+    AddEntity { entity: ModelEntity },
+    #[serde(rename_all = "camelCase")]
+    UpdateEntity { entity_id: Uuid, changes: Map<String, Value> },
+    #[serde(rename_all = "camelCase")]
+    RemoveEntity { entity_id: Uuid },
+    #[serde(rename_all = "camelCase")]
+    AddField { entity_id: Uuid, field: EntityField },
+    #[serde(rename_all = "camelCase")]
+    UpdateField { entity_id: Uuid, field_id: Uuid, changes: Map<String, Value> },
+    #[serde(rename_all = "camelCase")]
+    RemoveField { entity_id: Uuid, field_id: Uuid },
+    AddRelationship { relationship: ModelRelationship },
+    #[serde(rename_all = "camelCase")]
+    UpdateRelationship { relationship_id: Uuid, changes: Map<String, Value> },
+    #[serde(rename_all = "camelCase")]
+    RemoveRelationship { relationship_id: Uuid },
+    AddLayout { layout: ModelLayout },
+    #[serde(rename_all = "camelCase")]
+    UpdateLayout { layout_id: Uuid, changes: Map<String, Value> },
+    #[serde(rename_all = "camelCase")]
+    RemoveLayout { layout_id: Uuid },
+    AddFlow { flow: ModelFlow },
+    #[serde(rename_all = "camelCase")]
+    UpdateFlow { flow_id: Uuid, changes: Map<String, Value> },
+    #[serde(rename_all = "camelCase")]
+    RemoveFlow { flow_id: Uuid },
+}
+
+/// Part of a model touched by an operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditTarget {
+    /// Path from the model root, e.g. `["entities", <id>, "fields", <id>]`
+    pub path: Vec<String>,
+    /// Properties changed by an update; `None` when the whole item is added or removed
+    pub keys: Option<Vec<String>>,
+}
+
+impl EditOperation {
+    /// Part of the model this operation reads and writes
+    pub fn target(&self) -> EditTarget {
+        fn item(kind: &str, id: &Uuid) -> Vec<String> {
+            vec![kind.to_string(), id.to_string()]
+        }
+        fn keys(changes: &Map<String, Value>) -> Option<Vec<String>> {
+            Some(changes.keys().cloned().collect())
+        }
+        let (path, keys) = match self {
+            Self::SetModelProperty { property, .. } => (vec!["model".to_string()], Some(vec![property.clone()])),
+            Self::AddEntity { entity } => (item("entities", &entity.id), None),
+            Self::UpdateEntity { entity_id, changes } => (item("entities", entity_id), keys(changes)),
+            Self::RemoveEntity { entity_id } => (item("entities", entity_id), None),
+            Self::AddField { entity_id, field } => ([item("entities", entity_id), item("fields", &field.id)].concat(), None),
+            Self::UpdateField { entity_id, field_id, changes } => ([item("entities", entity_id), item("fields", field_id)].concat(), keys(changes)),
+            Self::RemoveField { entity_id, field_id } => ([item("entities", entity_id), item("fields", field_id)].concat(), None),
+            Self::AddRelationship { relationship } => (item("relationships", &relationship.id), None),
+            Self::UpdateRelationship { relationship_id, changes } => (item("relationships", relationship_id), keys(changes)),
+            Self::RemoveRelationship { relationship_id } => (item("relationships", relationship_id), None),
+            Self::AddLayout { layout } => (item("layouts", &layout.id), None),
+            Self::UpdateLayout { layout_id, changes } => (item("layouts", layout_id), keys(changes)),
+            Self::RemoveLayout { layout_id } => (item("layouts", layout_id), None),
+            Self::AddFlow { flow } => (item("flows", &flow.id), None),
+            Self::UpdateFlow { flow_id, changes } => (item("flows", flow_id), keys(changes)),
+            Self::RemoveFlow { flow_id } => (item("flows", flow_id), None),
+        };
+        EditTarget { path, keys }
+    }
+
+    /// Whether this operation can't be applied after `other` without losing one of the edits.
+    ///
+    /// Updates of different properties of the same item commute, as do edits of an item's
+    /// children and updates of the item itself. Anything else touching the same item conflicts.
+    pub fn conflicts_with(&self, other: &EditOperation) -> bool {
+        let (a, b) = (self.target(), other.target());
+        if a.path == b.path {
+            return match (&a.keys, &b.keys) {
+                (Some(a), Some(b)) => a.iter().any(|k| b.contains(k)),
+                _ => true,
+            };
+        }
+        let (shorter, longer) = if a.path.len() < b.path.len() { (&a, &b) } else { (&b, &a) };
+        longer.path.starts_with(&shorter.path) && shorter.keys.is_none()
+    }
+
+    /// Short description for version history messages
+    pub fn description(&self) -> String {
+        let target = self.target();
+        let name = match self {
+            Self::SetModelProperty { .. } => "set",
+            Self::AddEntity { .. } | Self::AddField { .. } | Self::AddRelationship { .. }
+            | Self::AddLayout { .. } | Self::AddFlow { .. } => "add",
+            Self::UpdateEntity { .. } | Self::UpdateField { .. } | Self::UpdateRelationship { .. }
+            | Self::UpdateLayout { .. } | Self::UpdateFlow { .. } => "update",
+            _ => "remove",
+        };
+        match target.keys {
+            Some(keys) => format!("{} {} ({})", name, target.path.join("/"), keys.join(", ")),
+            None => format!("{} {}", name, target.path.join("/")),
+        }
+    }
+
+    /// Apply the operation to a model in place
+    pub fn apply(&self, model: &mut TorqueModel) -> Result<(), Error> {
+        let now = UtcDateTime::now();
+        match self {
+            Self::SetModelProperty { property, value } => {
+                if !MODEL_PROPERTIES.contains(&property.as_str()) {
+                    return Err(Error::Validation(format!("Model property '{}' can't be edited", property)));
+                }
+                merge(model, &Map::from_iter([(property.clone(), value.clone())]), &[])?;
+            }
+            Self::AddEntity { entity } => {
+                if model.entities.iter().any(|e| e.id == entity.id || e.name == entity.name) {
+                    return Err(Error::Validation(format!("Entity '{}' already exists", entity.name)));
+                }
+                model.entities.push(entity.clone());
+            }
+            Self::UpdateEntity { entity_id, changes } => {
+                if let Some(name) = changes.get("name").and_then(Value::as_str) {
+                    if model.entities.iter().any(|e| &e.id != entity_id && e.name == name) {
+                        return Err(Error::Validation(format!("Entity '{}' already exists", name)));
+                    }
+                }
+                let entity = find_mut(&mut model.entities, |e| &e.id == entity_id, "Entity", entity_id)?;
+                // Fields are edited with the field operations so they merge independently
+                merge(entity, changes, &["id", "fields"])?;
+            }
+            Self::RemoveEntity { entity_id } => {
+                // Drops whatever refers to the entity, the same as deleting it with reference cleanup
+                model.remove_entity(entity_id, true)
+                    .ok_or_else(|| Error::NotFound(format!("Entity with id {} not found", entity_id)))?;
+            }
+            Self::AddField { entity_id, field } => {
+                let entity = find_mut(&mut model.entities, |e| &e.id == entity_id, "Entity", entity_id)?;
+                if entity.fields.iter().any(|f| f.id == field.id || f.name == field.name) {
+                    return Err(Error::Validation(format!("Field '{}' already exists on entity '{}'", field.name, entity.name)));
+                }
+                entity.fields.push(field.clone());
+            }
+            Self::UpdateField { entity_id, field_id, changes } => {
+                let entity = find_mut(&mut model.entities, |e| &e.id == entity_id, "Entity", entity_id)?;
+                if let Some(name) = changes.get("name").and_then(Value::as_str) {
+                    if entity.fields.iter().any(|f| &f.id != field_id && f.name == name) {
+                        return Err(Error::Validation(format!("Field '{}' already exists on entity '{}'", name, entity.name)));
+                    }
+                }
+                let field = find_mut(&mut entity.fields, |f| &f.id == field_id, "Field", field_id)?;
+                merge(field, changes, &["id"])?;
+            }
+            Self::RemoveField { entity_id, field_id } => {
+                let entity = find_mut(&mut model.entities, |e| &e.id == entity_id, "Entity", entity_id)?;
+                find_mut(&mut entity.fields, |f| &f.id == field_id, "Field", field_id)?;
+                entity.fields.retain(|f| &f.id != field_id);
+            }
+            Self::AddRelationship { relationship } => {
+                for entity_id in [&relationship.from_entity, &relationship.to_entity] {
+                    if !model.entities.iter().any(|e| &e.id == entity_id) {
+                        return Err(Error::Validation(format!("Relationship refers to unknown entity {}", entity_id)));
+                    }
+                }
+                if model.relationships.iter().any(|r| r.id == relationship.id) {
+                    return Err(Error::Validation(format!("Relationship '{}' already exists", relationship.name)));
+                }
+                model.relationships.push(relationship.clone());
+            }
+            Self::UpdateRelationship { relationship_id, changes } => {
+                let relationship = find_mut(&mut model.relationships, |r| &r.id == relationship_id, "Relationship", relationship_id)?;
+                merge(relationship, changes, &["id"])?;
+            }
+            Self::RemoveRelationship { relationship_id } => {
+                find_mut(&mut model.relationships, |r| &r.id == relationship_id, "Relationship", relationship_id)?;
+                model.relationships.retain(|r| &r.id != relationship_id);
+            }
+            Self::AddLayout { layout } => {
+                if model.layouts.iter().any(|l| l.id == layout.id) {
+                    return Err(Error::Validation(format!("Layout '{}' already exists", layout.name)));
+                }
+                model.layouts.push(layout.clone());
+            }
+            Self::UpdateLayout { layout_id, changes } => {
+                let layout = find_mut(&mut model.layouts, |l| &l.id == layout_id, "Layout", layout_id)?;
+                merge(layout, changes, &["id", "created_at"])?;
+                layout.updated_at = now.clone();
+            }
+            Self::RemoveLayout { layout_id } => {
+                find_mut(&mut model.layouts, |l| &l.id == layout_id, "Layout", layout_id)?;
+                model.layouts.retain(|l| &l.id != layout_id);
+            }
+            Self::AddFlow { flow } => {
+                if model.flows.iter().any(|f| f.id == flow.id) {
+                    return Err(Error::Validation(format!("Flow '{}' already exists", flow.name)));
+                }
+                model.flows.push(flow.clone());
+            }
+            Self::UpdateFlow { flow_id, changes } => {
+                let flow = find_mut(&mut model.flows, |f| &f.id == flow_id, "Flow", flow_id)?;
+                merge(flow, changes, &["id"])?;
+            }
+            Self::RemoveFlow { flow_id } => {
+                find_mut(&mut model.flows, |f| &f.id == flow_id, "Flow", flow_id)?;
+                model.flows.retain(|f| &f.id != flow_id);
+            }
+        }
+        model.updated_at = now;
+        Ok(())
+    }
+}
+
+/// An editor connected to a model's collaborative session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Presence {
+    pub client_id: String,
+    pub user: Option<String>,
+    pub color: Option<String>,
+    /// Editor-defined position, e.g. the selected entity and field
+    pub cursor: Option<Value>,
+    pub joined_at: UtcDateTime,
+    pub last_seen: UtcDateTime,
+}
+
+fn find_mut<'a, T>(items: &'a mut [T], matches: impl Fn(&T) -> bool, kind: &str, id: &Uuid) -> Result<&'a mut T, Error> {
+    items.iter_mut()
+        .find(|item| matches(item))
+        .ok_or_else(|| Error::NotFound(format!("{} with id {} not found", kind, id)))
+}
+
+/// Replace top-level properties of an item, keeping it well-typed
+fn merge<T: Serialize + DeserializeOwned>(item: &mut T, changes: &Map<String, Value>, protected: &[&str]) -> Result<(), Error> {
+    if let Some(key) = changes.keys().find(|k| protected.contains(&k.as_str())) {
+        return Err(Error::Validation(format!("Property '{}' can't be changed with this operation", key)));
+    }
+    let mut value = serde_json::to_value(&*item)?;
+    let Some(object) = value.as_object_mut() else {
+        return Err(Error::Internal("Model item is not an object".to_string()));
+    };
+    for (key, change) in changes {
+        if !object.contains_key(key) {
+            return Err(Error::Validation(format!("Unknown property '{}'", key)));
+        }
+        object.insert(key.clone(), change.clone());
+    }
+    *item = serde_json::from_value(value)
+        .map_err(|e| Error::Validation(format!("Invalid change: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_fixtures::{self as fixtures, entity, field, string_field};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    /// A model with a `customer` entity that has a `name` field
+    fn model() -> TorqueModel {
+        fixtures::model(vec![entity("customer", vec![string_field("name")])])
+    }
+
+    fn update_field(model: &TorqueModel, changes: Value) -> EditOperation {
+        let entity = &model.entities[0];
+        serde_json::from_value(json!({
+            "op": "updateField",
+            "entityId": entity.id,
+            "fieldId": entity.fields[0].id,
+            "changes": changes,
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_apply_operations() {
+        let mut model = model();
+
+        update_field(&model, json!({ "required": true, "display_name": "Full name" })).apply(&mut model).unwrap();
+        assert!(model.entities[0].fields[0].required);
+        assert_eq!(model.entities[0].fields[0].display_name, "Full name");
+
+        EditOperation::SetModelProperty { property: "name".to_string(), value: json!("Store") }.apply(&mut model).unwrap();
+        assert_eq!(model.name, "Store");
+        assert_eq!(model.entities.len(), 1);
+
+        // Changes must keep the item well-typed and can't touch protected properties
+        assert!(update_field(&model, json!({ "required": "yes" })).apply(&mut model).is_err());
+        assert!(update_field(&model, json!({ "id": "x" })).apply(&mut model).is_err());
+        assert!(EditOperation::SetModelProperty { property: "entities".to_string(), value: json!([]) }.apply(&mut model).is_err());
+
+        let entity_id = model.entities[0].id.clone();
+        EditOperation::RemoveEntity { entity_id: entity_id.clone() }.apply(&mut model).unwrap();
+        assert!(model.entities.is_empty());
+        assert!(matches!(update_field(&self::model(), json!({})).apply(&mut model), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn test_renames_keep_names_unique() {
+        let mut model = model();
+        let mut order = model.entities[0].clone();
+        order.id = Uuid::new_v4();
+        order.name = "order".to_string();
+        EditOperation::AddEntity { entity: order.clone() }.apply(&mut model).unwrap();
+
+        let rename = |name: &str| EditOperation::UpdateEntity {
+            entity_id: order.id.clone(),
+            changes: Map::from_iter([("name".to_string(), json!(name))]),
+        };
+        assert!(matches!(rename("customer").apply(&mut model), Err(Error::Validation(_))));
+        assert_eq!(model.entities[1].name, "order");
+        rename("order").apply(&mut model).unwrap();
+        rename("purchase").apply(&mut model).unwrap();
+        assert_eq!(model.entities[1].name, "purchase");
+
+        let mut email = model.entities[0].fields[0].clone();
+        email.id = Uuid::new_v4();
+        email.name = "email".to_string();
+        model.entities[0].fields.push(email);
+        assert!(matches!(update_field(&model, json!({ "name": "email" })).apply(&mut model), Err(Error::Validation(_))));
+        update_field(&model, json!({ "name": "full_name" })).apply(&mut model).unwrap();
+        assert_eq!(model.entities[0].fields[0].name, "full_name");
+    }
+
+    #[test]
+    fn test_remove_entity_cleans_up_references() {
+        let mut model = model();
+        let customer = model.entities[0].clone();
+
+        let mut order = customer.clone();
+        order.id = Uuid::new_v4();
+        order.name = "order".to_string();
+        order.fields[0].id = Uuid::new_v4();
+        order.fields.push(field("customer", FieldType::Reference { entity_id: customer.id.clone() }));
+        model.entities.push(order.clone());
+
+        model.relationships.push(ModelRelationship {
+            id: Uuid::new_v4(),
+            name: "customer_orders".to_string(),
+            relationship_type: RelationshipType::OneToMany,
+            from_entity: customer.id.clone(),
+            to_entity: order.id.clone(),
+            from_field: "id".to_string(),
+            to_field: "customer".to_string(),
+            cascade: CascadeAction::None,
+            ui_config: RelationshipUiConfig::default(),
+        });
+        model.flows.push(ModelFlow {
+            id: Uuid::new_v4(),
+            name: "welcome".to_string(),
+            flow_type: FlowType::Notification,
+            trigger: FlowTrigger::EntityEvent { entity_id: customer.id.clone(), event: LifecycleEvent::AfterCreate },
+            steps: vec![],
+            error_handling: ErrorHandling::default(),
+        });
+        let component = |entity_type: &str| LayoutComponent {
+            id: Uuid::new_v4(),
+            component_type: "DataGrid".to_string(),
+            position: ComponentPosition::default(),
+            properties: HashMap::from([("entityType".to_string(), json!(entity_type))]),
+            styling: HashMap::new(),
+            metadata: None,
+        };
+        let now = UtcDateTime::now();
+        model.layouts.push(ModelLayout {
+            id: Uuid::new_v4(),
+            name: "Overview".to_string(),
+            description: None,
+            layout_type: LayoutType::Dashboard,
+            target_entities: vec![customer.id.clone(), order.id.clone()],
+            components: vec![component("customer"), component("order")],
+            responsive: ResponsiveLayout::default(),
+            created_at: now.clone(),
+            updated_at: now,
+        });
+
+        EditOperation::RemoveEntity { entity_id: customer.id.clone() }.apply(&mut model).unwrap();
+
+        assert_eq!(model.entities.len(), 1);
+        assert_eq!(model.entities[0].fields.len(), 1, "the Reference field to the removed entity is dropped");
+        assert!(model.relationships.is_empty());
+        assert!(model.flows.is_empty());
+        assert_eq!(model.layouts[0].target_entities, vec![order.id.clone()]);
+        assert_eq!(model.layouts[0].components.len(), 1);
+        assert_eq!(model.layouts[0].components[0].properties["entityType"], "order");
+    }
+
+    #[test]
+    fn test_operation_conflicts() {
+        let model = model();
+        let required = update_field(&model, json!({ "required": true }));
+        let label = update_field(&model, json!({ "display_name": "Label" }));
+        let also_required = update_field(&model, json!({ "required": false }));
+        let entity_id = model.entities[0].id.clone();
+
+        assert!(!required.conflicts_with(&label));
+        assert!(required.conflicts_with(&also_required));
+        assert!(required.conflicts_with(&EditOperation::RemoveEntity { entity_id: entity_id.clone() }));
+
+        let rename = EditOperation::UpdateEntity {
+            entity_id,
+            changes: Map::from_iter([("display_name".to_string(), json!("Client"))]),
+        };
+        assert!(!rename.conflicts_with(&required));
+    }
+}
@@ -0,0 +1,542 @@
+use crate::common::Uuid;
+use crate::error::Error;
+use crate::model::diff::{diff_models, ChangeKind, DiffComponent, ModelChange};
+use crate::model::types::{EntityField, FieldType, ModelEntity, TorqueModel};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// What to do with app entity rows whose entity type no longer exists in the model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrphanStrategy {
+    /// Move orphaned rows to `app_entities_archive`
+    #[default]
+    Archive,
+    /// Permanently delete orphaned rows
+    Delete,
+}
+
+/// A single transformation applied to stored app entity rows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DataMigrationStep {
+    /// Rows of entity type `from` are relabelled as `to`. Entity renames are looked up by
+    /// the row's type before any of them, so entities can swap or chain names.
+    RenameEntityType { from: String, to: String },
+    /// JSON key `from` is renamed to `to`. Consecutive renames of one entity type are
+    /// applied together, so fields can swap names.
+    RenameField { entity_type: String, from: String, to: String },
+    /// Values of `field` are converted to the new field type; values that cannot be converted become null
+    CoerceField { entity_type: String, field: String, to_type: FieldType },
+    /// JSON key `field` is removed
+    RemoveField { entity_type: String, field: String },
+    /// Rows are moved to `app_entities_archive`
+    ArchiveRows { entity_type: String },
+    /// Rows are deleted
+    DeleteRows { entity_type: String },
+}
+
+impl DataMigrationStep {
+    /// Entity type (after earlier renames) the step applies to
+    pub fn entity_type(&self) -> &str {
+        match self {
+            DataMigrationStep::RenameEntityType { from, .. } => from,
+            DataMigrationStep::RenameField { entity_type, .. }
+            | DataMigrationStep::CoerceField { entity_type, .. }
+            | DataMigrationStep::RemoveField { entity_type, .. }
+            | DataMigrationStep::ArchiveRows { entity_type }
+            | DataMigrationStep::DeleteRows { entity_type } => entity_type,
+        }
+    }
+}
+
+/// Ordered list of steps that brings app data shaped for one model version in line with another
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataMigrationPlan {
+    pub from_version: i32,
+    pub to_version: i32,
+    pub orphan_strategy: OrphanStrategy,
+    pub steps: Vec<DataMigrationStep>,
+}
+
+impl DataMigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Add a step removing rows of an entity type that is not part of the target model.
+    /// Orphan steps are kept ahead of all other steps so they only match rows stored
+    /// under the old name, never rows another entity was renamed into.
+    pub fn push_orphan(&mut self, entity_type: String) {
+        let is_orphan_step = |step: &DataMigrationStep| matches!(step,
+            DataMigrationStep::ArchiveRows { .. } | DataMigrationStep::DeleteRows { .. });
+        if self.steps.iter().any(|step| is_orphan_step(step) && step.entity_type() == entity_type) {
+            return;
+        }
+
+        let position = self.steps.iter().position(|step| !is_orphan_step(step)).unwrap_or(self.steps.len());
+        self.steps.insert(position, match self.orphan_strategy {
+            OrphanStrategy::Archive => DataMigrationStep::ArchiveRows { entity_type },
+            OrphanStrategy::Delete => DataMigrationStep::DeleteRows { entity_type },
+        });
+    }
+}
+
+/// Result of running a plan against one stored row
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowOutcome {
+    Unchanged,
+    Updated {
+        entity_type: String,
+        data: Value,
+        coercion_failures: u64,
+    },
+    Archive,
+    Delete,
+}
+
+/// Build a data migration plan from the structural diff between two model versions.
+/// Orphaned rows are handled first, then entity renames, so field steps use the new names.
+/// Removed fields are dropped before field renames, which come before type coercions.
+pub fn plan_data_migration(
+    old: &TorqueModel,
+    new: &TorqueModel,
+    from_version: i32,
+    to_version: i32,
+    orphan_strategy: OrphanStrategy,
+) -> Result<DataMigrationPlan, Error> {
+    let diff = diff_models(old, new)?;
+
+    let mut renames = Vec::new();
+    let mut removals = Vec::new();
+    let mut field_renames = Vec::new();
+    let mut coercions = Vec::new();
+    let mut orphans = Vec::new();
+
+    for change in &diff.changes {
+        match (change.component, change.kind) {
+            (DiffComponent::Entity, ChangeKind::Modified) => {
+                if let Some((from, to)) = renamed(change) {
+                    renames.push(DataMigrationStep::RenameEntityType { from, to });
+                }
+            }
+            (DiffComponent::Entity, ChangeKind::Removed) => orphans.push(change.name.clone()),
+            (DiffComponent::Field, ChangeKind::Modified) => {
+                let Some((entity, field)) = change.id.as_ref().and_then(|id| find_field(new, id)) else {
+                    continue;
+                };
+                let entity_type = entity.name.clone();
+                if let Some((from, to)) = renamed(change) {
+                    field_renames.push(DataMigrationStep::RenameField {
+                        entity_type: entity_type.clone(),
+                        from,
+                        to,
+                    });
+                }
+                if change.properties.iter().any(|p| p.property == "field_type") {
+                    coercions.push(DataMigrationStep::CoerceField {
+                        entity_type,
+                        field: field.name.clone(),
+                        to_type: field.field_type.clone(),
+                    });
+                }
+            }
+            (DiffComponent::Field, ChangeKind::Removed) => {
+                // Fields removed together with their entity are covered by the orphan step
+                let Some(entity_type) = change.id.as_ref()
+                    .and_then(|id| find_field(old, id))
+                    .and_then(|(old_entity, _)| new.entities.iter().find(|e| e.id == old_entity.id))
+                    .map(|e| e.name.clone()) else {
+                    continue;
+                };
+                removals.push(DataMigrationStep::RemoveField {
+                    entity_type,
+                    field: change.name.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // Two renames onto one name would mix the rows of both entities
+    let mut targets = HashSet::new();
+    for step in &renames {
+        if let DataMigrationStep::RenameEntityType { to, .. } = step {
+            if !targets.insert(to.as_str()) {
+                return Err(Error::Validation(format!("More than one entity is renamed to {}", to)));
+            }
+        }
+    }
+
+    // Two renames onto one name would leave one of the values behind
+    let mut targets = HashSet::new();
+    for step in &field_renames {
+        if let DataMigrationStep::RenameField { entity_type, to, .. } = step {
+            if !targets.insert((entity_type.as_str(), to.as_str())) {
+                return Err(Error::Validation(format!(
+                    "More than one field of {} is renamed to {}", entity_type, to
+                )));
+            }
+        }
+    }
+
+    let mut plan = DataMigrationPlan {
+        from_version,
+        to_version,
+        orphan_strategy,
+        steps: Vec::new(),
+    };
+    for entity_type in orphans {
+        plan.push_orphan(entity_type);
+    }
+    plan.steps.extend(renames);
+    plan.steps.extend(removals);
+    plan.steps.extend(field_renames);
+    plan.steps.extend(coercions);
+
+    Ok(plan)
+}
+
+/// Old and new value of a `name` property change
+fn renamed(change: &ModelChange) -> Option<(String, String)> {
+    change.properties.iter()
+        .find(|p| p.property == "name")
+        .and_then(|p| Some((p.old_value.as_str()?.to_string(), p.new_value.as_str()?.to_string())))
+}
+
+/// Find a field by ID together with the entity it belongs to
+fn find_field<'a>(model: &'a TorqueModel, field_id: &Uuid) -> Option<(&'a ModelEntity, &'a EntityField)> {
+    model.entities.iter().find_map(|entity| {
+        entity.fields.iter()
+            .find(|f| &f.id == field_id)
+            .map(|f| (entity, f))
+    })
+}
+
+/// Apply a plan to one row's entity type and (uncompressed) JSON data
+pub fn apply_plan(plan: &DataMigrationPlan, entity_type: &str, data: &Value) -> RowOutcome {
+    let mut current_type = entity_type.to_string();
+    let mut current = data.clone();
+    let mut coercion_failures = 0;
+    let mut renames = Vec::new();
+    // Set once the row's own type has been renamed, so a later rename of the new name
+    // (a swap or a chain) doesn't rename it again
+    let mut type_renamed = false;
+
+    for step in &plan.steps {
+        if step.entity_type() != current_type {
+            continue;
+        }
+        if type_renamed && matches!(step, DataMigrationStep::RenameEntityType { .. }) {
+            continue;
+        }
+        if !matches!(step, DataMigrationStep::RenameField { .. }) {
+            rename_fields(&mut current, &mut renames);
+        }
+        match step {
+            DataMigrationStep::RenameEntityType { to, .. } => {
+                current_type = to.clone();
+                type_renamed = true;
+            }
+            DataMigrationStep::RenameField { from, to, .. } => renames.push((from, to)),
+            DataMigrationStep::CoerceField { field, to_type, .. } => {
+                if let Some(value) = current.as_object_mut().and_then(|o| o.get_mut(field)) {
+                    match coerce_value(value, to_type) {
+                        Some(coerced) => *value = coerced,
+                        None => {
+                            *value = Value::Null;
+                            coercion_failures += 1;
+                        }
+                    }
+                }
+            }
+            DataMigrationStep::RemoveField { field, .. } => {
+                if let Some(object) = current.as_object_mut() {
+                    object.remove(field);
+                }
+            }
+            DataMigrationStep::ArchiveRows { .. } => return RowOutcome::Archive,
+            DataMigrationStep::DeleteRows { .. } => return RowOutcome::Delete,
+        }
+    }
+    rename_fields(&mut current, &mut renames);
+
+    if current_type == entity_type && current == *data {
+        RowOutcome::Unchanged
+    } else {
+        RowOutcome::Updated {
+            entity_type: current_type,
+            data: current,
+            coercion_failures,
+        }
+    }
+}
+
+/// Apply a batch of field renames: every `from` value is taken out before any `to` is
+/// written, so renames that swap or chain names don't overwrite each other
+fn rename_fields(data: &mut Value, renames: &mut Vec<(&String, &String)>) {
+    let Some(object) = data.as_object_mut() else {
+        renames.clear();
+        return;
+    };
+    let values: Vec<_> = renames.iter()
+        .filter_map(|(from, to)| object.remove(from.as_str()).map(|value| (*to, value)))
+        .collect();
+    for (to, value) in values {
+        object.insert(to.clone(), value);
+    }
+    renames.clear();
+}
+
+/// Convert a stored value to a field type. Returns `None` if the value cannot be represented.
+pub fn coerce_value(value: &Value, field_type: &FieldType) -> Option<Value> {
+    if value.is_null() {
+        return Some(Value::Null);
+    }
+
+    match field_type {
+        FieldType::String { .. } | FieldType::DateTime | FieldType::Date | FieldType::Time => match value {
+            Value::String(_) => Some(value.clone()),
+            Value::Number(n) => Some(Value::String(n.to_string())),
+            Value::Bool(b) => Some(Value::String(b.to_string())),
+            _ => None,
+        },
+        FieldType::Integer { .. } => match value {
+            Value::Number(n) => n.as_i64()
+                .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64))
+                .map(Value::from),
+            Value::String(s) => s.trim().parse::<i64>().ok().map(Value::from),
+            Value::Bool(b) => Some(Value::from(*b as i64)),
+            _ => None,
+        },
+        FieldType::Float { .. } => match value {
+            Value::Number(n) => n.as_f64().map(Value::from),
+            Value::String(s) => s.trim().parse::<f64>().ok().map(Value::from),
+            Value::Bool(b) => Some(Value::from(if *b { 1.0 } else { 0.0 })),
+            _ => None,
+        },
+        FieldType::Boolean => match value {
+            Value::Bool(_) => Some(value.clone()),
+            Value::Number(n) => n.as_f64().map(|f| Value::Bool(f != 0.0)),
+            Value::String(s) => match s.trim().to_lowercase().as_str() {
+                "true" | "yes" | "1" => Some(Value::Bool(true)),
+                "false" | "no" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => None,
+        },
+        FieldType::Json => Some(value.clone()),
+        FieldType::Enum { values } => {
+            let text = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                _ => return None,
+            };
+            values.contains(&text).then_some(Value::String(text))
+        }
+        FieldType::Array { element_type } => match value {
+            Value::Array(items) => items.iter()
+                .map(|item| coerce_value(item, element_type))
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array),
+            scalar => coerce_value(scalar, element_type).map(|v| Value::Array(vec![v])),
+        },
+        FieldType::Reference { .. } | FieldType::Binary => match value {
+            Value::String(_) => Some(value.clone()),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_fixtures::{entity, model, string_field as field};
+    use serde_json::json;
+
+    #[test]
+    fn test_coerce_value() {
+        let int = FieldType::Integer { min: None, max: None };
+        assert_eq!(coerce_value(&json!("42"), &int), Some(json!(42)));
+        assert_eq!(coerce_value(&json!(3.0), &int), Some(json!(3)));
+        assert_eq!(coerce_value(&json!("abc"), &int), None);
+
+        assert_eq!(coerce_value(&json!("yes"), &FieldType::Boolean), Some(json!(true)));
+        assert_eq!(coerce_value(&json!(12), &FieldType::String { max_length: None }), Some(json!("12")));
+
+        let status = FieldType::Enum { values: vec!["open".to_string(), "closed".to_string()] };
+        assert_eq!(coerce_value(&json!("open"), &status), Some(json!("open")));
+        assert_eq!(coerce_value(&json!("pending"), &status), None);
+
+        let tags = FieldType::Array { element_type: Box::new(int) };
+        assert_eq!(coerce_value(&json!("7"), &tags), Some(json!([7])));
+        assert_eq!(coerce_value(&Value::Null, &FieldType::Boolean), Some(Value::Null));
+    }
+
+    #[test]
+    fn test_apply_plan() {
+        let plan = DataMigrationPlan {
+            from_version: 1,
+            to_version: 2,
+            orphan_strategy: OrphanStrategy::Archive,
+            steps: vec![
+                DataMigrationStep::RenameEntityType { from: "Client".to_string(), to: "Customer".to_string() },
+                DataMigrationStep::RenameField {
+                    entity_type: "Customer".to_string(),
+                    from: "mail".to_string(),
+                    to: "email".to_string(),
+                },
+                DataMigrationStep::CoerceField {
+                    entity_type: "Customer".to_string(),
+                    field: "age".to_string(),
+                    to_type: FieldType::Integer { min: None, max: None },
+                },
+                DataMigrationStep::ArchiveRows { entity_type: "Invoice".to_string() },
+            ],
+        };
+
+        let outcome = apply_plan(&plan, "Client", &json!({"mail": "a@b.c", "age": "x"}));
+        assert_eq!(outcome, RowOutcome::Updated {
+            entity_type: "Customer".to_string(),
+            data: json!({"email": "a@b.c", "age": null}),
+            coercion_failures: 1,
+        });
+
+        assert_eq!(apply_plan(&plan, "Invoice", &json!({})), RowOutcome::Archive);
+        assert_eq!(apply_plan(&plan, "Order", &json!({"mail": "x"})), RowOutcome::Unchanged);
+    }
+
+    #[test]
+    fn test_apply_plan_swaps_and_chains_entity_names() {
+        let rename = |from: &str, to: &str| DataMigrationStep::RenameEntityType {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let plan = |steps| DataMigrationPlan {
+            from_version: 1,
+            to_version: 2,
+            orphan_strategy: OrphanStrategy::Archive,
+            steps,
+        };
+        let renamed_to = |plan: &DataMigrationPlan, entity_type: &str| match apply_plan(plan, entity_type, &json!({})) {
+            RowOutcome::Updated { entity_type, .. } => entity_type,
+            outcome => panic!("{} rows weren't renamed: {:?}", entity_type, outcome),
+        };
+
+        let swap = plan(vec![rename("Client", "Customer"), rename("Customer", "Client")]);
+        assert_eq!(renamed_to(&swap, "Client"), "Customer");
+        assert_eq!(renamed_to(&swap, "Customer"), "Client");
+
+        let chain = plan(vec![rename("Client", "Customer"), rename("Customer", "Account")]);
+        assert_eq!(renamed_to(&chain, "Client"), "Customer");
+        assert_eq!(renamed_to(&chain, "Customer"), "Account");
+    }
+
+    #[test]
+    fn test_apply_plan_swaps_field_names() {
+        let rename = |from: &str, to: &str| DataMigrationStep::RenameField {
+            entity_type: "Customer".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let plan = DataMigrationPlan {
+            from_version: 1,
+            to_version: 2,
+            orphan_strategy: OrphanStrategy::Archive,
+            steps: vec![rename("first", "last"), rename("last", "first"), rename("nick", "alias")],
+        };
+
+        let outcome = apply_plan(&plan, "Customer", &json!({"first": "Ada", "last": "Lovelace", "nick": "A"}));
+        assert_eq!(outcome, RowOutcome::Updated {
+            entity_type: "Customer".to_string(),
+            data: json!({"first": "Lovelace", "last": "Ada", "alias": "A"}),
+            coercion_failures: 0,
+        });
+    }
+
+    #[test]
+    fn test_plan_field_swap() {
+        let old = model(vec![entity("Customer", vec![field("first"), field("last")])]);
+        let mut new = old.clone();
+        new.entities[0].fields[0].name = "last".to_string();
+        new.entities[0].fields[1].name = "first".to_string();
+
+        let plan = plan_data_migration(&old, &new, 1, 2, OrphanStrategy::Archive).unwrap();
+        assert_eq!(apply_plan(&plan, "Customer", &json!({"first": "Ada", "last": "Lovelace"})), RowOutcome::Updated {
+            entity_type: "Customer".to_string(),
+            data: json!({"first": "Lovelace", "last": "Ada"}),
+            coercion_failures: 0,
+        });
+    }
+
+    #[test]
+    fn test_plan_rejects_renames_onto_one_field() {
+        let old = model(vec![entity("Customer", vec![field("mail"), field("email_address")])]);
+        let mut new = old.clone();
+        new.entities[0].fields[0].name = "email".to_string();
+        new.entities[0].fields[1].name = "email".to_string();
+
+        assert!(matches!(
+            plan_data_migration(&old, &new, 1, 2, OrphanStrategy::Archive),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_plan_entity_swap() {
+        let old = model(vec![entity("Client", vec![field("name")]), entity("Customer", vec![])]);
+        let mut new = old.clone();
+        new.entities[0].name = "Customer".to_string();
+        new.entities[1].name = "Client".to_string();
+
+        let plan = plan_data_migration(&old, &new, 1, 2, OrphanStrategy::Archive).unwrap();
+        assert_eq!(apply_plan(&plan, "Client", &json!({"name": "Ada"})), RowOutcome::Updated {
+            entity_type: "Customer".to_string(),
+            data: json!({"name": "Ada"}),
+            coercion_failures: 0,
+        });
+    }
+
+    #[test]
+    fn test_plan_rejects_renames_onto_one_entity() {
+        let old = model(vec![entity("Client", vec![]), entity("Buyer", vec![])]);
+        let mut new = old.clone();
+        new.entities[0].name = "Customer".to_string();
+        new.entities[1].name = "Customer".to_string();
+
+        assert!(matches!(
+            plan_data_migration(&old, &new, 1, 2, OrphanStrategy::Archive),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_orphans_handled_before_renames() {
+        let old = model(vec![entity("Customer", vec![]), entity("Client", vec![])]);
+
+        // Remove `Customer` and rename `Client` into its place
+        let mut new = old.clone();
+        new.entities.remove(0);
+        new.entities[0].name = "Customer".to_string();
+
+        let mut plan = plan_data_migration(&old, &new, 1, 2, OrphanStrategy::Delete).unwrap();
+        assert_eq!(plan.steps, vec![
+            DataMigrationStep::DeleteRows { entity_type: "Customer".to_string() },
+            DataMigrationStep::RenameEntityType { from: "Client".to_string(), to: "Customer".to_string() },
+        ]);
+
+        // Orphans found later still go ahead of the renames
+        plan.push_orphan("Legacy".to_string());
+        assert_eq!(plan.steps[1], DataMigrationStep::DeleteRows { entity_type: "Legacy".to_string() });
+
+        assert_eq!(apply_plan(&plan, "Customer", &json!({})), RowOutcome::Delete);
+        assert_eq!(apply_plan(&plan, "Client", &json!({})), RowOutcome::Updated {
+            entity_type: "Customer".to_string(),
+            data: json!({}),
+            coercion_failures: 0,
+        });
+    }
+}
@@ -0,0 +1,574 @@
+use crate::common::Uuid;
+use crate::model::types::{FieldType, TorqueModel};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Kind of model component a change applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffComponent {
+    Model,
+    Entity,
+    Field,
+    Relationship,
+    Flow,
+    Layout,
+    Component,
+    Validation,
+}
+
+impl DiffComponent {
+    /// Nested collections that are compared item-by-item (matched by ID)
+    /// instead of as a single property value
+    fn collections(self) -> &'static [(&'static str, DiffComponent)] {
+        match self {
+            DiffComponent::Model => &[
+                ("entities", DiffComponent::Entity),
+                ("relationships", DiffComponent::Relationship),
+                ("flows", DiffComponent::Flow),
+                ("layouts", DiffComponent::Layout),
+                ("validations", DiffComponent::Validation),
+            ],
+            DiffComponent::Entity => &[("fields", DiffComponent::Field)],
+            DiffComponent::Layout => &[("components", DiffComponent::Component)],
+            _ => &[],
+        }
+    }
+
+    /// Bookkeeping properties that are not part of the model structure
+    fn ignored(self) -> &'static [&'static str] {
+        match self {
+            DiffComponent::Model => &["id", "created_at", "updated_at", "created_by"],
+            DiffComponent::Layout => &["id", "created_at", "updated_at"],
+            _ => &["id"],
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DiffComponent::Model => "model",
+            DiffComponent::Entity => "entity",
+            DiffComponent::Field => "field",
+            DiffComponent::Relationship => "relationship",
+            DiffComponent::Flow => "flow",
+            DiffComponent::Layout => "layout",
+            DiffComponent::Component => "component",
+            DiffComponent::Validation => "validation",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A single property that differs between two versions of a component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyChange {
+    pub property: String,
+    pub old_value: Value,
+    pub new_value: Value,
+}
+
+/// A change to one model component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelChange {
+    pub kind: ChangeKind,
+    pub component: DiffComponent,
+    /// Component ID (None for model-level properties)
+    pub id: Option<Uuid>,
+    pub name: String,
+    /// Location within the model, e.g. `entities[Customer].fields[email]`
+    pub path: String,
+    /// Changed properties (only for `Modified`)
+    pub properties: Vec<PropertyChange>,
+}
+
+/// Semantic diff between two TorqueModels
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelDiff {
+    pub changes: Vec<ModelChange>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+impl fmt::Display for ModelDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No differences");
+        }
+
+        for change in &self.changes {
+            let marker = match change.kind {
+                ChangeKind::Added => '+',
+                ChangeKind::Removed => '-',
+                ChangeKind::Modified => '~',
+            };
+            writeln!(f, "{} {} {}", marker, change.component.label(), change.path)?;
+            for property in &change.properties {
+                writeln!(f, "    {}: {} -> {}", property.property, property.old_value, property.new_value)?;
+            }
+        }
+
+        write!(
+            f,
+            "{} added, {} removed, {} modified",
+            self.count(ChangeKind::Added),
+            self.count(ChangeKind::Removed),
+            self.count(ChangeKind::Modified)
+        )
+    }
+}
+
+/// A conflict found during a three-way merge.
+/// The merged model keeps "ours" for every conflicting location.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeConflict {
+    pub component: DiffComponent,
+    pub id: Option<Uuid>,
+    pub path: String,
+    /// Conflicting property, or None when the whole component conflicts
+    pub property: Option<String>,
+    pub base: Value,
+    pub ours: Value,
+    pub theirs: Value,
+    pub reason: String,
+}
+
+/// Result of a three-way merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeResult {
+    pub merged: TorqueModel,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Compute a structural diff from `old` to `new`.
+/// Entities, fields, relationships, flows, layouts, components and validations
+/// are matched by ID so reordering is not reported as a change.
+pub fn diff_models(old: &TorqueModel, new: &TorqueModel) -> serde_json::Result<ModelDiff> {
+    let old = serde_json::to_value(old)?;
+    let new = serde_json::to_value(new)?;
+
+    let mut changes = Vec::new();
+    diff_object(DiffComponent::Model, "model", &old, &new, &mut changes);
+
+    Ok(ModelDiff { changes })
+}
+
+/// Merge the changes made in `ours` and `theirs` since `base`.
+/// Non-overlapping changes from both sides are combined; overlapping ones are reported as conflicts,
+/// and so are fields and relationships left pointing at an entity that one side removed.
+pub fn merge_models(base: &TorqueModel, ours: &TorqueModel, theirs: &TorqueModel) -> serde_json::Result<MergeResult> {
+    let base_value = serde_json::to_value(base)?;
+    let ours_value = serde_json::to_value(ours)?;
+    let theirs_value = serde_json::to_value(theirs)?;
+
+    let mut conflicts = Vec::new();
+    let merged = merge_object(DiffComponent::Model, "model", &base_value, &ours_value, &theirs_value, &mut conflicts);
+    let merged: TorqueModel = serde_json::from_value(merged)?;
+    conflicts.extend(dangling_references(base, ours, theirs, &merged));
+
+    Ok(MergeResult { merged, conflicts })
+}
+
+/// Conflicts for references in the merged model to entities it no longer has.
+/// Each side's entity (by name, or null when that side doesn't have it) is reported.
+fn dangling_references(base: &TorqueModel, ours: &TorqueModel, theirs: &TorqueModel, merged: &TorqueModel) -> Vec<MergeConflict> {
+    let exists = |id: &Uuid| merged.entities.iter().any(|e| &e.id == id);
+    let entity_name = |model: &TorqueModel, id: &Uuid| {
+        model.entities.iter()
+            .find(|e| &e.id == id)
+            .map(|e| Value::String(e.name.clone()))
+            .unwrap_or(Value::Null)
+    };
+    let conflict = |component, id: &Uuid, path: String, property: &str, target: &Uuid| MergeConflict {
+        component,
+        id: Some(id.clone()),
+        path,
+        property: Some(property.to_string()),
+        base: entity_name(base, target),
+        ours: entity_name(ours, target),
+        theirs: entity_name(theirs, target),
+        reason: format!("refers to entity {}, which the merged model no longer has", target),
+    };
+
+    let mut conflicts = Vec::new();
+    for entity in &merged.entities {
+        for field in &entity.fields {
+            if let Some(target) = referenced_entity(&field.field_type).filter(|target| !exists(target)) {
+                let path = format!("entities[{}].fields[{}]", entity.name, field.name);
+                conflicts.push(conflict(DiffComponent::Field, &field.id, path, "field_type", target));
+            }
+        }
+    }
+    for relationship in &merged.relationships {
+        for (property, target) in [("from_entity", &relationship.from_entity), ("to_entity", &relationship.to_entity)] {
+            if !exists(target) {
+                let path = format!("relationships[{}]", relationship.name);
+                conflicts.push(conflict(DiffComponent::Relationship, &relationship.id, path, property, target));
+            }
+        }
+    }
+    conflicts
+}
+
+fn referenced_entity(field_type: &FieldType) -> Option<&Uuid> {
+    match field_type {
+        FieldType::Reference { entity_id } => Some(entity_id),
+        FieldType::Array { element_type } => referenced_entity(element_type),
+        _ => None,
+    }
+}
+
+/// Key used to match collection items across versions
+fn item_key(item: &Value) -> String {
+    match item.get("id").and_then(|v| v.as_str()) {
+        Some(id) => id.to_string(),
+        None => format!("name:{}", item_name(item)),
+    }
+}
+
+fn item_name(item: &Value) -> String {
+    item.get("name")
+        .and_then(|v| v.as_str())
+        .or_else(|| item.get("component_type").and_then(|v| v.as_str()))
+        .or_else(|| item.get("id").and_then(|v| v.as_str()))
+        .unwrap_or("unnamed")
+        .to_string()
+}
+
+fn item_id(item: &Value) -> Option<Uuid> {
+    item.get("id")
+        .and_then(|v| v.as_str())
+        .and_then(|id| Uuid::parse(id).ok())
+}
+
+fn item_path(parent: &str, collection: &str, item: &Value) -> String {
+    let prefix = if parent == "model" { String::new() } else { format!("{}.", parent) };
+    format!("{}{}[{}]", prefix, collection, item_name(item))
+}
+
+fn as_items(value: Option<&Value>) -> Vec<&Value> {
+    value.and_then(|v| v.as_array())
+        .map(|items| items.iter().collect())
+        .unwrap_or_default()
+}
+
+fn is_structural(component: DiffComponent, key: &str) -> bool {
+    !component.ignored().contains(&key)
+        && !component.collections().iter().any(|(name, _)| *name == key)
+}
+
+/// Union of the structural property names of the given objects, in first-seen order
+fn property_names(component: DiffComponent, objects: &[&Value]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for object in objects {
+        if let Some(map) = object.as_object() {
+            for key in map.keys() {
+                if is_structural(component, key) && !names.iter().any(|n| n == key) {
+                    names.push(key.clone());
+                }
+            }
+        }
+    }
+    names
+}
+
+fn diff_object(component: DiffComponent, path: &str, old: &Value, new: &Value, out: &mut Vec<ModelChange>) {
+    let mut properties = Vec::new();
+    for key in property_names(component, &[old, new]) {
+        let old_value = old.get(&key).cloned().unwrap_or(Value::Null);
+        let new_value = new.get(&key).cloned().unwrap_or(Value::Null);
+        if old_value != new_value {
+            properties.push(PropertyChange { property: key, old_value, new_value });
+        }
+    }
+
+    if !properties.is_empty() {
+        out.push(ModelChange {
+            kind: ChangeKind::Modified,
+            component,
+            id: item_id(new),
+            name: item_name(new),
+            path: path.to_string(),
+            properties,
+        });
+    }
+
+    for (collection, child) in component.collections() {
+        let old_items = as_items(old.get(*collection));
+        let new_items = as_items(new.get(*collection));
+        let old_by_key: HashMap<String, &Value> = old_items.iter().map(|i| (item_key(i), *i)).collect();
+        let new_by_key: HashMap<String, &Value> = new_items.iter().map(|i| (item_key(i), *i)).collect();
+
+        for item in &old_items {
+            if !new_by_key.contains_key(&item_key(item)) {
+                out.push(ModelChange {
+                    kind: ChangeKind::Removed,
+                    component: *child,
+                    id: item_id(item),
+                    name: item_name(item),
+                    path: item_path(path, collection, item),
+                    properties: vec![],
+                });
+            }
+        }
+
+        for item in &new_items {
+            match old_by_key.get(&item_key(item)) {
+                Some(old_item) => {
+                    diff_object(*child, &item_path(path, collection, item), old_item, item, out);
+                }
+                None => out.push(ModelChange {
+                    kind: ChangeKind::Added,
+                    component: *child,
+                    id: item_id(item),
+                    name: item_name(item),
+                    path: item_path(path, collection, item),
+                    properties: vec![],
+                }),
+            }
+        }
+    }
+}
+
+fn merge_object(
+    component: DiffComponent,
+    path: &str,
+    base: &Value,
+    ours: &Value,
+    theirs: &Value,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Value {
+    let mut merged: Map<String, Value> = ours.as_object().cloned().unwrap_or_default();
+
+    for key in property_names(component, &[base, ours, theirs]) {
+        let b = base.get(&key);
+        let o = ours.get(&key);
+        let t = theirs.get(&key);
+
+        let chosen = if o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            conflicts.push(MergeConflict {
+                component,
+                id: item_id(ours),
+                path: path.to_string(),
+                property: Some(key.clone()),
+                base: b.cloned().unwrap_or(Value::Null),
+                ours: o.cloned().unwrap_or(Value::Null),
+                theirs: t.cloned().unwrap_or(Value::Null),
+                reason: "changed differently on both sides".to_string(),
+            });
+            o
+        };
+
+        match chosen {
+            Some(value) => {
+                merged.insert(key, value.clone());
+            }
+            None => {
+                merged.remove(&key);
+            }
+        }
+    }
+
+    for (collection, child) in component.collections() {
+        let items = merge_collection(
+            *child,
+            path,
+            collection,
+            as_items(base.get(*collection)),
+            as_items(ours.get(*collection)),
+            as_items(theirs.get(*collection)),
+            conflicts,
+        );
+        merged.insert(collection.to_string(), Value::Array(items));
+    }
+
+    Value::Object(merged)
+}
+
+fn merge_collection(
+    component: DiffComponent,
+    parent: &str,
+    collection: &str,
+    base: Vec<&Value>,
+    ours: Vec<&Value>,
+    theirs: Vec<&Value>,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Vec<Value> {
+    let base_by_key: HashMap<String, &Value> = base.iter().map(|i| (item_key(i), *i)).collect();
+    let ours_by_key: HashMap<String, &Value> = ours.iter().map(|i| (item_key(i), *i)).collect();
+    let theirs_by_key: HashMap<String, &Value> = theirs.iter().map(|i| (item_key(i), *i)).collect();
+
+    let whole_item_conflict = |item: &Value, b: Option<&Value>, o: Option<&Value>, t: Option<&Value>, reason: &str| MergeConflict {
+        component,
+        id: item_id(item),
+        path: item_path(parent, collection, item),
+        property: None,
+        base: b.cloned().unwrap_or(Value::Null),
+        ours: o.cloned().unwrap_or(Value::Null),
+        theirs: t.cloned().unwrap_or(Value::Null),
+        reason: reason.to_string(),
+    };
+
+    let mut merged = Vec::new();
+
+    // Our ordering wins; items only added on their side are appended afterwards
+    for item in &ours {
+        let key = item_key(item);
+        let path = item_path(parent, collection, item);
+        match (base_by_key.get(&key), theirs_by_key.get(&key)) {
+            (Some(b), Some(t)) => {
+                merged.push(merge_object(component, &path, b, item, t, conflicts));
+            }
+            (Some(b), None) => {
+                // Removed on their side
+                if *b != *item {
+                    conflicts.push(whole_item_conflict(item, Some(*b), Some(*item), None, "modified on our side but removed on theirs"));
+                    merged.push((*item).clone());
+                }
+            }
+            (None, Some(t)) => {
+                // Added on both sides with the same ID
+                if *t == *item {
+                    merged.push((*item).clone());
+                } else {
+                    merged.push(merge_object(component, &path, &Value::Null, item, t, conflicts));
+                }
+            }
+            (None, None) => merged.push((*item).clone()),
+        }
+    }
+
+    for item in &theirs {
+        let key = item_key(item);
+        if ours_by_key.contains_key(&key) {
+            continue;
+        }
+        match base_by_key.get(&key) {
+            Some(b) => {
+                // Removed on our side
+                if *b != *item {
+                    conflicts.push(whole_item_conflict(item, Some(*b), None, Some(*item), "removed on our side but modified on theirs"));
+                }
+            }
+            None => merged.push((*item).clone()),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_fixtures::{self as fixtures, entity, model, string_field as field};
+
+    #[test]
+    fn test_diff_matches_by_id_not_position() {
+        let old = model(vec![entity("Customer", vec![field("name")]), entity("Order", vec![])]);
+        let mut new = old.clone();
+        new.entities.reverse();
+
+        let diff = diff_models(&old, &new).unwrap();
+        assert!(diff.is_empty(), "reordering should not be a change: {}", diff);
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_and_modified() {
+        let old = model(vec![entity("Customer", vec![field("name"), field("email")]), entity("Order", vec![])]);
+        let mut new = old.clone();
+        new.entities.retain(|e| e.name != "Order");
+        new.entities.push(entity("Invoice", vec![]));
+        new.entities[0].fields[1].required = true;
+        new.name = "Renamed".to_string();
+
+        let diff = diff_models(&old, &new).unwrap();
+        assert_eq!(diff.count(ChangeKind::Added), 1);
+        assert_eq!(diff.count(ChangeKind::Removed), 1);
+
+        let modified: Vec<_> = diff.changes.iter().filter(|c| c.kind == ChangeKind::Modified).collect();
+        assert_eq!(modified.len(), 2);
+        assert!(modified.iter().any(|c| c.component == DiffComponent::Model && c.properties[0].property == "name"));
+        let field_change = modified.iter().find(|c| c.component == DiffComponent::Field).unwrap();
+        assert_eq!(field_change.path, "entities[Customer].fields[email]");
+        assert_eq!(field_change.properties[0].property, "required");
+    }
+
+    #[test]
+    fn test_merge_combines_independent_changes() {
+        let base = model(vec![entity("Customer", vec![field("name")])]);
+        let mut ours = base.clone();
+        ours.entities[0].fields.push(field("email"));
+        let mut theirs = base.clone();
+        theirs.entities.push(entity("Order", vec![]));
+        theirs.description = Some("Shop".to_string());
+
+        let result = merge_models(&base, &ours, &theirs).unwrap();
+        assert!(result.is_clean());
+        assert_eq!(result.merged.entities.len(), 2);
+        assert_eq!(result.merged.entities[0].fields.len(), 2);
+        assert_eq!(result.merged.description.as_deref(), Some("Shop"));
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts_and_keeps_ours() {
+        let base = model(vec![entity("Customer", vec![field("name")]), entity("Order", vec![])]);
+        let mut ours = base.clone();
+        ours.entities[0].display_name = "Client".to_string();
+        ours.entities[1].description = Some("changed".to_string());
+        let mut theirs = base.clone();
+        theirs.entities[0].display_name = "Buyer".to_string();
+        theirs.entities.retain(|e| e.name != "Order");
+
+        let result = merge_models(&base, &ours, &theirs).unwrap();
+        assert_eq!(result.conflicts.len(), 2);
+        assert!(result.conflicts.iter().any(|c| c.property.as_deref() == Some("display_name")));
+        assert!(result.conflicts.iter().any(|c| c.property.is_none() && c.path == "entities[Order]"));
+        assert_eq!(result.merged.entities[0].display_name, "Client");
+        assert_eq!(result.merged.entities.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_reports_references_to_removed_entities() {
+        let base = model(vec![entity("Customer", vec![field("name")]), entity("Order", vec![])]);
+        let order_id = base.entities[1].id.clone();
+        let mut ours = base.clone();
+        ours.entities[0].fields.push(fixtures::field("orders", FieldType::Array {
+            element_type: Box::new(FieldType::Reference { entity_id: order_id.clone() }),
+        }));
+        let mut theirs = base.clone();
+        theirs.entities.retain(|e| e.name != "Order");
+
+        let result = merge_models(&base, &ours, &theirs).unwrap();
+        assert!(!result.is_clean());
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.component, DiffComponent::Field);
+        assert_eq!(conflict.path, "entities[Customer].fields[orders]");
+        assert_eq!(conflict.ours, Value::String("Order".to_string()));
+        assert_eq!(conflict.theirs, Value::Null);
+    }
+}
@@ -1,101 +1,242 @@
 use serde::{Deserialize, Serialize};
 use crate::common::Uuid;
 use crate::common::UtcDateTime;
+use crate::model::collab::{EditOperation, Presence};
 use crate::model::types::TorqueModel;
 
-/// Event types for model changes
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", content = "data")]
-pub enum ModelChangeEvent {
-    /// A model was created
-    ModelCreated {
-        model_id: Uuid,
-        model: TorqueModel,
-        timestamp: UtcDateTime,
-    },
-    /// A model was updated
-    ModelUpdated {
-        model_id: Uuid,
-        model: TorqueModel,
-        timestamp: UtcDateTime,
-    },
-    /// A model was deleted
-    ModelDeleted {
-        model_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// An entity was added to a model
-    EntityAdded {
-        model_id: Uuid,
-        entity_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// An entity was updated in a model
-    EntityUpdated {
-        model_id: Uuid,
-        entity_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// An entity was removed from a model
-    EntityRemoved {
-        model_id: Uuid,
-        entity_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A relationship was added to a model
-    RelationshipAdded {
-        model_id: Uuid,
-        relationship_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A relationship was updated in a model
-    RelationshipUpdated {
-        model_id: Uuid,
-        relationship_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A relationship was removed from a model
-    RelationshipRemoved {
-        model_id: Uuid,
-        relationship_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A flow was added to a model
-    FlowAdded {
-        model_id: Uuid,
-        flow_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A flow was updated in a model
-    FlowUpdated {
-        model_id: Uuid,
-        flow_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A flow was removed from a model
-    FlowRemoved {
-        model_id: Uuid,
-        flow_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A layout was added to a model
-    LayoutAdded {
-        model_id: Uuid,
-        layout_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A layout was updated in a model
-    LayoutUpdated {
-        model_id: Uuid,
-        layout_id: Uuid,
-        timestamp: UtcDateTime,
-    },
-    /// A layout was removed from a model
-    LayoutRemoved {
-        model_id: Uuid,
-        layout_id: Uuid,
-        timestamp: UtcDateTime,
-    },
+/// Declares [`ModelChangeEvent`] together with [`EVENT_KINDS`] and `ModelChangeEvent::kind`,
+/// so the kinds are always the variant names. Serde uses the variant name as the `type`
+/// tag, which is why variants must not be renamed.
+macro_rules! model_change_events {
+    (
+        $(#[$meta:meta])*
+        pub enum ModelChangeEvent {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident { $($fields:tt)* },
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        pub enum ModelChangeEvent {
+            $(
+                $(#[$variant_meta])*
+                $variant { $($fields)* },
+            )*
+        }
+
+        /// Serialized `type` of every event, for filtering subscriptions by kind
+        pub const EVENT_KINDS: &[&str] = &[$(stringify!($variant)),*];
+
+        impl ModelChangeEvent {
+            /// The event's serialized `type`, one of [`EVENT_KINDS`]
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => stringify!($variant),)*
+                }
+            }
+        }
+    };
+}
+
+model_change_events! {
+    /// Event types for model changes
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(tag = "type", content = "data")]
+    pub enum ModelChangeEvent {
+        /// A model was created
+        ModelCreated {
+            model_id: Uuid,
+            model: TorqueModel,
+            timestamp: UtcDateTime,
+        },
+        /// A model was updated
+        ModelUpdated {
+            model_id: Uuid,
+            model: TorqueModel,
+            timestamp: UtcDateTime,
+        },
+        /// A model was deleted
+        ModelDeleted {
+            model_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// An entity was added to a model
+        EntityAdded {
+            model_id: Uuid,
+            entity_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// An entity was updated in a model
+        EntityUpdated {
+            model_id: Uuid,
+            entity_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// An entity was removed from a model
+        EntityRemoved {
+            model_id: Uuid,
+            entity_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A relationship was added to a model
+        RelationshipAdded {
+            model_id: Uuid,
+            relationship_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A relationship was updated in a model
+        RelationshipUpdated {
+            model_id: Uuid,
+            relationship_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A relationship was removed from a model
+        RelationshipRemoved {
+            model_id: Uuid,
+            relationship_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A flow was added to a model
+        FlowAdded {
+            model_id: Uuid,
+            flow_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A flow was updated in a model
+        FlowUpdated {
+            model_id: Uuid,
+            flow_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A flow was removed from a model
+        FlowRemoved {
+            model_id: Uuid,
+            flow_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A layout was added to a model
+        LayoutAdded {
+            model_id: Uuid,
+            layout_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A layout was updated in a model
+        LayoutUpdated {
+            model_id: Uuid,
+            layout_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A layout was removed from a model
+        LayoutRemoved {
+            model_id: Uuid,
+            layout_id: Uuid,
+            timestamp: UtcDateTime,
+        },
+        /// A collaborative edit was applied to a model
+        OperationApplied {
+            model_id: Uuid,
+            revision: u64,
+            client_id: String,
+            operation: EditOperation,
+            timestamp: UtcDateTime,
+        },
+        /// Editors of a model joined, left or moved their cursor
+        PresenceChanged {
+            model_id: Uuid,
+            editors: Vec<Presence>,
+            timestamp: UtcDateTime,
+        },
+        /// An app data record of a model was created, updated or deleted
+        EntityDataChanged {
+            model_id: Uuid,
+            entity_type: String,
+            record_id: String,
+            action: DataChangeAction,
+            /// The record after the change, when it is small enough to send
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            record: Option<serde_json::Value>,
+            /// The fields changed by an update, sent instead of a record that is too large
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            diff: Option<RecordDiff>,
+            timestamp: UtcDateTime,
+        },
+        /// App data records of an entity type were changed in bulk, such as records removed
+        /// by the retention job or rewritten by a data migration
+        EntityDataBulkChanged {
+            model_id: Uuid,
+            entity_type: String,
+            action: DataChangeAction,
+            /// Number of records changed
+            count: u64,
+            /// IDs of the changed records, when few enough to send; otherwise subscribers
+            /// should reload the entity's data
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            record_ids: Option<Vec<String>>,
+            timestamp: UtcDateTime,
+        },
+        /// A flow execution started, progressed or finished. Flows have no executor yet,
+        /// so nothing publishes this event until one exists.
+        FlowExecutionUpdated {
+            model_id: Uuid,
+            flow_id: Uuid,
+            execution_id: Uuid,
+            status: String,
+            timestamp: UtcDateTime,
+        },
+    }
+}
+
+/// Kind of change made to an app data record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataChangeAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl DataChangeAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+/// Top-level fields changed by an update of an app data record
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordDiff {
+    /// Fields that were added or changed, with their new values
+    pub set: serde_json::Map<String, serde_json::Value>,
+    /// Fields that were removed
+    pub removed: Vec<String>,
+}
+
+impl RecordDiff {
+    /// Compare two records field by field; non-object records replace each other wholesale
+    pub fn between(before: &serde_json::Value, after: &serde_json::Value) -> Self {
+        let empty = serde_json::Map::new();
+        let before = before.as_object().unwrap_or(&empty);
+        let after = after.as_object().unwrap_or(&empty);
+
+        Self {
+            set: after.iter()
+                .filter(|(field, value)| before.get(*field) != Some(*value))
+                .map(|(field, value)| (field.clone(), value.clone()))
+                .collect(),
+            removed: before.keys()
+                .filter(|field| !after.contains_key(*field))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.removed.is_empty()
+    }
 }
 
 impl ModelChangeEvent {
@@ -233,6 +374,75 @@ impl ModelChangeEvent {
         }
     }
 
+    /// Create a new operation applied event
+    pub fn operation_applied(model_id: Uuid, revision: u64, client_id: String, operation: EditOperation) -> Self {
+        Self::OperationApplied {
+            model_id,
+            revision,
+            client_id,
+            operation,
+            timestamp: UtcDateTime::now(),
+        }
+    }
+
+    /// Create a new presence changed event
+    pub fn presence_changed(model_id: Uuid, editors: Vec<Presence>) -> Self {
+        Self::PresenceChanged {
+            model_id,
+            editors,
+            timestamp: UtcDateTime::now(),
+        }
+    }
+
+    /// Create a new entity data changed event
+    pub fn entity_data_changed(
+        model_id: Uuid,
+        entity_type: String,
+        record_id: String,
+        action: DataChangeAction,
+        record: Option<serde_json::Value>,
+        diff: Option<RecordDiff>,
+    ) -> Self {
+        Self::EntityDataChanged {
+            model_id,
+            entity_type,
+            record_id,
+            action,
+            record,
+            diff,
+            timestamp: UtcDateTime::now(),
+        }
+    }
+
+    /// Create a new bulk entity data changed event
+    pub fn entity_data_bulk_changed(
+        model_id: Uuid,
+        entity_type: String,
+        action: DataChangeAction,
+        count: u64,
+        record_ids: Option<Vec<String>>,
+    ) -> Self {
+        Self::EntityDataBulkChanged {
+            model_id,
+            entity_type,
+            action,
+            count,
+            record_ids,
+            timestamp: UtcDateTime::now(),
+        }
+    }
+
+    /// Create a new flow execution updated event
+    pub fn flow_execution_updated(model_id: Uuid, flow_id: Uuid, execution_id: Uuid, status: String) -> Self {
+        Self::FlowExecutionUpdated {
+            model_id,
+            flow_id,
+            execution_id,
+            status,
+            timestamp: UtcDateTime::now(),
+        }
+    }
+
     /// Get the model ID associated with this event
     pub fn model_id(&self) -> Uuid {
         match self {
@@ -251,6 +461,11 @@ impl ModelChangeEvent {
             Self::LayoutAdded { model_id, .. } => model_id.clone(),
             Self::LayoutUpdated { model_id, .. } => model_id.clone(),
             Self::LayoutRemoved { model_id, .. } => model_id.clone(),
+            Self::OperationApplied { model_id, .. } => model_id.clone(),
+            Self::PresenceChanged { model_id, .. } => model_id.clone(),
+            Self::EntityDataChanged { model_id, .. } => model_id.clone(),
+            Self::EntityDataBulkChanged { model_id, .. } => model_id.clone(),
+            Self::FlowExecutionUpdated { model_id, .. } => model_id.clone(),
         }
     }
 
@@ -272,6 +487,11 @@ impl ModelChangeEvent {
             Self::LayoutAdded { timestamp, .. } => timestamp.clone(),
             Self::LayoutUpdated { timestamp, .. } => timestamp.clone(),
             Self::LayoutRemoved { timestamp, .. } => timestamp.clone(),
+            Self::OperationApplied { timestamp, .. } => timestamp.clone(),
+            Self::PresenceChanged { timestamp, .. } => timestamp.clone(),
+            Self::EntityDataChanged { timestamp, .. } => timestamp.clone(),
+            Self::EntityDataBulkChanged { timestamp, .. } => timestamp.clone(),
+            Self::FlowExecutionUpdated { timestamp, .. } => timestamp.clone(),
         }
     }
 
@@ -293,34 +513,80 @@ impl ModelChangeEvent {
             Self::LayoutAdded { layout_id, .. } => format!("Layout {} was added", layout_id),
             Self::LayoutUpdated { layout_id, .. } => format!("Layout {} was updated", layout_id),
             Self::LayoutRemoved { layout_id, .. } => format!("Layout {} was removed", layout_id),
+            Self::OperationApplied { revision, operation, .. } => format!("Revision {}: {}", revision, operation.description()),
+            Self::PresenceChanged { editors, .. } => format!("{} editors present", editors.len()),
+            Self::EntityDataChanged { entity_type, record_id, action, .. } => format!("{} record {} was {}", entity_type, record_id, action.as_str()),
+            Self::EntityDataBulkChanged { entity_type, count, action, .. } => format!("{} {} records were {}", count, entity_type, action.as_str()),
+            Self::FlowExecutionUpdated { flow_id, status, .. } => format!("Flow {} execution is {}", flow_id, status),
         }
     }
+
+    /// Events that only describe momentary state, such as editor presence, and are
+    /// not recorded in the event log
+    pub fn is_ephemeral(&self) -> bool {
+        matches!(self, Self::PresenceChanged { .. })
+    }
 }
 
 /// WebSocket message wrapper for model events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelEventMessage {
+    /// Position in the event log; `None` for ephemeral events, which are not logged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
     /// The event data
     pub event: ModelChangeEvent,
     /// Optional client ID to exclude from broadcast (avoid echo)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exclude_client: Option<String>,
+    /// Node that published the event, when it was read back from the event log
+    #[serde(skip)]
+    pub origin: Option<Uuid>,
 }
 
 impl ModelEventMessage {
     /// Create a new event message
     pub fn new(event: ModelChangeEvent) -> Self {
         Self {
+            seq: None,
             event,
             exclude_client: None,
+            origin: None,
         }
     }
 
     /// Create a new event message with client exclusion
     pub fn with_exclusion(event: ModelChangeEvent, exclude_client: String) -> Self {
         Self {
+            seq: None,
             event,
             exclude_client: Some(exclude_client),
+            origin: None,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_matches_serialized_type() {
+        let model_id = Uuid::new_v4();
+        let events = [
+            ModelChangeEvent::model_deleted(model_id.clone()),
+            ModelChangeEvent::entity_data_changed(model_id.clone(), "task".to_string(), "1".to_string(), DataChangeAction::Created, None, None),
+            ModelChangeEvent::entity_data_bulk_changed(model_id, "task".to_string(), DataChangeAction::Deleted, 2, None),
+        ];
+        for event in events {
+            let value = serde_json::to_value(&event).unwrap();
+            assert_eq!(value["type"], event.kind());
+            assert!(EVENT_KINDS.contains(&event.kind()));
+        }
+
+        let mut kinds = EVENT_KINDS.to_vec();
+        kinds.sort();
+        kinds.dedup();
+        assert_eq!(kinds.len(), EVENT_KINDS.len());
+    }
+}
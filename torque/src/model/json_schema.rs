@@ -0,0 +1,319 @@
+use crate::error::Error;
+use crate::model::types::{
+    EntityField, FieldType, ModelEntity, TorqueModel, ValidationSeverity, ValidationType,
+};
+use crate::common::Uuid;
+use crate::model::events::{ModelChangeEvent, ModelEventMessage};
+use dashmap::DashMap;
+use serde_json::{json, Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema (draft 2020-12) describing the data of one entity.
+///
+/// Only validations with `Error` severity are enforced; warnings and custom
+/// expressions are left to the UI. Fields that aren't required also accept `null`.
+pub fn entity_json_schema(model: &TorqueModel, entity: &ModelEntity) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for field in &entity.fields {
+        let is_required = field.required || field.validation.iter().any(|v| {
+            matches!(v.validation_type, ValidationType::Required)
+                && matches!(v.severity, ValidationSeverity::Error)
+        });
+        if is_required {
+            required.push(json!(field.name));
+        }
+        properties.insert(field.name.clone(), field_schema(model, field, is_required));
+    }
+
+    let mut schema = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "$id": format!("urn:torque:model:{}:entity:{}", model.id, entity.name),
+        "title": entity.display_name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        // Records are stored as JSON documents and may carry extra keys
+        "additionalProperties": true,
+    });
+    if let Some(description) = &entity.description {
+        schema["description"] = json!(description);
+    }
+    schema
+}
+
+fn field_schema(model: &TorqueModel, field: &EntityField, required: bool) -> Value {
+    let mut schema = field_type_schema(model, &field.field_type);
+
+    schema["title"] = json!(field.display_name);
+    if let Some(default) = &field.default_value {
+        schema["default"] = default.clone();
+    }
+
+    for validation in &field.validation {
+        if !matches!(validation.severity, ValidationSeverity::Error) {
+            continue;
+        }
+        match &validation.validation_type {
+            ValidationType::MinLength(n) => schema["minLength"] = json!(n),
+            ValidationType::MaxLength(n) => schema["maxLength"] = json!(n),
+            ValidationType::Pattern(pattern) => schema["pattern"] = json!(pattern),
+            ValidationType::Range { min, max } => {
+                if min.is_number() {
+                    schema["minimum"] = min.clone();
+                }
+                if max.is_number() {
+                    schema["maximum"] = max.clone();
+                }
+            }
+            ValidationType::Required | ValidationType::Custom(_) => {}
+        }
+    }
+
+    if required {
+        schema
+    } else {
+        nullable(schema)
+    }
+}
+
+/// Schema for a single value of the given field type
+pub fn field_type_schema(model: &TorqueModel, field_type: &FieldType) -> Value {
+    match field_type {
+        FieldType::String { max_length } => {
+            let mut schema = json!({ "type": "string" });
+            if let Some(max_length) = max_length {
+                schema["maxLength"] = json!(max_length);
+            }
+            schema
+        }
+        FieldType::Integer { min, max } => {
+            let mut schema = json!({ "type": "integer" });
+            if let Some(min) = min {
+                schema["minimum"] = json!(min);
+            }
+            if let Some(max) = max {
+                schema["maximum"] = json!(max);
+            }
+            schema
+        }
+        FieldType::Float { min, max } => {
+            let mut schema = json!({ "type": "number" });
+            if let Some(min) = min {
+                schema["minimum"] = json!(min);
+            }
+            if let Some(max) = max {
+                schema["maximum"] = json!(max);
+            }
+            schema
+        }
+        FieldType::Boolean => json!({ "type": "boolean" }),
+        FieldType::DateTime => json!({ "type": "string", "format": "date-time" }),
+        FieldType::Date => json!({ "type": "string", "format": "date" }),
+        FieldType::Time => json!({ "type": "string", "format": "time" }),
+        FieldType::Json => json!({}),
+        FieldType::Binary => json!({ "type": "string", "description": "ID of an uploaded blob" }),
+        FieldType::Enum { values } => json!({ "type": "string", "enum": values }),
+        FieldType::Reference { entity_id } => {
+            let target = model.entities.iter()
+                .find(|e| &e.id == entity_id)
+                .map(|e| e.name.clone())
+                .unwrap_or_else(|| entity_id.to_string());
+            // Records are referenced by their ID, which sample data also writes as an integer
+            json!({ "type": ["string", "integer"], "x-torque-reference": target })
+        }
+        FieldType::Array { element_type } => json!({
+            "type": "array",
+            "items": field_type_schema(model, element_type),
+        }),
+    }
+}
+
+/// Allow `null` in addition to what the schema accepts
+fn nullable(mut schema: Value) -> Value {
+    match schema.get("type").cloned() {
+        Some(Value::String(t)) => schema["type"] = json!([t, "null"]),
+        Some(Value::Array(mut types)) => {
+            types.push(json!("null"));
+            schema["type"] = Value::Array(types);
+        }
+        // No type constraint, anything (including null) already validates
+        _ => return schema,
+    }
+    if let Some(values) = schema.get_mut("enum").and_then(|v| v.as_array_mut()) {
+        values.push(Value::Null);
+    }
+    schema
+}
+
+/// Find an entity by name or ID
+pub fn find_entity<'a>(model: &'a TorqueModel, name_or_id: &str) -> Option<&'a ModelEntity> {
+    model.entities.iter()
+        .find(|e| e.name == name_or_id)
+        .or_else(|| model.entities.iter().find(|e| e.id.to_string() == name_or_id))
+}
+
+struct CachedValidator {
+    fingerprint: u64,
+    validator: Arc<jsonschema::Validator>,
+}
+
+/// Compiled validators by model and entity, reused until the model version or the entity changes
+static ENTITY_VALIDATORS: once_cell::sync::Lazy<DashMap<(Uuid, Uuid), CachedValidator>> =
+    once_cell::sync::Lazy::new(DashMap::new);
+
+/// Drop the cached validators of a model's entities, or of one of them, returning how many
+/// were dropped
+pub fn forget_validators(model_id: &Uuid, entity_id: Option<&Uuid>) -> usize {
+    let before = ENTITY_VALIDATORS.len();
+    ENTITY_VALIDATORS.retain(|(model, entity), _| model != model_id || entity_id.is_some_and(|id| id != entity));
+    before - ENTITY_VALIDATORS.len()
+}
+
+/// Drop cached validators as models and entities are deleted
+pub fn spawn_validator_eviction(mut receiver: Receiver<ModelEventMessage>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(ModelEventMessage { event: ModelChangeEvent::ModelDeleted { model_id, .. }, .. }) => {
+                    forget_validators(&model_id, None);
+                }
+                Ok(ModelEventMessage { event: ModelChangeEvent::EntityRemoved { model_id, entity_id, .. }, .. }) => {
+                    forget_validators(&model_id, Some(&entity_id));
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(_)) => ENTITY_VALIDATORS.clear(),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Fill in the default values of fields missing from an object
+pub fn apply_defaults(entity: &ModelEntity, data: &mut Value) {
+    let Some(object) = data.as_object_mut() else {
+        return;
+    };
+    for field in &entity.fields {
+        if let Some(default) = &field.default_value {
+            object.entry(field.name.clone()).or_insert_with(|| default.clone());
+        }
+    }
+}
+
+/// Validate entity data against the entity's generated schema
+pub fn validate_entity_data(model: &TorqueModel, entity: &ModelEntity, data: &Value) -> Result<(), Error> {
+    let validator = entity_validator(model, entity)?;
+
+    let errors: Vec<String> = validator.iter_errors(data)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            if path.is_empty() {
+                error.to_string()
+            } else {
+                format!("{}: {}", path, error)
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(format!("Invalid {} data: {}", entity.name, errors.join("; "))))
+    }
+}
+
+/// The compiled validator for an entity, compiling it again when the entity has changed
+fn entity_validator(model: &TorqueModel, entity: &ModelEntity) -> Result<Arc<jsonschema::Validator>, Error> {
+    let key = (model.id.clone(), entity.id.clone());
+    let fingerprint = validator_fingerprint(model, entity);
+    if let Some(cached) = ENTITY_VALIDATORS.get(&key).filter(|c| c.fingerprint == fingerprint) {
+        return Ok(cached.validator.clone());
+    }
+
+    let schema = entity_json_schema(model, entity);
+    let validator = Arc::new(jsonschema::validator_for(&schema).map_err(|e| {
+        Error::Validation(format!("Entity '{}' has an invalid schema: {}", entity.name, e))
+    })?);
+    ENTITY_VALIDATORS.insert(key, CachedValidator { fingerprint, validator: validator.clone() });
+    Ok(validator)
+}
+
+fn validator_fingerprint(model: &TorqueModel, entity: &ModelEntity) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    model.version.hash(&mut hasher);
+    serde_json::to_string(entity).unwrap_or_default().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_fixtures::{entity, field, model};
+    use crate::model::types::FieldValidation;
+
+    fn required(field: EntityField) -> EntityField {
+        EntityField { required: true, ..field }
+    }
+
+    fn model_with(fields: Vec<EntityField>) -> TorqueModel {
+        model(vec![entity("customer", fields)])
+    }
+
+    #[test]
+    fn test_entity_schema_validates_data() {
+        let mut email = required(field("email", FieldType::String { max_length: Some(50) }));
+        email.validation.push(FieldValidation {
+            validation_type: ValidationType::Pattern("^[^@]+@[^@]+$".to_string()),
+            message: "Invalid email".to_string(),
+            severity: ValidationSeverity::Error,
+        });
+        let model = model_with(vec![
+            email,
+            field("age", FieldType::Integer { min: Some(0), max: None }),
+            field("tier", FieldType::Enum { values: vec!["Gold".to_string()] }),
+        ]);
+        let entity = &model.entities[0];
+
+        let schema = entity_json_schema(&model, entity);
+        assert_eq!(schema["required"], json!(["email"]));
+        assert_eq!(schema["properties"]["age"]["type"], json!(["integer", "null"]));
+        assert_eq!(schema["properties"]["tier"]["enum"], json!(["Gold", null]));
+
+        assert!(validate_entity_data(&model, entity, &json!({"email": "a@b.c", "age": null})).is_ok());
+        assert!(validate_entity_data(&model, entity, &json!({"age": 3})).is_err());
+        assert!(validate_entity_data(&model, entity, &json!({"email": "nope"})).is_err());
+        assert!(validate_entity_data(&model, entity, &json!({"email": "a@b.c", "age": -1})).is_err());
+    }
+
+    #[test]
+    fn test_defaults_fill_required_fields() {
+        let mut status = required(field("status", FieldType::Enum { values: vec!["open".to_string(), "closed".to_string()] }));
+        status.default_value = Some(json!("open"));
+        let mut model = model_with(vec![status]);
+
+        let mut data = json!({"status": "closed"});
+        apply_defaults(&model.entities[0], &mut data);
+        assert_eq!(data, json!({"status": "closed"}));
+
+        let mut data = json!({});
+        assert!(validate_entity_data(&model, &model.entities[0], &data).is_err());
+        apply_defaults(&model.entities[0], &mut data);
+        assert_eq!(data, json!({"status": "open"}));
+        assert!(validate_entity_data(&model, &model.entities[0], &data).is_ok());
+
+        // A changed entity isn't checked with the validator compiled before the change
+        model.entities[0].fields[0].field_type = FieldType::Enum { values: vec!["closed".to_string()] };
+        assert!(validate_entity_data(&model, &model.entities[0], &data).is_err());
+
+        assert_eq!(forget_validators(&model.id, Some(&Uuid::new_v4())), 0);
+        assert_eq!(forget_validators(&model.id, Some(&model.entities[0].id)), 1);
+        assert_eq!(forget_validators(&model.id, None), 0);
+    }
+}
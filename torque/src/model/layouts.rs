@@ -0,0 +1,404 @@
+use crate::common::{Uuid, UtcDateTime};
+use crate::error::Error;
+use crate::jsonrpc::direct_mapping::DirectMapping;
+use crate::model::types::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Kind of layout generated for an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutKind {
+    List,
+    Detail,
+    Form,
+}
+
+impl LayoutKind {
+    pub const ALL: [LayoutKind; 3] = [LayoutKind::List, LayoutKind::Detail, LayoutKind::Form];
+
+    pub fn parse(kind: &str) -> Result<Self, Error> {
+        match kind.to_lowercase().as_str() {
+            "list" => Ok(Self::List),
+            "detail" => Ok(Self::Detail),
+            "form" => Ok(Self::Form),
+            other => Err(Error::InvalidInput(format!("Unknown layout kind '{}' (expected list, detail or form)", other))),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::List => "list",
+            Self::Detail => "detail",
+            Self::Form => "form",
+        }
+    }
+
+    fn layout_type(&self) -> LayoutType {
+        match self {
+            Self::List => LayoutType::List,
+            Self::Detail => LayoutType::Detail,
+            Self::Form => LayoutType::Form,
+        }
+    }
+}
+
+/// Name given to a generated layout, e.g. `customer_list`
+pub fn layout_name(entity: &ModelEntity, kind: LayoutKind) -> String {
+    format!("{}_{}", entity.name, kind.as_str())
+}
+
+/// List, detail and form layouts for an entity
+pub fn default_layouts(model: &TorqueModel, entity: &ModelEntity) -> Vec<ModelLayout> {
+    generate_layouts(model, entity, &LayoutKind::ALL)
+}
+
+/// Generate layouts for an entity.
+///
+/// Columns, sections and form settings come from the entity's `ui_config` views, falling back
+/// to all visible fields. Reference fields become lookups and entities that reference this
+/// one are shown as sub-grids on the detail layout.
+pub fn generate_layouts(model: &TorqueModel, entity: &ModelEntity, kinds: &[LayoutKind]) -> Vec<ModelLayout> {
+    kinds.iter().map(|kind| {
+        let components = match kind {
+            LayoutKind::List => list_components(entity),
+            LayoutKind::Detail => detail_components(model, entity),
+            LayoutKind::Form => vec![form_component(model, entity, &editable_fields(entity), 0, false)],
+        };
+        let now = UtcDateTime::now();
+        ModelLayout {
+            id: Uuid::new_v4(),
+            name: layout_name(entity, *kind),
+            description: Some(format!("Generated {} layout for {}", kind.as_str(), entity.display_name)),
+            layout_type: kind.layout_type(),
+            target_entities: vec![entity.id.clone()],
+            components,
+            responsive: ResponsiveLayout { breakpoints: vec![], adaptive_components: vec![] },
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }).collect()
+}
+
+fn component(component_type: &str, row: u32, height: u32, properties: HashMap<String, Value>) -> LayoutComponent {
+    LayoutComponent {
+        id: Uuid::new_v4(),
+        component_type: component_type.to_string(),
+        position: ComponentPosition { row, column: 0, width: 12, height },
+        properties,
+        styling: HashMap::new(),
+        metadata: Some(HashMap::from([("createdWith".to_string(), json!("LayoutGenerator"))])),
+    }
+}
+
+fn properties(pairs: Value) -> HashMap<String, Value> {
+    serde_json::from_value(pairs).unwrap_or_default()
+}
+
+fn is_visible(field: &EntityField) -> bool {
+    !matches!(field.ui_config.visibility, FieldVisibility::Hidden)
+}
+
+fn editable_fields(entity: &ModelEntity) -> Vec<&EntityField> {
+    entity.fields.iter().filter(|f| is_visible(f)).collect()
+}
+
+fn label(field: &EntityField) -> String {
+    field.ui_config.label.clone().unwrap_or_else(|| field.display_name.clone())
+}
+
+/// DataGrid column for a field, using the list view settings when the column is configured
+fn grid_column(field: &EntityField, configured: Option<&ListColumn>) -> Value {
+    let width = configured
+        .and_then(|c| c.width.as_deref())
+        .and_then(|w| w.trim_end_matches("px").parse::<u32>().ok())
+        .unwrap_or(150);
+    let mut column = json!({
+        "key": field.name,
+        "field": field.name,
+        "title": label(field),
+        "dataType": DirectMapping::column_data_type(&field.field_type),
+        "sortable": configured.is_none_or(|c| c.sortable),
+        "filterable": configured.is_none_or(|c| c.filterable),
+        "width": width,
+    });
+    if let FieldType::Enum { values } = &field.field_type {
+        column["filterOptions"] = json!(values);
+    }
+    if let Some(formatter) = configured.and_then(|c| c.formatter.as_ref()) {
+        column["formatter"] = json!(formatter);
+    }
+    column
+}
+
+fn list_components(entity: &ModelEntity) -> Vec<LayoutComponent> {
+    let list_view = &entity.ui_config.list_view;
+
+    let columns: Vec<Value> = if list_view.columns.is_empty() {
+        entity.fields.iter().filter(|f| is_visible(f)).map(|f| grid_column(f, None)).collect()
+    } else {
+        list_view.columns.iter()
+            .filter_map(|c| entity.fields.iter().find(|f| f.name == c.field).map(|f| grid_column(f, Some(c))))
+            .collect()
+    };
+
+    let mut grid = properties(json!({
+        "entityType": entity.name,
+        "title": entity.display_name,
+        "columns": columns,
+        "pageSize": list_view.pagination.page_size,
+        "pageSizeOptions": list_view.pagination.page_size_options,
+        "features": ["sort", "filter", "pagination"],
+    }));
+    if let Some(sort) = &list_view.default_sort {
+        let (field, direction) = match sort.strip_prefix('-') {
+            Some(field) => (field, "desc"),
+            None => (sort.as_str(), "asc"),
+        };
+        grid.insert("defaultSort".to_string(), json!({ "field": field, "direction": direction }));
+    }
+    if !list_view.filters.is_empty() {
+        grid.insert("filters".to_string(), json!(list_view.filters));
+    }
+
+    let create = component("TorqueButton", 8, 1, properties(json!({
+        "text": format!("New {}", entity.display_name),
+        "variant": "primary",
+        "action": { "type": "openModal", "entity": entity.name, "mode": "create" },
+    })));
+
+    vec![component("DataGrid", 0, 8, grid), create]
+}
+
+/// A relationship seen from the side holding the foreign key
+struct ForeignKey<'a> {
+    relationship: &'a ModelRelationship,
+    child: &'a Uuid,
+    child_field: &'a str,
+    parent: &'a Uuid,
+    parent_field: &'a str,
+}
+
+fn foreign_keys(model: &TorqueModel) -> impl Iterator<Item = ForeignKey<'_>> {
+    model.relationships.iter().filter_map(|r| match r.relationship_type {
+        RelationshipType::OneToMany => Some(ForeignKey {
+            relationship: r,
+            child: &r.to_entity,
+            child_field: &r.to_field,
+            parent: &r.from_entity,
+            parent_field: &r.from_field,
+        }),
+        RelationshipType::ManyToOne | RelationshipType::OneToOne => Some(ForeignKey {
+            relationship: r,
+            child: &r.from_entity,
+            child_field: &r.from_field,
+            parent: &r.to_entity,
+            parent_field: &r.to_field,
+        }),
+        // Join records aren't edited through either side
+        RelationshipType::ManyToMany => None,
+    })
+}
+
+/// Entity a field points at, with the field lookups should store
+fn lookup_target<'a>(model: &'a TorqueModel, entity: &ModelEntity, field: &EntityField) -> Option<(&'a ModelEntity, String)> {
+    if let Some(fk) = foreign_keys(model).find(|fk| fk.child == &entity.id && fk.child_field == field.name) {
+        let target = model.entities.iter().find(|e| &e.id == fk.parent)?;
+        return Some((target, fk.parent_field.to_string()));
+    }
+    let FieldType::Reference { entity_id } = &field.field_type else {
+        return None;
+    };
+    let target = model.entities.iter().find(|e| &e.id == entity_id)?;
+    // References store the target record's `_id`
+    Some((target, "_id".to_string()))
+}
+
+fn form_field(model: &TorqueModel, entity: &ModelEntity, field: &EntityField, read_only: bool) -> Value {
+    let ui = &field.ui_config;
+    let mut value = json!({
+        "id": field.id,
+        "name": field.name,
+        "label": label(field),
+        "type": DirectMapping::form_input_type(field),
+        "component": ui.component_type,
+        "required": field.required,
+        "readOnly": read_only || matches!(ui.edit_mode, FieldEditMode::ReadOnly),
+        "disabled": matches!(ui.edit_mode, FieldEditMode::Disabled),
+        "defaultValue": field.default_value,
+        "validation": field.validation,
+    });
+    if let Some(placeholder) = &ui.placeholder {
+        value["placeholder"] = json!(placeholder);
+    }
+    if let Some(help_text) = &ui.help_text {
+        value["helpText"] = json!(help_text);
+    }
+    if let FieldType::Enum { values } = &field.field_type {
+        value["options"] = json!(values);
+    }
+    if let Some((target, value_field)) = lookup_target(model, entity, field) {
+        let display_field = target.fields.iter()
+            .find(|f| matches!(f.field_type, FieldType::String { .. }))
+            .map_or(value_field.clone(), |f| f.name.clone());
+        value["type"] = json!("lookup");
+        value["lookup"] = json!({
+            "entity": target.name,
+            "valueField": value_field,
+            "displayField": display_field,
+        });
+    }
+    value
+}
+
+fn form_component(model: &TorqueModel, entity: &ModelEntity, fields: &[&EntityField], row: u32, read_only: bool) -> LayoutComponent {
+    let form_view = &entity.ui_config.form_view;
+    let layout = match form_view.layout {
+        FormLayout::Single => "single",
+        FormLayout::TwoColumn => "two-column",
+        FormLayout::Wizard => "wizard",
+        FormLayout::Custom => "custom",
+    };
+    let height = (fields.len() as u32).div_ceil(2).max(2);
+    component("TorqueForm", row, height, properties(json!({
+        "entityName": entity.name,
+        "title": entity.display_name,
+        "mode": if read_only { "view" } else { "edit" },
+        "layout": layout,
+        "fields": fields.iter().map(|f| form_field(model, entity, f, read_only)).collect::<Vec<_>>(),
+        "validation": {
+            "clientSide": form_view.validation.client_side,
+            "serverSide": form_view.validation.server_side,
+            "realTime": form_view.validation.real_time,
+        },
+        "submission": {
+            "autoSave": form_view.submission.auto_save,
+            "confirmation": form_view.submission.confirmation,
+            "redirectAfterSave": form_view.submission.redirect_after_save,
+        },
+    })))
+}
+
+fn detail_components(model: &TorqueModel, entity: &ModelEntity) -> Vec<LayoutComponent> {
+    let detail_view = &entity.ui_config.detail_view;
+    let mut components = Vec::new();
+    let mut row = 0;
+
+    // One read-only form per section, or a single section with every visible field
+    let sections: Vec<(String, Vec<&EntityField>)> = if detail_view.sections.is_empty() {
+        vec![(entity.display_name.clone(), editable_fields(entity))]
+    } else {
+        detail_view.sections.iter().map(|section| {
+            let fields = section.fields.iter()
+                .filter_map(|name| entity.fields.iter().find(|f| &f.name == name))
+                .collect();
+            (section.title.clone(), fields)
+        }).collect()
+    };
+    for (title, fields) in sections {
+        let mut form = form_component(model, entity, &fields, row, true);
+        form.properties.insert("title".to_string(), json!(title));
+        row += form.position.height;
+        components.push(form);
+    }
+
+    // Records of other entities that reference this one
+    for fk in foreign_keys(model).filter(|fk| fk.parent == &entity.id) {
+        let Some(child) = model.entities.iter().find(|e| &e.id == fk.child) else {
+            continue;
+        };
+        let columns: Vec<Value> = child.fields.iter()
+            .filter(|f| is_visible(f) && f.name != fk.child_field)
+            .map(|f| grid_column(f, None))
+            .collect();
+        components.push(component("DataGrid", row, 6, properties(json!({
+            "entityType": child.name,
+            "title": child.display_name,
+            "columns": columns,
+            "pageSize": child.ui_config.list_view.pagination.page_size,
+            "features": ["sort", "pagination"],
+            "relationship": fk.relationship.name,
+            // Resolved against the record shown by the layout
+            "parentFilter": { "field": fk.child_field, "parentField": fk.parent_field },
+        }))));
+        row += 6;
+    }
+
+    for (i, action) in detail_view.actions.iter().enumerate() {
+        let mut button = component("TorqueButton", row, 1, properties(json!({
+            "text": action.label,
+            "variant": if matches!(action.action_type, ActionType::Delete) { "danger" } else { "secondary" },
+            "action": { "name": action.name, "type": action.action_type, "entity": entity.name },
+            "confirmation": action.confirmation,
+        })));
+        button.position.column = (i as u32 % 4) * 3;
+        button.position.width = 3;
+        components.push(button);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_fixtures::{entity, field, model};
+
+    #[test]
+    fn test_generate_layouts() {
+        let mut customer = entity("customer", vec![field("id", FieldType::Integer { min: None, max: None }), field("name", FieldType::String { max_length: None })]);
+        let mut secret = field("secret", FieldType::String { max_length: None });
+        secret.ui_config.visibility = FieldVisibility::Hidden;
+        customer.fields.push(secret);
+        customer.ui_config.list_view.columns = vec![ListColumn {
+            field: "name".to_string(),
+            width: Some("200px".to_string()),
+            sortable: true,
+            filterable: false,
+            formatter: None,
+        }];
+        let order = entity("order", vec![field("customer_id", FieldType::Reference { entity_id: customer.id.clone() })]);
+
+        let relationships = vec![ModelRelationship {
+            id: Uuid::new_v4(),
+            name: "order_customer".to_string(),
+            relationship_type: RelationshipType::ManyToOne,
+            from_entity: order.id.clone(),
+            to_entity: customer.id.clone(),
+            from_field: "customer_id".to_string(),
+            to_field: "id".to_string(),
+            cascade: CascadeAction::None,
+            ui_config: RelationshipUiConfig::default(),
+        }];
+        let mut model = model(vec![customer, order]);
+        model.relationships = relationships;
+        let (customer, order) = (&model.entities[0], &model.entities[1]);
+
+        let layouts = default_layouts(&model, customer);
+        assert_eq!(layouts.iter().map(|l| l.name.as_str()).collect::<Vec<_>>(), ["customer_list", "customer_detail", "customer_form"]);
+
+        // Configured list columns win over the field list
+        let columns = layouts[0].components[0].properties["columns"].as_array().unwrap();
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0]["width"], 200);
+
+        // Hidden fields stay off the form; referencing entities become sub-grids on the detail
+        let form_fields = layouts[2].components[0].properties["fields"].as_array().unwrap();
+        assert!(form_fields.iter().all(|f| f["name"] != "secret"));
+        assert!(layouts[1].components.iter().any(|c| c.component_type == "DataGrid" && c.properties["entityType"] == "order"));
+
+        let order_form = generate_layouts(&model, order, &[LayoutKind::Form]);
+        let lookup = &order_form[0].components[0].properties["fields"][0];
+        assert_eq!(lookup["type"], "lookup");
+        assert_eq!(lookup["lookup"]["entity"], "customer");
+        assert_eq!(lookup["lookup"]["displayField"], "name");
+        assert_eq!(lookup["lookup"]["valueField"], "id");
+
+        // Without a relationship, a Reference field looks up the target record by its `_id`
+        let mut unrelated = model.clone();
+        unrelated.relationships.clear();
+        let order_form = generate_layouts(&unrelated, order, &[LayoutKind::Form]);
+        let lookup = &order_form[0].components[0].properties["fields"][0];
+        assert_eq!(lookup["type"], "lookup");
+        assert_eq!(lookup["lookup"]["valueField"], "_id");
+    }
+}
@@ -7,10 +7,23 @@ pub mod events;
 pub mod validation;
 pub mod remediation;
 pub mod remediation_executor;
+pub mod diff;
+pub mod data_migration;
+pub mod templates;
+pub mod json_schema;
+pub mod openapi;
+pub mod codegen;
+pub mod layouts;
+pub mod collab;
+
+// Models, entities and fields shared by the unit tests of the model modules
+#[cfg(test)]
+pub(crate) mod test_fixtures;
 
 pub use service::ModelService;
 pub use types::*;
 pub use events::*;
 pub use validation::*;
 pub use remediation::*;
-pub use remediation_executor::RemediationExecutor;
\ No newline at end of file
+pub use remediation_executor::RemediationExecutor;
+pub use diff::{diff_models, merge_models, ModelDiff, MergeResult};
\ No newline at end of file
@@ -0,0 +1,302 @@
+use crate::model::json_schema::{entity_json_schema, field_type_schema};
+use crate::model::types::{FieldType, ModelEntity, TorqueModel};
+use serde_json::{json, Map, Value};
+
+/// OpenAPI 3.1 document for the generated REST API of a model.
+///
+/// Paths are relative to `base_path` (e.g. `/api/models/<id>`). Component schemas are the
+/// entity JSON Schemas; relationships become response links between entity operations.
+pub fn model_openapi(model: &TorqueModel, base_path: &str) -> Value {
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+
+    for entity in &model.entities {
+        schemas.insert(entity.name.clone(), component_schema(model, entity));
+        schemas.insert(format!("{}Record", entity.name), record_schema(entity));
+
+        paths.insert(format!("/{}", entity.name), collection_path(model, entity));
+        paths.insert(format!("/{}/{{id}}", entity.name), item_path(model, entity));
+    }
+
+    schemas.insert("Error".to_string(), json!({
+        "type": "object",
+        "properties": { "error": { "type": "string" } },
+        "required": ["error"],
+    }));
+
+    let mut info = json!({
+        "title": model.name,
+        "version": model.version,
+    });
+    if let Some(description) = &model.description {
+        info["description"] = json!(description);
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": info,
+        "jsonSchemaDialect": crate::model::json_schema::JSON_SCHEMA_DIALECT,
+        "servers": [{ "url": base_path }],
+        "paths": paths,
+        "components": { "schemas": schemas },
+    })
+}
+
+fn component_schema(model: &TorqueModel, entity: &ModelEntity) -> Value {
+    let mut schema = entity_json_schema(model, entity);
+    if let Some(object) = schema.as_object_mut() {
+        // The document declares the dialect once; a `$id` per component would change `$ref` resolution
+        object.remove("$schema");
+        object.remove("$id");
+    }
+    schema
+}
+
+/// Stored record: the entity data plus the metadata added by the API
+fn record_schema(entity: &ModelEntity) -> Value {
+    json!({
+        "allOf": [
+            { "$ref": schema_ref(&entity.name) },
+            {
+                "type": "object",
+                "properties": {
+                    "_id": { "type": "string", "format": "uuid" },
+                    "_created_at": { "type": "string" },
+                    "_updated_at": { "type": "string" },
+                },
+                "required": ["_id", "_created_at", "_updated_at"],
+            }
+        ]
+    })
+}
+
+fn schema_ref(name: &str) -> String {
+    format!("#/components/schemas/{}", name)
+}
+
+fn error_response(description: &str) -> Value {
+    json!({
+        "description": description,
+        "content": { "application/json": { "schema": { "$ref": schema_ref("Error") } } },
+    })
+}
+
+fn record_response(entity: &ModelEntity, description: &str, links: Option<Value>) -> Value {
+    let mut response = json!({
+        "description": description,
+        "content": {
+            "application/json": { "schema": { "$ref": schema_ref(&format!("{}Record", entity.name)) } }
+        },
+    });
+    if let Some(links) = links.filter(|l| l.as_object().is_some_and(|o| !o.is_empty())) {
+        response["links"] = links;
+    }
+    response
+}
+
+fn operation_id(action: &str, entity: &ModelEntity) -> String {
+    format!("{}_{}", action, entity.name)
+}
+
+fn collection_path(model: &TorqueModel, entity: &ModelEntity) -> Value {
+    let mut parameters = vec![
+        json!({ "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 1 } }),
+        json!({ "name": "offset", "in": "query", "schema": { "type": "integer", "minimum": 0 } }),
+        json!({
+            "name": "sort",
+            "in": "query",
+            "description": "Field to sort by, prefixed with `-` for descending order",
+            "schema": { "type": "string" },
+        }),
+    ];
+
+    for field in &entity.fields {
+        if let Some(schema) = filter_schema(model, &field.field_type) {
+            parameters.push(json!({
+                "name": field.name,
+                "in": "query",
+                "description": format!("Filter on {}; use `{}[op]` with op one of ne, gt, gte, lt, lte, contains, in (comma-separated)", field.display_name, field.name),
+                "schema": schema,
+            }));
+        }
+    }
+
+    json!({
+        "get": {
+            "operationId": operation_id("list", entity),
+            "summary": format!("List {} records", entity.display_name),
+            "tags": [entity.name],
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "description": "A page of records",
+                    "content": {
+                        "application/json": {
+                            "schema": {
+                                "type": "object",
+                                "properties": {
+                                    "data": { "type": "array", "items": { "$ref": schema_ref(&format!("{}Record", entity.name)) } },
+                                    "total": { "type": "integer" },
+                                    "limit": { "type": "integer" },
+                                    "offset": { "type": "integer" },
+                                },
+                                "required": ["data", "total", "limit", "offset"],
+                            }
+                        }
+                    }
+                },
+                "400": error_response("Invalid filter or paging parameters"),
+                "404": error_response("Model or entity not found"),
+            }
+        },
+        "post": {
+            "operationId": operation_id("create", entity),
+            "summary": format!("Create a {} record", entity.display_name),
+            "tags": [entity.name],
+            "requestBody": {
+                "required": true,
+                "content": { "application/json": { "schema": { "$ref": schema_ref(&entity.name) } } },
+            },
+            "responses": {
+                "201": record_response(entity, "Created record", None),
+                "404": error_response("Model or entity not found"),
+                "422": error_response("Data does not match the entity schema"),
+            }
+        }
+    })
+}
+
+fn item_path(model: &TorqueModel, entity: &ModelEntity) -> Value {
+    let links = relationship_links(model, entity);
+
+    json!({
+        "parameters": [
+            { "name": "id", "in": "path", "required": true, "schema": { "type": "string", "format": "uuid" } }
+        ],
+        "get": {
+            "operationId": operation_id("get", entity),
+            "summary": format!("Get a {} record", entity.display_name),
+            "tags": [entity.name],
+            "responses": {
+                "200": record_response(entity, "The record", Some(links.clone())),
+                "404": error_response("Record not found"),
+            }
+        },
+        "patch": {
+            "operationId": operation_id("update", entity),
+            "summary": format!("Update fields of a {} record", entity.display_name),
+            "description": "Top-level fields in the body replace the stored values; the result must match the entity schema",
+            "tags": [entity.name],
+            "requestBody": {
+                "required": true,
+                "content": { "application/json": { "schema": { "type": "object" } } },
+            },
+            "responses": {
+                "200": record_response(entity, "Updated record", Some(links)),
+                "404": error_response("Record not found"),
+                "422": error_response("Data does not match the entity schema"),
+            }
+        },
+        "delete": {
+            "operationId": operation_id("delete", entity),
+            "summary": format!("Delete a {} record", entity.display_name),
+            "tags": [entity.name],
+            "responses": {
+                "204": { "description": "Record deleted" },
+                "404": error_response("Record not found"),
+            }
+        }
+    })
+}
+
+/// Links from a record to the records related to it through model relationships
+fn relationship_links(model: &TorqueModel, entity: &ModelEntity) -> Value {
+    let mut links = Map::new();
+
+    for relationship in &model.relationships {
+        let from = model.entities.iter().find(|e| e.id == relationship.from_entity);
+        let to = model.entities.iter().find(|e| e.id == relationship.to_entity);
+        let (Some(from), Some(to)) = (from, to) else {
+            continue;
+        };
+
+        // The record holding the foreign key points at its target...
+        if from.id == entity.id {
+            links.insert(relationship.name.clone(), json!({
+                "operationId": operation_id("list", to),
+                "parameters": { relationship.to_field.clone(): format!("$response.body#/{}", relationship.from_field) },
+                "description": format!("{} records referenced by this {}", to.display_name, entity.display_name),
+            }));
+        }
+        // ...and the target can list the records that point at it
+        if to.id == entity.id {
+            links.insert(format!("{}_inverse", relationship.name), json!({
+                "operationId": operation_id("list", from),
+                "parameters": { relationship.from_field.clone(): format!("$response.body#/{}", relationship.to_field) },
+                "description": format!("{} records referencing this {}", from.display_name, entity.display_name),
+            }));
+        }
+    }
+
+    Value::Object(links)
+}
+
+/// Query parameter schema for filtering on a field, `None` for fields that can't be filtered
+fn filter_schema(model: &TorqueModel, field_type: &FieldType) -> Option<Value> {
+    match field_type {
+        FieldType::Json | FieldType::Binary | FieldType::Array { .. } => None,
+        other => Some(field_type_schema(model, other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Uuid;
+    use crate::model::test_fixtures::{entity, field, model};
+    use crate::model::types::*;
+
+    #[test]
+    fn test_openapi_document() {
+        let customer = entity("customer", vec![
+            field("name", FieldType::String { max_length: None }),
+            field("notes", FieldType::Json),
+        ]);
+        let order = entity("order", vec![
+            field("customer_id", FieldType::Reference { entity_id: customer.id.clone() }),
+            field("total", FieldType::Float { min: Some(0.0), max: None }),
+        ]);
+        let relationships = vec![ModelRelationship {
+            id: Uuid::new_v4(),
+            name: "order_customer".to_string(),
+            relationship_type: RelationshipType::ManyToOne,
+            from_entity: order.id.clone(),
+            to_entity: customer.id.clone(),
+            from_field: "customer_id".to_string(),
+            to_field: "id".to_string(),
+            cascade: CascadeAction::None,
+            ui_config: RelationshipUiConfig::default(),
+        }];
+        let mut model = model(vec![customer, order]);
+        model.relationships = relationships;
+
+        let document = model_openapi(&model, "/api/models/shop");
+        assert_eq!(document["openapi"], "3.1.0");
+        assert!(document["paths"]["/customer"]["post"].is_object());
+        assert!(document["paths"]["/order/{id}"]["patch"].is_object());
+        assert!(document["components"]["schemas"]["customer"].get("$id").is_none());
+
+        // JSON fields can't be filtered on
+        let parameters: Vec<&str> = document["paths"]["/customer"]["get"]["parameters"].as_array().unwrap()
+            .iter()
+            .filter_map(|p| p["name"].as_str())
+            .collect();
+        assert!(parameters.contains(&"name"));
+        assert!(!parameters.contains(&"notes"));
+
+        let order_links = &document["paths"]["/order/{id}"]["get"]["responses"]["200"]["links"];
+        assert_eq!(order_links["order_customer"]["operationId"], "list_customer");
+        let customer_links = &document["paths"]["/customer/{id}"]["get"]["responses"]["200"]["links"];
+        assert_eq!(customer_links["order_customer_inverse"]["parameters"]["customer_id"], "$response.body#/id");
+    }
+}
@@ -0,0 +1,152 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Starter model a new project can be created from
+pub struct ModelTemplate {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    source: &'static str,
+}
+
+/// Catalogue entry describing a template
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub entity_count: usize,
+    pub sample_record_count: usize,
+}
+
+const BLANK_TEMPLATE: &str = r#"{
+  "name": "Blank Project",
+  "description": "An empty model to start from",
+  "version": "1.0.0",
+  "created_by": "system",
+  "entities": [],
+  "relationships": [],
+  "flows": [],
+  "layouts": [],
+  "sample_data": {}
+}"#;
+
+static TEMPLATES: &[ModelTemplate] = &[
+    ModelTemplate {
+        id: "crm",
+        name: "Customer Order Management",
+        description: "Customers and orders with sample data",
+        source: include_str!("../../../sample/models/customer-order.json"),
+    },
+    ModelTemplate {
+        id: "todo",
+        name: "Todo Application",
+        description: "Projects, tasks and categories with sample data",
+        source: include_str!("../../../sample/models/todo-app.json"),
+    },
+    ModelTemplate {
+        id: "blank",
+        name: "Blank Project",
+        description: "An empty model to start from",
+        source: BLANK_TEMPLATE,
+    },
+];
+
+/// All registered templates
+pub fn templates() -> &'static [ModelTemplate] {
+    TEMPLATES
+}
+
+/// Look up a template by ID
+pub fn find_template(id: &str) -> Option<&'static ModelTemplate> {
+    TEMPLATES.iter().find(|t| t.id == id)
+}
+
+/// Catalogue of all templates
+pub fn template_catalogue() -> Vec<TemplateInfo> {
+    TEMPLATES.iter().map(ModelTemplate::info).collect()
+}
+
+impl ModelTemplate {
+    fn source_json(&self) -> Value {
+        // Template sources are embedded at compile time and covered by tests
+        serde_json::from_str(self.source).expect("embedded model template is valid JSON")
+    }
+
+    pub fn info(&self) -> TemplateInfo {
+        let source = self.source_json();
+        TemplateInfo {
+            id: self.id.to_string(),
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            entity_count: source["entities"].as_array().map_or(0, |e| e.len()),
+            sample_record_count: sample_records(&source).values()
+                .filter_map(|v| v.as_array())
+                .map(|v| v.len())
+                .sum(),
+        }
+    }
+
+    /// Model definition in the sample model format, without its sample data
+    pub fn definition(&self, name: Option<&str>, description: Option<&str>) -> Value {
+        let mut definition = self.source_json();
+        if let Some(object) = definition.as_object_mut() {
+            object.remove("sample_data");
+            if let Some(name) = name {
+                object.insert("name".to_string(), json!(name));
+            }
+            if let Some(description) = description {
+                object.insert("description".to_string(), json!(description));
+            }
+        }
+        definition
+    }
+
+    /// Seed records keyed by entity name
+    pub fn sample_data(&self) -> Map<String, Value> {
+        sample_records(&self.source_json())
+    }
+}
+
+fn sample_records(source: &Value) -> Map<String, Value> {
+    source.get("sample_data")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// File-system friendly form of a project or model name
+pub fn slugify(name: &str) -> String {
+    let slug = name.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() { "project".to_string() } else { slug }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_are_valid() {
+        for template in templates() {
+            let definition = template.definition(Some("My App"), None);
+            assert_eq!(definition["name"], "My App");
+            assert!(definition.get("sample_data").is_none());
+            assert!(definition["entities"].is_array(), "template {} has no entities array", template.id);
+        }
+
+        assert!(find_template("crm").unwrap().info().entity_count > 0);
+        assert!(find_template("todo").unwrap().info().sample_record_count > 0);
+        assert!(find_template("unknown").is_none());
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("My CRM App"), "my-crm-app");
+        assert_eq!(slugify("  "), "project");
+    }
+}
@@ -0,0 +1,57 @@
+use crate::common::{Uuid, UtcDateTime};
+use crate::model::types::*;
+
+/// An optional field without validations
+pub fn field(name: &str, field_type: FieldType) -> EntityField {
+    EntityField {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        display_name: name.to_string(),
+        field_type,
+        required: false,
+        default_value: None,
+        validation: vec![],
+        ui_config: FieldUiConfig::default(),
+    }
+}
+
+/// An optional string field without a length limit
+pub fn string_field(name: &str) -> EntityField {
+    field(name, FieldType::String { max_length: None })
+}
+
+/// A data entity with the given fields
+pub fn entity(name: &str, fields: Vec<EntityField>) -> ModelEntity {
+    ModelEntity {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        display_name: name.to_string(),
+        description: None,
+        entity_type: EntityType::Data,
+        fields,
+        constraints: vec![],
+        indexes: vec![],
+        ui_config: EntityUiConfig::default(),
+        behavior: EntityBehavior::default(),
+    }
+}
+
+/// A model with the given entities and nothing else
+pub fn model(entities: Vec<ModelEntity>) -> TorqueModel {
+    let now = UtcDateTime::now();
+    TorqueModel {
+        id: Uuid::new_v4(),
+        name: "Test".to_string(),
+        description: None,
+        version: "1.0.0".to_string(),
+        created_at: now.clone(),
+        updated_at: now,
+        created_by: "test".to_string(),
+        config: ModelConfig::default(),
+        entities,
+        relationships: vec![],
+        flows: vec![],
+        layouts: vec![],
+        validations: vec![],
+    }
+}
@@ -24,6 +24,94 @@ pub struct TorqueModel {
     pub validations: Vec<ModelValidation>,
 }
 
+/// Outcome of removing an entity from a model
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityDeletion {
+    pub entity_id: Uuid,
+    /// Relationships that referenced the entity
+    pub relationships: Vec<Uuid>,
+    /// Layout components bound to the entity
+    pub components: Vec<Uuid>,
+    /// Layouts that targeted the entity or held components bound to it
+    pub layouts: Vec<Uuid>,
+    /// Flows triggered by events of the entity
+    pub flows: Vec<Uuid>,
+    /// `Reference` fields of other entities pointing at the entity
+    pub fields: Vec<Uuid>,
+    /// Whether the referencing items were removed too
+    pub references_removed: bool,
+}
+
+impl EntityDeletion {
+    /// Whether anything in the model referenced the removed entity
+    pub fn has_references(&self) -> bool {
+        !(self.relationships.is_empty() && self.components.is_empty() && self.flows.is_empty() && self.fields.is_empty())
+    }
+}
+
+impl TorqueModel {
+    /// Remove an entity, returning `None` when the model has no such entity.
+    /// Relationships, layout components, flows and `Reference` fields that refer to the entity
+    /// are reported, and removed as well when `cleanup_references` is set.
+    pub fn remove_entity(&mut self, entity_id: &Uuid, cleanup_references: bool) -> Option<EntityDeletion> {
+        let index = self.entities.iter().position(|e| &e.id == entity_id)?;
+        let entity = self.entities.remove(index);
+
+        let relationships: Vec<Uuid> = self.relationships.iter()
+            .filter(|r| &r.from_entity == entity_id || &r.to_entity == entity_id)
+            .map(|r| r.id.clone())
+            .collect();
+
+        let mut components = Vec::new();
+        let mut layouts = Vec::new();
+        for layout in &mut self.layouts {
+            let referencing: Vec<Uuid> = layout.components.iter()
+                .filter(|c| c.references_entity(&entity))
+                .map(|c| c.id.clone())
+                .collect();
+            if layout.target_entities.contains(entity_id) || !referencing.is_empty() {
+                if cleanup_references {
+                    layout.components.retain(|c| !referencing.contains(&c.id));
+                    layout.target_entities.retain(|id| id != entity_id);
+                    layout.updated_at = UtcDateTime::now();
+                }
+                layouts.push(layout.id.clone());
+            }
+            components.extend(referencing);
+        }
+
+        let flows: Vec<Uuid> = self.flows.iter()
+            .filter(|f| matches!(&f.trigger, FlowTrigger::EntityEvent { entity_id: id, .. } if id == entity_id))
+            .map(|f| f.id.clone())
+            .collect();
+
+        let fields: Vec<Uuid> = self.entities.iter()
+            .flat_map(|e| &e.fields)
+            .filter(|f| f.field_type.references_entity(entity_id))
+            .map(|f| f.id.clone())
+            .collect();
+
+        if cleanup_references {
+            self.relationships.retain(|r| !relationships.contains(&r.id));
+            self.flows.retain(|f| !flows.contains(&f.id));
+            for other in &mut self.entities {
+                other.fields.retain(|f| !fields.contains(&f.id));
+            }
+        }
+
+        Some(EntityDeletion {
+            entity_id: entity_id.clone(),
+            relationships,
+            components,
+            layouts,
+            flows,
+            fields,
+            references_removed: cleanup_references,
+        })
+    }
+}
+
 /// Model configuration and metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelConfig {
@@ -201,7 +289,7 @@ pub struct EntityField {
     pub ui_config: FieldUiConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FieldType {
     String { max_length: Option<usize> },
     Integer { min: Option<i64>, max: Option<i64> },
@@ -217,6 +305,133 @@ pub enum FieldType {
     Array { element_type: Box<FieldType> },
 }
 
+impl FieldType {
+    /// Whether values of this type point at `entity_id`, directly or as array elements
+    pub fn references_entity(&self, entity_id: &Uuid) -> bool {
+        match self {
+            FieldType::Reference { entity_id: target } => target == entity_id,
+            FieldType::Array { element_type } => element_type.references_entity(entity_id),
+            _ => false,
+        }
+    }
+}
+
+/// Compact notation of a field type, e.g. `String(100)`, `Integer(0..10)`, `Enum[low,high]`,
+/// `Reference(<entity id>)` or `Array<Float>`. Parses back to the same type.
+impl std::fmt::Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn range<T: std::fmt::Display>(f: &mut std::fmt::Formatter<'_>, name: &str, min: &Option<T>, max: &Option<T>) -> std::fmt::Result {
+            let bound = |value: &Option<T>| value.as_ref().map(|v| v.to_string()).unwrap_or_default();
+            match (min, max) {
+                (None, None) => write!(f, "{}", name),
+                _ => write!(f, "{}({}..{})", name, bound(min), bound(max)),
+            }
+        }
+
+        match self {
+            FieldType::String { max_length: None } => write!(f, "String"),
+            FieldType::String { max_length: Some(len) } => write!(f, "String({})", len),
+            FieldType::Integer { min, max } => range(f, "Integer", min, max),
+            FieldType::Float { min, max } => range(f, "Float", min, max),
+            FieldType::Boolean => write!(f, "Boolean"),
+            FieldType::DateTime => write!(f, "DateTime"),
+            FieldType::Date => write!(f, "Date"),
+            FieldType::Time => write!(f, "Time"),
+            FieldType::Json => write!(f, "Json"),
+            FieldType::Binary => write!(f, "Binary"),
+            // Values that would not survive splitting on commas are written as a JSON array
+            FieldType::Enum { values } if values.iter().all(|v| is_plain_enum_value(v)) => {
+                write!(f, "Enum[{}]", values.join(","))
+            }
+            FieldType::Enum { values } => {
+                write!(f, "Enum{}", serde_json::to_string(values).map_err(|_| std::fmt::Error)?)
+            }
+            FieldType::Reference { entity_id } => write!(f, "Reference({})", entity_id),
+            FieldType::Array { element_type } => write!(f, "Array<{}>", element_type),
+        }
+    }
+}
+
+fn is_plain_enum_value(value: &str) -> bool {
+    !value.is_empty()
+        && value.trim() == value
+        && !value.contains([',', '[', ']', '"'])
+}
+
+impl std::str::FromStr for FieldType {
+    type Err = crate::Error;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| crate::Error::InvalidInput(format!("field type '{}': {}", notation, reason));
+        let notation = notation.trim();
+        let (name, args) = match notation.find(['(', '[', '<']) {
+            Some(i) => (&notation[..i], &notation[i..]),
+            None => (notation, ""),
+        };
+        let inner = |open: char, close: char| args.strip_prefix(open).and_then(|a| a.strip_suffix(close));
+
+        fn bound<T: std::str::FromStr>(value: &str) -> Option<Option<T>> {
+            match value.trim() {
+                "" => Some(None),
+                value => value.parse().ok().map(Some),
+            }
+        }
+        fn range<T: std::str::FromStr>(args: Option<&str>) -> Option<(Option<T>, Option<T>)> {
+            let (min, max) = args?.split_once("..")?;
+            Some((bound(min)?, bound(max)?))
+        }
+
+        let field_type = match (name, args) {
+            ("String", "") => FieldType::String { max_length: None },
+            ("String", _) => {
+                let max_length = inner('(', ')').and_then(|len| len.trim().parse().ok())
+                    .ok_or_else(|| invalid("expected String(<max length>)"))?;
+                FieldType::String { max_length: Some(max_length) }
+            }
+            ("Integer", "") => FieldType::Integer { min: None, max: None },
+            ("Integer", _) => {
+                let (min, max) = range(inner('(', ')')).ok_or_else(|| invalid("expected Integer(<min>..<max>)"))?;
+                FieldType::Integer { min, max }
+            }
+            ("Float", "") => FieldType::Float { min: None, max: None },
+            ("Float", _) => {
+                let (min, max) = range(inner('(', ')')).ok_or_else(|| invalid("expected Float(<min>..<max>)"))?;
+                FieldType::Float { min, max }
+            }
+            ("Boolean", "") => FieldType::Boolean,
+            ("DateTime", "") => FieldType::DateTime,
+            ("Date", "") => FieldType::Date,
+            ("Time", "") => FieldType::Time,
+            ("Json", "") => FieldType::Json,
+            ("Binary", "") => FieldType::Binary,
+            ("Enum", "") => return Err(invalid("enum values are missing, e.g. Enum[low,high]")),
+            ("Enum", _) => {
+                let list = inner('[', ']').ok_or_else(|| invalid("expected Enum[<value>,...]"))?;
+                let values = serde_json::from_str::<Vec<String>>(args).unwrap_or_else(|_| {
+                    match list.trim() {
+                        "" => Vec::new(),
+                        list => list.split(',').map(|v| v.trim().to_string()).collect(),
+                    }
+                });
+                FieldType::Enum { values }
+            }
+            ("Reference", "") => return Err(invalid("the referenced entity is missing, e.g. Reference(<entity id>)")),
+            ("Reference", _) => {
+                let entity_id = inner('(', ')').and_then(|id| id.trim().parse::<Uuid>().ok())
+                    .ok_or_else(|| invalid("expected Reference(<entity id>)"))?;
+                FieldType::Reference { entity_id }
+            }
+            ("Array", "") => return Err(invalid("the element type is missing, e.g. Array<String>")),
+            ("Array", _) => {
+                let element = inner('<', '>').ok_or_else(|| invalid("expected Array<<element type>>"))?;
+                FieldType::Array { element_type: Box::new(element.parse()?) }
+            }
+            _ => return Err(invalid("unknown type")),
+        };
+        Ok(field_type)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldValidation {
     pub validation_type: ValidationType,
@@ -603,6 +818,16 @@ pub struct LayoutComponent {
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
+impl LayoutComponent {
+    /// Whether the component is bound to an entity by name (`entityType`) or ID (`entityId`)
+    pub fn references_entity(&self, entity: &ModelEntity) -> bool {
+        let matches = |key: &str, expected: &str| {
+            self.properties.get(key).and_then(|v| v.as_str()) == Some(expected)
+        };
+        matches("entityType", &entity.name) || matches("entityId", entity.id.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ComponentPosition {
     pub row: u32,
@@ -743,4 +968,50 @@ impl Default for ComponentConfig {
             custom_components: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_type_notation_round_trips() {
+        let entity_id = Uuid::new_v4();
+        let types = vec![
+            FieldType::String { max_length: None },
+            FieldType::String { max_length: Some(100) },
+            FieldType::Integer { min: Some(-5), max: None },
+            FieldType::Float { min: Some(0.5), max: Some(1e10) },
+            FieldType::Json,
+            FieldType::Enum { values: vec!["Todo".to_string(), "In Progress".to_string()] },
+            FieldType::Enum { values: vec!["a,b".to_string(), " padded".to_string(), "[x]".to_string()] },
+            FieldType::Enum { values: vec![] },
+            FieldType::Reference { entity_id: entity_id.clone() },
+            FieldType::Array { element_type: Box::new(FieldType::Array {
+                element_type: Box::new(FieldType::Enum { values: vec!["x>y".to_string()] }),
+            }) },
+        ];
+
+        for field_type in types {
+            let notation = field_type.to_string();
+            let parsed: FieldType = notation.parse().unwrap();
+            assert_eq!(
+                serde_json::to_value(&parsed).unwrap(),
+                serde_json::to_value(&field_type).unwrap(),
+                "{}",
+                notation
+            );
+        }
+
+        assert_eq!(FieldType::Integer { min: None, max: Some(10) }.to_string(), "Integer(..10)");
+        assert_eq!(format!("{}", FieldType::Reference { entity_id: entity_id.clone() }), format!("Reference({})", entity_id));
+        assert!(matches!("Enum[low, high]".parse(), Ok(FieldType::Enum { values }) if values == ["low", "high"]));
+    }
+
+    #[test]
+    fn test_field_type_notation_rejects_incomplete_types() {
+        for notation in ["Enum", "Reference", "Reference(project)", "Array", "String(long)", "Integer(1)", "Text"] {
+            let error = notation.parse::<FieldType>().unwrap_err();
+            assert!(matches!(error, crate::Error::InvalidInput(_)), "{}", notation);
+        }
+    }
+}
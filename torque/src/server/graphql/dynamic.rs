@@ -0,0 +1,755 @@
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::dynamic::{
+    Enum, EnumItem, Field, FieldFuture, FieldValue, InputObject, InputValue, Object, ObjectAccessor, Scalar, Schema,
+    TypeRef,
+};
+use async_graphql::{ErrorExtensions, Name, Value as GraphQLValue};
+use dashmap::DashMap;
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use crate::common::Uuid;
+use crate::config::{GraphQLConfig, RestApiConfig};
+use crate::model::codegen::{camel_case, pascal_case, relationships};
+use crate::model::events::{ModelChangeEvent, ModelEventMessage};
+use crate::model::types::{EntityField, FieldType, ModelEntity, TorqueModel};
+use crate::server::AppState;
+use crate::services::app_database::{entity_record, AppDatabaseService, EntityFilter, EntityListQuery, FilterOp};
+use crate::Error;
+
+/// Most related records one batch of parent records may load for a relationship field;
+/// larger batches fail rather than return some parents' records incompletely
+const RELATED_RECORDS_LIMIT: u64 = 1000;
+
+const JSON_SCALAR: &str = "JSON";
+
+/// Names of the types every generated schema shares
+const SHARED_TYPES: &[&str] = &[
+    "Query", "Mutation", "PageInfo", "SortDirection", JSON_SCALAR,
+    "StringFilter", "IntFilter", "FloatFilter", "BooleanFilter", "IDFilter",
+    TypeRef::STRING, TypeRef::INT, TypeRef::FLOAT, TypeRef::BOOLEAN, TypeRef::ID,
+];
+
+struct CachedSchema {
+    fingerprint: u64,
+    schema: Schema,
+}
+
+/// Generated schemas by model, reused until the model's entities or relationships change
+static MODEL_SCHEMAS: once_cell::sync::Lazy<DashMap<Uuid, CachedSchema>> =
+    once_cell::sync::Lazy::new(DashMap::new);
+
+/// GraphQL schema for a model's application data, building it again when the model has changed
+pub fn model_schema(model: &TorqueModel, config: &GraphQLConfig) -> crate::Result<Schema> {
+    let fingerprint = schema_fingerprint(model);
+    if let Some(cached) = MODEL_SCHEMAS.get(&model.id).filter(|c| c.fingerprint == fingerprint) {
+        return Ok(cached.schema.clone());
+    }
+
+    let schema = build_schema(model, config)?;
+    MODEL_SCHEMAS.insert(model.id.clone(), CachedSchema { fingerprint, schema: schema.clone() });
+    Ok(schema)
+}
+
+/// Drop a model's cached schema, returning whether there was one
+pub fn forget_model_schema(model_id: &Uuid) -> bool {
+    MODEL_SCHEMAS.remove(model_id).is_some()
+}
+
+/// Drop cached schemas of models as they are deleted
+pub fn spawn_schema_eviction(mut receiver: Receiver<ModelEventMessage>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(ModelEventMessage { event: ModelChangeEvent::ModelDeleted { model_id, .. }, .. }) => {
+                    forget_model_schema(&model_id);
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(_)) => MODEL_SCHEMAS.clear(),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Execute a request against a model's generated schema
+pub async fn execute(
+    state: &AppState,
+    model: &TorqueModel,
+    request: async_graphql::Request,
+) -> crate::Result<async_graphql::Response> {
+    let schema = model_schema(model, &state.services.config.graphql)?;
+    let loader = DataLoader::new(
+        RelatedRecords {
+            app_database: state.services.app_database_service.clone(),
+            model_id: model.id.to_string(),
+        },
+        tokio::spawn,
+    );
+    Ok(schema.execute(request.data(state.clone()).data(loader)).await)
+}
+
+fn schema_fingerprint(model: &TorqueModel) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    model.version.hash(&mut hasher);
+    serde_json::to_string(&(&model.entities, &model.relationships))
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// An entity resolvers work on, with the model its records belong to
+struct EntityContext {
+    model_id: String,
+    entity: ModelEntity,
+}
+
+/// Build the schema: per entity an object type with its fields and relationships, a single-record
+/// and a paginated list query, and create, update and delete mutations. Query limits and
+/// introspection follow `Config.graphql` like the main schema.
+pub fn build_schema(model: &TorqueModel, config: &GraphQLConfig) -> crate::Result<Schema> {
+    let TypeNames { entities: type_names, enums: enum_names } = type_names(model);
+
+    let version = model.version.clone();
+    let mut query = Object::new("Query").field(
+        Field::new("_version", TypeRef::named_nn(TypeRef::STRING), move |_| {
+            let version = version.clone();
+            FieldFuture::new(async move { Ok(Some(GraphQLValue::from(version))) })
+        })
+        .description("Version of the model this schema was generated from"),
+    );
+    let mut mutation = Object::new("Mutation");
+    let mut objects: HashMap<String, Object> = HashMap::new();
+    let mut inputs = Vec::new();
+    let mut enums = Vec::new();
+
+    for entity in model.entities.iter().filter(|e| type_names.contains_key(&e.id)) {
+        let type_name = &type_names[&entity.id];
+        let context = Arc::new(EntityContext {
+            model_id: model.id.to_string(),
+            entity: entity.clone(),
+        });
+        let fields: Vec<&EntityField> = graphql_fields(entity).collect();
+
+        let mut object = Object::new(type_name.as_str())
+            .description(entity.description.clone().unwrap_or_else(|| entity.display_name.clone()))
+            .field(record_field("_id", "_id", TypeRef::named_nn(TypeRef::ID), None))
+            .field(record_field("_createdAt", "_created_at", TypeRef::named_nn(TypeRef::STRING), None))
+            .field(record_field("_updatedAt", "_updated_at", TypeRef::named_nn(TypeRef::STRING), None));
+        let mut input = InputObject::new(format!("{}Input", type_name));
+        let mut update_input = InputObject::new(format!("{}UpdateInput", type_name));
+        let mut filter = InputObject::new(format!("{}Filter", type_name));
+        let mut sort_fields = Enum::new(format!("{}SortField", type_name));
+        let (mut filterable, mut has_inputs) = (false, false);
+
+        for field in &fields {
+            let enum_name = enum_names.get(&field.id).map(String::as_str);
+            if let (Some(enum_name), Some(values)) = (enum_name, enum_values(&field.field_type)) {
+                enums.push(Enum::new(enum_name).items(values.iter().map(|v| EnumItem::new(v.as_str()))));
+            }
+
+            object = object.field(
+                record_field(&field.name, &field.name, field_type_ref(enum_name, &field.field_type, false), Some(field.field_type.clone()))
+                    .description(field.display_name.clone()),
+            );
+
+            let input_type = field_type_ref(enum_name, &field.field_type, true);
+            let required = field.required && field.default_value.is_none();
+            input = input.field(InputValue::new(
+                field.name.as_str(),
+                if required { TypeRef::NonNull(Box::new(input_type.clone())) } else { input_type.clone() },
+            ));
+            update_input = update_input.field(InputValue::new(field.name.as_str(), input_type));
+            has_inputs = true;
+
+            if let Some(filter_type) = filter_type_name(&field.field_type) {
+                filter = filter.field(InputValue::new(field.name.as_str(), TypeRef::named(filter_type)));
+                if !matches!(field.name.as_str(), "true" | "false" | "null") {
+                    sort_fields = sort_fields.item(EnumItem::new(field.name.as_str()));
+                }
+                filterable = true;
+            }
+        }
+
+        let field_name = lower_first(type_name);
+        let get = Field::new(field_name.as_str(), TypeRef::named(type_name.as_str()), {
+            let context = context.clone();
+            move |ctx| {
+                let context = context.clone();
+                FieldFuture::new(async move {
+                    let state = ctx.data::<AppState>()?;
+                    let id = ctx.args.try_get("id")?.string()?;
+                    let record = state.services.app_database_service
+                        .get_entity(&context.model_id, id)
+                        .await
+                        .map_err(graphql_error)?
+                        .filter(|r| r.entity_type == context.entity.name);
+                    Ok(record.map(|r| FieldValue::owned_any(entity_record(r))))
+                })
+            }
+        })
+        .description(format!("A {} record by ID", entity.display_name))
+        .argument(InputValue::new("id", TypeRef::named_nn(TypeRef::ID)));
+        query = query.field(get);
+
+        let connection_type = format!("{}Connection", type_name);
+        let mut list = Field::new(format!("{}List", field_name), TypeRef::named_nn(connection_type.as_str()), {
+            let context = context.clone();
+            move |ctx| {
+                let context = context.clone();
+                FieldFuture::new(async move {
+                    let state = ctx.data::<AppState>()?;
+                    let query = list_query(&context.entity, &ctx.args, &state.services.config.rest)?;
+                    let (records, total) = state.services.app_database_service
+                        .query_entities(&context.model_id, &context.entity.name, &query)
+                        .await
+                        .map_err(graphql_error)?;
+                    Ok(Some(FieldValue::owned_any(connection(records, query.offset, total))))
+                })
+            }
+        })
+        .description(format!("{} records, filtered, sorted and paginated", entity.display_name))
+        .argument(InputValue::new("first", TypeRef::named(TypeRef::INT)))
+        .argument(InputValue::new("after", TypeRef::named(TypeRef::STRING)));
+        if filterable {
+            list = list
+                .argument(InputValue::new("filter", TypeRef::named(format!("{}Filter", type_name))))
+                .argument(InputValue::new("sort", TypeRef::named(format!("{}Sort", type_name))));
+            inputs.push(filter);
+            inputs.push(
+                InputObject::new(format!("{}Sort", type_name))
+                    .field(InputValue::new("field", TypeRef::named_nn(format!("{}SortField", type_name))))
+                    .field(InputValue::new("direction", TypeRef::named("SortDirection"))
+                        .default_value(GraphQLValue::Enum(Name::new("ASC")))),
+            );
+            enums.push(sort_fields);
+        }
+        query = query.field(list);
+
+        objects.insert(format!("{}Edge", type_name), Object::new(format!("{}Edge", type_name))
+            .field(nested_field("node", "node", TypeRef::named_nn(type_name.as_str())))
+            .field(record_field("cursor", "cursor", TypeRef::named_nn(TypeRef::STRING), None)));
+        objects.insert(connection_type.clone(), Object::new(connection_type.as_str())
+            .field(nested_field("edges", "edges", TypeRef::named_nn_list_nn(format!("{}Edge", type_name))))
+            .field(nested_field("nodes", "nodes", TypeRef::named_nn_list_nn(type_name.as_str())))
+            .field(nested_field("pageInfo", "pageInfo", TypeRef::named_nn("PageInfo")))
+            .field(record_field("totalCount", "totalCount", TypeRef::named_nn(TypeRef::INT), None)));
+
+        if has_inputs {
+            mutation = mutation
+                .field(create_mutation(type_name, context.clone()))
+                .field(update_mutation(type_name, context.clone()));
+            inputs.push(input);
+            inputs.push(update_input);
+        }
+        mutation = mutation.field(delete_mutation(type_name, context));
+
+        objects.insert(type_name.clone(), object);
+    }
+
+    // Relationship fields follow the naming of generated clients: `<name>` on the source
+    // entity and `<name>Inverse` on the target
+    for (relationship, from, to) in relationships(model) {
+        let (Some(from_type), Some(to_type)) = (type_names.get(&from.id), type_names.get(&to.id)) else {
+            continue;
+        };
+        let name = camel_case(&relationship.name);
+        let inverse = format!("{}Inverse", name);
+        for (owner, owner_type, field_name, related, related_type, field, source_field) in [
+            (from, from_type, &name, to, to_type, &relationship.to_field, &relationship.from_field),
+            (to, to_type, &inverse, from, from_type, &relationship.from_field, &relationship.to_field),
+        ] {
+            if !is_name(field_name) || owner.fields.iter().any(|f| &f.name == field_name) {
+                continue;
+            }
+            if let Some(object) = objects.remove(owner_type) {
+                let field = related_field(field_name, related_type, &related.name, field, source_field);
+                objects.insert(owner_type.clone(), object.field(field));
+            }
+        }
+    }
+
+    let mut builder = Schema::build("Query", (!type_names.is_empty()).then_some("Mutation"), None)
+    .limit_depth(config.max_query_depth)
+    .limit_complexity(config.max_query_complexity)
+    .register(Scalar::new(JSON_SCALAR).description("Arbitrary JSON value"))
+    .register(Enum::new("SortDirection").item(EnumItem::new("ASC")).item(EnumItem::new("DESC")))
+    .register(
+        Object::new("PageInfo")
+            .field(record_field("hasNextPage", "hasNextPage", TypeRef::named_nn(TypeRef::BOOLEAN), None))
+            .field(record_field("hasPreviousPage", "hasPreviousPage", TypeRef::named_nn(TypeRef::BOOLEAN), None))
+            .field(record_field("startCursor", "startCursor", TypeRef::named(TypeRef::STRING), None))
+            .field(record_field("endCursor", "endCursor", TypeRef::named(TypeRef::STRING), None)),
+    )
+    .register(scalar_filter("StringFilter", TypeRef::STRING, true, true))
+    .register(scalar_filter("IntFilter", TypeRef::INT, true, false))
+    .register(scalar_filter("FloatFilter", TypeRef::FLOAT, true, false))
+    .register(scalar_filter("BooleanFilter", TypeRef::BOOLEAN, false, false))
+    .register(scalar_filter("IDFilter", TypeRef::ID, false, false))
+    .register(query);
+    if !type_names.is_empty() {
+        builder = builder.register(mutation);
+    }
+    if !config.enable_introspection {
+        builder = builder.disable_introspection();
+    }
+    for object in objects.into_values() {
+        builder = builder.register(object);
+    }
+    for input in inputs {
+        builder = builder.register(input);
+    }
+    for enum_type in enums {
+        builder = builder.register(enum_type);
+    }
+
+    builder.finish()
+        .map_err(|e| Error::Internal(format!("Failed to build GraphQL schema for model {}: {}", model.id, e)))
+}
+
+fn create_mutation(type_name: &str, context: Arc<EntityContext>) -> Field {
+    Field::new(format!("create{}", type_name), TypeRef::named_nn(type_name), move |ctx| {
+        let context = context.clone();
+        FieldFuture::new(async move {
+            let state = ctx.data::<AppState>()?;
+            let mut data = ctx.args.try_get("data")?.as_value().clone().into_json()?;
+            if let Value::Object(ref mut object) = data {
+                object.retain(|_, v| !v.is_null());
+            }
+            let record = state.services.app_database_service
+                .create_entity(&context.model_id, &context.entity.name, data)
+                .await
+                .map_err(graphql_error)?;
+            Ok(Some(FieldValue::owned_any(entity_record(record))))
+        })
+    })
+    .argument(InputValue::new("data", TypeRef::named_nn(format!("{}Input", type_name))))
+}
+
+/// Fields given in `data` replace the stored values, like PATCH in the REST API
+fn update_mutation(type_name: &str, context: Arc<EntityContext>) -> Field {
+    Field::new(format!("update{}", type_name), TypeRef::named_nn(type_name), move |ctx| {
+        let context = context.clone();
+        FieldFuture::new(async move {
+            let state = ctx.data::<AppState>()?;
+            let id = ctx.args.try_get("id")?.string()?;
+            let Value::Object(changes) = ctx.args.try_get("data")?.as_value().clone().into_json()? else {
+                return Err(async_graphql::Error::new("data must be an object"));
+            };
+            let updated = state.services.app_database_service
+                .patch_entity(&context.model_id, Some(&context.entity.name), id, changes)
+                .await
+                .map_err(graphql_error)?;
+            Ok(Some(FieldValue::owned_any(entity_record(updated))))
+        })
+    })
+    .argument(InputValue::new("id", TypeRef::named_nn(TypeRef::ID)))
+    .argument(InputValue::new("data", TypeRef::named_nn(format!("{}UpdateInput", type_name))))
+}
+
+fn delete_mutation(type_name: &str, context: Arc<EntityContext>) -> Field {
+    Field::new(format!("delete{}", type_name), TypeRef::named_nn(TypeRef::BOOLEAN), move |ctx| {
+        let context = context.clone();
+        FieldFuture::new(async move {
+            let state = ctx.data::<AppState>()?;
+            let id = ctx.args.try_get("id")?.string()?;
+            load_record(state, &context, id).await?;
+            state.services.app_database_service
+                .delete_entity(&context.model_id, id)
+                .await
+                .map_err(graphql_error)?;
+            Ok(Some(GraphQLValue::from(true)))
+        })
+    })
+    .argument(InputValue::new("id", TypeRef::named_nn(TypeRef::ID)))
+}
+
+/// Load a record and check it belongs to the entity being edited
+async fn load_record(
+    state: &AppState,
+    context: &EntityContext,
+    id: &str,
+) -> async_graphql::Result<crate::database::entities::app_entities::Model> {
+    state.services.app_database_service
+        .get_entity(&context.model_id, id)
+        .await
+        .map_err(graphql_error)?
+        .filter(|r| r.entity_type == context.entity.name)
+        .ok_or_else(|| graphql_error(Error::NotFound(format!("{} record '{}' not found", context.entity.name, id))))
+}
+
+/// A field read from a key of the parent record
+fn record_field(name: &str, key: &str, type_ref: TypeRef, field_type: Option<FieldType>) -> Field {
+    let key = key.to_string();
+    Field::new(name, type_ref, move |ctx| {
+        let key = key.clone();
+        let field_type = field_type.clone();
+        FieldFuture::new(async move {
+            let record = ctx.parent_value.try_downcast_ref::<Value>()?;
+            match record.get(&key).filter(|v| !v.is_null()) {
+                Some(value) => Ok(Some(output_value(field_type.as_ref(), value)?)),
+                None => Ok(None),
+            }
+        })
+    })
+}
+
+/// An object or list of objects nested under a key of the parent value
+fn nested_field(name: &str, key: &str, type_ref: TypeRef) -> Field {
+    let key = key.to_string();
+    Field::new(name, type_ref, move |ctx| {
+        let key = key.clone();
+        FieldFuture::new(async move {
+            let parent = ctx.parent_value.try_downcast_ref::<Value>()?;
+            Ok(match parent.get(&key) {
+                Some(Value::Array(items)) => Some(FieldValue::list(items.iter().cloned().map(FieldValue::owned_any))),
+                Some(Value::Null) | None => None,
+                Some(value) => Some(FieldValue::owned_any(value.clone())),
+            })
+        })
+    })
+}
+
+/// Records of `entity_type` whose `field` matches the parent's `source_field`, loaded in batches
+fn related_field(name: &str, related_type: &str, entity_type: &str, field: &str, source_field: &str) -> Field {
+    let (entity_type, field, source_field) = (entity_type.to_string(), field.to_string(), source_field.to_string());
+    Field::new(name, TypeRef::named_nn_list_nn(related_type), move |ctx| {
+        let (entity_type, field, source_field) = (entity_type.clone(), field.clone(), source_field.clone());
+        FieldFuture::new(async move {
+            let record = ctx.parent_value.try_downcast_ref::<Value>()?;
+            let records = match record.get(&source_field).filter(|v| !v.is_null()) {
+                Some(value) => {
+                    let key = RelatedKey { entity_type, field, value: related_value(value).to_string() };
+                    ctx.data::<DataLoader<RelatedRecords>>()?.load_one(key).await?.unwrap_or_default()
+                }
+                None => vec![],
+            };
+            Ok(Some(FieldValue::list(records.into_iter().map(FieldValue::owned_any))))
+        })
+    })
+}
+
+/// Connection value read by the `<Type>Connection`, `<Type>Edge` and `PageInfo` resolvers.
+/// Cursors are record offsets in the list.
+fn connection(records: Vec<Value>, offset: u64, total: u64) -> Value {
+    let end = offset + records.len() as u64;
+    let edges: Vec<Value> = records.iter().enumerate()
+        .map(|(i, record)| json!({ "node": record, "cursor": (offset + i as u64).to_string() }))
+        .collect();
+    json!({
+        "pageInfo": {
+            "hasNextPage": end < total,
+            "hasPreviousPage": offset > 0,
+            "startCursor": edges.first().map(|e| e["cursor"].clone()),
+            "endCursor": edges.last().map(|e| e["cursor"].clone()),
+        },
+        "edges": edges,
+        "nodes": records,
+        "totalCount": total,
+    })
+}
+
+/// Build a list query from the `first`, `after`, `filter` and `sort` arguments
+fn list_query(entity: &ModelEntity, args: &ObjectAccessor<'_>, rest: &RestApiConfig) -> async_graphql::Result<EntityListQuery> {
+    let mut query = EntityListQuery {
+        limit: rest.default_page_size,
+        ..Default::default()
+    };
+
+    if let Some(first) = args.get("first").filter(|v| !v.is_null()) {
+        query.limit = first.u64()?.clamp(1, rest.max_page_size);
+    }
+    if let Some(after) = args.get("after").filter(|v| !v.is_null()) {
+        let cursor = after.string()?;
+        query.offset = cursor.parse::<u64>()
+            .map_err(|_| async_graphql::Error::new(format!("Invalid cursor '{}'", cursor)))? + 1;
+    }
+
+    if let Some(filter) = args.get("filter").filter(|v| !v.is_null()) {
+        for (field, ops) in filter.object()?.iter() {
+            let field_type = entity.fields.iter().find(|f| f.name == field.as_str()).map(|f| &f.field_type);
+            for (op, value) in ops.object()?.iter() {
+                if value.is_null() {
+                    continue;
+                }
+                let op = FilterOp::parse(op.as_str())
+                    .ok_or_else(|| async_graphql::Error::new(format!("Unknown filter operator '{}'", op)))?;
+                let mut value = value.as_value().clone().into_json()?;
+                if let Some(FieldType::Reference { .. }) = field_type {
+                    value = reference_filter_value(value);
+                }
+                query.filters.push(EntityFilter { field: field.to_string(), op, value });
+            }
+        }
+    }
+
+    if let Some(sort) = args.get("sort").filter(|v| !v.is_null()) {
+        let sort = sort.object()?;
+        query.sort_field = Some(sort.try_get("field")?.enum_name()?.to_string());
+        query.sort_descending = match sort.get("direction").filter(|v| !v.is_null()) {
+            Some(direction) => direction.enum_name()? == "DESC",
+            None => false,
+        };
+    }
+
+    Ok(query)
+}
+
+/// Sample data stores references as integers, records created through the API as strings
+fn reference_filter_value(value: Value) -> Value {
+    match value {
+        Value::String(s) => s.parse::<i64>().map(Value::from).unwrap_or(Value::String(s)),
+        Value::Array(items) => Value::Array(items.into_iter().map(reference_filter_value).collect()),
+        other => other,
+    }
+}
+
+/// A relationship value normalized the same way, so `"1"` and `1` load the same records
+fn related_value(value: &Value) -> Value {
+    reference_filter_value(value.clone())
+}
+
+/// Filter input over a scalar type with equality, `in` and optionally ordering and substring operators
+fn scalar_filter(name: &str, scalar: &str, ordered: bool, text: bool) -> InputObject {
+    let mut filter = InputObject::new(name)
+        .field(InputValue::new("eq", TypeRef::named(scalar)))
+        .field(InputValue::new("ne", TypeRef::named(scalar)))
+        .field(InputValue::new("in", TypeRef::named_nn_list(scalar)));
+    if ordered {
+        for op in ["gt", "gte", "lt", "lte"] {
+            filter = filter.field(InputValue::new(op, TypeRef::named(scalar)));
+        }
+    }
+    if text {
+        filter = filter.field(InputValue::new("contains", TypeRef::named(scalar)));
+    }
+    filter
+}
+
+fn filter_type_name(field_type: &FieldType) -> Option<&'static str> {
+    match field_type {
+        FieldType::String { .. } | FieldType::DateTime | FieldType::Date | FieldType::Time | FieldType::Enum { .. } => {
+            Some("StringFilter")
+        }
+        FieldType::Integer { .. } => Some("IntFilter"),
+        FieldType::Float { .. } => Some("FloatFilter"),
+        FieldType::Boolean => Some("BooleanFilter"),
+        FieldType::Reference { .. } => Some("IDFilter"),
+        FieldType::Json | FieldType::Binary | FieldType::Array { .. } => None,
+    }
+}
+
+/// GraphQL type of a field, whose enum type is `enum_name` if it has one. Input lists don't
+/// take null elements.
+fn field_type_ref(enum_name: Option<&str>, field_type: &FieldType, input: bool) -> TypeRef {
+    let named = match field_type {
+        FieldType::Array { element_type } => {
+            let element = field_type_ref(enum_name, element_type, input);
+            return TypeRef::List(Box::new(if input { TypeRef::NonNull(Box::new(element)) } else { element }));
+        }
+        FieldType::Integer { .. } => TypeRef::INT.to_string(),
+        FieldType::Float { .. } => TypeRef::FLOAT.to_string(),
+        FieldType::Boolean => TypeRef::BOOLEAN.to_string(),
+        FieldType::Json => JSON_SCALAR.to_string(),
+        FieldType::Reference { .. } => TypeRef::ID.to_string(),
+        FieldType::Enum { .. } => enum_name.unwrap_or(TypeRef::STRING).to_string(),
+        FieldType::String { .. } | FieldType::DateTime | FieldType::Date | FieldType::Time
+        | FieldType::Binary => TypeRef::STRING.to_string(),
+    };
+    TypeRef::named(named)
+}
+
+/// Convert stored data to the GraphQL value of a field's type
+fn output_value(field_type: Option<&FieldType>, value: &Value) -> async_graphql::Result<GraphQLValue> {
+    Ok(match (field_type, value) {
+        (Some(FieldType::Enum { values }), Value::String(s)) if has_enum_type(values) => GraphQLValue::Enum(Name::new(s)),
+        (Some(FieldType::Reference { .. }), Value::Number(n)) => GraphQLValue::String(n.to_string()),
+        (Some(FieldType::Array { element_type }), Value::Array(items)) => GraphQLValue::List(
+            items.iter()
+                .map(|item| output_value(Some(element_type), item))
+                .collect::<async_graphql::Result<_>>()?,
+        ),
+        _ => GraphQLValue::from_json(value.clone())?,
+    })
+}
+
+fn enum_values(field_type: &FieldType) -> Option<&Vec<String>> {
+    match field_type {
+        FieldType::Enum { values } => Some(values),
+        FieldType::Array { element_type } => enum_values(element_type),
+        _ => None,
+    }
+}
+
+/// Enum fields get a GraphQL enum when all their values are valid enum value names
+fn has_enum_type(values: &[String]) -> bool {
+    !values.is_empty() && values.iter().all(|v| is_name(v) && !matches!(v.as_str(), "true" | "false" | "null"))
+}
+
+/// Fields exposed in an entity's GraphQL types
+fn graphql_fields(entity: &ModelEntity) -> impl Iterator<Item = &EntityField> {
+    entity.fields.iter().filter(|f| is_name(&f.name) && !f.name.starts_with('_'))
+}
+
+/// Types generated for an entity, as suffixes of its object type's name
+const ENTITY_TYPE_SUFFIXES: &[&str] = &["", "Input", "UpdateInput", "Filter", "Sort", "SortField", "Connection", "Edge"];
+
+/// GraphQL type names generated for a model
+struct TypeNames {
+    /// Object type names by entity ID
+    entities: HashMap<Uuid, String>,
+    /// Enum type names by field ID, for enum fields with a GraphQL enum
+    enums: HashMap<Uuid, String>,
+}
+
+/// Type names of a model's entities and enum fields. Entity types are named after the entity
+/// in PascalCase, prefixed with `Entity` when that doesn't start with a letter; enum types
+/// after their entity's type and the field. Names are numbered when they would clash with
+/// the shared types or types named before them, entity types first.
+fn type_names(model: &TorqueModel) -> TypeNames {
+    let mut used: HashSet<String> = SHARED_TYPES.iter().map(|t| t.to_string()).collect();
+    let mut type_names = HashMap::new();
+    for entity in &model.entities {
+        let pascal = pascal_case(&entity.name);
+        let base = if pascal.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            pascal.clone()
+        } else {
+            format!("Entity{}", pascal)
+        };
+        let mut type_name = base.clone();
+        let mut number = 1;
+        while ENTITY_TYPE_SUFFIXES.iter().any(|suffix| used.contains(&format!("{}{}", type_name, suffix))) {
+            number += 1;
+            type_name = format!("{}{}", base, number);
+        }
+        if type_name != pascal {
+            tracing::warn!(
+                "Entity '{}' of model {} is named '{}' in its GraphQL schema", entity.name, model.id, type_name
+            );
+        }
+        used.extend(ENTITY_TYPE_SUFFIXES.iter().map(|suffix| format!("{}{}", type_name, suffix)));
+        type_names.insert(entity.id.clone(), type_name);
+    }
+
+    let mut enum_names = HashMap::new();
+    for entity in &model.entities {
+        let type_name = &type_names[&entity.id];
+        let enum_fields = graphql_fields(entity)
+            .filter(|f| enum_values(&f.field_type).is_some_and(|v| has_enum_type(v)));
+        for field in enum_fields {
+            let base = format!("{}{}", type_name, pascal_case(&field.name));
+            let mut enum_name = base.clone();
+            let mut number = 1;
+            while used.contains(&enum_name) {
+                number += 1;
+                enum_name = format!("{}{}", base, number);
+            }
+            if enum_name != base {
+                tracing::warn!(
+                    "Enum field '{}' of entity '{}' in model {} has the GraphQL type '{}'",
+                    field.name, entity.name, model.id, enum_name
+                );
+            }
+            used.insert(enum_name.clone());
+            enum_names.insert(field.id.clone(), enum_name);
+        }
+    }
+
+    TypeNames { entities: type_names, enums: enum_names }
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Whether a string is a GraphQL name not reserved for introspection
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+}
+
+fn graphql_error(error: Error) -> async_graphql::Error {
+    let (message, code) = match error {
+        Error::Validation(message) => (message, "VALIDATION_ERROR"),
+        Error::NotFound(message) | Error::EntityNotFound(message) | Error::ModelNotFound(message) => (message, "NOT_FOUND"),
+        Error::InvalidInput(message) => (message, "BAD_USER_INPUT"),
+        other => {
+            tracing::error!("Model GraphQL request failed: {}", other);
+            ("Internal server error".to_string(), "INTERNAL_ERROR")
+        }
+    };
+    async_graphql::Error::new(message).extend_with(|_, e| e.set("code", code))
+}
+
+/// Records related to a parent record: those of `entity_type` whose `field` holds `value` (as JSON)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RelatedKey {
+    entity_type: String,
+    field: String,
+    value: String,
+}
+
+/// Loads related records for all parents in a response with one `in` query per entity and field
+struct RelatedRecords {
+    app_database: Arc<AppDatabaseService>,
+    model_id: String,
+}
+
+impl Loader<RelatedKey> for RelatedRecords {
+    type Value = Vec<Value>;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[RelatedKey]) -> Result<HashMap<RelatedKey, Self::Value>, Self::Error> {
+        let mut groups: HashMap<(&str, &str), Vec<Value>> = HashMap::new();
+        for key in keys {
+            let value = serde_json::from_str(&key.value).unwrap_or(Value::Null);
+            let values = groups.entry((&key.entity_type, &key.field)).or_default();
+            // Match the value whether records store it as a number or as a string
+            if value.is_number() {
+                values.push(Value::String(value.to_string()));
+            }
+            values.push(value);
+        }
+
+        let mut loaded: HashMap<RelatedKey, Vec<Value>> = HashMap::new();
+        for ((entity_type, field), values) in groups {
+            let query = EntityListQuery {
+                filters: vec![EntityFilter { field: field.to_string(), op: FilterOp::In, value: Value::Array(values) }],
+                limit: RELATED_RECORDS_LIMIT,
+                ..Default::default()
+            };
+            let (records, total) = self.app_database
+                .query_entities(&self.model_id, entity_type, &query)
+                .await
+                .map_err(graphql_error)?;
+            if total > RELATED_RECORDS_LIMIT {
+                return Err(async_graphql::Error::new(format!(
+                    "More than {} related {} records; query them with a filter on '{}' instead",
+                    RELATED_RECORDS_LIMIT, entity_type, field
+                )));
+            }
+            for record in records {
+                let Some(value) = record.get(field).filter(|v| !v.is_null()) else {
+                    continue;
+                };
+                let key = RelatedKey {
+                    entity_type: entity_type.to_string(),
+                    field: field.to_string(),
+                    value: related_value(value).to_string(),
+                };
+                loaded.entry(key).or_default().push(record);
+            }
+        }
+        Ok(loaded)
+    }
+}
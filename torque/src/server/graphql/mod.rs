@@ -1,34 +1,26 @@
+pub mod dynamic;
 pub mod schema;
 pub mod zero_copy;
 
 pub use schema::*;
 
+use async_graphql::extensions::apollo_persisted_queries::{ApolloPersistedQueries, LruCacheStorage};
 use async_graphql::Schema;
 
-/// GraphQL schema type - can be either standard or optimized
-pub enum GraphQLSchema {
-    Standard(Schema<Query, Mutation, SubscriptionRoot>),
-    Optimized(Schema<zero_copy::OptimizedQuery, zero_copy::OptimizedMutation, zero_copy::OptimizedSubscription>),
-}
+/// The GraphQL schema served at `/graphql`
+pub type GraphQLSchema = Schema<Query, Mutation, SubscriptionRoot>;
 
-/// Create a new GraphQL schema based on configuration
-pub fn create_schema(config: &GraphQLConfig) -> GraphQLSchema {
-    if config.use_optimized_schema {
-        GraphQLSchema::Optimized(zero_copy::create_optimized_schema())
-    } else {
-        GraphQLSchema::Standard(Schema::build(Query, Mutation, SubscriptionRoot).finish())
+/// Create the GraphQL schema with the query limits, introspection toggle and persisted queries
+/// from `Config.graphql`
+pub fn create_schema(settings: &crate::config::GraphQLConfig) -> GraphQLSchema {
+    let mut builder = Schema::build(Query, Mutation, SubscriptionRoot)
+        .limit_depth(settings.max_query_depth)
+        .limit_complexity(settings.max_query_complexity);
+    if !settings.enable_introspection {
+        builder = builder.disable_introspection();
     }
-}
-
-/// Configuration for GraphQL optimization
-pub struct GraphQLConfig {
-    pub use_optimized_schema: bool,
-}
-
-impl Default for GraphQLConfig {
-    fn default() -> Self {
-        Self {
-            use_optimized_schema: true, // Use optimized schema by default
-        }
+    if settings.persisted_query_cache_size > 0 {
+        builder = builder.extension(ApolloPersistedQueries::new(LruCacheStorage::new(settings.persisted_query_cache_size)));
     }
-}
\ No newline at end of file
+    builder.finish()
+}
@@ -1,12 +1,18 @@
 use async_graphql::{
-    Context, Enum, InputObject, Object, Result, SimpleObject, Subscription,
+    Context, Enum, ErrorExtensions, InputObject, Object, Result, SimpleObject, Subscription,
 };
-use futures_util::Stream;
+use futures_util::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 use crate::common::Uuid;
 
+use crate::model::events::ModelChangeEvent;
 use crate::model::types::*;
 use crate::server::AppState;
+use crate::server::graphql::zero_copy::{
+    BatchError, BatchResult, EntityWrapper, FlowWrapper, LayoutWrapper, ModelStats, ModelVersionWrapper,
+    ModelWrapper, RelationshipWrapper,
+};
 use crate::services::model::{CreateModelInput as ServiceCreateModelInput, UpdateModelInput as ServiceUpdateModelInput};
 use crate::Error;
 
@@ -23,109 +29,191 @@ pub struct Query;
 #[Object]
 impl Query {
     /// Get all models
-    async fn models(&self, ctx: &Context<'_>) -> Result<Vec<Model>> {
+    async fn models(&self, ctx: &Context<'_>) -> Result<Vec<ModelWrapper>> {
         let state = ctx.data::<AppState>()?;
         let models = state.services.model_service.get_models().await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get models: {}", e)))?;
-        Ok(models.into_iter().map(Model::from).collect())
-    }
-
-    /// Test method to debug GraphQL registration
-    async fn test_method(&self, _ctx: &Context<'_>) -> Result<String> {
-        Ok("Test method works".to_string())
+        Ok(models.into_iter().map(|m| ModelWrapper { inner: m }).collect())
     }
 
     /// Get a specific model by ID
-    async fn model(&self, ctx: &Context<'_>, id: String) -> Result<Option<Model>> {
+    async fn model(&self, ctx: &Context<'_>, id: String) -> Result<Option<ModelWrapper>> {
         let state = ctx.data::<AppState>()?;
         let uuid = id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
         let model = state.services.model_service.get_model(uuid).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?;
-        Ok(model.map(Model::from))
+        Ok(model.map(|m| ModelWrapper { inner: m }))
     }
 
     /// Get entities for a specific model
-    async fn entities(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<Entity>> {
+    async fn entities(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<EntityWrapper>> {
         let state = ctx.data::<AppState>()?;
         let uuid = model_id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
         let entities = state.services.model_service.get_entities(uuid).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get entities: {}", e)))?;
-        Ok(entities.into_iter().map(Entity::from).collect())
+        Ok(entities.into_iter().map(|e| EntityWrapper { inner: e }).collect())
     }
 
     /// Get a specific entity by ID
-    async fn entity(&self, ctx: &Context<'_>, id: String) -> Result<Option<Entity>> {
+    async fn entity(&self, ctx: &Context<'_>, id: String) -> Result<Option<EntityWrapper>> {
         let state = ctx.data::<AppState>()?;
         let uuid = id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
         // Get entity by searching through all models for now
         let models = state.services.model_service.get_models().await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get models: {}", e)))?;
-        
+
         for model in models {
             if let Some(entity) = model.entities.iter().find(|e| e.id == uuid) {
-                return Ok(Some(Entity::from(entity.clone())));
+                return Ok(Some(EntityWrapper { inner: entity.clone() }));
             }
         }
         Ok(None)
     }
 
     /// Get relationships for a specific model
-    async fn relationships(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<Relationship>> {
+    async fn relationships(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<RelationshipWrapper>> {
         let state = ctx.data::<AppState>()?;
         let uuid = model_id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
         let relationships = state.services.model_service.get_relationships(uuid).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get relationships: {}", e)))?;
-        Ok(relationships.into_iter().map(Relationship::from).collect())
+        Ok(relationships.into_iter().map(|r| RelationshipWrapper { inner: r }).collect())
     }
 
     /// Get flows for a specific model
-    async fn flows(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<Flow>> {
+    async fn flows(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<FlowWrapper>> {
         let state = ctx.data::<AppState>()?;
         let uuid = model_id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
         let flows = state.services.model_service.get_flows(uuid).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get flows: {}", e)))?;
-        Ok(flows.into_iter().map(Flow::from).collect())
+        Ok(flows.into_iter().map(|f| FlowWrapper { inner: f }).collect())
     }
 
     /// Get layouts for a specific model
-    async fn layouts(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<Layout>> {
+    async fn layouts(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<LayoutWrapper>> {
         let state = ctx.data::<AppState>()?;
         let uuid = model_id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
         let layouts = state.services.model_service.get_layouts(uuid).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get layouts: {}", e)))?;
-        Ok(layouts.into_iter().map(Layout::from).collect())
+        Ok(layouts.into_iter().map(|l| LayoutWrapper { inner: l }).collect())
     }
 
     /// Get a specific layout by ID
-    async fn layout(&self, ctx: &Context<'_>, id: String) -> Result<Option<Layout>> {
+    async fn layout(&self, ctx: &Context<'_>, id: String) -> Result<Option<LayoutWrapper>> {
         let state = ctx.data::<AppState>()?;
         let uuid = id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        
+
         // Search through all models to find the layout
         let models = state.services.model_service.get_models().await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get models: {}", e)))?;
-        
+
         for model in models {
             if let Some(layout) = model.layouts.iter().find(|l| l.id == uuid) {
-                return Ok(Some(Layout::from(layout.clone())));
+                return Ok(Some(LayoutWrapper { inner: layout.clone() }));
             }
         }
         Ok(None)
     }
 
     /// Search models by name or description
-    async fn search_models(&self, ctx: &Context<'_>, query: String) -> Result<Vec<Model>> {
+    async fn search_models(&self, ctx: &Context<'_>, query: String) -> Result<Vec<ModelWrapper>> {
         let state = ctx.data::<AppState>()?;
         let models = state.services.model_service.search_models(query).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to search models: {}", e)))?;
-        Ok(models.into_iter().map(Model::from).collect())
+        Ok(models.into_iter().map(|m| ModelWrapper { inner: m }).collect())
+    }
+
+    /// Full-text search returning each match with its path inside the model
+    async fn search_model_hits(&self, ctx: &Context<'_>, query: String, limit: Option<i32>) -> Result<Vec<ModelSearchHit>> {
+        let state = ctx.data::<AppState>()?;
+        let hits = state.services.model_service
+            .search_model_hits(&query, limit.map(|l| l.max(1) as u64)).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to search models: {}", e)))?;
+        Ok(hits.into_iter().map(ModelSearchHit::from).collect())
+    }
+
+    /// Get model statistics
+    async fn model_stats(&self, ctx: &Context<'_>, id: String) -> Result<ModelStats> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let model = state.services.model_service.get_model(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?
+            .ok_or_else(|| async_graphql::Error::new("Model not found"))?;
+
+        Ok(ModelStats {
+            entity_count: model.entities.len() as i32,
+            relationship_count: model.relationships.len() as i32,
+            flow_count: model.flows.len() as i32,
+            layout_count: model.layouts.len() as i32,
+            validation_count: model.validations.len() as i32,
+        })
+    }
+
+    /// Verify a model for configuration mismatches
+    async fn verify_model(&self, ctx: &Context<'_>, model_id: String) -> Result<ConfigurationReport> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let model = state.services.model_service.get_model(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?
+            .ok_or_else(|| async_graphql::Error::new("Model not found"))?;
+
+        let report = state.services.model_service.verify_model(&model).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to verify model: {}", e)))?;
+        Ok(ConfigurationReport::from(report))
+    }
+
+    /// Get remediation strategies for a specific configuration error
+    async fn get_remediation_strategies(&self, ctx: &Context<'_>, input: GetRemediationStrategiesInput) -> Result<Vec<RemediationStrategy>> {
+        let state = ctx.data::<AppState>()?;
+        let model_uuid = input.model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid model ID format"))?;
+
+        let strategies = state.services.model_service.get_remediation_strategies_by_type(
+            model_uuid,
+            &input.error_type,
+            &input.error_parameters
+        ).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get remediation strategies: {}", e)))?;
+
+        Ok(strategies.into_iter().map(RemediationStrategy::from).collect())
+    }
+
+    /// Get the version history of a model, newest first
+    async fn model_versions(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<ModelVersionInfo>> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let versions = state.services.model_service.list_model_versions(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get model versions: {}", e)))?;
+        Ok(versions.into_iter().map(ModelVersionInfo::from).collect())
+    }
+
+    /// Get a specific version snapshot of a model
+    async fn model_version(&self, ctx: &Context<'_>, model_id: String, version_number: i32) -> Result<ModelVersionWrapper> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let version = state.services.model_service.get_model_version(uuid, version_number).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get model version: {}", e)))?;
+        Ok(ModelVersionWrapper { inner: version })
+    }
+
+    /// Get the version running apps of a model are pinned to
+    async fn model_version_pin(&self, ctx: &Context<'_>, model_id: String) -> Result<Option<ModelVersionPin>> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let pin = state.services.model_service.get_model_version_pin(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get model version pin: {}", e)))?;
+        Ok(pin.map(ModelVersionPin::from))
     }
 }
 
@@ -135,7 +223,7 @@ pub struct Mutation;
 #[Object]
 impl Mutation {
     /// Create a new model
-    async fn create_model(&self, ctx: &Context<'_>, input: CreateModelInput) -> Result<Model> {
+    async fn create_model(&self, ctx: &Context<'_>, input: CreateModelInput) -> Result<ModelWrapper> {
         let state = ctx.data::<AppState>()?;
         let service_input = ServiceCreateModelInput {
             name: input.name,
@@ -144,11 +232,11 @@ impl Mutation {
         };
         let model = state.services.model_service.create_model(service_input).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to create model: {}", e)))?;
-        Ok(Model::from(model))
+        Ok(ModelWrapper { inner: model })
     }
 
     /// Update an existing model
-    async fn update_model(&self, ctx: &Context<'_>, id: String, input: UpdateModelInput) -> Result<Model> {
+    async fn update_model(&self, ctx: &Context<'_>, id: String, input: UpdateModelInput) -> Result<ModelWrapper> {
         let state = ctx.data::<AppState>()?;
         let uuid = id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
@@ -156,10 +244,46 @@ impl Mutation {
             name: input.name,
             description: input.description,
             config: input.config.map(|c| serde_json::from_value(c).unwrap_or_default()),
+            author: input.author,
+            message: input.message,
         };
         let model = state.services.model_service.update_model(uuid, service_input).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to update model: {}", e)))?;
-        Ok(Model::from(model))
+        Ok(ModelWrapper { inner: model })
+    }
+
+    /// Update model configuration
+    async fn update_model_config(&self, ctx: &Context<'_>, id: String, input: UpdateModelConfigInput) -> Result<ModelWrapper> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+
+        let mut model = state.services.model_service.get_model(uuid.clone()).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?
+            .ok_or_else(|| async_graphql::Error::new("Model not found"))?;
+
+        if let Some(start_page_layout_id) = input.start_page_layout_id {
+            if start_page_layout_id.is_empty() {
+                // Remove the start page layout if empty string is provided
+                model.config.custom.remove("startPageLayoutId");
+            } else {
+                model.config.custom.insert(
+                    "startPageLayoutId".to_string(),
+                    serde_json::Value::String(start_page_layout_id)
+                );
+            }
+        }
+
+        let service_input = ServiceUpdateModelInput {
+            name: None,
+            description: None,
+            config: Some(model.config.clone()),
+            author: None,
+            message: None,
+        };
+        let updated_model = state.services.model_service.update_model(uuid, service_input).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to update model config: {}", e)))?;
+        Ok(ModelWrapper { inner: updated_model })
     }
 
     /// Delete a model
@@ -171,259 +295,294 @@ impl Mutation {
             .map_err(|e| async_graphql::Error::new(format!("Failed to delete model: {}", e)))
     }
 
+    /// Roll a model back to a previous version (recorded as a new version)
+    async fn rollback_model(&self, ctx: &Context<'_>, input: RollbackModelInput) -> Result<ModelWrapper> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = input.model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let model = state.services.model_service
+            .rollback_model(uuid, input.version_number, input.author, input.message).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to roll back model: {}", e)))?;
+        Ok(ModelWrapper { inner: model })
+    }
+
+    /// Pin running apps of a model to a specific version
+    async fn pin_model_version(&self, ctx: &Context<'_>, input: PinModelVersionInput) -> Result<ModelVersionPin> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = input.model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let pin = state.services.model_service
+            .pin_model_version(uuid, input.version_number, input.pinned_by).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to pin model version: {}", e)))?;
+        Ok(ModelVersionPin::from(pin))
+    }
+
+    /// Remove a version pin so running apps follow the latest definition
+    async fn unpin_model_version(&self, ctx: &Context<'_>, model_id: String) -> Result<bool> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = model_id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        state.services.model_service.unpin_model_version(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to unpin model version: {}", e)))
+    }
+
+    /// Export a model to JSON
+    async fn export_model(&self, ctx: &Context<'_>, id: String) -> Result<String> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        state.services.model_service.export_model(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to export model: {}", e)))
+    }
+
+    /// Import a model from JSON
+    async fn import_model(&self, ctx: &Context<'_>, data: String) -> Result<ModelWrapper> {
+        let state = ctx.data::<AppState>()?;
+        let model = state.services.model_service.import_model(data).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to import model: {}", e)))?;
+        Ok(ModelWrapper { inner: model })
+    }
+
+    /// Replace an existing model with imported JSON.
+    /// Pass `baseVersion` (the version the import was edited from) to merge instead of
+    /// overwriting changes saved in the meantime.
+    async fn replace_model(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        data: String,
+        base_version: Option<i32>,
+        author: Option<String>,
+    ) -> Result<ModelWrapper> {
+        let state = ctx.data::<AppState>()?;
+        let model_id = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid model ID format"))?;
+        let model = state.services.model_service.replace_model(model_id, data, base_version, author).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to replace model: {}", e)))?;
+        Ok(ModelWrapper { inner: model })
+    }
+
     /// Create a new entity
-    async fn create_entity(&self, ctx: &Context<'_>, input: CreateEntityInput) -> Result<Entity> {
+    async fn create_entity(&self, ctx: &Context<'_>, input: CreateEntityInput) -> Result<EntityWrapper> {
         let state = ctx.data::<AppState>()?;
-        
-        let service_input = crate::services::model::CreateEntityInput {
-            model_id: input.model_id,
-            name: input.name,
-            display_name: input.display_name,
-            description: input.description,
-            entity_type: match input.entity_type {
-                EntityTypeEnum::Data => crate::model::types::EntityType::Data,
-                EntityTypeEnum::Lookup => crate::model::types::EntityType::Lookup,
-                EntityTypeEnum::Audit => crate::model::types::EntityType::Audit,
-                EntityTypeEnum::Temporary => crate::model::types::EntityType::Temporary,
-                EntityTypeEnum::View => crate::model::types::EntityType::View,
-            },
-            fields: input.fields.into_iter().map(|f| crate::services::model::CreateFieldInput {
-                name: f.name,
-                display_name: f.display_name,
-                field_type: match f.field_type {
-                    FieldTypeEnum::String => crate::model::types::FieldType::String { max_length: None },
-                    FieldTypeEnum::Integer => crate::model::types::FieldType::Integer { min: None, max: None },
-                    FieldTypeEnum::Float => crate::model::types::FieldType::Float { min: None, max: None },
-                    FieldTypeEnum::Boolean => crate::model::types::FieldType::Boolean,
-                    FieldTypeEnum::DateTime => crate::model::types::FieldType::DateTime,
-                    FieldTypeEnum::Date => crate::model::types::FieldType::Date,
-                    FieldTypeEnum::Time => crate::model::types::FieldType::Time,
-                    FieldTypeEnum::Json => crate::model::types::FieldType::Json,
-                    FieldTypeEnum::Binary => crate::model::types::FieldType::Binary,
-                    FieldTypeEnum::Enum => crate::model::types::FieldType::Enum { values: vec![] },
-                    FieldTypeEnum::Reference => crate::model::types::FieldType::Reference { entity_id: Uuid::new_v4() },
-                    FieldTypeEnum::Array => crate::model::types::FieldType::Array { element_type: Box::new(crate::model::types::FieldType::String { max_length: None }) },
-                },
-                required: f.required,
-                default_value: f.default_value,
-                ui_config: f.ui_config.map(|c| serde_json::from_value(c).unwrap_or_default()),
-            }).collect(),
-            ui_config: input.ui_config.map(|c| serde_json::from_value(c).unwrap_or_default()),
-            behavior: input.behavior.map(|b| serde_json::from_value(b).unwrap_or_default()),
-        };
-        
+        let model_id = input.model_id.clone();
+        let service_input = input.into_service(model_id)?;
         let entity = state.services.model_service.create_entity(service_input).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to create entity: {}", e)))?;
-            
-        Ok(Entity::from(entity))
+        Ok(EntityWrapper { inner: entity })
     }
 
-    /// Update an existing entity
-    async fn update_entity(&self, ctx: &Context<'_>, id: String, input: UpdateEntityInput) -> Result<Entity> {
+    /// Update an existing entity. `fields`, when given, replaces all fields of the entity.
+    async fn update_entity(&self, ctx: &Context<'_>, id: String, input: UpdateEntityInput) -> Result<EntityWrapper> {
         let state = ctx.data::<AppState>()?;
-        
-        // Parse entity ID
         let entity_id = id.parse::<Uuid>()
             .map_err(|_| Error::InvalidInput("Invalid entity ID format".to_string()))?;
-        
-        // Convert GraphQL input to model service input
-        let model_input = crate::services::model::UpdateEntityInput {
+
+        let fields = match input.fields {
+            Some(fields) => Some(fields.into_iter().map(UpdateFieldInput::into_field).collect::<Result<Vec<_>>>()?),
+            None => None,
+        };
+        let service_input = crate::services::model::UpdateEntityInput {
             name: input.name,
             display_name: input.display_name,
             description: input.description,
-            entity_type: input.entity_type.map(|et| match et {
-                EntityTypeEnum::Data => crate::model::types::EntityType::Data,
-                EntityTypeEnum::Lookup => crate::model::types::EntityType::Lookup,
-                EntityTypeEnum::Audit => crate::model::types::EntityType::Audit,
-                EntityTypeEnum::Temporary => crate::model::types::EntityType::Temporary,
-                EntityTypeEnum::View => crate::model::types::EntityType::View,
-            }),
-            fields: None, // TODO: Add field support to standard GraphQL schema
+            entity_type: input.entity_type.map(|et| parse_variant("entity type", &et)).transpose()?,
+            fields,
             ui_config: input.ui_config.map(|config| serde_json::from_value(config).unwrap_or_default()),
             behavior: input.behavior.map(|behavior| serde_json::from_value(behavior).unwrap_or_default()),
         };
-        
-        // Update entity through model service
-        let updated_entity = state.services.model_service.update_entity(entity_id, model_input).await?;
-        
-        // Convert model entity to GraphQL entity using existing From implementation
-        Ok(Entity::from(updated_entity))
+
+        let entity = state.services.model_service.update_entity(entity_id, service_input).await?;
+        Ok(EntityWrapper { inner: entity })
+    }
+
+    /// Delete an entity.
+    /// With `cleanupReferences`, relationships, layout components, flows and `Reference` fields bound to the entity are removed as well.
+    async fn delete_entity(&self, ctx: &Context<'_>, id: String, cleanup_references: Option<bool>) -> Result<bool> {
+        let state = ctx.data::<AppState>()?;
+        let entity_id = id.parse::<Uuid>()
+            .map_err(|_| Error::InvalidInput("Invalid entity ID format".to_string()))?;
+
+        let deletion = state.services.model_service
+            .delete_entity(entity_id, cleanup_references.unwrap_or(false)).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to delete entity: {}", e)))?;
+
+        if !deletion.references_removed && deletion.has_references() {
+            tracing::warn!(
+                "Deleted entity {} is still referenced by {} relationship(s), {} layout component(s), {} flow(s) and {} field(s)",
+                id, deletion.relationships.len(), deletion.components.len(), deletion.flows.len(), deletion.fields.len()
+            );
+        }
+
+        Ok(true)
     }
 
-    /// Delete an entity
-    async fn delete_entity(&self, ctx: &Context<'_>, _id: String) -> Result<bool> {
-        let _state = ctx.data::<AppState>()?;
-        // TODO: Implement entity deletion
-        Ok(false)
+    /// Create several entities of a model, reporting failures per entity
+    async fn batch_create_entities(&self, ctx: &Context<'_>, model_id: String, entities: Vec<CreateEntityInput>) -> Result<BatchResult> {
+        let state = ctx.data::<AppState>()?;
+
+        let mut created = 0;
+        let mut errors = Vec::new();
+        for (index, input) in entities.into_iter().enumerate() {
+            let result = match input.into_service(model_id.clone()) {
+                Ok(service_input) => state.services.model_service.create_entity(service_input).await
+                    .map_err(|e| async_graphql::Error::new(format!("Failed to create entity: {}", e))),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(_) => created += 1,
+                Err(e) => errors.push(BatchError {
+                    index: index as i32,
+                    message: e.message,
+                }),
+            }
+        }
+
+        Ok(BatchResult {
+            success_count: created,
+            error_count: errors.len() as i32,
+            errors,
+        })
     }
 
     /// Create a new relationship
-    async fn create_relationship(&self, ctx: &Context<'_>, input: CreateRelationshipInput) -> Result<Relationship> {
+    async fn create_relationship(&self, ctx: &Context<'_>, input: CreateRelationshipInput) -> Result<RelationshipWrapper> {
         let state = ctx.data::<AppState>()?;
-        
         let service_input = crate::services::model::CreateRelationshipInput {
             model_id: input.model_id,
             name: input.name,
-            relationship_type: match input.relationship_type {
-                RelationshipTypeEnum::OneToOne => crate::model::types::RelationshipType::OneToOne,
-                RelationshipTypeEnum::OneToMany => crate::model::types::RelationshipType::OneToMany,
-                RelationshipTypeEnum::ManyToOne => crate::model::types::RelationshipType::ManyToOne,
-                RelationshipTypeEnum::ManyToMany => crate::model::types::RelationshipType::ManyToMany,
-            },
+            relationship_type: parse_variant("relationship type", &input.relationship_type)?,
             from_entity: input.from_entity,
             to_entity: input.to_entity,
             from_field: input.from_field,
             to_field: input.to_field,
-            cascade: match input.cascade {
-                CascadeActionEnum::None => crate::model::types::CascadeAction::None,
-                CascadeActionEnum::Delete => crate::model::types::CascadeAction::Delete,
-                CascadeActionEnum::SetNull => crate::model::types::CascadeAction::SetNull,
-                CascadeActionEnum::Restrict => crate::model::types::CascadeAction::Restrict,
-            },
-            ui_config: input.ui_config.map(|c| serde_json::from_value(c).unwrap_or_default()),
+            cascade: parse_variant("cascade action", &input.cascade)?,
+            ui_config: input.ui_config.and_then(|v| serde_json::from_value(v).ok()),
         };
-        
         let relationship = state.services.model_service.create_relationship(service_input).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to create relationship: {}", e)))?;
-            
-        Ok(Relationship::from(relationship))
+        Ok(RelationshipWrapper { inner: relationship })
     }
 
     /// Update an existing relationship
-    async fn update_relationship(&self, ctx: &Context<'_>, _id: String, _input: UpdateRelationshipInput) -> Result<Relationship> {
-        let _state = ctx.data::<AppState>()?;
-        // TODO: Implement relationship update
-        Err(async_graphql::Error::new(format!("Relationship update is not yet implemented for ID: {}", _id)))
+    async fn update_relationship(&self, ctx: &Context<'_>, id: String, input: UpdateRelationshipInput) -> Result<RelationshipWrapper> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        let service_input = crate::services::model::UpdateRelationshipInput {
+            name: input.name,
+            relationship_type: input.relationship_type.map(|rt| parse_variant("relationship type", &rt)).transpose()?,
+            from_entity: input.from_entity,
+            to_entity: input.to_entity,
+            from_field: input.from_field,
+            to_field: input.to_field,
+            cascade: input.cascade.map(|c| parse_variant("cascade action", &c)).transpose()?,
+            ui_config: input.ui_config
+                .map(|v| serde_json::from_value(v)
+                    .map_err(|e| async_graphql::Error::new(format!("Invalid relationship uiConfig: {}", e))))
+                .transpose()?,
+        };
+        let relationship = state.services.model_service.update_relationship(uuid, service_input).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to update relationship: {}", e)))?;
+        Ok(RelationshipWrapper { inner: relationship })
     }
 
     /// Delete a relationship
-    async fn delete_relationship(&self, ctx: &Context<'_>, _id: String) -> Result<bool> {
-        let _state = ctx.data::<AppState>()?;
-        // TODO: Implement relationship deletion
-        Ok(false)
+    async fn delete_relationship(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        state.services.model_service.delete_relationship(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to delete relationship: {}", e)))
     }
 
     /// Create a new flow
-    async fn create_flow(&self, ctx: &Context<'_>, input: CreateFlowInput) -> Result<Flow> {
+    async fn create_flow(&self, ctx: &Context<'_>, input: CreateFlowInput) -> Result<FlowWrapper> {
         let state = ctx.data::<AppState>()?;
-        
         let service_input = crate::services::model::CreateFlowInput {
             model_id: input.model_id,
             name: input.name,
-            flow_type: match input.flow_type {
-                FlowTypeEnum::Validation => crate::model::types::FlowType::Validation,
-                FlowTypeEnum::Automation => crate::model::types::FlowType::Automation,
-                FlowTypeEnum::Approval => crate::model::types::FlowType::Approval,
-                FlowTypeEnum::Notification => crate::model::types::FlowType::Notification,
-                FlowTypeEnum::Custom => crate::model::types::FlowType::Custom,
-            },
-            trigger: serde_json::from_value(input.trigger).unwrap_or_default(),
-            steps: input.steps.into_iter().map(|s| crate::services::model::CreateFlowStepInput {
-                name: s.name,
-                step_type: match s.step_type {
-                    FlowStepTypeEnum::Validation => crate::model::types::FlowStepType::Validation,
-                    FlowStepTypeEnum::Transformation => crate::model::types::FlowStepType::Transformation,
-                    FlowStepTypeEnum::Notification => crate::model::types::FlowStepType::Notification,
-                    FlowStepTypeEnum::Integration => crate::model::types::FlowStepType::Integration,
-                    FlowStepTypeEnum::Approval => crate::model::types::FlowStepType::Approval,
-                    FlowStepTypeEnum::Custom => crate::model::types::FlowStepType::Custom("".to_string()),
-                },
-                condition: s.condition,
-                configuration: serde_json::from_value(s.configuration).unwrap_or_default(),
-            }).collect(),
+            flow_type: parse_variant("flow type", &input.flow_type)?,
+            trigger: flow_trigger(input.trigger, input.trigger_spec)?.unwrap_or_default(),
+            steps: input.steps.into_iter().map(FlowStepInput::into_service).collect::<Result<_>>()?,
             error_handling: input.error_handling.map(|e| serde_json::from_value(e).unwrap_or_default()),
         };
-        
         let flow = state.services.model_service.create_flow(service_input).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to create flow: {}", e)))?;
-            
-        Ok(Flow::from(flow))
+        Ok(FlowWrapper { inner: flow })
     }
 
     /// Update an existing flow
-    async fn update_flow(&self, ctx: &Context<'_>, _id: String, _input: UpdateFlowInput) -> Result<Flow> {
-        let _state = ctx.data::<AppState>()?;
-        // TODO: Implement flow update
-        Err(async_graphql::Error::new(format!("Flow update is not yet implemented for ID: {}", _id)))
+    async fn update_flow(&self, ctx: &Context<'_>, id: String, input: UpdateFlowInput) -> Result<FlowWrapper> {
+        let state = ctx.data::<AppState>()?;
+        let flow_id = id.parse::<Uuid>()
+            .map_err(|_| Error::InvalidInput("Invalid flow ID format".to_string()))?;
+
+        let steps = match input.steps {
+            Some(steps) => Some(steps.into_iter().map(FlowStepInput::into_service).collect::<Result<_>>()?),
+            None => None,
+        };
+        let service_input = crate::services::model::UpdateFlowInput {
+            name: input.name,
+            flow_type: input.flow_type.map(|ft| parse_variant("flow type", &ft)).transpose()?,
+            trigger: flow_trigger(input.trigger, input.trigger_spec)?,
+            steps,
+            error_handling: input.error_handling.map(|e| serde_json::from_value(e).unwrap_or_default()),
+        };
+        let flow = state.services.model_service.update_flow(flow_id, service_input).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to update flow: {}", e)))?;
+        Ok(FlowWrapper { inner: flow })
     }
 
     /// Delete a flow
-    async fn delete_flow(&self, ctx: &Context<'_>, _id: String) -> Result<bool> {
-        let _state = ctx.data::<AppState>()?;
-        // TODO: Implement flow deletion
-        Ok(false)
+    async fn delete_flow(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
+        let state = ctx.data::<AppState>()?;
+        let flow_id = id.parse::<Uuid>()
+            .map_err(|_| Error::InvalidInput("Invalid flow ID format".to_string()))?;
+        state.services.model_service.delete_flow(flow_id).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to delete flow: {}", e)))
     }
 
     /// Create a new layout
-    async fn create_layout(&self, ctx: &Context<'_>, input: CreateLayoutInput) -> Result<Layout> {
+    async fn create_layout(&self, ctx: &Context<'_>, input: CreateLayoutInput) -> Result<LayoutWrapper> {
         let state = ctx.data::<AppState>()?;
-        
         let service_input = crate::services::model::CreateLayoutInput {
             model_id: input.model_id,
             name: input.name,
-            layout_type: match input.layout_type {
-                LayoutTypeEnum::List => crate::model::types::LayoutType::List,
-                LayoutTypeEnum::Grid => crate::model::types::LayoutType::Grid,
-                LayoutTypeEnum::Dashboard => crate::model::types::LayoutType::Dashboard,
-                LayoutTypeEnum::Form => crate::model::types::LayoutType::Form,
-                LayoutTypeEnum::Detail => crate::model::types::LayoutType::Detail,
-                LayoutTypeEnum::Custom => crate::model::types::LayoutType::Custom,
-            },
+            layout_type: parse_variant("layout type", &input.layout_type)?,
             target_entities: input.target_entities,
-            components: input.components.into_iter().map(|c| crate::services::model::CreateLayoutComponentInput {
-                component_type: c.component_type,
-                position: serde_json::from_value(c.position).unwrap_or_default(),
-                properties: serde_json::from_value(c.properties).unwrap_or_default(),
-                styling: c.styling.map(|s| serde_json::from_value(s).unwrap_or_default()),
-                metadata: c.metadata.map(|m| serde_json::from_value(m).unwrap_or_default()),
-            }).collect(),
-            responsive: input.responsive.map(|r| serde_json::from_value(r).unwrap_or_default()),
+            components: input.components.into_iter().map(LayoutComponentInput::into_service).collect(),
+            responsive: input.responsive,
         };
-        
         let layout = state.services.model_service.create_layout(service_input).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to create layout: {}", e)))?;
-            
-        Ok(Layout::from(layout))
+        Ok(LayoutWrapper { inner: layout })
     }
 
     /// Update an existing layout
-    async fn update_layout(&self, ctx: &Context<'_>, id: String, input: UpdateLayoutInput) -> Result<Layout> {
+    async fn update_layout(&self, ctx: &Context<'_>, id: String, input: UpdateLayoutInput) -> Result<LayoutWrapper> {
         let state = ctx.data::<AppState>()?;
-        let layout_id = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid layout ID format"))?;
-        
+        let uuid = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
         let service_input = crate::services::model::UpdateLayoutInput {
-            name: input.name,
-            layout_type: input.layout_type.map(|lt| match lt {
-                LayoutTypeEnum::List => crate::model::types::LayoutType::List,
-                LayoutTypeEnum::Grid => crate::model::types::LayoutType::Grid,
-                LayoutTypeEnum::Dashboard => crate::model::types::LayoutType::Dashboard,
-                LayoutTypeEnum::Form => crate::model::types::LayoutType::Form,
-                LayoutTypeEnum::Detail => crate::model::types::LayoutType::Detail,
-                LayoutTypeEnum::Custom => crate::model::types::LayoutType::Custom,
-            }),
-            target_entities: input.target_entities,
-            components: input.components.map(|components| components.into_iter().map(|c| crate::services::model::CreateLayoutComponentInput {
-                component_type: c.component_type,
-                position: serde_json::from_value(c.position).unwrap_or_default(),
-                properties: serde_json::from_value(c.properties).unwrap_or_default(),
-                styling: c.styling.map(|s| serde_json::from_value(s).unwrap_or_default()),
-                metadata: c.metadata.map(|m| serde_json::from_value(m).unwrap_or_default()),
-            }).collect()),
-            responsive: input.responsive.map(|r| serde_json::from_value(r).unwrap_or_default()),
+            name: Some(input.name),
+            layout_type: Some(parse_variant("layout type", &input.layout_type)?),
+            target_entities: Some(input.target_entities),
+            components: Some(input.components.into_iter().map(LayoutComponentInput::into_service).collect()),
+            responsive: input.responsive,
         };
-        
-        let layout = state.services.model_service.update_layout(layout_id, service_input).await
+        let layout = state.services.model_service.update_layout(uuid, service_input).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to update layout: {}", e)))?;
-            
-        Ok(Layout::from(layout))
+        Ok(LayoutWrapper { inner: layout })
     }
 
     /// Delete a layout
-    async fn delete_layout(&self, ctx: &Context<'_>, _id: String) -> Result<bool> {
-        let _state = ctx.data::<AppState>()?;
-        // TODO: Implement layout deletion
-        Ok(false)
+    async fn delete_layout(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
+        let state = ctx.data::<AppState>()?;
+        let uuid = id.parse::<Uuid>()
+            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
+        state.services.model_service.delete_layout(uuid).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to delete layout: {}", e)))
     }
 
     /// Validate a model
@@ -436,23 +595,6 @@ impl Mutation {
         Ok(result.into())
     }
 
-    /// Export a model to JSON
-    async fn export_model(&self, ctx: &Context<'_>, id: String) -> Result<String> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        state.services.model_service.export_model(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to export model: {}", e)))
-    }
-
-    /// Import a model from JSON
-    async fn import_model(&self, ctx: &Context<'_>, data: String) -> Result<Model> {
-        let state = ctx.data::<AppState>()?;
-        let model = state.services.model_service.import_model(data).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to import model: {}", e)))?;
-        Ok(Model::from(model))
-    }
-
     /// Load sample data for a model
     async fn load_sample_data(&self, ctx: &Context<'_>, model_id: String) -> Result<u64> {
         let state = ctx.data::<AppState>()?;
@@ -461,231 +603,273 @@ impl Mutation {
         Ok(count)
     }
 
-    /// Get remediation strategies for a specific error type and parameters
-    async fn get_remediation_strategies(&self, ctx: &Context<'_>, input: GetRemediationStrategiesInput) -> Result<Vec<RemediationStrategy>> {
-        let state = ctx.data::<AppState>()?;
-        let model_uuid = input.model_id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid model ID format"))?;
-        
-        let strategies = state.services.model_service.get_remediation_strategies_by_type(
-            model_uuid,
-            &input.error_type,
-            &input.error_parameters
-        ).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get remediation strategies: {}", e)))?;
-        
-        Ok(strategies.into_iter().map(RemediationStrategy::from).collect())
-    }
-
     /// Execute auto-remediation for a configuration error
     async fn execute_auto_remediation(&self, ctx: &Context<'_>, input: ExecuteRemediationInput) -> Result<RemediationResult> {
         let state = ctx.data::<AppState>()?;
         let model_uuid = input.model_id.parse::<Uuid>()
             .map_err(|_| async_graphql::Error::new("Invalid model ID format"))?;
-        
+
         // Convert GraphQL parameters to HashMap
         let parameters: std::collections::HashMap<String, serde_json::Value> = input.parameters
             .into_iter()
             .map(|p| (p.name, p.value))
             .collect();
-        
+
         let result = state.services.model_service.execute_auto_remediation_by_type(
-            model_uuid, 
+            model_uuid,
             &input.error_type,
             &input.error_parameters,
             &input.strategy_type,
             parameters
         ).await
             .map_err(|e| async_graphql::Error::new(format!("Failed to execute auto-remediation: {}", e)))?;
-        
-        Ok(RemediationResult::from(result))
-    }
-}
-
-/// Subscription type for real-time updates (placeholder for now)
-pub struct SubscriptionRoot;
 
-#[Subscription]
-impl SubscriptionRoot {
-    /// Placeholder subscription
-    async fn placeholder(&self) -> impl Stream<Item = bool> {
-        futures_util::stream::once(async { true })
+        Ok(RemediationResult::from(result))
     }
 }
 
-// GraphQL Types based on Rust model types
-
-/// Model representation for GraphQL
-#[derive(SimpleObject)]
-pub struct Model {
-    pub id: String,
-    pub name: String,
-    pub description: Option<String>,
-    pub version: String,
-    #[graphql(name = "createdAt")]
-    pub created_at: String,
-    #[graphql(name = "updatedAt")]
-    pub updated_at: String,
-    #[graphql(name = "createdBy")]
-    pub created_by: String,
-    pub config: JSON,
-    pub entities: Vec<Entity>,
-    pub relationships: Vec<Relationship>,
-    pub flows: Vec<Flow>,
-    pub layouts: Vec<Layout>,
-    pub validations: Vec<Validation>,
-}
-
-/// Entity representation for GraphQL
-#[derive(SimpleObject)]
-pub struct Entity {
-    pub id: UuidString,
-    pub name: String,
-    #[graphql(name = "displayName")]
-    pub display_name: String,
-    pub description: Option<String>,
-    #[graphql(name = "entityType")]
-    pub entity_type: EntityTypeEnum,
-    pub fields: Vec<Field>,
-    pub constraints: Vec<Constraint>,
-    pub indexes: Vec<Index>,
-    #[graphql(name = "uiConfig")]
-    pub ui_config: JSON,
-    pub behavior: JSON,
-}
-
-/// Field representation for GraphQL
-#[derive(SimpleObject)]
-pub struct Field {
-    pub id: UuidString,
-    pub name: String,
-    #[graphql(name = "displayName")]
-    pub display_name: String,
-    #[graphql(name = "fieldType")]
-    pub field_type: FieldTypeEnum,
-    pub required: bool,
-    #[graphql(name = "defaultValue")]
-    pub default_value: Option<JSON>,
-    pub validation: Vec<FieldValidationRule>,
-    #[graphql(name = "uiConfig")]
-    pub ui_config: JSON,
+/// Parse an enum from the variant name the schema returns, e.g. `OneToMany` or `Dashboard`
+fn parse_variant<T: DeserializeOwned>(kind: &str, name: &str) -> Result<T> {
+    serde_json::from_value(Value::String(name.to_string()))
+        .map_err(|_| async_graphql::Error::new(format!("Invalid {} '{}'", kind, name)))
 }
 
-/// Relationship representation for GraphQL
-#[derive(SimpleObject)]
-pub struct Relationship {
-    pub id: UuidString,
-    pub name: String,
-    #[graphql(name = "relationshipType")]
-    pub relationship_type: RelationshipTypeEnum,
-    #[graphql(name = "fromEntity")]
-    pub from_entity: UuidString,
-    #[graphql(name = "toEntity")]
-    pub to_entity: UuidString,
-    #[graphql(name = "fromField")]
-    pub from_field: String,
-    #[graphql(name = "toField")]
-    pub to_field: String,
-    pub cascade: CascadeActionEnum,
-    #[graphql(name = "uiConfig")]
-    pub ui_config: JSON,
+/// Take a value from exactly one of its JSON and structured inputs
+fn one_of<J, S>(name: &str, json: Option<J>, spec: Option<S>) -> Result<Option<Result<J, S>>> {
+    match (json, spec) {
+        (Some(_), Some(_)) => Err(async_graphql::Error::new(format!("Pass either {} or {}Spec, not both", name, name))),
+        (Some(json), None) => Ok(Some(Ok(json))),
+        (None, Some(spec)) => Ok(Some(Err(spec))),
+        (None, None) => Ok(None),
+    }
 }
 
-/// Flow representation for GraphQL
-#[derive(SimpleObject)]
-pub struct Flow {
-    pub id: UuidString,
-    pub name: String,
-    #[graphql(name = "flowType")]
-    pub flow_type: FlowTypeEnum,
-    pub trigger: JSON,
-    pub steps: Vec<FlowStep>,
-    #[graphql(name = "errorHandling")]
-    pub error_handling: JSON,
+fn flow_trigger(trigger: Option<JSON>, trigger_spec: Option<FlowTriggerSpec>) -> Result<Option<FlowTrigger>> {
+    Ok(match one_of("trigger", trigger, trigger_spec)? {
+        Some(Ok(json)) => Some(serde_json::from_value(json)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid flow trigger: {}", e)))?),
+        Some(Err(spec)) => Some(FlowTrigger::try_from(spec)?),
+        None => None,
+    })
 }
 
-/// Flow step representation for GraphQL
-#[derive(SimpleObject)]
-pub struct FlowStep {
-    pub id: UuidString,
-    pub name: String,
-    #[graphql(name = "stepType")]
-    pub step_type: FlowStepTypeEnum,
-    pub condition: Option<String>,
-    pub configuration: JSON,
-}
+/// Subscription type for real-time updates, fed from the broadcast service.
+/// Served over `graphql-ws` and `graphql-transport-ws` at `/graphql/ws`.
+pub struct SubscriptionRoot;
 
-/// Layout representation for GraphQL
-#[derive(SimpleObject)]
-pub struct Layout {
-    pub id: UuidString,
-    pub name: String,
-    pub description: Option<String>,
-    #[graphql(name = "layoutType")]
-    pub layout_type: LayoutTypeEnum,
-    #[graphql(name = "targetEntities")]
-    pub target_entities: Vec<UuidString>,
-    pub components: Vec<LayoutComponent>,
-    pub responsive: JSON,
-    #[graphql(name = "createdAt")]
-    pub created_at: String,
-    #[graphql(name = "updatedAt")]
-    pub updated_at: String,
+/// Model events from the broadcast service. A subscriber that lags behind gets a
+/// `RESYNC_REQUIRED` error in place of the events it missed and keeps receiving the ones after them.
+fn model_events(ctx: &Context<'_>) -> Result<impl Stream<Item = Result<ModelChangeEvent>>> {
+    let state = ctx.data::<AppState>()?;
+    let receiver = state.services.broadcast.subscribe();
+    Ok(futures_util::stream::unfold(receiver, |mut receiver| async move {
+        match receiver.recv().await {
+            Ok(message) => Some((Ok(message.event), receiver)),
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("GraphQL subscriber lagged, skipped {} events", skipped);
+                Some((Err(resync_required(skipped)), receiver))
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => None,
+        }
+    }))
 }
 
-/// Layout component representation for GraphQL
-#[derive(SimpleObject)]
-pub struct LayoutComponent {
-    pub id: UuidString,
-    #[graphql(name = "componentType")]
-    pub component_type: String,
-    pub position: JSON,
-    pub properties: JSON,
-    pub styling: JSON,
-    pub metadata: Option<JSON>,
+/// Tells a lagging subscriber to reload what it shows, like `resyncRequired` on the WebSocket
+fn resync_required(skipped: u64) -> async_graphql::Error {
+    async_graphql::Error::new(format!("Subscriber lagged behind and missed {} events; reload and resubscribe", skipped))
+        .extend_with(|_, extensions| {
+            extensions.set("code", "RESYNC_REQUIRED");
+            extensions.set("skipped", skipped);
+        })
 }
 
-/// Validation representation for GraphQL
+#[Subscription]
+impl SubscriptionRoot {
+    /// Changes to model definitions: the model itself, entities, relationships, flows and layouts
+    #[graphql(name = "modelChanged")]
+    async fn model_changed(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "modelId")] model_id: Option<String>,
+    ) -> Result<impl Stream<Item = Result<ModelDefinitionChange>>> {
+        Ok(model_events(ctx)?.filter_map(move |event| {
+            let change = event
+                .map(|event| ModelDefinitionChange::from_event(&event)
+                    .filter(|change| model_id.as_ref().is_none_or(|id| *id == change.model_id)))
+                .transpose();
+            async move { change }
+        }))
+    }
+
+    /// Created, updated and deleted app data records of a model, one at a time or in bulk
+    #[graphql(name = "entityDataChanged")]
+    async fn entity_data_changed(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "modelId")] model_id: String,
+        #[graphql(name = "entityType")] entity_type: Option<String>,
+    ) -> Result<impl Stream<Item = Result<EntityDataChange>>> {
+        Ok(model_events(ctx)?.filter_map(move |event| {
+            let change = event.map(|event| match event {
+                ModelChangeEvent::EntityDataChanged { model_id: changed_model, entity_type: changed_type, record_id, action, record, diff, timestamp }
+                    if changed_model.as_str() == model_id && entity_type.as_ref().is_none_or(|t| *t == changed_type) =>
+                {
+                    Some(EntityDataChange {
+                        model_id: changed_model.to_string(),
+                        entity_type: changed_type,
+                        record_id: Some(record_id),
+                        record_ids: None,
+                        count: 1,
+                        action: action.as_str().to_string(),
+                        record,
+                        diff: diff.and_then(|diff| serde_json::to_value(diff).ok()),
+                        timestamp: timestamp.to_string(),
+                    })
+                }
+                ModelChangeEvent::EntityDataBulkChanged { model_id: changed_model, entity_type: changed_type, action, count, record_ids, timestamp }
+                    if changed_model.as_str() == model_id && entity_type.as_ref().is_none_or(|t| *t == changed_type) =>
+                {
+                    Some(EntityDataChange {
+                        model_id: changed_model.to_string(),
+                        entity_type: changed_type,
+                        record_id: None,
+                        record_ids,
+                        count: count as i64,
+                        action: action.as_str().to_string(),
+                        record: None,
+                        diff: None,
+                        timestamp: timestamp.to_string(),
+                    })
+                }
+                _ => None,
+            }).transpose();
+            async move { change }
+        }))
+    }
+
+    /// Status updates of flow executions. Flows have no executor yet, so this
+    /// subscription emits nothing until one publishes `FlowExecutionUpdated` events.
+    #[graphql(name = "flowExecutionUpdated")]
+    async fn flow_execution_updated(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "modelId")] model_id: Option<String>,
+        #[graphql(name = "flowId")] flow_id: Option<String>,
+    ) -> Result<impl Stream<Item = Result<FlowExecutionUpdate>>> {
+        Ok(model_events(ctx)?.filter_map(move |event| {
+            let update = event.map(|event| match event {
+                ModelChangeEvent::FlowExecutionUpdated { model_id: changed_model, flow_id: changed_flow, execution_id, status, timestamp }
+                    if model_id.as_ref().is_none_or(|id| id == changed_model.as_str())
+                        && flow_id.as_ref().is_none_or(|id| id == changed_flow.as_str()) =>
+                {
+                    Some(FlowExecutionUpdate {
+                        model_id: changed_model.to_string(),
+                        flow_id: changed_flow.to_string(),
+                        execution_id: execution_id.to_string(),
+                        status,
+                        timestamp: timestamp.to_string(),
+                    })
+                }
+                _ => None,
+            }).transpose();
+            async move { update }
+        }))
+    }
+}
+
+/// A change to a model definition
 #[derive(SimpleObject)]
-pub struct Validation {
-    pub id: UuidString,
-    pub name: String,
-    #[graphql(name = "validationType")]
-    pub validation_type: ValidationTypeEnum,
-    pub scope: JSON,
-    pub rule: String,
-    pub message: String,
-    pub severity: ValidationSeverityEnum,
+pub struct ModelDefinitionChange {
+    /// Event type, e.g. `EntityAdded` or `LayoutUpdated`
+    #[graphql(name = "changeType")]
+    pub change_type: String,
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    /// ID of the entity, relationship, flow or layout that changed
+    #[graphql(name = "itemId")]
+    pub item_id: Option<String>,
+    pub description: String,
+    pub timestamp: String,
+    /// The full model, for model created and updated events
+    pub model: Option<ModelWrapper>,
+}
+
+impl ModelDefinitionChange {
+    /// Model definition changes; app data, presence and flow execution events map to `None`
+    fn from_event(event: &ModelChangeEvent) -> Option<Self> {
+        use ModelChangeEvent as E;
+        let (change_type, item_id, model) = match event {
+            E::ModelCreated { model, .. } => ("ModelCreated", None, Some(ModelWrapper { inner: model.clone() })),
+            E::ModelUpdated { model, .. } => ("ModelUpdated", None, Some(ModelWrapper { inner: model.clone() })),
+            E::ModelDeleted { .. } => ("ModelDeleted", None, None),
+            E::EntityAdded { entity_id, .. } => ("EntityAdded", Some(entity_id), None),
+            E::EntityUpdated { entity_id, .. } => ("EntityUpdated", Some(entity_id), None),
+            E::EntityRemoved { entity_id, .. } => ("EntityRemoved", Some(entity_id), None),
+            E::RelationshipAdded { relationship_id, .. } => ("RelationshipAdded", Some(relationship_id), None),
+            E::RelationshipUpdated { relationship_id, .. } => ("RelationshipUpdated", Some(relationship_id), None),
+            E::RelationshipRemoved { relationship_id, .. } => ("RelationshipRemoved", Some(relationship_id), None),
+            E::FlowAdded { flow_id, .. } => ("FlowAdded", Some(flow_id), None),
+            E::FlowUpdated { flow_id, .. } => ("FlowUpdated", Some(flow_id), None),
+            E::FlowRemoved { flow_id, .. } => ("FlowRemoved", Some(flow_id), None),
+            E::LayoutAdded { layout_id, .. } => ("LayoutAdded", Some(layout_id), None),
+            E::LayoutUpdated { layout_id, .. } => ("LayoutUpdated", Some(layout_id), None),
+            E::LayoutRemoved { layout_id, .. } => ("LayoutRemoved", Some(layout_id), None),
+            E::OperationApplied { .. } => ("OperationApplied", None, None),
+            E::PresenceChanged { .. }
+            | E::EntityDataChanged { .. }
+            | E::EntityDataBulkChanged { .. }
+            | E::FlowExecutionUpdated { .. } => return None,
+        };
+        Some(Self {
+            change_type: change_type.to_string(),
+            model_id: event.model_id().to_string(),
+            item_id: item_id.map(|id| id.to_string()),
+            description: event.description(),
+            timestamp: event.timestamp().to_string(),
+            model,
+        })
+    }
 }
 
-/// Constraint representation for GraphQL
+/// A created, updated or deleted app data record
 #[derive(SimpleObject)]
-pub struct Constraint {
-    #[graphql(name = "constraintType")]
-    pub constraint_type: ConstraintTypeEnum,
-    pub name: String,
-    pub fields: Vec<String>,
-    pub message: Option<String>,
-}
-
-/// Index representation for GraphQL
+pub struct EntityDataChange {
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    #[graphql(name = "entityType")]
+    pub entity_type: String,
+    /// ID of the changed record; unset for bulk changes
+    #[graphql(name = "recordId")]
+    pub record_id: Option<String>,
+    /// IDs of the records of a bulk change, when few enough to send. When both IDs are
+    /// unset, the entity's data should be reloaded.
+    #[graphql(name = "recordIds")]
+    pub record_ids: Option<Vec<String>>,
+    /// Number of records changed
+    pub count: i64,
+    /// `created`, `updated` or `deleted`
+    pub action: String,
+    /// The record after the change, when small enough to send
+    pub record: Option<JSON>,
+    /// `{ set, removed }` fields of an update whose record is too large to send
+    pub diff: Option<JSON>,
+    pub timestamp: String,
+}
+
+/// Status of a flow execution
 #[derive(SimpleObject)]
-pub struct Index {
-    pub name: String,
-    pub fields: Vec<String>,
-    #[graphql(name = "indexType")]
-    pub index_type: IndexTypeEnum,
-    pub unique: bool,
+pub struct FlowExecutionUpdate {
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    #[graphql(name = "flowId")]
+    pub flow_id: String,
+    #[graphql(name = "executionId")]
+    pub execution_id: String,
+    pub status: String,
+    pub timestamp: String,
 }
 
-/// Field validation rule for GraphQL
-#[derive(SimpleObject)]
-pub struct FieldValidationRule {
-    #[graphql(name = "validationType")]
-    pub validation_type: JSON,
-    pub message: String,
-    pub severity: ValidationSeverityEnum,
-}
+// GraphQL output types
 
 /// Validation result for GraphQL
 #[derive(SimpleObject)]
@@ -773,19 +957,55 @@ pub struct ErrorSuggestion {
     pub estimated_effort: EstimatedEffortEnum,
 }
 
-// GraphQL Enums
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ValidationSeverityEnum {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ConfigErrorSeverityEnum {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ConfigErrorCategoryEnum {
+    DataModel,
+    UserInterface,
+    BusinessLogic,
+    Integration,
+    Performance,
+    Security,
+}
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum EntityTypeEnum {
-    Data,
-    Lookup,
-    Audit,
-    Temporary,
-    View,
+pub enum SuggestionActionTypeEnum {
+    CreateMissingEntity,
+    UpdateEntitySchema,
+    FixLayoutConfiguration,
+    RemoveInvalidReferences,
+    UpdateValidationRules,
+    RefactorRelationships,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum FieldTypeEnum {
+pub enum EstimatedEffortEnum {
+    Low,
+    Medium,
+    High,
+    Complex,
+}
+
+// Structured field types, validation rules and flow triggers.
+// Each is both an output (on Field/Flow) and an input, so a client can read a definition
+// and send it back unchanged.
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FieldTypeKind {
     String,
     Integer,
     Float,
@@ -800,119 +1020,341 @@ pub enum FieldTypeEnum {
     Array,
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum RelationshipTypeEnum {
-    OneToOne,
-    OneToMany,
-    ManyToOne,
-    ManyToMany,
-}
-
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum CascadeActionEnum {
-    None,
-    Delete,
-    SetNull,
-    Restrict,
+/// A field type with its parameters. Only the properties belonging to `kind` may be set.
+#[derive(SimpleObject, InputObject, Clone, Debug)]
+#[graphql(input_name = "FieldTypeInput")]
+pub struct FieldTypeSpec {
+    pub kind: FieldTypeKind,
+    /// `String`: maximum length
+    pub max_length: Option<u64>,
+    /// `Integer`: bounds
+    pub min_int: Option<i64>,
+    pub max_int: Option<i64>,
+    /// `Float`: bounds
+    pub min_float: Option<f64>,
+    pub max_float: Option<f64>,
+    /// `Enum`: allowed values
+    pub values: Option<Vec<String>>,
+    /// `Reference`: the referenced entity
+    pub entity_id: Option<UuidString>,
+    /// `Array`: element type
+    pub element_type: Option<Box<FieldTypeSpec>>,
+}
+
+impl FieldTypeSpec {
+    fn new(kind: FieldTypeKind) -> Self {
+        Self {
+            kind,
+            max_length: None,
+            min_int: None,
+            max_int: None,
+            min_float: None,
+            max_float: None,
+            values: None,
+            entity_id: None,
+            element_type: None,
+        }
+    }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum FlowTypeEnum {
-    Validation,
-    Automation,
-    Approval,
-    Notification,
-    Custom,
+impl From<&FieldType> for FieldTypeSpec {
+    fn from(field_type: &FieldType) -> Self {
+        match field_type {
+            FieldType::String { max_length } => FieldTypeSpec {
+                max_length: max_length.map(|l| l as u64),
+                ..FieldTypeSpec::new(FieldTypeKind::String)
+            },
+            FieldType::Integer { min, max } => FieldTypeSpec {
+                min_int: *min,
+                max_int: *max,
+                ..FieldTypeSpec::new(FieldTypeKind::Integer)
+            },
+            FieldType::Float { min, max } => FieldTypeSpec {
+                min_float: *min,
+                max_float: *max,
+                ..FieldTypeSpec::new(FieldTypeKind::Float)
+            },
+            FieldType::Boolean => FieldTypeSpec::new(FieldTypeKind::Boolean),
+            FieldType::DateTime => FieldTypeSpec::new(FieldTypeKind::DateTime),
+            FieldType::Date => FieldTypeSpec::new(FieldTypeKind::Date),
+            FieldType::Time => FieldTypeSpec::new(FieldTypeKind::Time),
+            FieldType::Json => FieldTypeSpec::new(FieldTypeKind::Json),
+            FieldType::Binary => FieldTypeSpec::new(FieldTypeKind::Binary),
+            FieldType::Enum { values } => FieldTypeSpec {
+                values: Some(values.clone()),
+                ..FieldTypeSpec::new(FieldTypeKind::Enum)
+            },
+            FieldType::Reference { entity_id } => FieldTypeSpec {
+                entity_id: Some(entity_id.to_string()),
+                ..FieldTypeSpec::new(FieldTypeKind::Reference)
+            },
+            FieldType::Array { element_type } => FieldTypeSpec {
+                element_type: Some(Box::new(FieldTypeSpec::from(element_type.as_ref()))),
+                ..FieldTypeSpec::new(FieldTypeKind::Array)
+            },
+        }
+    }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum FlowStepTypeEnum {
-    Validation,
-    Transformation,
-    Notification,
-    Integration,
-    Approval,
-    Custom,
+impl TryFrom<FieldTypeSpec> for FieldType {
+    type Error = Error;
+
+    fn try_from(spec: FieldTypeSpec) -> std::result::Result<Self, Error> {
+        let kind = spec.kind;
+        let set = [
+            ("maxLength", spec.max_length.is_some(), FieldTypeKind::String),
+            ("minInt", spec.min_int.is_some(), FieldTypeKind::Integer),
+            ("maxInt", spec.max_int.is_some(), FieldTypeKind::Integer),
+            ("minFloat", spec.min_float.is_some(), FieldTypeKind::Float),
+            ("maxFloat", spec.max_float.is_some(), FieldTypeKind::Float),
+            ("values", spec.values.is_some(), FieldTypeKind::Enum),
+            ("entityId", spec.entity_id.is_some(), FieldTypeKind::Reference),
+            ("elementType", spec.element_type.is_some(), FieldTypeKind::Array),
+        ];
+        if let Some((name, _, _)) = set.iter().find(|(_, is_set, owner)| *is_set && *owner != kind) {
+            return Err(Error::InvalidInput(format!("'{}' does not apply to field type {:?}", name, kind)));
+        }
+        let missing = |name: &str| Error::InvalidInput(format!("field type {:?} requires '{}'", kind, name));
+
+        Ok(match kind {
+            FieldTypeKind::String => FieldType::String { max_length: spec.max_length.map(|l| l as usize) },
+            FieldTypeKind::Integer => FieldType::Integer { min: spec.min_int, max: spec.max_int },
+            FieldTypeKind::Float => FieldType::Float { min: spec.min_float, max: spec.max_float },
+            FieldTypeKind::Boolean => FieldType::Boolean,
+            FieldTypeKind::DateTime => FieldType::DateTime,
+            FieldTypeKind::Date => FieldType::Date,
+            FieldTypeKind::Time => FieldType::Time,
+            FieldTypeKind::Json => FieldType::Json,
+            FieldTypeKind::Binary => FieldType::Binary,
+            FieldTypeKind::Enum => FieldType::Enum { values: spec.values.ok_or_else(|| missing("values"))? },
+            FieldTypeKind::Reference => {
+                let entity_id = spec.entity_id.ok_or_else(|| missing("entityId"))?;
+                FieldType::Reference {
+                    entity_id: entity_id.parse::<Uuid>()
+                        .map_err(|_| Error::InvalidInput(format!("Invalid referenced entity ID '{}'", entity_id)))?,
+                }
+            }
+            FieldTypeKind::Array => {
+                let element_type = spec.element_type.ok_or_else(|| missing("elementType"))?;
+                FieldType::Array { element_type: Box::new(FieldType::try_from(*element_type)?) }
+            }
+        })
+    }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum LayoutTypeEnum {
-    List,
-    Grid,
-    Dashboard,
-    Form,
-    Detail,
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ValidationRuleKind {
+    Required,
+    MinLength,
+    MaxLength,
+    Pattern,
+    Range,
     Custom,
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum ValidationTypeEnum {
-    EntityValidation,
-    RelationshipValidation,
-    BusinessRule,
-    DataIntegrity,
+/// A field validation rule. Only the properties belonging to `kind` may be set.
+#[derive(SimpleObject, InputObject, Clone, Debug)]
+#[graphql(input_name = "FieldValidationInput")]
+pub struct FieldValidationSpec {
+    pub kind: ValidationRuleKind,
+    /// `MinLength` / `MaxLength`: the length
+    pub length: Option<u64>,
+    /// `Pattern`: the regular expression
+    pub pattern: Option<String>,
+    /// `Range`: bounds, either may be left open
+    pub min: Option<JSON>,
+    pub max: Option<JSON>,
+    /// `Custom`: the validation expression
+    pub expression: Option<String>,
+    pub message: String,
+    pub severity: ValidationSeverityEnum,
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum ValidationSeverityEnum {
-    Error,
-    Warning,
-    Info,
+impl From<&FieldValidation> for FieldValidationSpec {
+    fn from(validation: &FieldValidation) -> Self {
+        let bound = |v: &Value| if v.is_null() { None } else { Some(v.clone()) };
+        let (kind, length, pattern, min, max, expression) = match &validation.validation_type {
+            ValidationType::Required => (ValidationRuleKind::Required, None, None, None, None, None),
+            ValidationType::MinLength(l) => (ValidationRuleKind::MinLength, Some(*l as u64), None, None, None, None),
+            ValidationType::MaxLength(l) => (ValidationRuleKind::MaxLength, Some(*l as u64), None, None, None, None),
+            ValidationType::Pattern(p) => (ValidationRuleKind::Pattern, None, Some(p.clone()), None, None, None),
+            ValidationType::Range { min, max } => (ValidationRuleKind::Range, None, None, bound(min), bound(max), None),
+            ValidationType::Custom(e) => (ValidationRuleKind::Custom, None, None, None, None, Some(e.clone())),
+        };
+        FieldValidationSpec {
+            kind,
+            length,
+            pattern,
+            min,
+            max,
+            expression,
+            message: validation.message.clone(),
+            severity: validation.severity.clone().into(),
+        }
+    }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum ConstraintTypeEnum {
-    PrimaryKey,
-    UniqueKey,
-    ForeignKey,
-    Check,
-}
+impl TryFrom<FieldValidationSpec> for FieldValidation {
+    type Error = Error;
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum IndexTypeEnum {
-    BTree,
-    Hash,
-    Gin,
-    Gist,
+    fn try_from(spec: FieldValidationSpec) -> std::result::Result<Self, Error> {
+        let kind = spec.kind;
+        let is_length = matches!(kind, ValidationRuleKind::MinLength | ValidationRuleKind::MaxLength);
+        let set = [
+            ("length", spec.length.is_some(), is_length),
+            ("pattern", spec.pattern.is_some(), kind == ValidationRuleKind::Pattern),
+            ("min", spec.min.is_some(), kind == ValidationRuleKind::Range),
+            ("max", spec.max.is_some(), kind == ValidationRuleKind::Range),
+            ("expression", spec.expression.is_some(), kind == ValidationRuleKind::Custom),
+        ];
+        if let Some((name, _, _)) = set.iter().find(|(_, is_set, applies)| *is_set && !*applies) {
+            return Err(Error::InvalidInput(format!("'{}' does not apply to validation rule {:?}", name, kind)));
+        }
+        let missing = |name: &str| Error::InvalidInput(format!("validation rule {:?} requires '{}'", kind, name));
+
+        let validation_type = match kind {
+            ValidationRuleKind::Required => ValidationType::Required,
+            ValidationRuleKind::MinLength => ValidationType::MinLength(spec.length.ok_or_else(|| missing("length"))? as usize),
+            ValidationRuleKind::MaxLength => ValidationType::MaxLength(spec.length.ok_or_else(|| missing("length"))? as usize),
+            ValidationRuleKind::Pattern => ValidationType::Pattern(spec.pattern.ok_or_else(|| missing("pattern"))?),
+            ValidationRuleKind::Range => {
+                if spec.min.is_none() && spec.max.is_none() {
+                    return Err(Error::InvalidInput("validation rule Range requires 'min' or 'max'".to_string()));
+                }
+                ValidationType::Range {
+                    min: spec.min.unwrap_or(Value::Null),
+                    max: spec.max.unwrap_or(Value::Null),
+                }
+            }
+            ValidationRuleKind::Custom => ValidationType::Custom(spec.expression.ok_or_else(|| missing("expression"))?),
+        };
+        Ok(FieldValidation {
+            validation_type,
+            message: spec.message,
+            severity: spec.severity.into(),
+        })
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FlowTriggerKind {
+    EntityEvent,
+    Schedule,
+    Manual,
+    Webhook,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum LifecycleEventEnum {
+    BeforeCreate,
+    AfterCreate,
+    BeforeUpdate,
+    AfterUpdate,
+    BeforeDelete,
+    AfterDelete,
+    OnRead,
+}
+
+/// What starts a flow. `EntityEvent` needs `entityId` and `event`, `Schedule` needs `cron`.
+#[derive(SimpleObject, InputObject, Clone, Debug)]
+#[graphql(input_name = "FlowTriggerInput")]
+pub struct FlowTriggerSpec {
+    pub kind: FlowTriggerKind,
+    pub entity_id: Option<UuidString>,
+    pub event: Option<LifecycleEventEnum>,
+    pub cron: Option<String>,
+}
+
+impl From<&FlowTrigger> for FlowTriggerSpec {
+    fn from(trigger: &FlowTrigger) -> Self {
+        let spec = |kind| FlowTriggerSpec { kind, entity_id: None, event: None, cron: None };
+        match trigger {
+            FlowTrigger::EntityEvent { entity_id, event } => FlowTriggerSpec {
+                entity_id: Some(entity_id.to_string()),
+                event: Some(event.clone().into()),
+                ..spec(FlowTriggerKind::EntityEvent)
+            },
+            FlowTrigger::Schedule(cron) => FlowTriggerSpec {
+                cron: Some(cron.clone()),
+                ..spec(FlowTriggerKind::Schedule)
+            },
+            FlowTrigger::Manual => spec(FlowTriggerKind::Manual),
+            FlowTrigger::Webhook => spec(FlowTriggerKind::Webhook),
+        }
+    }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum ConfigErrorSeverityEnum {
-    Critical,
-    High,
-    Medium,
-    Low,
-}
+impl TryFrom<FlowTriggerSpec> for FlowTrigger {
+    type Error = Error;
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum ConfigErrorCategoryEnum {
-    DataModel,
-    UserInterface,
-    BusinessLogic,
-    Integration,
-    Performance,
-    Security,
+    fn try_from(spec: FlowTriggerSpec) -> std::result::Result<Self, Error> {
+        let kind = spec.kind;
+        let is_event = kind == FlowTriggerKind::EntityEvent;
+        let set = [
+            ("entityId", spec.entity_id.is_some(), is_event),
+            ("event", spec.event.is_some(), is_event),
+            ("cron", spec.cron.is_some(), kind == FlowTriggerKind::Schedule),
+        ];
+        if let Some((name, _, _)) = set.iter().find(|(_, is_set, applies)| *is_set && !*applies) {
+            return Err(Error::InvalidInput(format!("'{}' does not apply to flow trigger {:?}", name, kind)));
+        }
+        let missing = |name: &str| Error::InvalidInput(format!("flow trigger {:?} requires '{}'", kind, name));
+
+        Ok(match kind {
+            FlowTriggerKind::EntityEvent => {
+                let entity_id = spec.entity_id.ok_or_else(|| missing("entityId"))?;
+                FlowTrigger::EntityEvent {
+                    entity_id: entity_id.parse::<Uuid>()
+                        .map_err(|_| Error::InvalidInput(format!("Invalid trigger entity ID '{}'", entity_id)))?,
+                    event: spec.event.ok_or_else(|| missing("event"))?.into(),
+                }
+            }
+            FlowTriggerKind::Schedule => FlowTrigger::Schedule(spec.cron.ok_or_else(|| missing("cron"))?),
+            FlowTriggerKind::Manual => FlowTrigger::Manual,
+            FlowTriggerKind::Webhook => FlowTrigger::Webhook,
+        })
+    }
+}
+
+impl From<LifecycleEvent> for LifecycleEventEnum {
+    fn from(event: LifecycleEvent) -> Self {
+        match event {
+            LifecycleEvent::BeforeCreate => LifecycleEventEnum::BeforeCreate,
+            LifecycleEvent::AfterCreate => LifecycleEventEnum::AfterCreate,
+            LifecycleEvent::BeforeUpdate => LifecycleEventEnum::BeforeUpdate,
+            LifecycleEvent::AfterUpdate => LifecycleEventEnum::AfterUpdate,
+            LifecycleEvent::BeforeDelete => LifecycleEventEnum::BeforeDelete,
+            LifecycleEvent::AfterDelete => LifecycleEventEnum::AfterDelete,
+            LifecycleEvent::OnRead => LifecycleEventEnum::OnRead,
+        }
+    }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum SuggestionActionTypeEnum {
-    CreateMissingEntity,
-    UpdateEntitySchema,
-    FixLayoutConfiguration,
-    RemoveInvalidReferences,
-    UpdateValidationRules,
-    RefactorRelationships,
+impl From<LifecycleEventEnum> for LifecycleEvent {
+    fn from(event: LifecycleEventEnum) -> Self {
+        match event {
+            LifecycleEventEnum::BeforeCreate => LifecycleEvent::BeforeCreate,
+            LifecycleEventEnum::AfterCreate => LifecycleEvent::AfterCreate,
+            LifecycleEventEnum::BeforeUpdate => LifecycleEvent::BeforeUpdate,
+            LifecycleEventEnum::AfterUpdate => LifecycleEvent::AfterUpdate,
+            LifecycleEventEnum::BeforeDelete => LifecycleEvent::BeforeDelete,
+            LifecycleEventEnum::AfterDelete => LifecycleEvent::AfterDelete,
+            LifecycleEventEnum::OnRead => LifecycleEvent::OnRead,
+        }
+    }
 }
 
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum EstimatedEffortEnum {
-    Low,
-    Medium,
-    High,
-    Complex,
+impl From<ValidationSeverityEnum> for ValidationSeverity {
+    fn from(vs: ValidationSeverityEnum) -> Self {
+        match vs {
+            ValidationSeverityEnum::Error => ValidationSeverity::Error,
+            ValidationSeverityEnum::Warning => ValidationSeverity::Warning,
+            ValidationSeverityEnum::Info => ValidationSeverity::Info,
+        }
+    }
 }
 
-// Input types for mutations
+// Input types
 
 #[derive(InputObject)]
 pub struct CreateModelInput {
@@ -926,151 +1368,352 @@ pub struct UpdateModelInput {
     pub name: Option<String>,
     pub description: Option<String>,
     pub config: Option<JSON>,
+    /// Author recorded on the version snapshot
+    pub author: Option<String>,
+    /// Message recorded on the version snapshot
+    pub message: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct UpdateModelConfigInput {
+    pub start_page_layout_id: Option<String>,
 }
 
 #[derive(InputObject)]
 pub struct CreateEntityInput {
-    #[graphql(name = "modelId")]
     pub model_id: UuidString,
     pub name: String,
-    #[graphql(name = "displayName")]
     pub display_name: String,
     pub description: Option<String>,
-    #[graphql(name = "entityType")]
-    pub entity_type: EntityTypeEnum,
+    /// Entity type variant name, e.g. `Lookup` (defaults to `Data`)
+    pub entity_type: Option<String>,
     pub fields: Vec<CreateFieldInput>,
-    #[graphql(name = "uiConfig")]
     pub ui_config: Option<JSON>,
     pub behavior: Option<JSON>,
 }
 
+impl CreateEntityInput {
+    fn into_service(self, model_id: String) -> Result<crate::services::model::CreateEntityInput> {
+        Ok(crate::services::model::CreateEntityInput {
+            model_id,
+            name: self.name,
+            display_name: self.display_name,
+            description: self.description,
+            entity_type: match self.entity_type {
+                Some(et) => parse_variant("entity type", &et)?,
+                None => EntityType::Data,
+            },
+            fields: self.fields.into_iter().map(CreateFieldInput::into_service).collect::<Result<_>>()?,
+            ui_config: self.ui_config.map(|config| serde_json::from_value(config).unwrap_or_default()),
+            behavior: self.behavior.map(|behavior| serde_json::from_value(behavior).unwrap_or_default()),
+        })
+    }
+}
+
 #[derive(InputObject)]
 pub struct UpdateEntityInput {
     pub name: Option<String>,
-    #[graphql(name = "displayName")]
     pub display_name: Option<String>,
     pub description: Option<String>,
-    #[graphql(name = "entityType")]
-    pub entity_type: Option<EntityTypeEnum>,
-    #[graphql(name = "uiConfig")]
+    /// Entity type variant name, e.g. `Lookup`
+    pub entity_type: Option<String>,
+    /// Replaces all fields of the entity
+    pub fields: Option<Vec<UpdateFieldInput>>,
     pub ui_config: Option<JSON>,
     pub behavior: Option<JSON>,
 }
 
+/// A field of a new entity.
+/// Give the type either as `fieldType` notation (e.g. `String(100)`, `Enum[a,b]`,
+/// `Reference(<entity id>)`, `Array<Integer>`) or structured as `fieldTypeSpec`, and the
+/// validation rules either as serialized `validation` JSON or as `validationRules`.
 #[derive(InputObject)]
 pub struct CreateFieldInput {
     pub name: String,
-    #[graphql(name = "displayName")]
     pub display_name: String,
-    #[graphql(name = "fieldType")]
-    pub field_type: FieldTypeEnum,
+    pub field_type: Option<String>,
+    pub field_type_spec: Option<FieldTypeSpec>,
+    pub required: bool,
+    pub default_value: Option<JSON>,
+    pub validation: Option<JSON>,
+    pub validation_rules: Option<Vec<FieldValidationSpec>>,
+    pub ui_config: Option<JSON>,
+}
+
+impl CreateFieldInput {
+    fn into_service(self) -> Result<crate::services::model::CreateFieldInput> {
+        Ok(crate::services::model::CreateFieldInput {
+            field_type: field_type(&self.name, self.field_type, self.field_type_spec)?,
+            validation: field_validation(&self.name, self.validation, self.validation_rules)?,
+            name: self.name,
+            display_name: self.display_name,
+            required: self.required,
+            default_value: self.default_value,
+            ui_config: self.ui_config.and_then(|v| serde_json::from_value(v).ok()),
+        })
+    }
+}
+
+/// A field of an updated entity; fields without `id` are created
+#[derive(InputObject)]
+pub struct UpdateFieldInput {
+    pub id: Option<UuidString>,
+    pub name: String,
+    pub display_name: String,
+    pub field_type: Option<String>,
+    pub field_type_spec: Option<FieldTypeSpec>,
     pub required: bool,
-    #[graphql(name = "defaultValue")]
     pub default_value: Option<JSON>,
-    #[graphql(name = "uiConfig")]
+    pub validation: Option<JSON>,
+    pub validation_rules: Option<Vec<FieldValidationSpec>>,
     pub ui_config: Option<JSON>,
 }
 
+impl UpdateFieldInput {
+    fn into_field(self) -> Result<EntityField> {
+        let id = match self.id {
+            Some(id) => id.parse::<Uuid>()
+                .map_err(|_| Error::InvalidInput(format!("Invalid field ID '{}'", id)))?,
+            None => Uuid::new_v4(),
+        };
+        Ok(EntityField {
+            id,
+            field_type: field_type(&self.name, self.field_type, self.field_type_spec)?,
+            validation: field_validation(&self.name, self.validation, self.validation_rules)?,
+            name: self.name,
+            display_name: self.display_name,
+            required: self.required,
+            default_value: self.default_value,
+            ui_config: self.ui_config.and_then(|v| serde_json::from_value(v).ok()).unwrap_or_default(),
+        })
+    }
+}
+
+fn field_type(field: &str, notation: Option<String>, spec: Option<FieldTypeSpec>) -> Result<FieldType> {
+    let field_type = match one_of("fieldType", notation, spec)? {
+        Some(Ok(notation)) => notation.parse::<FieldType>(),
+        Some(Err(spec)) => FieldType::try_from(spec),
+        None => Err(Error::InvalidInput("fieldType or fieldTypeSpec is required".to_string())),
+    };
+    field_type.map_err(|e| async_graphql::Error::new(format!("Field '{}': {}", field, e)))
+}
+
+fn field_validation(field: &str, json: Option<JSON>, rules: Option<Vec<FieldValidationSpec>>) -> Result<Vec<FieldValidation>> {
+    if json.is_some() && rules.is_some() {
+        return Err(async_graphql::Error::new(format!("Field '{}': pass either validation or validationRules, not both", field)));
+    }
+    let validation = match (json, rules) {
+        (Some(json), _) => serde_json::from_value(json)
+            .map_err(|e| Error::InvalidInput(format!("validation: {}", e))),
+        (None, Some(rules)) => rules.into_iter().map(FieldValidation::try_from).collect(),
+        (None, None) => Ok(Vec::new()),
+    };
+    validation.map_err(|e| async_graphql::Error::new(format!("Field '{}': {}", field, e)))
+}
+
 #[derive(InputObject)]
 pub struct CreateRelationshipInput {
-    #[graphql(name = "modelId")]
     pub model_id: UuidString,
     pub name: String,
-    #[graphql(name = "relationshipType")]
-    pub relationship_type: RelationshipTypeEnum,
-    #[graphql(name = "fromEntity")]
+    /// Relationship type variant name, e.g. `OneToMany`
+    pub relationship_type: String,
     pub from_entity: UuidString,
-    #[graphql(name = "toEntity")]
     pub to_entity: UuidString,
-    #[graphql(name = "fromField")]
     pub from_field: String,
-    #[graphql(name = "toField")]
     pub to_field: String,
-    pub cascade: CascadeActionEnum,
-    #[graphql(name = "uiConfig")]
+    /// Cascade action variant name, e.g. `SetNull`
+    pub cascade: String,
     pub ui_config: Option<JSON>,
 }
 
 #[derive(InputObject)]
 pub struct UpdateRelationshipInput {
     pub name: Option<String>,
-    #[graphql(name = "relationshipType")]
-    pub relationship_type: Option<RelationshipTypeEnum>,
-    #[graphql(name = "fromField")]
+    pub relationship_type: Option<String>,
+    pub from_entity: Option<UuidString>,
+    pub to_entity: Option<UuidString>,
     pub from_field: Option<String>,
-    #[graphql(name = "toField")]
     pub to_field: Option<String>,
-    pub cascade: Option<CascadeActionEnum>,
-    #[graphql(name = "uiConfig")]
+    pub cascade: Option<String>,
     pub ui_config: Option<JSON>,
 }
 
+/// A new flow. Give the trigger either as serialized `trigger` JSON or as `triggerSpec`
+/// (defaults to a manual trigger).
 #[derive(InputObject)]
 pub struct CreateFlowInput {
-    #[graphql(name = "modelId")]
     pub model_id: UuidString,
     pub name: String,
-    #[graphql(name = "flowType")]
-    pub flow_type: FlowTypeEnum,
-    pub trigger: JSON,
-    pub steps: Vec<CreateFlowStepInput>,
-    #[graphql(name = "errorHandling")]
+    /// Flow type variant name, e.g. `Automation`
+    pub flow_type: String,
+    pub trigger: Option<JSON>,
+    pub trigger_spec: Option<FlowTriggerSpec>,
+    pub steps: Vec<FlowStepInput>,
     pub error_handling: Option<JSON>,
 }
 
 #[derive(InputObject)]
 pub struct UpdateFlowInput {
     pub name: Option<String>,
-    #[graphql(name = "flowType")]
-    pub flow_type: Option<FlowTypeEnum>,
+    pub flow_type: Option<String>,
     pub trigger: Option<JSON>,
-    #[graphql(name = "errorHandling")]
+    pub trigger_spec: Option<FlowTriggerSpec>,
+    /// Replaces all steps of the flow
+    pub steps: Option<Vec<FlowStepInput>>,
     pub error_handling: Option<JSON>,
 }
 
 #[derive(InputObject)]
-pub struct CreateFlowStepInput {
+pub struct FlowStepInput {
+    /// Existing step whose ID is kept when a flow's steps are replaced
+    pub id: Option<UuidString>,
     pub name: String,
-    #[graphql(name = "stepType")]
-    pub step_type: FlowStepTypeEnum,
+    /// Step type variant name, e.g. `Notification` or `Custom`
+    pub step_type: String,
+    /// Handler name of a `Custom` step
+    pub custom_type: Option<String>,
     pub condition: Option<String>,
     pub configuration: JSON,
 }
 
+impl FlowStepInput {
+    fn into_service(self) -> Result<crate::services::model::CreateFlowStepInput> {
+        let step_type = match (self.step_type.as_str(), self.custom_type) {
+            ("Custom", custom_type) => FlowStepType::Custom(custom_type.unwrap_or_default()),
+            (_, Some(_)) => {
+                return Err(async_graphql::Error::new(format!("Step '{}': customType only applies to Custom steps", self.name)));
+            }
+            (step_type, None) => parse_variant("flow step type", step_type)?,
+        };
+        let id = self.id
+            .map(|id| id.parse::<Uuid>().map_err(|_| async_graphql::Error::new(format!("Step '{}': invalid ID", self.name))))
+            .transpose()?;
+        Ok(crate::services::model::CreateFlowStepInput {
+            id,
+            name: self.name,
+            step_type,
+            condition: self.condition,
+            configuration: self.configuration,
+        })
+    }
+}
+
 #[derive(InputObject)]
 pub struct CreateLayoutInput {
-    #[graphql(name = "modelId")]
     pub model_id: UuidString,
     pub name: String,
-    #[graphql(name = "layoutType")]
-    pub layout_type: LayoutTypeEnum,
-    #[graphql(name = "targetEntities")]
+    /// Layout type variant name, e.g. `Dashboard`
+    pub layout_type: String,
     pub target_entities: Vec<UuidString>,
-    pub components: Vec<CreateLayoutComponentInput>,
+    pub components: Vec<LayoutComponentInput>,
     pub responsive: Option<JSON>,
 }
 
 #[derive(InputObject)]
 pub struct UpdateLayoutInput {
-    pub name: Option<String>,
-    #[graphql(name = "layoutType")]
-    pub layout_type: Option<LayoutTypeEnum>,
-    #[graphql(name = "targetEntities")]
-    pub target_entities: Option<Vec<UuidString>>,
-    pub components: Option<Vec<CreateLayoutComponentInput>>,
+    pub model_id: UuidString,
+    pub name: String,
+    pub layout_type: String,
+    pub target_entities: Vec<UuidString>,
+    pub components: Vec<LayoutComponentInput>,
     pub responsive: Option<JSON>,
 }
 
 #[derive(InputObject)]
-pub struct CreateLayoutComponentInput {
-    #[graphql(name = "componentType")]
+pub struct LayoutComponentInput {
     pub component_type: String,
-    pub position: JSON,
+    pub position: ComponentPositionInput,
     pub properties: JSON,
     pub styling: Option<JSON>,
     pub metadata: Option<JSON>,
 }
 
+impl LayoutComponentInput {
+    fn into_service(self) -> crate::services::model::CreateLayoutComponentInput {
+        crate::services::model::CreateLayoutComponentInput {
+            component_type: self.component_type,
+            position: ComponentPosition {
+                row: self.position.row as u32,
+                column: self.position.column as u32,
+                width: self.position.width as u32,
+                height: self.position.height as u32,
+            },
+            properties: self.properties,
+            styling: self.styling,
+            metadata: self.metadata,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct ComponentPositionInput {
+    pub row: i32,
+    pub column: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Model version snapshot metadata for GraphQL
+#[derive(SimpleObject)]
+pub struct ModelVersionInfo {
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    #[graphql(name = "versionNumber")]
+    pub version_number: i32,
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    pub message: Option<String>,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+}
+
+/// Version pin for running apps
+#[derive(SimpleObject)]
+pub struct ModelVersionPin {
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    #[graphql(name = "versionNumber")]
+    pub version_number: i32,
+    #[graphql(name = "pinnedBy")]
+    pub pinned_by: String,
+    #[graphql(name = "pinnedAt")]
+    pub pinned_at: String,
+}
+
+/// Full-text search match inside a model
+#[derive(SimpleObject)]
+pub struct ModelSearchHit {
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    #[graphql(name = "modelName")]
+    pub model_name: String,
+    pub path: String,
+    pub kind: String,
+    pub name: String,
+    pub score: f64,
+}
+
+#[derive(InputObject)]
+pub struct RollbackModelInput {
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    #[graphql(name = "versionNumber")]
+    pub version_number: i32,
+    pub author: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct PinModelVersionInput {
+    #[graphql(name = "modelId")]
+    pub model_id: String,
+    #[graphql(name = "versionNumber")]
+    pub version_number: i32,
+    #[graphql(name = "pinnedBy")]
+    pub pinned_by: Option<String>,
+}
+
 // Remediation types
 
 #[derive(SimpleObject)]
@@ -1174,314 +1817,40 @@ pub struct RemediationParameterInput {
     pub value: JSON,
 }
 
-// Event types for subscriptions
-
-#[derive(SimpleObject)]
-pub struct ModelEvent {
-    pub event_type: ModelEventType,
-    pub model_id: UuidString,
-    pub data: Option<JSON>,
-    pub timestamp: DateTimeString,
-}
-
-#[derive(SimpleObject)]
-pub struct EntityEvent {
-    pub event_type: EntityEventType,
-    pub entity_id: UuidString,
-    pub data: Option<JSON>,
-    pub timestamp: DateTimeString,
-}
-
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum ModelEventType {
-    Created,
-    Updated,
-    Deleted,
-    EntityAdded,
-    EntityRemoved,
-    RelationshipAdded,
-    RelationshipRemoved,
-}
-
-#[derive(Enum, Copy, Clone, Eq, PartialEq)]
-pub enum EntityEventType {
-    Created,
-    Updated,
-    Deleted,
-    FieldAdded,
-    FieldRemoved,
-    FieldUpdated,
-}
-
-// Helper functions for type conversion
-
-impl From<TorqueModel> for Model {
-    fn from(model: TorqueModel) -> Self {
-        Self {
-            id: model.id.to_string(),
-            name: model.name,
-            description: model.description,
-            version: model.version,
-            created_at: model.created_at.to_iso8601(),
-            updated_at: model.updated_at.to_iso8601(),
-            created_by: model.created_by,
-            config: serde_json::to_value(model.config).unwrap_or_default(),
-            entities: model.entities.into_iter().map(Entity::from).collect(),
-            relationships: model.relationships.into_iter().map(Relationship::from).collect(),
-            flows: model.flows.into_iter().map(Flow::from).collect(),
-            layouts: model.layouts.into_iter().map(Layout::from).collect(),
-            validations: model.validations.into_iter().map(Validation::from).collect(),
-        }
-    }
-}
-
-impl From<ModelEntity> for Entity {
-    fn from(entity: ModelEntity) -> Self {
-        Self {
-            id: entity.id.to_string(),
-            name: entity.name,
-            display_name: entity.display_name,
-            description: entity.description,
-            entity_type: entity.entity_type.into(),
-            fields: entity.fields.into_iter().map(Field::from).collect(),
-            constraints: entity.constraints.into_iter().map(Constraint::from).collect(),
-            indexes: entity.indexes.into_iter().map(Index::from).collect(),
-            ui_config: serde_json::to_value(entity.ui_config).unwrap_or_default(),
-            behavior: serde_json::to_value(entity.behavior).unwrap_or_default(),
-        }
-    }
-}
-
-impl From<EntityField> for Field {
-    fn from(field: EntityField) -> Self {
-        Self {
-            id: field.id.to_string(),
-            name: field.name,
-            display_name: field.display_name,
-            field_type: field.field_type.into(),
-            required: field.required,
-            default_value: field.default_value,
-            validation: field.validation.into_iter().map(FieldValidationRule::from).collect(),
-            ui_config: serde_json::to_value(field.ui_config).unwrap_or_default(),
-        }
-    }
-}
-
-impl From<ModelRelationship> for Relationship {
-    fn from(rel: ModelRelationship) -> Self {
-        Self {
-            id: rel.id.to_string(),
-            name: rel.name,
-            relationship_type: rel.relationship_type.into(),
-            from_entity: rel.from_entity.to_string(),
-            to_entity: rel.to_entity.to_string(),
-            from_field: rel.from_field,
-            to_field: rel.to_field,
-            cascade: rel.cascade.into(),
-            ui_config: serde_json::to_value(rel.ui_config).unwrap_or_default(),
-        }
-    }
-}
-
-impl From<ModelFlow> for Flow {
-    fn from(flow: ModelFlow) -> Self {
-        Self {
-            id: flow.id.to_string(),
-            name: flow.name,
-            flow_type: flow.flow_type.into(),
-            trigger: serde_json::to_value(flow.trigger).unwrap_or_default(),
-            steps: flow.steps.into_iter().map(FlowStep::from).collect(),
-            error_handling: serde_json::to_value(flow.error_handling).unwrap_or_default(),
-        }
-    }
-}
-
-impl From<crate::model::types::FlowStep> for FlowStep {
-    fn from(step: crate::model::types::FlowStep) -> Self {
-        Self {
-            id: step.id.to_string(),
-            name: step.name,
-            step_type: step.step_type.into(),
-            condition: step.condition,
-            configuration: serde_json::to_value(step.configuration).unwrap_or_default(),
-        }
-    }
-}
-
-impl From<ModelLayout> for Layout {
-    fn from(layout: ModelLayout) -> Self {
-        Self {
-            id: layout.id.to_string(),
-            name: layout.name,
-            description: layout.description,
-            layout_type: layout.layout_type.into(),
-            target_entities: layout.target_entities.into_iter().map(|id| id.to_string()).collect(),
-            components: layout.components.into_iter().map(LayoutComponent::from).collect(),
-            responsive: serde_json::to_value(layout.responsive).unwrap_or_default(),
-            created_at: layout.created_at.to_iso8601(),
-            updated_at: layout.updated_at.to_iso8601(),
-        }
-    }
-}
-
-impl From<crate::model::types::LayoutComponent> for LayoutComponent {
-    fn from(comp: crate::model::types::LayoutComponent) -> Self {
-        Self {
-            id: comp.id.to_string(),
-            component_type: comp.component_type,
-            position: serde_json::to_value(comp.position).unwrap_or_default(),
-            properties: serde_json::to_value(comp.properties).unwrap_or_default(),
-            styling: serde_json::to_value(comp.styling).unwrap_or_default(),
-            metadata: comp.metadata.map(|m| serde_json::to_value(m).unwrap_or_default()),
-        }
-    }
-}
-
-impl From<ModelValidation> for Validation {
-    fn from(val: ModelValidation) -> Self {
-        Self {
-            id: val.id.to_string(),
-            name: val.name,
-            validation_type: val.validation_type.into(),
-            scope: serde_json::to_value(val.scope).unwrap_or_default(),
-            rule: val.rule,
-            message: val.message,
-            severity: val.severity.into(),
-        }
-    }
-}
-
-impl From<EntityConstraint> for Constraint {
-    fn from(constraint: EntityConstraint) -> Self {
+impl From<crate::services::model::ModelVersionInfo> for ModelVersionInfo {
+    fn from(info: crate::services::model::ModelVersionInfo) -> Self {
         Self {
-            constraint_type: constraint.constraint_type.into(),
-            name: constraint.name,
-            fields: constraint.fields,
-            message: constraint.message,
+            model_id: info.model_id.to_string(),
+            version_number: info.version_number,
+            name: info.name,
+            version: info.version,
+            author: info.author,
+            message: info.message,
+            created_at: info.created_at.to_iso8601(),
         }
     }
 }
 
-impl From<EntityIndex> for Index {
-    fn from(index: EntityIndex) -> Self {
+impl From<crate::services::model::ModelVersionPin> for ModelVersionPin {
+    fn from(pin: crate::services::model::ModelVersionPin) -> Self {
         Self {
-            name: index.name,
-            fields: index.fields,
-            index_type: index.index_type.into(),
-            unique: index.unique,
+            model_id: pin.model_id.to_string(),
+            version_number: pin.version_number,
+            pinned_by: pin.pinned_by,
+            pinned_at: pin.pinned_at.to_iso8601(),
         }
     }
 }
 
-impl From<FieldValidation> for FieldValidationRule {
-    fn from(val: FieldValidation) -> Self {
+impl From<crate::services::model_search::ModelSearchHit> for ModelSearchHit {
+    fn from(hit: crate::services::model_search::ModelSearchHit) -> Self {
         Self {
-            validation_type: serde_json::to_value(val.validation_type).unwrap_or_default(),
-            message: val.message,
-            severity: val.severity.into(),
-        }
-    }
-}
-
-// Enum conversions
-impl From<EntityType> for EntityTypeEnum {
-    fn from(et: EntityType) -> Self {
-        match et {
-            EntityType::Data => EntityTypeEnum::Data,
-            EntityType::Lookup => EntityTypeEnum::Lookup,
-            EntityType::Audit => EntityTypeEnum::Audit,
-            EntityType::Temporary => EntityTypeEnum::Temporary,
-            EntityType::View => EntityTypeEnum::View,
-        }
-    }
-}
-
-impl From<FieldType> for FieldTypeEnum {
-    fn from(ft: FieldType) -> Self {
-        match ft {
-            FieldType::String { .. } => FieldTypeEnum::String,
-            FieldType::Integer { .. } => FieldTypeEnum::Integer,
-            FieldType::Float { .. } => FieldTypeEnum::Float,
-            FieldType::Boolean => FieldTypeEnum::Boolean,
-            FieldType::DateTime => FieldTypeEnum::DateTime,
-            FieldType::Date => FieldTypeEnum::Date,
-            FieldType::Time => FieldTypeEnum::Time,
-            FieldType::Json => FieldTypeEnum::Json,
-            FieldType::Binary => FieldTypeEnum::Binary,
-            FieldType::Enum { .. } => FieldTypeEnum::Enum,
-            FieldType::Reference { .. } => FieldTypeEnum::Reference,
-            FieldType::Array { .. } => FieldTypeEnum::Array,
-        }
-    }
-}
-
-impl From<RelationshipType> for RelationshipTypeEnum {
-    fn from(rt: RelationshipType) -> Self {
-        match rt {
-            RelationshipType::OneToOne => RelationshipTypeEnum::OneToOne,
-            RelationshipType::OneToMany => RelationshipTypeEnum::OneToMany,
-            RelationshipType::ManyToOne => RelationshipTypeEnum::ManyToOne,
-            RelationshipType::ManyToMany => RelationshipTypeEnum::ManyToMany,
-        }
-    }
-}
-
-impl From<CascadeAction> for CascadeActionEnum {
-    fn from(ca: CascadeAction) -> Self {
-        match ca {
-            CascadeAction::None => CascadeActionEnum::None,
-            CascadeAction::Delete => CascadeActionEnum::Delete,
-            CascadeAction::SetNull => CascadeActionEnum::SetNull,
-            CascadeAction::Restrict => CascadeActionEnum::Restrict,
-        }
-    }
-}
-
-impl From<crate::model::types::FlowType> for FlowTypeEnum {
-    fn from(ft: crate::model::types::FlowType) -> Self {
-        match ft {
-            crate::model::types::FlowType::Validation => FlowTypeEnum::Validation,
-            crate::model::types::FlowType::Automation => FlowTypeEnum::Automation,
-            crate::model::types::FlowType::Approval => FlowTypeEnum::Approval,
-            crate::model::types::FlowType::Notification => FlowTypeEnum::Notification,
-            crate::model::types::FlowType::Custom => FlowTypeEnum::Custom,
-        }
-    }
-}
-
-impl From<crate::model::types::FlowStepType> for FlowStepTypeEnum {
-    fn from(fst: crate::model::types::FlowStepType) -> Self {
-        match fst {
-            crate::model::types::FlowStepType::Validation => FlowStepTypeEnum::Validation,
-            crate::model::types::FlowStepType::Transformation => FlowStepTypeEnum::Transformation,
-            crate::model::types::FlowStepType::Notification => FlowStepTypeEnum::Notification,
-            crate::model::types::FlowStepType::Integration => FlowStepTypeEnum::Integration,
-            crate::model::types::FlowStepType::Approval => FlowStepTypeEnum::Approval,
-            crate::model::types::FlowStepType::Custom(_) => FlowStepTypeEnum::Custom,
-        }
-    }
-}
-
-impl From<crate::model::types::LayoutType> for LayoutTypeEnum {
-    fn from(lt: crate::model::types::LayoutType) -> Self {
-        match lt {
-            crate::model::types::LayoutType::List => LayoutTypeEnum::List,
-            crate::model::types::LayoutType::Grid => LayoutTypeEnum::Grid,
-            crate::model::types::LayoutType::Dashboard => LayoutTypeEnum::Dashboard,
-            crate::model::types::LayoutType::Form => LayoutTypeEnum::Form,
-            crate::model::types::LayoutType::Detail => LayoutTypeEnum::Detail,
-            crate::model::types::LayoutType::Custom => LayoutTypeEnum::Custom,
-        }
-    }
-}
-
-impl From<ModelValidationType> for ValidationTypeEnum {
-    fn from(mvt: ModelValidationType) -> Self {
-        match mvt {
-            ModelValidationType::EntityValidation => ValidationTypeEnum::EntityValidation,
-            ModelValidationType::RelationshipValidation => ValidationTypeEnum::RelationshipValidation,
-            ModelValidationType::BusinessRule => ValidationTypeEnum::BusinessRule,
-            ModelValidationType::DataIntegrity => ValidationTypeEnum::DataIntegrity,
+            model_id: hit.model_id.to_string(),
+            model_name: hit.model_name,
+            path: hit.path,
+            kind: hit.kind,
+            name: hit.name,
+            score: hit.score,
         }
     }
 }
@@ -1496,28 +1865,6 @@ impl From<ValidationSeverity> for ValidationSeverityEnum {
     }
 }
 
-impl From<ConstraintType> for ConstraintTypeEnum {
-    fn from(ct: ConstraintType) -> Self {
-        match ct {
-            ConstraintType::PrimaryKey => ConstraintTypeEnum::PrimaryKey,
-            ConstraintType::UniqueKey => ConstraintTypeEnum::UniqueKey,
-            ConstraintType::ForeignKey { .. } => ConstraintTypeEnum::ForeignKey,
-            ConstraintType::Check(_) => ConstraintTypeEnum::Check,
-        }
-    }
-}
-
-impl From<crate::model::types::IndexType> for IndexTypeEnum {
-    fn from(it: crate::model::types::IndexType) -> Self {
-        match it {
-            crate::model::types::IndexType::BTree => IndexTypeEnum::BTree,
-            crate::model::types::IndexType::Hash => IndexTypeEnum::Hash,
-            crate::model::types::IndexType::Gin => IndexTypeEnum::Gin,
-            crate::model::types::IndexType::Gist => IndexTypeEnum::Gist,
-        }
-    }
-}
-
 // Convert service ValidationResult to GraphQL ValidationResult
 impl From<crate::services::model::ValidationResult> for ValidationResult {
     fn from(result: crate::services::model::ValidationResult) -> Self {
@@ -1739,4 +2086,4 @@ impl From<crate::model::remediation::ModelChangeType> for ModelChangeTypeEnum {
             crate::model::remediation::ModelChangeType::ReferenceRemoved => ModelChangeTypeEnum::ReferenceRemoved,
         }
     }
-}
\ No newline at end of file
+}
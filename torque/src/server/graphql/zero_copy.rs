@@ -1,88 +1,6 @@
-use async_graphql::{Context, Object, Result, SimpleObject, InputObject};
-use crate::common::Uuid;
+use async_graphql::{Object, SimpleObject};
 use crate::model::types as model;
-use crate::server::AppState;
-use crate::server::graphql::schema::{ConfigurationReport, ConfigurationErrorDetail, ErrorSeverityCount, ErrorLocation, ErrorSuggestion};
-
-/// Validation message for GraphQL
-#[derive(SimpleObject)]
-pub struct ValidationMessage {
-    pub message: String,
-    pub field: Option<String>,
-    pub code: String,
-}
-
-/// Validation result for GraphQL
-#[derive(SimpleObject)]
-pub struct ValidationResult {
-    pub valid: bool,
-    pub errors: Vec<ValidationMessage>,
-    pub warnings: Vec<ValidationMessage>,
-}
-
-/// Parse field type from string representation
-fn parse_field_type(field_type_str: &str) -> model::FieldType {
-    match field_type_str {
-        "String" => model::FieldType::String { max_length: None },
-        "Integer" => model::FieldType::Integer { min: None, max: None },
-        "Float" => model::FieldType::Float { min: None, max: None },
-        "Boolean" => model::FieldType::Boolean,
-        "DateTime" => model::FieldType::DateTime,
-        "Date" => model::FieldType::Date,
-        "Time" => model::FieldType::Time,
-        "Json" => model::FieldType::Json,
-        "Binary" => model::FieldType::Binary,
-        _ if field_type_str.starts_with("String(") => {
-            // Parse String(100) format
-            let len_str = field_type_str.trim_start_matches("String(").trim_end_matches(")");
-            let max_length = len_str.parse::<usize>().ok();
-            model::FieldType::String { max_length }
-        },
-        _ if field_type_str.starts_with("Reference(") => {
-            // Parse Reference(entity_id) format
-            let entity_str = field_type_str.trim_start_matches("Reference(").trim_end_matches(")");
-            if let Ok(entity_id) = entity_str.parse::<Uuid>() {
-                model::FieldType::Reference { entity_id }
-            } else {
-                model::FieldType::String { max_length: None }
-            }
-        },
-        _ => model::FieldType::String { max_length: None },
-    }
-}
-
-/// Parse layout type from string representation
-fn parse_layout_type(layout_type_str: &str) -> model::LayoutType {
-    match layout_type_str {
-        "List" => model::LayoutType::List,
-        "Grid" => model::LayoutType::Grid,
-        "Dashboard" => model::LayoutType::Dashboard,
-        "Form" => model::LayoutType::Form,
-        "Detail" => model::LayoutType::Detail,
-        "Custom" => model::LayoutType::Custom,
-        _ => model::LayoutType::Dashboard,
-    }
-}
-
-fn parse_relationship_type(relationship_type_str: &str) -> model::RelationshipType {
-    match relationship_type_str {
-        "OneToOne" => model::RelationshipType::OneToOne,
-        "OneToMany" => model::RelationshipType::OneToMany,
-        "ManyToOne" => model::RelationshipType::ManyToOne,
-        "ManyToMany" => model::RelationshipType::ManyToMany,
-        _ => model::RelationshipType::OneToMany,
-    }
-}
-
-fn parse_cascade_action(cascade_str: &str) -> model::CascadeAction {
-    match cascade_str {
-        "None" => model::CascadeAction::None,
-        "Delete" => model::CascadeAction::Delete,
-        "SetNull" => model::CascadeAction::SetNull,
-        "Restrict" => model::CascadeAction::Restrict,
-        _ => model::CascadeAction::None,
-    }
-}
+use crate::server::graphql::schema::{FieldTypeSpec, FieldValidationSpec, FlowTriggerSpec, ModelVersionInfo};
 
 /// Optimized GraphQL wrappers that minimize conversions
 /// Key optimizations:
@@ -159,6 +77,22 @@ impl ModelWrapper {
     }
 }
 
+/// Zero-copy wrapper for a model version snapshot
+pub struct ModelVersionWrapper {
+    pub inner: crate::services::model::ModelVersion,
+}
+
+#[Object]
+impl ModelVersionWrapper {
+    async fn info(&self) -> ModelVersionInfo {
+        ModelVersionInfo::from(self.inner.info.clone())
+    }
+
+    async fn model(&self) -> ModelWrapper {
+        ModelWrapper { inner: self.inner.model.clone() }
+    }
+}
+
 pub struct EntityWrapper {
     pub inner: model::ModelEntity,
 }
@@ -226,27 +160,15 @@ impl FieldWrapper {
         &self.inner.display_name
     }
 
+    /// Type in compact notation, e.g. `String(100)`, `Enum[low,high]` or `Array<Integer>`.
+    /// Accepted as-is by the `fieldType` input.
     async fn field_type(&self) -> String {
-        match &self.inner.field_type {
-            model::FieldType::String { max_length } => {
-                if let Some(len) = max_length {
-                    format!("String({})", len)
-                } else {
-                    "String".to_string()
-                }
-            }
-            model::FieldType::Integer { .. } => "Integer".to_string(),
-            model::FieldType::Float { .. } => "Float".to_string(),
-            model::FieldType::Boolean => "Boolean".to_string(),
-            model::FieldType::DateTime => "DateTime".to_string(),
-            model::FieldType::Date => "Date".to_string(),
-            model::FieldType::Time => "Time".to_string(),
-            model::FieldType::Json => "Json".to_string(),
-            model::FieldType::Binary => "Binary".to_string(),
-            model::FieldType::Enum { values } => format!("Enum[{}]", values.join(",")),
-            model::FieldType::Reference { entity_id } => format!("Reference({})", entity_id),
-            model::FieldType::Array { .. } => "Array".to_string(),
-        }
+        self.inner.field_type.to_string()
+    }
+
+    /// Structured form of `fieldType`, accepted as-is by the `fieldTypeSpec` input
+    async fn field_type_spec(&self) -> FieldTypeSpec {
+        FieldTypeSpec::from(&self.inner.field_type)
     }
 
     async fn required(&self) -> bool {
@@ -261,6 +183,11 @@ impl FieldWrapper {
         serde_json::to_value(&self.inner.validation).unwrap_or_default()
     }
 
+    /// Structured form of `validation`, accepted as-is by the `validationRules` input
+    async fn validation_rules(&self) -> Vec<FieldValidationSpec> {
+        self.inner.validation.iter().map(FieldValidationSpec::from).collect()
+    }
+
     async fn ui_config(&self) -> serde_json::Value {
         serde_json::to_value(&self.inner.ui_config).unwrap_or_default()
     }
@@ -324,7 +251,18 @@ impl FlowStepWrapper {
     }
 
     async fn step_type(&self) -> String {
-        format!("{:?}", self.inner.step_type)
+        match &self.inner.step_type {
+            model::FlowStepType::Custom(_) => "Custom".to_string(),
+            step_type => format!("{:?}", step_type),
+        }
+    }
+
+    /// Name of a `Custom` step type
+    async fn custom_type(&self) -> Option<&str> {
+        match &self.inner.step_type {
+            model::FlowStepType::Custom(name) => Some(name.as_str()),
+            _ => None,
+        }
     }
 
     async fn condition(&self) -> Option<&str> {
@@ -358,6 +296,11 @@ impl FlowWrapper {
         serde_json::to_value(&self.inner.trigger).unwrap_or_default()
     }
 
+    /// Structured form of `trigger`, accepted as-is by the `triggerSpec` input
+    async fn trigger_spec(&self) -> FlowTriggerSpec {
+        FlowTriggerSpec::from(&self.inner.trigger)
+    }
+
     async fn steps(&self) -> Vec<FlowStepWrapper> {
         self.inner.steps.iter()
             .map(|s| FlowStepWrapper { inner: s.clone() })
@@ -482,762 +425,6 @@ impl ValidationWrapper {
     }
 }
 
-/// Optimized query root
-pub struct OptimizedQuery;
-
-#[Object]
-impl OptimizedQuery {
-    /// Get all models with minimal conversions
-    async fn models(&self, ctx: &Context<'_>) -> Result<Vec<ModelWrapper>> {
-        let state = ctx.data::<AppState>()?;
-        let models = state.services.model_service.get_models().await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get models: {}", e)))?;
-        
-        Ok(models.into_iter().map(|m| ModelWrapper { inner: m }).collect())
-    }
-
-    /// Get a specific model by ID
-    async fn model(&self, ctx: &Context<'_>, id: String) -> Result<Option<ModelWrapper>> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let model = state.services.model_service.get_model(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?;
-        Ok(model.map(|m| ModelWrapper { inner: m }))
-    }
-
-    /// Get entities for a specific model with optimized loading
-    async fn entities(&self, ctx: &Context<'_>, model_id: String) -> Result<Vec<EntityWrapper>> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = model_id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let entities = state.services.model_service.get_entities(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get entities: {}", e)))?;
-        Ok(entities.into_iter().map(|e| EntityWrapper { inner: e }).collect())
-    }
-
-    /// Search models with optimized query
-    async fn search_models(&self, ctx: &Context<'_>, query: String) -> Result<Vec<ModelWrapper>> {
-        let state = ctx.data::<AppState>()?;
-        let models = state.services.model_service.search_models(query).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to search models: {}", e)))?;
-        Ok(models.into_iter().map(|m| ModelWrapper { inner: m }).collect())
-    }
-
-    /// Get a specific layout by ID
-    async fn layout(&self, ctx: &Context<'_>, id: String) -> Result<Option<LayoutWrapper>> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        
-        // Search through all models to find the layout
-        let models = state.services.model_service.get_models().await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get models: {}", e)))?;
-        
-        for model in models {
-            if let Some(layout) = model.layouts.iter().find(|l| l.id == uuid) {
-                return Ok(Some(LayoutWrapper { inner: layout.clone() }));
-            }
-        }
-        Ok(None)
-    }
-
-    /// Get model statistics
-    async fn model_stats(&self, ctx: &Context<'_>, id: String) -> Result<ModelStats> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let model = state.services.model_service.get_model(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?
-            .ok_or_else(|| async_graphql::Error::new("Model not found"))?;
-        
-        Ok(ModelStats {
-            entity_count: model.entities.len() as i32,
-            relationship_count: model.relationships.len() as i32,
-            flow_count: model.flows.len() as i32,
-            layout_count: model.layouts.len() as i32,
-            validation_count: model.validations.len() as i32,
-        })
-    }
-
-    /// Verify a model for configuration mismatches
-    async fn verify_model(&self, ctx: &Context<'_>, model_id: String) -> Result<ConfigurationReport> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = model_id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        
-        // Get the model first
-        let model = state.services.model_service.get_model(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?;
-            
-        if let Some(model) = model {
-            let report = state.services.model_service.verify_model(&model).await
-                .map_err(|e| async_graphql::Error::new(format!("Failed to verify model: {}", e)))?;
-            Ok(ConfigurationReport::from(report))
-        } else {
-            Err(async_graphql::Error::new("Model not found"))
-        }
-    }
-
-    /// Get remediation strategies for a specific configuration error
-    async fn get_remediation_strategies(&self, ctx: &Context<'_>, input: crate::server::graphql::schema::GetRemediationStrategiesInput) -> Result<Vec<crate::server::graphql::schema::RemediationStrategy>> {
-        let state = ctx.data::<AppState>()?;
-        let model_uuid = input.model_id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid model ID format"))?;
-        
-        let strategies = state.services.model_service.get_remediation_strategies_by_type(
-            model_uuid,
-            &input.error_type,
-            &input.error_parameters
-        ).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get remediation strategies: {}", e)))?;
-        
-        Ok(strategies.into_iter().map(crate::server::graphql::schema::RemediationStrategy::from).collect())
-    }
-}
-
-/// Optimized mutation root
-pub struct OptimizedMutation;
-
-#[Object]
-impl OptimizedMutation {
-    /// Create a new model
-    async fn create_model(
-        &self, 
-        ctx: &Context<'_>, 
-        input: CreateModelInput
-    ) -> Result<ModelWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let service_input = crate::services::model::CreateModelInput {
-            name: input.name,
-            description: input.description,
-            config: None,
-        };
-        let model = state.services.model_service.create_model(service_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to create model: {}", e)))?;
-        Ok(ModelWrapper { inner: model })
-    }
-
-    /// Update an existing model
-    async fn update_model(
-        &self, 
-        ctx: &Context<'_>, 
-        id: String, 
-        input: UpdateModelInput
-    ) -> Result<ModelWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let service_input = crate::services::model::UpdateModelInput {
-            name: input.name,
-            description: input.description,
-            config: None,
-        };
-        let model = state.services.model_service.update_model(uuid, service_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to update model: {}", e)))?;
-        Ok(ModelWrapper { inner: model })
-    }
-
-    /// Update model configuration
-    async fn update_model_config(
-        &self,
-        ctx: &Context<'_>,
-        id: String,
-        input: UpdateModelConfigInput
-    ) -> Result<ModelWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        
-        // Get the existing model
-        let mut model = state.services.model_service.get_model(uuid.clone()).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get model: {}", e)))?
-            .ok_or_else(|| async_graphql::Error::new("Model not found"))?;
-        
-        // Update the config
-        if let Some(start_page_layout_id) = input.start_page_layout_id {
-            if start_page_layout_id.is_empty() {
-                // Remove the start page layout if empty string is provided
-                model.config.custom.remove("startPageLayoutId");
-            } else {
-                model.config.custom.insert(
-                    "startPageLayoutId".to_string(),
-                    serde_json::Value::String(start_page_layout_id)
-                );
-            }
-        }
-        
-        // Save the updated model
-        let service_input = crate::services::model::UpdateModelInput {
-            name: None,
-            description: None,
-            config: Some(model.config.clone()),
-        };
-        
-        let updated_model = state.services.model_service.update_model(uuid, service_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to update model config: {}", e)))?;
-        
-        Ok(ModelWrapper { inner: updated_model })
-    }
-    
-    /// Delete a model
-    async fn delete_model(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let result = state.services.model_service.delete_model(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to delete model: {}", e)))?;
-        Ok(result)
-    }
-
-    /// Import a model from JSON
-    async fn import_model(&self, ctx: &Context<'_>, data: String) -> Result<ModelWrapper> {
-        let state = ctx.data::<AppState>()?;
-        
-        let model = state.services.model_service.import_model(data).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to import model: {}", e)))?;
-        Ok(ModelWrapper { inner: model })
-    }
-
-    /// Replace an existing model with imported JSON
-    async fn replace_model(&self, ctx: &Context<'_>, id: String, data: String) -> Result<ModelWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let model_id = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid model ID format"))?;
-        
-        let model = state.services.model_service.replace_model(model_id, data).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to replace model: {}", e)))?;
-        Ok(ModelWrapper { inner: model })
-    }
-
-    /// Create a new entity
-    async fn create_entity(
-        &self,
-        ctx: &Context<'_>,
-        input: CreateEntityInput
-    ) -> Result<EntityWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let entity_input = crate::services::model::CreateEntityInput {
-            model_id: input.model_id,
-            name: input.name,
-            display_name: input.display_name,
-            description: input.description,
-            entity_type: model::EntityType::Data, // Always use Data type
-            fields: vec![],
-            ui_config: None,
-            behavior: None,
-        };
-        
-        let entity = state.services.model_service.create_entity(entity_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to create entity: {}", e)))?;
-        Ok(EntityWrapper { inner: entity })
-    }
-
-    /// Update an existing entity
-    async fn update_entity(
-        &self,
-        ctx: &Context<'_>,
-        id: String,
-        input: UpdateEntityInput
-    ) -> Result<EntityWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        
-        // Convert fields if provided
-        let fields = if let Some(input_fields) = input.fields {
-            Some(input_fields.into_iter().map(|f| {
-                use crate::model::types::EntityField;
-                
-                EntityField {
-                    id: f.id.and_then(|id| id.parse::<Uuid>().ok()).unwrap_or_else(Uuid::new_v4),
-                    name: f.name,
-                    display_name: f.display_name,
-                    field_type: parse_field_type(&f.field_type),
-                    required: f.required,
-                    default_value: f.default_value,
-                    validation: f.validation.and_then(|v| serde_json::from_value(v).ok()).unwrap_or_default(),
-                    ui_config: f.ui_config.and_then(|v| serde_json::from_value(v).ok()).unwrap_or_default(),
-                }
-            }).collect())
-        } else {
-            None
-        };
-        
-        let entity_input = crate::services::model::UpdateEntityInput {
-            name: input.name,
-            display_name: input.display_name,
-            description: input.description,
-            entity_type: None,
-            fields,
-            ui_config: None,
-            behavior: None,
-        };
-        
-        let entity = state.services.model_service.update_entity(uuid, entity_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to update entity: {}", e)))?;
-        Ok(EntityWrapper { inner: entity })
-    }
-
-    /// Delete an entity
-    async fn delete_entity(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let result = state.services.model_service.delete_entity(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to delete entity: {}", e)))?;
-        Ok(result)
-    }
-
-    /// Batch operations for performance
-    async fn batch_create_entities(
-        &self,
-        ctx: &Context<'_>,
-        model_id: String,
-        entities: Vec<CreateEntityInput>
-    ) -> Result<BatchResult> {
-        let state = ctx.data::<AppState>()?;
-        
-        let mut created = 0;
-        let mut errors = Vec::new();
-        
-        for (index, input) in entities.into_iter().enumerate() {
-            match self.create_entity_internal(&state, model_id.clone(), input).await {
-                Ok(_) => created += 1,
-                Err(e) => errors.push(BatchError {
-                    index: index as i32,
-                    message: e.message,
-                }),
-            }
-        }
-        
-        Ok(BatchResult { 
-            success_count: created,
-            error_count: errors.len() as i32,
-            errors,
-        })
-    }
-    
-    /// Create a new layout
-    async fn create_layout(
-        &self,
-        ctx: &Context<'_>,
-        input: CreateLayoutInput
-    ) -> Result<LayoutWrapper> {
-        let state = ctx.data::<AppState>()?;
-        
-        let service_input = crate::services::model::CreateLayoutInput {
-            model_id: input.model_id,
-            name: input.name,
-            layout_type: parse_layout_type(&input.layout_type),
-            target_entities: input.target_entities,
-            components: input.components.into_iter().map(|c| {
-                crate::services::model::CreateLayoutComponentInput {
-                    component_type: c.component_type,
-                    position: model::ComponentPosition {
-                        row: c.position.row as u32,
-                        column: c.position.column as u32,
-                        width: c.position.width as u32,
-                        height: c.position.height as u32,
-                    },
-                    properties: c.properties,
-                    styling: c.styling,
-                    metadata: None,
-                }
-            }).collect(),
-            responsive: input.responsive,
-        };
-        
-        let layout = state.services.model_service.create_layout(service_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to create layout: {}", e)))?;
-            
-        Ok(LayoutWrapper { inner: layout })
-    }
-    
-    /// Update an existing layout
-    async fn update_layout(
-        &self,
-        ctx: &Context<'_>,
-        id: String,
-        input: UpdateLayoutInput
-    ) -> Result<LayoutWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-            
-        // Use the new update_layout method
-        let service_input = crate::services::model::UpdateLayoutInput {
-            name: Some(input.name),
-            layout_type: Some(parse_layout_type(&input.layout_type)),
-            target_entities: Some(input.target_entities),
-            components: Some(input.components.into_iter().map(|c| {
-                crate::services::model::CreateLayoutComponentInput {
-                    component_type: c.component_type,
-                    position: model::ComponentPosition {
-                        row: c.position.row as u32,
-                        column: c.position.column as u32,
-                        width: c.position.width as u32,
-                        height: c.position.height as u32,
-                    },
-                    properties: c.properties,
-                    styling: c.styling,
-                    metadata: None,
-                }
-            }).collect()),
-            responsive: input.responsive,
-        };
-        
-        let layout = state.services.model_service.update_layout(uuid, service_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to update layout: {}", e)))?;
-            
-        Ok(LayoutWrapper { inner: layout })
-    }
-    
-    /// Delete a layout
-    async fn delete_layout(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let result = state.services.model_service.delete_layout(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to delete layout: {}", e)))?;
-        Ok(result)
-    }
-    
-    /// Create a new relationship
-    async fn create_relationship(
-        &self,
-        ctx: &Context<'_>,
-        input: CreateRelationshipInput
-    ) -> Result<RelationshipWrapper> {
-        let state = ctx.data::<AppState>()?;
-        
-        let service_input = crate::services::model::CreateRelationshipInput {
-            model_id: input.model_id,
-            name: input.name,
-            relationship_type: parse_relationship_type(&input.relationship_type),
-            from_entity: input.from_entity,
-            to_entity: input.to_entity,
-            from_field: input.from_field,
-            to_field: input.to_field,
-            cascade: parse_cascade_action(&input.cascade),
-            ui_config: input.ui_config.and_then(|v| serde_json::from_value(v).ok()),
-        };
-        
-        let relationship = state.services.model_service.create_relationship(service_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to create relationship: {}", e)))?;
-            
-        Ok(RelationshipWrapper { inner: relationship })
-    }
-    
-    /// Update an existing relationship
-    async fn update_relationship(
-        &self,
-        ctx: &Context<'_>,
-        id: String,
-        input: UpdateRelationshipInput
-    ) -> Result<RelationshipWrapper> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-            
-        // For now, we'll implement update by modifying the model directly
-        // TODO: Add a dedicated update_relationship method to ModelService
-        let models = state.services.model_service.get_models().await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get models: {}", e)))?;
-            
-        for model in models {
-            if let Some(_relationship) = model.relationships.iter().find(|r| r.id == uuid) {
-                // Found the relationship, update it
-                let mut updated_model = model.clone();
-                for rel in &mut updated_model.relationships {
-                    if rel.id == uuid {
-                        rel.name = input.name.clone();
-                        rel.relationship_type = parse_relationship_type(&input.relationship_type);
-                        rel.from_entity = input.from_entity.parse()
-                            .map_err(|_| async_graphql::Error::new("Invalid from_entity UUID"))?;
-                        rel.to_entity = input.to_entity.parse()
-                            .map_err(|_| async_graphql::Error::new("Invalid to_entity UUID"))?;
-                        rel.from_field = input.from_field.parse()
-                            .map_err(|_| async_graphql::Error::new("Invalid from_field UUID"))?;
-                        rel.to_field = input.to_field.parse()
-                            .map_err(|_| async_graphql::Error::new("Invalid to_field UUID"))?;
-                        rel.cascade = parse_cascade_action(&input.cascade);
-                        if let Some(ref ui_config_json) = input.ui_config {
-                            if let Ok(ui_config) = serde_json::from_value(ui_config_json.clone()) {
-                                rel.ui_config = ui_config;
-                            }
-                        }
-                        break;
-                    }
-                }
-                
-                // Save the updated model
-                let update_input = crate::services::model::UpdateModelInput {
-                    name: Some(updated_model.name.clone()),
-                    description: updated_model.description.clone(),
-                    config: None,
-                };
-                
-                state.services.model_service.update_model(model.id, update_input).await
-                    .map_err(|e| async_graphql::Error::new(format!("Failed to update model: {}", e)))?;
-                
-                // Return the updated relationship
-                if let Some(updated_rel) = updated_model.relationships.iter().find(|r| r.id == uuid) {
-                    return Ok(RelationshipWrapper { inner: updated_rel.clone() });
-                }
-            }
-        }
-        
-        Err(async_graphql::Error::new("Relationship not found"))
-    }
-    
-    /// Delete a relationship
-    async fn delete_relationship(&self, ctx: &Context<'_>, id: String) -> Result<bool> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-            
-        // TODO: Implement relationship deletion
-        let models = state.services.model_service.get_models().await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to get models: {}", e)))?;
-            
-        for model in models {
-            if model.relationships.iter().any(|r| r.id == uuid) {
-                // Found the model containing the relationship
-                let mut updated_model = model.clone();
-                updated_model.relationships.retain(|r| r.id != uuid);
-                
-                // Save the updated model
-                let update_input = crate::services::model::UpdateModelInput {
-                    name: Some(updated_model.name.clone()),
-                    description: updated_model.description.clone(),
-                    config: None,
-                };
-                
-                state.services.model_service.update_model(model.id, update_input).await
-                    .map_err(|e| async_graphql::Error::new(format!("Failed to update model: {}", e)))?;
-                    
-                return Ok(true);
-            }
-        }
-        
-        Ok(false)
-    }
-
-    /// Validate a model
-    async fn validate_model(&self, ctx: &Context<'_>, id: String) -> Result<ValidationResult> {
-        let state = ctx.data::<AppState>()?;
-        let uuid = id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid UUID format"))?;
-        let result = state.services.model_service.validate_model(uuid).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to validate model: {}", e)))?;
-        Ok(ValidationResult {
-            valid: result.valid,
-            errors: result.errors.into_iter().map(|e| ValidationMessage {
-                message: e.message,
-                field: e.field,
-                code: e.code,
-            }).collect(),
-            warnings: result.warnings.into_iter().map(|w| ValidationMessage {
-                message: w.message,
-                field: w.field,
-                code: w.code,
-            }).collect(),
-        })
-    }
-
-    /// Load sample data for a model
-    async fn load_sample_data(&self, ctx: &Context<'_>, model_id: String) -> Result<u64> {
-        let state = ctx.data::<AppState>()?;
-        let count = state.services.app_database_service.load_sample_data(&model_id).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to load sample data: {}", e)))?;
-        Ok(count)
-    }
-
-    /// Execute auto-remediation for a configuration error
-    async fn execute_auto_remediation(&self, ctx: &Context<'_>, input: crate::server::graphql::schema::ExecuteRemediationInput) -> Result<crate::server::graphql::schema::RemediationResult> {
-        let state = ctx.data::<AppState>()?;
-        let model_uuid = input.model_id.parse::<Uuid>()
-            .map_err(|_| async_graphql::Error::new("Invalid model ID format"))?;
-        
-        // Convert GraphQL parameters to HashMap
-        let parameters: std::collections::HashMap<String, serde_json::Value> = input.parameters
-            .into_iter()
-            .map(|p| (p.name, p.value))
-            .collect();
-        
-        let result = state.services.model_service.execute_auto_remediation_by_type(
-            model_uuid,
-            &input.error_type,
-            &input.error_parameters,
-            &input.strategy_type,
-            parameters
-        ).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to execute auto-remediation: {}", e)))?;
-        
-        Ok(crate::server::graphql::schema::RemediationResult::from(result))
-    }
-}
-
-impl OptimizedMutation {
-    /// Internal helper for entity creation
-    async fn create_entity_internal(
-        &self,
-        state: &AppState,
-        model_id: String,
-        input: CreateEntityInput
-    ) -> Result<()> {
-        let entity_input = crate::services::model::CreateEntityInput {
-            model_id: model_id,
-            name: input.name,
-            display_name: input.display_name,
-            description: input.description,
-            entity_type: model::EntityType::Data, // Always use Data type
-            fields: vec![],
-            ui_config: None,
-            behavior: None,
-        };
-        
-        state.services.model_service.create_entity(entity_input).await
-            .map_err(|e| async_graphql::Error::new(format!("Failed to create entity: {}", e)))?;
-        Ok(())
-    }
-}
-
-/// Optimized subscription
-pub struct OptimizedSubscription;
-
-#[async_graphql::Subscription]
-impl OptimizedSubscription {
-    /// Placeholder subscription
-    async fn placeholder(&self) -> impl futures_util::Stream<Item = bool> {
-        futures_util::stream::once(async { true })
-    }
-}
-
-// Input types for mutations
-#[derive(InputObject)]
-pub struct CreateModelInput {
-    pub name: String,
-    pub description: Option<String>,
-}
-
-#[derive(InputObject)]
-pub struct UpdateModelInput {
-    pub name: Option<String>,
-    pub description: Option<String>,
-}
-
-#[derive(InputObject)]
-pub struct UpdateModelConfigInput {
-    pub start_page_layout_id: Option<String>,
-}
-
-#[derive(InputObject)]
-pub struct CreateEntityInput {
-    pub model_id: String,
-    pub name: String,
-    pub display_name: String,
-    pub description: Option<String>,
-    pub fields: Vec<CreateFieldInput>,
-    pub ui_config: Option<serde_json::Value>,
-    pub behavior: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct UpdateEntityInput {
-    pub name: Option<String>,
-    pub display_name: Option<String>,
-    pub description: Option<String>,
-    pub fields: Option<Vec<UpdateFieldInput>>,
-    pub ui_config: Option<serde_json::Value>,
-    pub behavior: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct CreateFieldInput {
-    pub name: String,
-    pub display_name: String,
-    pub field_type: String,
-    pub required: bool,
-    pub default_value: Option<serde_json::Value>,
-    pub validation: Option<serde_json::Value>,
-    pub ui_config: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct UpdateFieldInput {
-    pub id: Option<String>,
-    pub name: String,
-    pub display_name: String,
-    pub field_type: String,
-    pub required: bool,
-    pub default_value: Option<serde_json::Value>,
-    pub validation: Option<serde_json::Value>,
-    pub ui_config: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct CreateLayoutInput {
-    pub model_id: String,
-    pub name: String,
-    pub layout_type: String,
-    pub target_entities: Vec<String>,
-    pub components: Vec<LayoutComponentInput>,
-    pub responsive: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct UpdateLayoutInput {
-    pub model_id: String,
-    pub name: String,
-    pub layout_type: String,
-    pub target_entities: Vec<String>,
-    pub components: Vec<LayoutComponentInput>,
-    pub responsive: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct LayoutComponentInput {
-    pub component_type: String,
-    pub position: ComponentPositionInput,
-    pub properties: serde_json::Value,
-    pub styling: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct ComponentPositionInput {
-    pub row: i32,
-    pub column: i32,
-    pub width: i32,
-    pub height: i32,
-}
-
-#[derive(InputObject)]
-pub struct CreateRelationshipInput {
-    pub model_id: String,
-    pub name: String,
-    pub relationship_type: String,
-    pub from_entity: String,
-    pub to_entity: String,
-    pub from_field: String,
-    pub to_field: String,
-    pub cascade: String,
-    pub ui_config: Option<serde_json::Value>,
-}
-
-#[derive(InputObject)]
-pub struct UpdateRelationshipInput {
-    pub name: String,
-    pub relationship_type: String,
-    pub from_entity: String,
-    pub to_entity: String,
-    pub from_field: String,
-    pub to_field: String,
-    pub cascade: String,
-    pub ui_config: Option<serde_json::Value>,
-}
-
 // Output types
 #[derive(SimpleObject)]
 pub struct ModelStats {
@@ -1260,18 +447,3 @@ pub struct BatchError {
     pub index: i32,
     pub message: String,
 }
-
-/// Create optimized GraphQL schema
-pub fn create_optimized_schema() -> async_graphql::Schema<OptimizedQuery, OptimizedMutation, OptimizedSubscription> {
-    async_graphql::Schema::build(OptimizedQuery, OptimizedMutation, OptimizedSubscription)
-        .finish()
-}
-
-/// Performance comparison metrics
-#[derive(SimpleObject)]
-pub struct PerformanceMetrics {
-    pub conversion_overhead_ms: f64,
-    pub memory_usage_bytes: i64,
-    pub graphql_parse_time_ms: f64,
-    pub total_request_time_ms: f64,
-}
\ No newline at end of file
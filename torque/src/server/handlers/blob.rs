@@ -0,0 +1,162 @@
+use axum::{
+    body::Body,
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::Response,
+    Json,
+};
+use serde_json::{json, Value};
+
+use crate::server::AppState;
+use crate::services::blob_store::{BlobError, BlobInfo, BlobUpload};
+
+/// Map blob errors onto HTTP status codes
+fn blob_error_status(err: &BlobError) -> StatusCode {
+    match err {
+        BlobError::NotFound(_) => StatusCode::NOT_FOUND,
+        BlobError::TooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        BlobError::ContentTypeNotAllowed(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        BlobError::InvalidKey(_) | BlobError::InvalidReference(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Map errors from checking an upload's owner onto HTTP status codes
+fn owner_error_status(err: &crate::Error) -> StatusCode {
+    match err {
+        crate::Error::ModelNotFound(_) | crate::Error::NotFound(_) => StatusCode::NOT_FOUND,
+        crate::Error::Validation(_) | crate::Error::InvalidInput(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// POST /api/v1/models/{model_id}/app-database/blobs
+/// Upload a binary attachment as multipart/form-data.
+/// Fields: `file` (required), `entity_id` and `field_name` (optional owner).
+/// Uploads that no record refers to are removed after `storage.unreferenced_blob_minutes`.
+pub async fn upload_blob(
+    Path(model_id): Path<String>,
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<BlobInfo>, StatusCode> {
+    model_id.parse::<crate::common::Uuid>()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let mut entity_id = None;
+    let mut field_name = None;
+    let mut file = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::warn!("Invalid multipart upload for model {}: {}", model_id, e);
+        StatusCode::BAD_REQUEST
+    })? {
+        match field.name().unwrap_or_default() {
+            "entity_id" => {
+                entity_id = Some(field.text().await.map_err(|_| StatusCode::BAD_REQUEST)?);
+            }
+            "field_name" => {
+                field_name = Some(field.text().await.map_err(|_| StatusCode::BAD_REQUEST)?);
+            }
+            "file" => {
+                let filename = field.file_name().unwrap_or("upload.bin").to_string();
+                let content_type = field.content_type()
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                let data = field.bytes().await.map_err(|e| {
+                    tracing::warn!("Failed to read upload body for model {}: {}", model_id, e);
+                    StatusCode::PAYLOAD_TOO_LARGE
+                })?;
+                file = Some((filename, content_type, data.to_vec()));
+            }
+            _ => {
+                // Ignore unknown form fields
+            }
+        }
+    }
+
+    let (filename, content_type, data) = file.ok_or(StatusCode::BAD_REQUEST)?;
+
+    state.services.app_database_service
+        .check_blob_owner(&model_id, entity_id.as_deref(), field_name.as_deref())
+        .await
+        .map_err(|e| {
+            tracing::warn!("Rejected blob upload for model {}: {}", model_id, e);
+            owner_error_status(&e)
+        })?;
+
+    let upload = BlobUpload {
+        model_id: model_id.clone(),
+        entity_id,
+        field_name,
+        filename,
+        content_type,
+        data,
+    };
+
+    let info = state.services.blob_service
+        .upload(upload)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to upload blob for model {}: {}", model_id, e);
+            blob_error_status(&e)
+        })?;
+
+    tracing::info!("Stored blob {} ({} bytes) for model {}", info.id, info.size_bytes, model_id);
+    Ok(Json(info))
+}
+
+/// GET /api/v1/models/{model_id}/app-database/blobs/{blob_id}
+/// Download the raw content of a blob
+pub async fn download_blob(
+    Path((model_id, blob_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<Response, StatusCode> {
+    let (info, data) = state.services.blob_service
+        .download(&model_id, &blob_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to download blob {} for model {}: {}", blob_id, model_id, e);
+            blob_error_status(&e)
+        })?;
+
+    let disposition = format!("attachment; filename=\"{}\"", info.filename.replace('"', ""));
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, info.content_type)
+        .header(header::CONTENT_LENGTH, data.len())
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .body(Body::from(data))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// GET /api/v1/models/{model_id}/app-database/blobs/{blob_id}/metadata
+/// Get metadata for a blob without downloading it
+pub async fn get_blob_metadata(
+    Path((model_id, blob_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<BlobInfo>, StatusCode> {
+    let info = state.services.blob_service
+        .get_info(&model_id, &blob_id)
+        .await
+        .map_err(|e| blob_error_status(&e))?;
+
+    Ok(Json(info))
+}
+
+/// DELETE /api/v1/models/{model_id}/app-database/blobs/{blob_id}
+/// Delete a blob and its stored content
+pub async fn delete_blob(
+    Path((model_id, blob_id)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, StatusCode> {
+    state.services.blob_service
+        .delete(&model_id, &blob_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to delete blob {} for model {}: {}", blob_id, model_id, e);
+            blob_error_status(&e)
+        })?;
+
+    Ok(Json(json!({ "id": blob_id, "deleted": true })))
+}
@@ -1,11 +1,15 @@
 use crate::server::AppState;
-use crate::server::graphql::{create_schema, GraphQLConfig, GraphQLSchema};
+use crate::common::Uuid;
 use axum::{
-    extract::State,
+    extract::{Path, RawQuery, State, WebSocketUpgrade},
     http::StatusCode,
-    response::{Html, Json},
+    response::{Html, Json, Response},
 };
-use async_graphql::{http::GraphQLPlaygroundConfig, Request};
+use async_graphql::{
+    http::{GraphQLPlaygroundConfig, ALL_WEBSOCKET_PROTOCOLS},
+    Data, Request,
+};
+use async_graphql_axum::{GraphQLProtocol, GraphQLWebSocket};
 use serde_json::{json, Value};
 
 /// GraphQL endpoint handler
@@ -32,13 +36,36 @@ pub async fn graphql_handler(
         }
     };
     
-    let config = GraphQLConfig::default();
-    let schema = create_schema(&config);
-    
-    let response = match schema {
-        GraphQLSchema::Standard(s) => s.execute(request.data(state)).await,
-        GraphQLSchema::Optimized(s) => s.execute(request.data(state)).await,
+    execute(state, request).await
+}
+
+/// GraphQL over GET, with the request in the query string. Together with persisted queries
+/// this lets clients send just a query hash.
+pub async fn graphql_get_handler(
+    State(state): State<AppState>,
+    RawQuery(query): RawQuery,
+) -> Result<Json<Value>, StatusCode> {
+    let request = match async_graphql::http::parse_query_string(query.as_deref().unwrap_or_default()) {
+        Ok(request) => request,
+        Err(e) => {
+            return Ok(Json(json!({
+                "data": null,
+                "errors": [{
+                    "message": format!("Invalid GraphQL request: {}", e),
+                    "extensions": {
+                        "code": "PARSE_ERROR"
+                    }
+                }]
+            })));
+        }
     };
+
+    execute(state, request).await
+}
+
+async fn execute(state: AppState, request: Request) -> Result<Json<Value>, StatusCode> {
+    let schema = state.graphql_schema.clone();
+    let response = schema.execute(request.data(state)).await;
     
     // Convert the response to JSON
     let json_response: Value = serde_json::to_value(response)
@@ -47,12 +74,68 @@ pub async fn graphql_handler(
     Ok(Json(json_response))
 }
 
+/// GraphQL subscriptions over WebSocket, speaking `graphql-transport-ws` or the legacy `graphql-ws`
+pub async fn graphql_ws_handler(
+    State(state): State<AppState>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let schema = state.graphql_schema.clone();
+
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| async move {
+            let mut data = Data::default();
+            data.insert(state);
+            GraphQLWebSocket::new(stream, schema, protocol).with_data(data).serve().await
+        })
+}
+
+/// POST /api/v1/models/{model_id}/graphql
+/// GraphQL API over a model's application data, generated from its entities and relationships
+pub async fn model_graphql_handler(
+    Path(model_id): Path<String>,
+    State(state): State<AppState>,
+    body: String,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let error = |status: StatusCode, message: String, code: &str| {
+        (status, Json(json!({
+            "data": null,
+            "errors": [{ "message": message, "extensions": { "code": code } }]
+        })))
+    };
+
+    let request: Request = serde_json::from_str(&body)
+        .map_err(|e| error(StatusCode::BAD_REQUEST, format!("Invalid GraphQL request: {}", e), "PARSE_ERROR"))?;
+
+    let not_found = || error(StatusCode::NOT_FOUND, format!("Model '{}' not found", model_id), "NOT_FOUND");
+    let id = Uuid::parse(&model_id).map_err(|_| not_found())?;
+    let model = state.services.model_service.get_runtime_model(id, None).await
+        .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string(), "INTERNAL_ERROR"))?
+        .ok_or_else(not_found)?;
+
+    let response = crate::server::graphql::dynamic::execute(&state, &model, request).await
+        .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string(), "INTERNAL_ERROR"))?;
+
+    let json_response: Value = serde_json::to_value(response)
+        .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string(), "INTERNAL_ERROR"))?;
+    Ok(Json(json_response))
+}
+
+/// GET /api/v1/models/{model_id}/graphql
+/// Playground for a model's generated GraphQL API
+pub async fn model_playground(Path(model_id): Path<String>) -> Html<String> {
+    Html(async_graphql::http::playground_source(
+        GraphQLPlaygroundConfig::new(&format!("/api/v1/models/{}/graphql", model_id))
+    ))
+}
+
 /// GraphQL Playground handler
 pub async fn playground() -> Html<String> {
     Html(
         async_graphql::http::playground_source(
             GraphQLPlaygroundConfig::new("/graphql")
-                .subscription_endpoint("/ws")
+                .subscription_endpoint("/graphql/ws")
         )
     )
 }
@@ -1,10 +1,12 @@
 pub mod health;
-pub mod entity;
+pub mod rest;
 pub mod graphql;
 pub mod jsonrpc;
 pub mod frontend;
 pub mod websocket;
 pub mod app_database;
+pub mod blob;
+pub mod schema;
 
 // Re-export common types
 pub use health::*;
\ No newline at end of file
@@ -0,0 +1,319 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use crate::common::Uuid;
+use crate::error::Error;
+use crate::model::json_schema::find_entity;
+use crate::model::openapi::model_openapi;
+use crate::model::types::{FieldType, ModelEntity, TorqueModel};
+use crate::server::AppState;
+use crate::services::app_database::{entity_record, EntityFilter, EntityListQuery, FilterOp};
+
+/// Error body returned by the generated REST API: `{"error": "..."}`
+#[derive(Debug)]
+pub struct RestError(StatusCode, String);
+
+impl RestError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self(StatusCode::BAD_REQUEST, message.into())
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self(StatusCode::NOT_FOUND, message.into())
+    }
+}
+
+impl From<Error> for RestError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Validation(message) => Self(StatusCode::UNPROCESSABLE_ENTITY, message),
+            Error::NotFound(message) | Error::EntityNotFound(message) | Error::ModelNotFound(message) => {
+                Self(StatusCode::NOT_FOUND, message)
+            }
+            Error::InvalidInput(message) => Self(StatusCode::BAD_REQUEST, message),
+            other => {
+                tracing::error!("REST API request failed: {}", other);
+                Self(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            }
+        }
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+/// Resolve a model by ID or name to the version runtime data is validated against
+async fn resolve_model(state: &AppState, model: &str) -> Result<TorqueModel, RestError> {
+    let model_service = &state.services.model_service;
+
+    let model_id = match model.parse::<Uuid>() {
+        Ok(id) => id,
+        Err(_) => model_id_by_name(&model_service.get_models().await?, model)?,
+    };
+
+    model_service.get_runtime_model(model_id, None).await?
+        .ok_or_else(|| RestError::not_found(format!("Model '{}' not found", model)))
+}
+
+/// ID of the only model whose name (or its slug) is `name`; a name shared by several models must be
+/// replaced by the model ID
+fn model_id_by_name(models: &[TorqueModel], name: &str) -> Result<Uuid, RestError> {
+    let matches: Vec<&TorqueModel> = models.iter()
+        .filter(|m| m.name == name || crate::model::templates::slugify(&m.name) == name)
+        .collect();
+    match matches.as_slice() {
+        [] => Err(RestError::not_found(format!("Model '{}' not found", name))),
+        [model] => Ok(model.id.clone()),
+        _ => Err(RestError::bad_request(format!(
+            "Model name '{}' matches {} models; use the model ID instead",
+            name,
+            matches.len()
+        ))),
+    }
+}
+
+fn resolve_entity<'a>(model: &'a TorqueModel, entity: &str) -> Result<&'a ModelEntity, RestError> {
+    find_entity(model, entity)
+        .ok_or_else(|| RestError::not_found(format!("Model has no entity '{}'", entity)))
+}
+
+/// Load a record and check it belongs to the entity in the path
+async fn load_record(
+    state: &AppState,
+    model: &TorqueModel,
+    entity: &ModelEntity,
+    id: &str,
+) -> Result<crate::database::entities::app_entities::Model, RestError> {
+    state.services.app_database_service
+        .get_entity(model.id.as_str(), id)
+        .await?
+        .filter(|record| record.entity_type == entity.name)
+        .ok_or_else(|| RestError::not_found(format!("{} record '{}' not found", entity.name, id)))
+}
+
+/// Build a list query from `field=value`, `field[op]=value`, `sort`, `limit` and `offset` parameters.
+/// `field[in]` takes a comma-separated list of values.
+fn parse_list_query(
+    entity: &ModelEntity,
+    params: &HashMap<String, String>,
+    default_page_size: u64,
+    max_page_size: u64,
+) -> Result<EntityListQuery, RestError> {
+    let mut query = EntityListQuery {
+        limit: default_page_size,
+        ..Default::default()
+    };
+
+    for (key, raw) in params {
+        match key.as_str() {
+            "limit" => {
+                let limit: u64 = raw.parse()
+                    .map_err(|_| RestError::bad_request(format!("Invalid limit '{}'", raw)))?;
+                query.limit = limit.clamp(1, max_page_size);
+            }
+            "offset" => {
+                query.offset = raw.parse()
+                    .map_err(|_| RestError::bad_request(format!("Invalid offset '{}'", raw)))?;
+            }
+            "sort" => {
+                let (field, descending) = match raw.strip_prefix('-') {
+                    Some(field) => (field, true),
+                    None => (raw.as_str(), false),
+                };
+                field_type(entity, field)?;
+                query.sort_field = Some(field.to_string());
+                query.sort_descending = descending;
+            }
+            _ => {
+                let (field, op) = match key.split_once('[') {
+                    Some((field, op)) => {
+                        let op = op.strip_suffix(']')
+                            .and_then(FilterOp::parse)
+                            .ok_or_else(|| RestError::bad_request(format!("Invalid filter '{}'", key)))?;
+                        (field, op)
+                    }
+                    None => (key.as_str(), FilterOp::Eq),
+                };
+                let field_type = field_type(entity, field)?;
+                let value = match op {
+                    FilterOp::Contains => Value::String(raw.clone()),
+                    FilterOp::In => {
+                        Value::Array(raw.split(',').map(|v| typed_value(field_type, field, v)).collect::<Result<_, _>>()?)
+                    }
+                    _ => typed_value(field_type, field, raw)?,
+                };
+                query.filters.push(EntityFilter { field: field.to_string(), op, value });
+            }
+        }
+    }
+
+    Ok(query)
+}
+
+fn field_type<'a>(entity: &'a ModelEntity, field: &str) -> Result<&'a FieldType, RestError> {
+    entity.fields.iter()
+        .find(|f| f.name == field)
+        .map(|f| &f.field_type)
+        .ok_or_else(|| RestError::bad_request(format!("Entity '{}' has no field '{}'", entity.name, field)))
+}
+
+/// Convert a query string value to the JSON type stored for the field
+fn typed_value(field_type: &FieldType, field: &str, raw: &str) -> Result<Value, RestError> {
+    let invalid = || RestError::bad_request(format!("Invalid value '{}' for field '{}'", raw, field));
+    match field_type {
+        FieldType::Integer { .. } => raw.parse::<i64>().map(Value::from).map_err(|_| invalid()),
+        FieldType::Float { .. } => raw.parse::<f64>().map(Value::from).map_err(|_| invalid()),
+        FieldType::Boolean => raw.parse::<bool>().map(Value::from).map_err(|_| invalid()),
+        // Sample data stores references as integers, records created through the API as strings
+        FieldType::Reference { .. } => Ok(raw.parse::<i64>().map(Value::from).unwrap_or_else(|_| Value::from(raw))),
+        FieldType::Json | FieldType::Binary | FieldType::Array { .. } => {
+            Err(RestError::bad_request(format!("Field '{}' can't be filtered on", field)))
+        }
+        _ => Ok(Value::from(raw)),
+    }
+}
+
+/// GET /api/models/{model}/openapi.json
+/// OpenAPI 3.1 document describing the generated REST API of a model
+pub async fn get_openapi_document(
+    Path(model): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, RestError> {
+    let model = resolve_model(&state, &model).await?;
+    let base_path = format!("/api/models/{}", model.id);
+    Ok(Json(model_openapi(&model, &base_path)))
+}
+
+/// GET /api/models/{model}/{entity}
+/// List records with field filters, sorting and pagination
+pub async fn list_records(
+    Path((model, entity)): Path<(String, String)>,
+    Query(params): Query<HashMap<String, String>>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, RestError> {
+    let model = resolve_model(&state, &model).await?;
+    let entity = resolve_entity(&model, &entity)?;
+
+    let rest = &state.services.config.rest;
+    let query = parse_list_query(entity, &params, rest.default_page_size, rest.max_page_size)?;
+
+    let (records, total) = state.services.app_database_service
+        .query_entities(model.id.as_str(), &entity.name, &query)
+        .await?;
+
+    Ok(Json(json!({
+        "data": records,
+        "total": total,
+        "limit": query.limit,
+        "offset": query.offset,
+    })))
+}
+
+/// POST /api/models/{model}/{entity}
+pub async fn create_record(
+    Path((model, entity)): Path<(String, String)>,
+    State(state): State<AppState>,
+    Json(data): Json<Value>,
+) -> Result<(StatusCode, Json<Value>), RestError> {
+    let model = resolve_model(&state, &model).await?;
+    let entity = resolve_entity(&model, &entity)?;
+
+    let record = state.services.app_database_service
+        .create_entity(model.id.as_str(), &entity.name, data)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(entity_record(record))))
+}
+
+/// GET /api/models/{model}/{entity}/{id}
+pub async fn get_record(
+    Path((model, entity, id)): Path<(String, String, String)>,
+    State(state): State<AppState>,
+) -> Result<Json<Value>, RestError> {
+    let model = resolve_model(&state, &model).await?;
+    let entity = resolve_entity(&model, &entity)?;
+
+    let record = load_record(&state, &model, entity, &id).await?;
+    Ok(Json(entity_record(record)))
+}
+
+/// PATCH /api/models/{model}/{entity}/{id}
+/// Top-level fields in the body replace the stored values
+pub async fn update_record(
+    Path((model, entity, id)): Path<(String, String, String)>,
+    State(state): State<AppState>,
+    Json(changes): Json<Value>,
+) -> Result<Json<Value>, RestError> {
+    let Value::Object(changes) = changes else {
+        return Err(RestError::bad_request("Request body must be a JSON object"));
+    };
+
+    let model = resolve_model(&state, &model).await?;
+    let entity = resolve_entity(&model, &entity)?;
+
+    let updated = state.services.app_database_service
+        .patch_entity(model.id.as_str(), Some(&entity.name), &id, changes)
+        .await?;
+
+    Ok(Json(entity_record(updated)))
+}
+
+/// DELETE /api/models/{model}/{entity}/{id}
+pub async fn delete_record(
+    Path((model, entity, id)): Path<(String, String, String)>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, RestError> {
+    let model = resolve_model(&state, &model).await?;
+    let entity = resolve_entity(&model, &entity)?;
+    load_record(&state, &model, entity, &id).await?;
+
+    state.services.app_database_service
+        .delete_entity(model.id.as_str(), &id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::test_fixtures::{entity, model, string_field};
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_filters_on_unknown_fields_are_rejected() {
+        let customer = entity("customer", vec![string_field("name")]);
+
+        for key in ["nickname", "nickname[contains]", "nickname[in]", "nickname[gt]"] {
+            let error = parse_list_query(&customer, &params(&[(key, "a")]), 20, 100).unwrap_err();
+            assert_eq!(error.0, StatusCode::BAD_REQUEST, "{}", key);
+        }
+
+        let query = parse_list_query(&customer, &params(&[("name[contains]", "an")]), 20, 100).unwrap();
+        assert_eq!(query.filters[0].value, json!("an"));
+    }
+
+    #[test]
+    fn test_shared_model_names_are_ambiguous() {
+        let shop = model(vec![]);
+        let mut other = model(vec![]);
+        other.name = "Other".to_string();
+
+        assert_eq!(model_id_by_name(&[shop.clone(), other.clone()], "test").unwrap(), shop.id);
+        assert_eq!(model_id_by_name(&[shop.clone()], "Missing").unwrap_err().0, StatusCode::NOT_FOUND);
+
+        let error = model_id_by_name(&[shop, model(vec![])], "Test").unwrap_err();
+        assert_eq!(error.0, StatusCode::BAD_REQUEST);
+    }
+}
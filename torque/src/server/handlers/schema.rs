@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use crate::common::Uuid;
+
+use crate::model::json_schema::{entity_json_schema, find_entity};
+use crate::server::AppState;
+
+/// GET /api/v1/models/{model_id}/schemas/{entity}
+/// JSON Schema (draft 2020-12) for the data of an entity, looked up by name or ID.
+/// Served for the model version that runtime writes are validated against.
+pub async fn get_entity_schema(
+    Path((model_id, entity)): Path<(String, String)>,
+    State(state): State<AppState>,
+) -> Result<Response, StatusCode> {
+    let model_uuid = model_id.parse::<Uuid>()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let model = state.services.model_service
+        .get_runtime_model(model_uuid, None)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load model {} for schema: {}", model_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let entity = find_entity(&model, &entity).ok_or(StatusCode::NOT_FOUND)?;
+    let schema = entity_json_schema(&model, entity);
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/schema+json")],
+        schema.to_string(),
+    ).into_response())
+}
@@ -3,15 +3,23 @@ use axum::{
         ws::{WebSocket, Message, WebSocketUpgrade},
         State, Query,
     },
-    response::Response,
+    http::StatusCode,
+    response::{IntoResponse, Response},
 };
 use futures_util::{sink::SinkExt, stream::StreamExt};
 use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch, Semaphore};
 use tracing::{info, warn, error, debug};
 use crate::common::{Uuid, UtcDateTime};
 
 use crate::server::AppState;
-use crate::model::events::ModelChangeEvent;
+use crate::model::collab::EditOperation;
+use crate::jsonrpc;
+use crate::model::events::{ModelChangeEvent, ModelEventMessage};
+use crate::services::broadcast::{EventFilter, Replay, SeqTracker};
+use crate::services::collaboration::EditOutcome;
 use crate::Result;
 
 /// Query parameters for WebSocket connection
@@ -21,8 +29,33 @@ pub struct WebSocketQuery {
     pub client_id: Option<String>,
     /// Optional model ID to filter events
     pub model_filter: Option<String>,
+    /// Optional comma-separated entity types to filter app data change events
+    pub entity_filter: Option<String>,
+    /// Optional comma-separated event kinds, e.g. `EntityDataChanged`
+    pub event_kinds: Option<String>,
+    /// Sequence number of the last event the client received, to resume after reconnecting
+    pub last_seq: Option<u64>,
 }
 
+/// Messages a client sends to take part in collaborative editing of a model
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum ClientMessage {
+    #[serde(rename_all = "camelCase")]
+    Join { model_id: Uuid, user: Option<String>, color: Option<String> },
+    #[serde(rename_all = "camelCase")]
+    Leave { model_id: Uuid },
+    /// An edit made against the last revision the client has seen
+    #[serde(rename_all = "camelCase")]
+    Operation { model_id: Uuid, base_revision: u64, op_id: Option<String>, operation: Box<EditOperation> },
+    #[serde(rename_all = "camelCase")]
+    Cursor { model_id: Uuid, cursor: Option<Value> },
+}
+
+/// Requests from one connection dispatched concurrently; further requests wait
+/// before the connection's next message is read
+const MAX_CONCURRENT_REQUESTS: usize = 16;
+
 /// Handle WebSocket upgrade for real-time model synchronization
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
@@ -30,14 +63,39 @@ pub async fn websocket_handler(
     State(state): State<AppState>,
 ) -> Response {
     info!("WebSocket connection requested with params: {:?}", params);
-    
-    ws.on_upgrade(move |socket| handle_websocket(socket, params, state))
+
+    // Filters given up front are checked like those set later through `setEventFilter`
+    let filter = match query_filter(&params) {
+        Ok(filter) => filter,
+        Err(message) => {
+            warn!("Rejected WebSocket connection: {}", message);
+            return (StatusCode::BAD_REQUEST, message).into_response();
+        }
+    };
+
+    ws.on_upgrade(move |socket| handle_websocket(socket, params, filter, state))
+}
+
+/// Event filter from the connection's query parameters
+fn query_filter(params: &WebSocketQuery) -> std::result::Result<EventFilter, String> {
+    let model_id = params.model_filter.as_deref()
+        .map(|model_id| model_id.parse::<Uuid>().map_err(|e| format!("Invalid model_filter '{}': {}", model_id, e)))
+        .transpose()?;
+
+    let filter = EventFilter {
+        model_id,
+        entity_types: params.entity_filter.as_deref().map(split_list),
+        event_kinds: params.event_kinds.as_deref().map(split_list),
+    };
+    filter.validate()?;
+    Ok(filter)
 }
 
 /// Handle individual WebSocket connection
 async fn handle_websocket(
     socket: WebSocket,
     params: WebSocketQuery,
+    filter: EventFilter,
     state: AppState,
 ) {
     // Generate client ID if not provided
@@ -45,23 +103,10 @@ async fn handle_websocket(
         format!("client_{}", Uuid::new_v4().to_string()[..8].to_string())
     });
 
-    // Parse model filter if provided
-    let model_filter = if let Some(model_id_str) = params.model_filter {
-        match model_id_str.parse::<Uuid>() {
-            Ok(uuid) => Some(uuid),
-            Err(e) => {
-                warn!("Invalid model_filter UUID '{}': {}", model_id_str, e);
-                None
-            }
-        }
-    } else {
-        None
-    };
-
-    info!("WebSocket client connected: {} (model_filter: {:?})", client_id, model_filter);
+    info!("WebSocket client connected: {} (filter: {:?})", client_id, filter);
 
     // Register client with broadcast service
-    if let Err(e) = state.services.broadcast.register_client(client_id.clone(), model_filter.clone()).await {
+    if let Err(e) = state.services.broadcast.register_client(client_id.clone(), filter.clone()).await {
         error!("Failed to register WebSocket client {}: {}", client_id, e);
         return;
     }
@@ -76,27 +121,110 @@ async fn handle_websocket(
     let client_id_for_sender = client_id.clone();
     let client_id_for_cleanup = client_id.clone();
     let broadcast_service = state.services.broadcast.clone();
-    let model_filter_for_task = model_filter;
+    let broadcast_for_sender = state.services.broadcast.clone();
+    // The filter can be changed at runtime through `setEventFilter`
+    let (filter_sender, filter_receiver) = watch::channel(filter);
+
+    // Replies to this client's own messages, sent alongside broadcast events
+    let (reply_sender, mut reply_receiver) = mpsc::unbounded_channel::<Value>();
 
     // Task for sending events to client
+    let last_seq = params.last_seq;
     let send_task = tokio::spawn(async move {
-        loop {
-            match event_receiver.recv().await {
-                Ok(message) => {
-                    // Skip if this client should be excluded
-                    if let Some(ref exclude_client) = message.exclude_client {
-                        if exclude_client == &client_id_for_sender {
-                            debug!("Skipping message for excluded client: {}", client_id_for_sender);
+        let latest_seq = broadcast_for_sender.latest_seq().await.unwrap_or_else(|e| {
+            warn!("Failed to read the event log position: {}", e);
+            0
+        });
+        // Events the client has, so those sent during replay are not sent again. Events can
+        // arrive out of sequence order, so the client resumes from the first gap.
+        let mut seen = SeqTracker::new(last_seq.unwrap_or(latest_seq));
+        // Set after the client lagged, when the skipped events will not come
+        let mut lagged = false;
+
+        let connected = json!({ "type": "connected", "clientId": client_id_for_sender, "latestSeq": latest_seq });
+        if sender.send(Message::Text(connected.to_string())).await.is_err() {
+            return;
+        }
+
+        if let Some(last_seq) = last_seq {
+            let resync = match broadcast_for_sender.replay_since(last_seq).await {
+                Ok(Replay::Events(messages)) => {
+                    let count = messages.len();
+                    for message in messages {
+                        if let Some(seq) = message.seq {
+                            seen.insert(seq);
+                        }
+                        let deliver = should_deliver(&message, &client_id_for_sender, &filter_receiver.borrow());
+                        if !deliver {
                             continue;
                         }
+                        match serde_json::to_string(&message) {
+                            Ok(json_str) => {
+                                if sender.send(Message::Text(json_str)).await.is_err() {
+                                    return;
+                                }
+                            }
+                            Err(e) => error!("Failed to serialize event message: {}", e),
+                        }
                     }
+                    debug!("Replayed {} events after seq {} to client {}", count, last_seq, client_id_for_sender);
+                    json!({ "type": "replayComplete", "lastSeq": seen.contiguous(), "count": count })
+                }
+                Ok(Replay::ResyncRequired { latest_seq }) => {
+                    seen = SeqTracker::new(latest_seq);
+                    json!({
+                        "type": "resyncRequired",
+                        "reason": "replayUnavailable",
+                        "lastSeq": last_seq,
+                        "latestSeq": latest_seq,
+                    })
+                }
+                Err(e) => {
+                    error!("Failed to replay events for client {}: {}", client_id_for_sender, e);
+                    seen = SeqTracker::new(latest_seq);
+                    json!({ "type": "resyncRequired", "reason": "replayFailed", "lastSeq": last_seq, "latestSeq": latest_seq })
+                }
+            };
+            if sender.send(Message::Text(resync.to_string())).await.is_err() {
+                return;
+            }
+        }
 
-                    // Check model filter
-                    if let Some(ref model_filter) = model_filter_for_task {
-                        if message.event.model_id() != *model_filter {
-                            debug!("Skipping message due to model filter for client: {}", client_id_for_sender);
+        loop {
+            let received = tokio::select! {
+                received = event_receiver.recv() => received,
+                Some(reply) = reply_receiver.recv() => {
+                    if let Err(e) = sender.send(Message::Text(reply.to_string())).await {
+                        error!("Failed to send WebSocket reply to {}: {}", client_id_for_sender, e);
+                        break;
+                    }
+                    continue;
+                }
+            };
+            match received {
+                Ok(message) => {
+                    if let Some(seq) = message.seq {
+                        if std::mem::take(&mut lagged) {
+                            seen = SeqTracker::new(seq - 1);
+                        }
+                        // Skip events already sent during replay
+                        if !seen.insert(seq) {
                             continue;
                         }
+                        if seen.gap_stalled() {
+                            // Reconnecting with `last_seq` replays the missing events, if the log has them
+                            let resync = json!({ "type": "resyncRequired", "reason": "gap", "lastSeq": seen.skip_gaps() });
+                            if let Err(e) = sender.send(Message::Text(resync.to_string())).await {
+                                error!("Failed to send WebSocket message to {}: {}", client_id_for_sender, e);
+                                break;
+                            }
+                        }
+                    }
+
+                    let deliver = should_deliver(&message, &client_id_for_sender, &filter_receiver.borrow());
+                    if !deliver {
+                        debug!("Skipping message for client {} due to exclusion or event filter", client_id_for_sender);
+                        continue;
                     }
 
                     // Serialize and send the message
@@ -115,6 +243,18 @@ async fn handle_websocket(
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
                     warn!("Client {} lagged, skipped {} messages", client_id_for_sender, skipped);
+                    // Reconnecting with `last_seq` replays what was skipped, if the log still has it
+                    let resync = json!({
+                        "type": "resyncRequired",
+                        "reason": "lagged",
+                        "skipped": skipped,
+                        "lastSeq": seen.skip_gaps(),
+                    });
+                    lagged = true;
+                    if let Err(e) = sender.send(Message::Text(resync.to_string())).await {
+                        error!("Failed to send WebSocket message to {}: {}", client_id_for_sender, e);
+                        break;
+                    }
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => {
                     info!("Broadcast channel closed for client {}", client_id_for_sender);
@@ -124,15 +264,28 @@ async fn handle_websocket(
         }
     });
 
-    // Task for receiving messages from client (for future bidirectional communication)
+    // Task for receiving JSON-RPC requests and collaborative editing messages from client
     let client_id_for_receiver = client_id.clone();
+    let state_for_receiver = state.clone();
+    let dispatch_limit = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
     let receive_task = tokio::spawn(async move {
         while let Some(msg) = receiver.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
                     debug!("Received message from client {}: {}", client_id_for_receiver, text);
-                    // For now, just log received messages
-                    // In the future, we could handle client-to-server commands here
+                    let rpc_request = serde_json::from_str::<Value>(&text).ok().filter(|value| value.get("jsonrpc").is_some());
+                    let reply = match rpc_request {
+                        Some(request) => {
+                            handle_rpc_request(&state_for_receiver, &client_id_for_receiver, request, &filter_sender, &reply_sender, &dispatch_limit).await;
+                            None
+                        }
+                        None => handle_client_message(&state_for_receiver, &client_id_for_receiver, &text).await,
+                    };
+                    if let Some(reply) = reply {
+                        if reply_sender.send(reply).is_err() {
+                            break;
+                        }
+                    }
                 }
                 Ok(Message::Close(_)) => {
                     info!("Client {} sent close message", client_id_for_receiver);
@@ -159,7 +312,8 @@ async fn handle_websocket(
         }
     }
 
-    // Cleanup: unregister client
+    // Cleanup: leave editing sessions and unregister client
+    state.services.collaboration.leave_all(&client_id_for_cleanup).await;
     if let Err(e) = broadcast_service.unregister_client(&client_id_for_cleanup).await {
         error!("Failed to unregister WebSocket client {}: {}", client_id_for_cleanup, e);
     } else {
@@ -167,6 +321,141 @@ async fn handle_websocket(
     }
 }
 
+/// Split a comma-separated query parameter into its non-empty entries
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',').map(str::trim).filter(|entry| !entry.is_empty()).map(str::to_string).collect()
+}
+
+/// Answer a JSON-RPC 2.0 request sent over the socket. Subscription methods are handled
+/// here; all other methods go through the JSON-RPC dispatcher and are answered as they
+/// complete, so responses may arrive out of order and are matched by `id`. At most
+/// [`MAX_CONCURRENT_REQUESTS`] run at once per connection. Requests without an `id`
+/// are notifications and get no response.
+async fn handle_rpc_request(
+    state: &AppState,
+    client_id: &str,
+    request: Value,
+    filter: &watch::Sender<EventFilter>,
+    replies: &mpsc::UnboundedSender<Value>,
+    dispatch_limit: &Arc<Semaphore>,
+) {
+    let id = request.get("id").cloned();
+    if let Err((code, message)) = jsonrpc::validate_jsonrpc_request(&request) {
+        let _ = replies.send(jsonrpc::error_response(id.unwrap_or(Value::Null), code, message));
+        return;
+    }
+
+    let method = request["method"].as_str().unwrap_or_default().to_string();
+    let params = request.get("params").cloned().unwrap_or_else(|| json!({}));
+    let result = match method.as_str() {
+        "setEventFilter" => set_event_filter(state, client_id, params, filter).await,
+        "getEventFilter" => serde_json::to_value(&*filter.borrow()).map_err(|e| (-32603, e.to_string())),
+        _ => {
+            // Waiting here holds back reading further messages from this client
+            let Ok(permit) = dispatch_limit.clone().acquire_owned().await else {
+                return;
+            };
+            let state = state.clone();
+            let replies = replies.clone();
+            tokio::spawn(async move {
+                let _permit = permit;
+                let response = jsonrpc::handle_request(&state, &request).await;
+                if id.is_some() {
+                    let _ = replies.send(response);
+                }
+            });
+            return;
+        }
+    };
+
+    let Some(id) = id else {
+        return;
+    };
+    let response = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => jsonrpc::error_response(id, code, message),
+    };
+    let _ = replies.send(response);
+}
+
+/// Replace the client's event filter, e.g. `{"modelId": "...", "entityTypes": ["task"]}`.
+/// Omitted criteria match every event.
+async fn set_event_filter(
+    state: &AppState,
+    client_id: &str,
+    params: Value,
+    filter: &watch::Sender<EventFilter>,
+) -> std::result::Result<Value, (i32, String)> {
+    let new_filter: EventFilter = serde_json::from_value(params)
+        .map_err(|e| (-32602, format!("Invalid event filter: {}", e)))?;
+    new_filter.validate().map_err(|message| (-32602, message))?;
+
+    state.services.broadcast.update_client_filter(client_id, new_filter.clone()).await
+        .map_err(|e| (-32603, e.to_string()))?;
+    let result = serde_json::to_value(&new_filter).map_err(|e| (-32603, e.to_string()))?;
+    debug!("Client {} changed its event filter to {:?}", client_id, new_filter);
+    filter.send_replace(new_filter);
+    Ok(result)
+}
+
+/// Whether an event goes to this client: not caused by it and within its event filter
+fn should_deliver(message: &ModelEventMessage, client_id: &str, filter: &EventFilter) -> bool {
+    message.exclude_client.as_deref() != Some(client_id) && filter.matches(&message.event)
+}
+
+/// Handle a collaborative editing message, returning the reply for the sending client
+async fn handle_client_message(state: &AppState, client_id: &str, text: &str) -> Option<Value> {
+    let message: ClientMessage = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(e) => return Some(json!({ "type": "error", "message": format!("Invalid message: {}", e) })),
+    };
+    let collaboration = &state.services.collaboration;
+
+    let reply = match message {
+        ClientMessage::Join { model_id, user, color } => {
+            match collaboration.join(model_id.clone(), client_id, user, color).await {
+                Ok((revision, editors)) => json!({
+                    "type": "joined",
+                    "clientId": client_id,
+                    "modelId": model_id,
+                    "revision": revision,
+                    "editors": editors,
+                }),
+                Err(e) => json!({ "type": "error", "modelId": model_id, "message": e.to_string() }),
+            }
+        }
+        ClientMessage::Leave { model_id } => match collaboration.leave(&model_id, client_id).await {
+            Ok(()) => json!({ "type": "left", "modelId": model_id }),
+            Err(e) => json!({ "type": "error", "modelId": model_id, "message": e.to_string() }),
+        },
+        ClientMessage::Operation { model_id, base_revision, op_id, operation } => {
+            match collaboration.apply(&model_id, client_id, base_revision, *operation).await {
+                Ok(EditOutcome::Applied { revision }) => json!({
+                    "type": "ack",
+                    "modelId": model_id,
+                    "opId": op_id,
+                    "revision": revision,
+                }),
+                Ok(EditOutcome::Conflict { revision, operations, resync }) => json!({
+                    "type": "conflict",
+                    "modelId": model_id,
+                    "opId": op_id,
+                    "revision": revision,
+                    "operations": operations,
+                    "resync": resync,
+                }),
+                Err(e) => json!({ "type": "error", "modelId": model_id, "opId": op_id, "message": e.to_string() }),
+            }
+        }
+        // Cursor updates are frequent, so only failures are answered
+        ClientMessage::Cursor { model_id, cursor } => match collaboration.update_cursor(&model_id, client_id, cursor).await {
+            Ok(()) => return None,
+            Err(e) => json!({ "type": "error", "modelId": model_id, "message": e.to_string() }),
+        },
+    };
+    Some(reply)
+}
+
 /// Send a ping message to test WebSocket connection
 pub async fn ping_websocket_clients(state: AppState) -> Result<()> {
     let ping_event = ModelChangeEvent::model_created(crate::model::types::TorqueModel {
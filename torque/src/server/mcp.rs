@@ -1,39 +1,86 @@
 // MCP server implementation for console component
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
     routing::post,
     Router,
 };
+use dashmap::DashMap;
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use crate::common::Uuid;
 use crate::server::AppState;
 use crate::jsonrpc::handlers::jsonrpc_handler;
+use crate::model::json_schema::{entity_json_schema, find_entity};
+use crate::model::templates::slugify;
+use crate::model::events::ModelChangeEvent;
+use crate::model::types::{CascadeAction, EntityField, EntityType, FieldType, ModelEntity, TorqueModel};
+use crate::model::validation::ConfigurationError;
+use crate::services::app_database::{entity_record, EntityFilter, EntityListQuery, FilterOp};
+use crate::services::model::{
+    CreateEntityInput, CreateFieldInput, CreateLayoutInput, CreateRelationshipInput, UpdateEntityInput,
+};
 
 /// Create MCP router that exposes JSON-RPC methods as MCP tools
 pub fn create_mcp_router() -> Router<AppState> {
     Router::new()
-        .route("/mcp", post(mcp_handler))
+        .route("/mcp", post(mcp_handler).get(mcp_event_stream).delete(end_mcp_session))
         .route("/mcp/capabilities", post(mcp_capabilities))
         .route("/mcp/tools/list", post(list_mcp_tools))
         .route("/mcp/tools/call", post(call_mcp_tool))
+        .route("/mcp/models/:model_id", post(model_mcp_handler))
 }
 
 /// Main MCP handler - delegates to appropriate sub-handlers
-async fn mcp_handler(
+pub async fn mcp_handler(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<Value>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Response, StatusCode> {
     tracing::debug!("MCP request received: {}", request);
     
     let method = request.get("method")
         .and_then(|v| v.as_str())
         .unwrap_or("unknown");
+    let session = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok());
+    if let Some(mut entry) = session.and_then(|session| SESSIONS.get_mut(session)) {
+        entry.last_seen = Instant::now();
+    }
+    let resources_enabled = state.services.config.mcp.enable_resources;
+    let prompts_enabled = state.services.config.mcp.enable_prompts;
     
     match method {
-        "initialize" => initialize_mcp(state, request).await,
-        "tools/list" => list_tools_mcp(state, request).await,
-        "tools/call" => call_tool_mcp(State(state), request).await,
+        "initialize" if resources_enabled => {
+            // Resource subscriptions are kept per session
+            expire_idle_sessions(&SESSIONS, SESSION_IDLE_TTL);
+            let session = Uuid::new_v4().to_string();
+            SESSIONS.insert(session.clone(), McpSession::new());
+            let response = initialize_mcp(state, request).await?;
+            Ok(([(SESSION_HEADER, session)], response).into_response())
+        }
+        "initialize" => initialize_mcp(state, request).await.map(IntoResponse::into_response),
+        "tools/list" => list_tools_mcp(state, request).await.map(IntoResponse::into_response),
+        "tools/call" => call_tool_mcp(State(state), request).await.map(IntoResponse::into_response),
+        "resources/list" if resources_enabled => Ok(rpc_result(&request, list_resources(&state).await)),
+        "resources/templates/list" if resources_enabled => Ok(rpc_result(&request, Ok(resource_templates()))),
+        "resources/read" if resources_enabled => Ok(rpc_result(&request, read_resource(&state, &request["params"]).await)),
+        "resources/subscribe" if resources_enabled => {
+            Ok(rpc_result(&request, update_subscription(session, &request["params"], true)))
+        }
+        "resources/unsubscribe" if resources_enabled => {
+            Ok(rpc_result(&request, update_subscription(session, &request["params"], false)))
+        }
+        "prompts/list" if prompts_enabled => Ok(rpc_result(&request, Ok(list_prompts()))),
+        "prompts/get" if prompts_enabled => Ok(rpc_result(&request, get_prompt(&state, &request["params"]).await)),
         _ => {
             let error_response = json!({
                 "jsonrpc": "2.0",
@@ -43,14 +90,14 @@ async fn mcp_handler(
                     "message": format!("Method '{}' not found", method)
                 }
             });
-            Ok(Json(error_response))
+            Ok(Json(error_response).into_response())
         }
     }
 }
 
 /// Initialize MCP server
 async fn initialize_mcp(
-    _state: AppState,
+    state: AppState,
     request: Value,
 ) -> Result<Json<Value>, StatusCode> {
     let response = json!({
@@ -58,10 +105,7 @@ async fn initialize_mcp(
         "id": request.get("id"),
         "result": {
             "protocolVersion": "2024-11-05",
-            "capabilities": {
-                "tools": {},
-                "logging": {}
-            },
+            "capabilities": server_capabilities(&state),
             "serverInfo": {
                 "name": "torque-mcp-server",
                 "version": env!("CARGO_PKG_VERSION")
@@ -76,7 +120,7 @@ async fn list_tools_mcp(
     _state: AppState,
     request: Value,
 ) -> Result<Json<Value>, StatusCode> {
-    let tools = vec![
+    let mut tools = vec![
         json!({
             "name": "torque_list_projects",
             "description": "List all available projects/models in the Torque server",
@@ -327,6 +371,8 @@ async fn list_tools_mcp(
             }
         })
     ];
+    tools.extend(record_tools());
+    tools.extend(model_editing_tools(None));
     
     let response = json!({
         "jsonrpc": "2.0",
@@ -354,6 +400,10 @@ async fn call_tool_mcp(
     let empty_args = json!({});
     let arguments = params.get("arguments")
         .unwrap_or(&empty_args);
+
+    if let Some(result) = call_app_tool(&state, tool_name, arguments).await {
+        return Ok(Json(tool_response(request.get("id"), result)));
+    }
     
     // Map MCP tool names to JSON-RPC methods
     let jsonrpc_method = match tool_name {
@@ -426,12 +476,27 @@ async fn call_tool_mcp(
 }
 
 // Legacy endpoints for backwards compatibility
-async fn mcp_capabilities(_state: State<AppState>) -> Result<Json<Value>, StatusCode> {
-    let response = json!({
+async fn mcp_capabilities(State(state): State<AppState>) -> Result<Json<Value>, StatusCode> {
+    Ok(Json(server_capabilities(&state)))
+}
+
+/// Capabilities advertised on initialize; resources and prompts follow `McpConfig`
+fn server_capabilities(state: &AppState) -> Value {
+    let mut capabilities = json!({
         "tools": {},
         "logging": {}
     });
-    Ok(Json(response))
+    let config = &state.services.config.mcp;
+    if config.enable_resources {
+        capabilities["resources"] = json!({
+            "subscribe": true,
+            "listChanged": true
+        });
+    }
+    if config.enable_prompts {
+        capabilities["prompts"] = json!({});
+    }
+    capabilities
 }
 
 async fn list_mcp_tools(
@@ -450,4 +515,1270 @@ async fn call_mcp_tool(
     Json(request): Json<Value>,
 ) -> Result<Json<Value>, StatusCode> {
     call_tool_mcp(State(state), request).await
-}
\ No newline at end of file
+}
+// App data and model editing tools
+//
+// These tools call the app database and model services directly. The global `/mcp`
+// endpoint lists them with a `modelId` argument and untyped record data; the
+// per-model endpoint `/mcp/models/:model_id` adds one set of record tools per
+// entity whose arguments are generated from the entity definition.
+
+const FILTER_OPS: &[&str] = &["eq", "ne", "gt", "gte", "lt", "lte", "contains", "in"];
+const RELATIONSHIP_TYPES: &[&str] = &["OneToOne", "OneToMany", "ManyToOne", "ManyToMany"];
+const CASCADE_ACTIONS: &[&str] = &["None", "Delete", "SetNull", "Restrict"];
+const LAYOUT_TYPES: &[&str] = &["List", "Grid", "Dashboard", "Form", "Detail", "Custom"];
+
+/// Per-entity tool prefixes and the generic record tool each one calls
+const ENTITY_TOOL_ACTIONS: &[(&str, &str)] = &[
+    ("query", "torque_query_records"),
+    ("get", "torque_get_record"),
+    ("create", "torque_create_record"),
+    ("update", "torque_update_record"),
+    ("delete", "torque_delete_record"),
+];
+
+/// MCP endpoint scoped to one model. Lists typed record tools for each of its
+/// entities plus the model editing tools, all without a `modelId` argument; other
+/// methods behave as on the global endpoint.
+pub async fn model_mcp_handler(
+    Path(model_id): Path<String>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<Value>,
+) -> Result<Response, StatusCode> {
+    tracing::debug!("MCP request for model {} received: {}", model_id, request);
+
+    let method = request.get("method")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    if method != "tools/list" && method != "tools/call" {
+        return mcp_handler(State(state), headers, Json(request)).await;
+    }
+
+    let response = model_tools_request(&state, &model_id, method == "tools/list", &request).await?;
+    Ok(Json(response).into_response())
+}
+
+async fn model_tools_request(
+    state: &AppState,
+    model_id: &str,
+    list: bool,
+    request: &Value,
+) -> Result<Value, StatusCode> {
+    let model = match load_runtime_model(state, model_id).await {
+        Ok(model) => model,
+        Err(message) => return Ok(rpc_error(request.get("id"), -32602, message)),
+    };
+
+    if list {
+        let mut tools: Vec<Value> = model.entities.iter()
+            .flat_map(|entity| entity_tools(&model, entity))
+            .collect();
+        // Edits apply to the latest model, which can differ from a pinned runtime version
+        let latest = state.services.model_service.get_model(model.id.clone()).await.ok().flatten();
+        tools.extend(model_editing_tools(Some(latest.as_ref().unwrap_or(&model))));
+        return Ok(json!({
+            "jsonrpc": "2.0",
+            "id": request.get("id"),
+            "result": {
+                "tools": tools
+            }
+        }));
+    }
+
+    let params = request.get("params")
+        .and_then(|p| p.as_object())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let tool_name = params.get("name")
+        .and_then(|v| v.as_str())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    // Typed tools carry the model and entity in their name
+    let mut arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+    let Some(arguments_object) = arguments.as_object_mut() else {
+        return Ok(rpc_error(request.get("id"), -32602, "Tool arguments must be an object".to_string()));
+    };
+    arguments_object.insert("modelId".to_string(), json!(model.id.to_string()));
+    let target = match entity_tool_target(&model, tool_name) {
+        Some((target, entity)) => {
+            arguments_object.insert("entityType".to_string(), json!(entity.name));
+            target
+        }
+        None => tool_name,
+    };
+
+    match call_app_tool(state, target, &arguments).await {
+        Some(result) => Ok(tool_response(request.get("id"), result)),
+        None => Ok(rpc_error(request.get("id"), -32601, format!("Unknown MCP tool: {}", tool_name))),
+    }
+}
+
+/// Run one of the app data or model editing tools; `None` if the name is not one of them
+async fn call_app_tool(state: &AppState, name: &str, arguments: &Value) -> Option<Result<Value, String>> {
+    let result = match name {
+        "torque_query_records" => query_records(state, arguments).await,
+        "torque_get_record" => get_record(state, arguments).await,
+        "torque_create_record" => create_record(state, arguments).await,
+        "torque_update_record" => update_record(state, arguments).await,
+        "torque_delete_record" => delete_record(state, arguments).await,
+        "torque_add_entity" => add_entity(state, arguments).await,
+        "torque_add_field" => add_field(state, arguments).await,
+        "torque_add_relationship" => add_relationship(state, arguments).await,
+        "torque_add_layout" => add_layout(state, arguments).await,
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Wrap a tool result as MCP content. Failures are reported as tool errors so the
+/// agent can read the message and correct its arguments.
+fn tool_response(id: Option<&Value>, result: Result<Value, String>) -> Value {
+    let (text, is_error) = match result {
+        Ok(value) => (serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string()), false),
+        Err(message) => (message, true),
+    };
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": {
+            "content": [
+                {
+                    "type": "text",
+                    "text": text
+                }
+            ],
+            "isError": is_error
+        }
+    })
+}
+
+fn rpc_error(id: Option<&Value>, code: i32, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": code,
+            "message": message
+        }
+    })
+}
+
+fn tool(name: &str, description: &str, properties: Value, required: &[&str]) -> Value {
+    json!({
+        "name": name,
+        "description": description,
+        "inputSchema": {
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false
+        }
+    })
+}
+
+/// Generic record tools of the global endpoint, taking the model and entity as arguments
+fn record_tools() -> Vec<Value> {
+    let target = |mut properties: Value| {
+        properties["modelId"] = json!({"type": "string", "description": "Model/project ID"});
+        properties["entityType"] = json!({"type": "string", "description": "Entity name or ID"});
+        properties
+    };
+    let id = json!({"type": "string", "description": "Record ID"});
+    let data = json!({
+        "type": "object",
+        "description": "Record fields; /mcp/models/{modelId} lists tools with the entity's field schema",
+        "additionalProperties": true
+    });
+
+    vec![
+        tool(
+            "torque_query_records",
+            "Query app data records of an entity with field filters, sorting and pagination",
+            target(query_properties(None)),
+            &["modelId", "entityType"],
+        ),
+        tool("torque_get_record", "Get an app data record by ID", target(json!({"id": id})), &["modelId", "entityType", "id"]),
+        tool(
+            "torque_create_record",
+            "Create an app data record; the data is validated against the entity definition",
+            target(json!({"data": data})),
+            &["modelId", "entityType", "data"],
+        ),
+        tool(
+            "torque_update_record",
+            "Update an app data record; fields in data replace the stored values, other fields are kept",
+            target(json!({"id": id, "data": data})),
+            &["modelId", "entityType", "id", "data"],
+        ),
+        tool("torque_delete_record", "Delete an app data record by ID", target(json!({"id": id})), &["modelId", "entityType", "id"]),
+    ]
+}
+
+/// Record tools for one entity with arguments generated from its definition
+fn entity_tools(model: &TorqueModel, entity: &ModelEntity) -> Vec<Value> {
+    let id = json!({"type": "string", "description": format!("{} record ID", entity.display_name)});
+    let name = |action: &str| entity_tool_name(action, entity);
+
+    vec![
+        tool(
+            &name("query"),
+            &format!("Query {} records with field filters, sorting and pagination", entity.display_name),
+            query_properties(Some(entity)),
+            &[],
+        ),
+        tool(&name("get"), &format!("Get a {} record by ID", entity.display_name), json!({"id": id}), &["id"]),
+        tool(
+            &name("create"),
+            &format!("Create a {} record", entity.display_name),
+            json!({"data": record_data_schema(model, entity, false)}),
+            &["data"],
+        ),
+        tool(
+            &name("update"),
+            &format!("Update a {} record; fields in data replace the stored values, other fields are kept", entity.display_name),
+            json!({"id": id, "data": record_data_schema(model, entity, true)}),
+            &["id", "data"],
+        ),
+        tool(&name("delete"), &format!("Delete a {} record by ID", entity.display_name), json!({"id": id}), &["id"]),
+    ]
+}
+
+/// Tool name of an entity action, e.g. `create_order_item` for entity "Order Item"
+fn entity_tool_name(action: &str, entity: &ModelEntity) -> String {
+    format!("{}_{}", action, slugify(&entity.name).replace('-', "_"))
+}
+
+/// Generic record tool and entity behind a per-entity tool name
+fn entity_tool_target<'a>(model: &'a TorqueModel, tool_name: &str) -> Option<(&'static str, &'a ModelEntity)> {
+    model.entities.iter().find_map(|entity| {
+        ENTITY_TOOL_ACTIONS.iter()
+            .find(|(action, _)| entity_tool_name(action, entity) == tool_name)
+            .map(|(_, target)| (*target, entity))
+    })
+}
+
+/// Entity JSON Schema used as the `data` argument. Updates merge into the stored
+/// record, so none of the fields are required there.
+fn record_data_schema(model: &TorqueModel, entity: &ModelEntity, partial: bool) -> Value {
+    let mut schema = entity_json_schema(model, entity);
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("$id");
+        if partial {
+            object.remove("required");
+        }
+    }
+    schema
+}
+
+fn query_properties(entity: Option<&ModelEntity>) -> Value {
+    let mut field = json!({"type": "string", "description": "Field name"});
+    if let Some(entity) = entity {
+        field["enum"] = json!(entity.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>());
+    }
+
+    json!({
+        "filters": {
+            "type": "array",
+            "description": "Conditions every record must match",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "field": field,
+                    "op": {
+                        "type": "string",
+                        "enum": FILTER_OPS,
+                        "description": "Comparison, 'eq' by default; 'contains' is a case-insensitive substring match and 'in' takes an array"
+                    },
+                    "value": {"description": "Value to compare with"}
+                },
+                "required": ["field", "value"],
+                "additionalProperties": false
+            }
+        },
+        "sort": field,
+        "descending": {"type": "boolean", "description": "Sort in descending order"},
+        "limit": {"type": "number", "description": "Maximum records to return"},
+        "offset": {"type": "number", "description": "Records to skip"}
+    })
+}
+
+/// Model editing tools; scoped to a model they drop `modelId` and list its entities
+fn model_editing_tools(model: Option<&TorqueModel>) -> Vec<Value> {
+    let mut entity = json!({"type": "string", "description": "Entity name or ID"});
+    let mut entities = json!({"type": "array", "items": {"type": "string"}, "description": "Entity names or IDs"});
+    if let Some(model) = model {
+        let names: Vec<&str> = model.entities.iter().map(|e| e.name.as_str()).collect();
+        entity["enum"] = json!(names);
+        entities["items"]["enum"] = json!(names);
+    }
+    let field_type = json!({
+        "type": "string",
+        "description": "Field type notation, e.g. String, String(100), Integer(0..10), Float, Boolean, DateTime, Date, Time, Json, Binary, Enum[low,high], Reference(<entity id>) or Array<String>"
+    });
+    let field_properties = json!({
+        "name": {"type": "string", "description": "Field name"},
+        "displayName": {"type": "string", "description": "Label shown in the UI; defaults to the name"},
+        "fieldType": field_type,
+        "required": {"type": "boolean", "description": "Whether records must have a value"},
+        "defaultValue": {"description": "Value used when a record omits the field"}
+    });
+
+    let scoped = |mut properties: Value, required: &[&'static str]| {
+        let mut required: Vec<&str> = required.to_vec();
+        if model.is_none() {
+            properties["modelId"] = json!({"type": "string", "description": "Model/project ID"});
+            required.insert(0, "modelId");
+        }
+        (properties, required)
+    };
+
+    let mut add_field = field_properties.clone();
+    add_field["entity"] = entity.clone();
+
+    [
+        (
+            "torque_add_entity",
+            "Add an entity to a model",
+            scoped(json!({
+                "name": {"type": "string", "description": "Entity name"},
+                "displayName": {"type": "string", "description": "Label shown in the UI; defaults to the name"},
+                "description": {"type": "string", "description": "Optional entity description"},
+                "fields": {
+                    "type": "array",
+                    "description": "Fields of the entity",
+                    "items": {
+                        "type": "object",
+                        "properties": field_properties,
+                        "required": ["name", "fieldType"],
+                        "additionalProperties": false
+                    }
+                }
+            }), &["name"]),
+        ),
+        (
+            "torque_add_field",
+            "Add a field to an entity of a model",
+            scoped(add_field, &["entity", "name", "fieldType"]),
+        ),
+        (
+            "torque_add_relationship",
+            "Add a relationship between two entities of a model",
+            scoped(json!({
+                "name": {"type": "string", "description": "Relationship name"},
+                "relationshipType": {"type": "string", "enum": RELATIONSHIP_TYPES},
+                "fromEntity": entity,
+                "toEntity": entity,
+                "fromField": {"type": "string", "description": "Field of the source entity"},
+                "toField": {"type": "string", "description": "Field of the target entity"},
+                "cascade": {"type": "string", "enum": CASCADE_ACTIONS, "description": "Action on delete, 'None' by default"}
+            }), &["name", "relationshipType", "fromEntity", "toEntity", "fromField", "toField"]),
+        ),
+        (
+            "torque_add_layout",
+            "Add a layout showing entities of a model",
+            scoped(json!({
+                "name": {"type": "string", "description": "Layout name"},
+                "layoutType": {"type": "string", "enum": LAYOUT_TYPES},
+                "targetEntities": entities
+            }), &["name", "layoutType"]),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, description, (properties, required))| tool(name, description, properties, &required))
+    .collect()
+}
+
+fn str_arg<'a>(arguments: &'a Value, name: &str) -> Result<&'a str, String> {
+    arguments.get(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| format!("Missing required argument: {}", name))
+}
+
+/// Parse an enum argument from its variant name
+fn enum_arg<T: DeserializeOwned>(arguments: &Value, name: &str, allowed: &[&str]) -> Result<T, String> {
+    let value = str_arg(arguments, name)?;
+    serde_json::from_value(json!(value))
+        .map_err(|_| format!("Invalid {} '{}', expected one of: {}", name, value, allowed.join(", ")))
+}
+
+/// Model version runtime data is validated against
+async fn load_runtime_model(state: &AppState, model_id: &str) -> Result<TorqueModel, String> {
+    let id = model_id.parse::<Uuid>()
+        .map_err(|_| format!("Invalid model ID '{}'", model_id))?;
+    state.services.model_service.get_runtime_model(id, None).await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Model '{}' not found", model_id))
+}
+
+/// Latest model version, which model edits apply to
+async fn load_model(state: &AppState, arguments: &Value) -> Result<TorqueModel, String> {
+    let model_id = str_arg(arguments, "modelId")?;
+    let id = model_id.parse::<Uuid>()
+        .map_err(|_| format!("Invalid model ID '{}'", model_id))?;
+    state.services.model_service.get_model(id).await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Model '{}' not found", model_id))
+}
+
+fn entity_arg<'a>(model: &'a TorqueModel, arguments: &Value, name: &str) -> Result<&'a ModelEntity, String> {
+    let entity = str_arg(arguments, name)?;
+    find_entity(model, entity).ok_or_else(|| format!("Model has no entity '{}'", entity))
+}
+
+fn check_field(entity: &ModelEntity, field: &str) -> Result<(), String> {
+    if entity.fields.iter().any(|f| f.name == field) {
+        Ok(())
+    } else {
+        Err(format!("Entity '{}' has no field '{}'", entity.name, field))
+    }
+}
+
+/// Load a record and check it belongs to the entity
+async fn load_record(
+    state: &AppState,
+    model: &TorqueModel,
+    entity: &ModelEntity,
+    id: &str,
+) -> Result<crate::database::entities::app_entities::Model, String> {
+    state.services.app_database_service
+        .get_entity(model.id.as_str(), id)
+        .await
+        .map_err(|e| e.to_string())?
+        .filter(|record| record.entity_type == entity.name)
+        .ok_or_else(|| format!("{} record '{}' not found", entity.name, id))
+}
+
+async fn query_records(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_runtime_model(state, str_arg(arguments, "modelId")?).await?;
+    let entity = entity_arg(&model, arguments, "entityType")?;
+
+    let rest = &state.services.config.rest;
+    let mut query = EntityListQuery {
+        limit: arguments.get("limit")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(rest.default_page_size)
+            .clamp(1, rest.max_page_size),
+        offset: arguments.get("offset").and_then(|v| v.as_u64()).unwrap_or(0),
+        ..Default::default()
+    };
+
+    if let Some(sort) = arguments.get("sort").and_then(|v| v.as_str()) {
+        check_field(entity, sort)?;
+        query.sort_field = Some(sort.to_string());
+        query.sort_descending = arguments.get("descending").and_then(|v| v.as_bool()).unwrap_or(false);
+    }
+
+    for filter in arguments.get("filters").and_then(|v| v.as_array()).into_iter().flatten() {
+        let field = str_arg(filter, "field")?;
+        check_field(entity, field)?;
+        let op = match filter.get("op").and_then(|v| v.as_str()) {
+            Some(op) => FilterOp::parse(op)
+                .ok_or_else(|| format!("Invalid filter op '{}', expected one of: {}", op, FILTER_OPS.join(", ")))?,
+            None => FilterOp::Eq,
+        };
+        let value = filter.get("value").cloned()
+            .ok_or_else(|| format!("Filter on '{}' has no value", field))?;
+        if op == FilterOp::In && !value.is_array() {
+            return Err(format!("Filter 'in' on '{}' needs an array value", field));
+        }
+        query.filters.push(EntityFilter { field: field.to_string(), op, value });
+    }
+
+    let (records, total) = state.services.app_database_service
+        .query_entities(model.id.as_str(), &entity.name, &query)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(json!({
+        "data": records,
+        "total": total,
+        "limit": query.limit,
+        "offset": query.offset
+    }))
+}
+
+async fn get_record(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_runtime_model(state, str_arg(arguments, "modelId")?).await?;
+    let entity = entity_arg(&model, arguments, "entityType")?;
+
+    let record = load_record(state, &model, entity, str_arg(arguments, "id")?).await?;
+    Ok(entity_record(record))
+}
+
+async fn create_record(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_runtime_model(state, str_arg(arguments, "modelId")?).await?;
+    let entity = entity_arg(&model, arguments, "entityType")?;
+    let data = arguments.get("data").cloned()
+        .ok_or_else(|| "Missing required argument: data".to_string())?;
+
+    let record = state.services.app_database_service
+        .create_entity(model.id.as_str(), &entity.name, data)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(entity_record(record))
+}
+
+async fn update_record(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_runtime_model(state, str_arg(arguments, "modelId")?).await?;
+    let entity = entity_arg(&model, arguments, "entityType")?;
+    let id = str_arg(arguments, "id")?;
+    let changes = arguments.get("data")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .ok_or_else(|| "Argument data must be an object".to_string())?;
+
+    let updated = state.services.app_database_service
+        .patch_entity(model.id.as_str(), Some(&entity.name), id, changes)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(entity_record(updated))
+}
+
+async fn delete_record(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_runtime_model(state, str_arg(arguments, "modelId")?).await?;
+    let entity = entity_arg(&model, arguments, "entityType")?;
+    let id = str_arg(arguments, "id")?;
+    load_record(state, &model, entity, id).await?;
+
+    state.services.app_database_service
+        .delete_entity(model.id.as_str(), id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(json!({"id": id, "deleted": true}))
+}
+
+/// Field definition from `name`, `displayName`, `fieldType`, `required` and `defaultValue` arguments
+fn field_input(arguments: &Value) -> Result<CreateFieldInput, String> {
+    let name = str_arg(arguments, "name")?;
+    let field_type = str_arg(arguments, "fieldType")?
+        .parse::<FieldType>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(CreateFieldInput {
+        name: name.to_string(),
+        display_name: arguments.get("displayName").and_then(|v| v.as_str()).unwrap_or(name).to_string(),
+        field_type,
+        required: arguments.get("required").and_then(|v| v.as_bool()).unwrap_or(false),
+        default_value: arguments.get("defaultValue").cloned(),
+        validation: vec![],
+        ui_config: None,
+    })
+}
+
+async fn add_entity(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_model(state, arguments).await?;
+    let name = str_arg(arguments, "name")?;
+    if find_entity(&model, name).is_some() {
+        return Err(format!("Model already has an entity '{}'", name));
+    }
+
+    let fields = arguments.get("fields")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(field_input)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let entity = state.services.model_service
+        .create_entity(CreateEntityInput {
+            model_id: model.id.to_string(),
+            name: name.to_string(),
+            display_name: arguments.get("displayName").and_then(|v| v.as_str()).unwrap_or(name).to_string(),
+            description: arguments.get("description").and_then(|v| v.as_str()).map(String::from),
+            entity_type: EntityType::Data,
+            fields,
+            ui_config: None,
+            behavior: None,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(entity).map_err(|e| e.to_string())
+}
+
+async fn add_field(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_model(state, arguments).await?;
+    let entity = entity_arg(&model, arguments, "entity")?;
+    let input = field_input(arguments)?;
+    if entity.fields.iter().any(|f| f.name == input.name) {
+        return Err(format!("Entity '{}' already has a field '{}'", entity.name, input.name));
+    }
+
+    let mut fields = entity.fields.clone();
+    fields.push(EntityField {
+        id: Uuid::new_v4(),
+        name: input.name,
+        display_name: input.display_name,
+        field_type: input.field_type,
+        required: input.required,
+        default_value: input.default_value,
+        validation: input.validation,
+        ui_config: Default::default(),
+    });
+
+    let entity = state.services.model_service
+        .update_entity(entity.id.clone(), UpdateEntityInput {
+            name: None,
+            display_name: None,
+            description: None,
+            entity_type: None,
+            fields: Some(fields),
+            ui_config: None,
+            behavior: None,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(entity).map_err(|e| e.to_string())
+}
+
+async fn add_relationship(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_model(state, arguments).await?;
+    let from_entity = entity_arg(&model, arguments, "fromEntity")?;
+    let to_entity = entity_arg(&model, arguments, "toEntity")?;
+    let from_field = str_arg(arguments, "fromField")?;
+    let to_field = str_arg(arguments, "toField")?;
+    check_field(from_entity, from_field)?;
+    check_field(to_entity, to_field)?;
+
+    let cascade = match arguments.get("cascade") {
+        Some(_) => enum_arg(arguments, "cascade", CASCADE_ACTIONS)?,
+        None => CascadeAction::None,
+    };
+
+    let relationship = state.services.model_service
+        .create_relationship(CreateRelationshipInput {
+            model_id: model.id.to_string(),
+            name: str_arg(arguments, "name")?.to_string(),
+            relationship_type: enum_arg(arguments, "relationshipType", RELATIONSHIP_TYPES)?,
+            from_entity: from_entity.id.to_string(),
+            to_entity: to_entity.id.to_string(),
+            from_field: from_field.to_string(),
+            to_field: to_field.to_string(),
+            cascade,
+            ui_config: None,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(relationship).map_err(|e| e.to_string())
+}
+
+async fn add_layout(state: &AppState, arguments: &Value) -> Result<Value, String> {
+    let model = load_model(state, arguments).await?;
+    let target_entities = arguments.get("targetEntities")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(|target| {
+            let target = target.as_str().unwrap_or_default();
+            find_entity(&model, target)
+                .map(|entity| entity.id.to_string())
+                .ok_or_else(|| format!("Model has no entity '{}'", target))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let layout = state.services.model_service
+        .create_layout(CreateLayoutInput {
+            model_id: model.id.to_string(),
+            name: str_arg(arguments, "name")?.to_string(),
+            layout_type: enum_arg(arguments, "layoutType", LAYOUT_TYPES)?,
+            target_entities,
+            components: vec![],
+            responsive: None,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(layout).map_err(|e| e.to_string())
+}
+
+// Resources and prompts
+//
+// Resources are addressed by `torque://` URIs:
+//   torque://models                                         all models
+//   torque://models/{modelId}                               model definition
+//   torque://models/{modelId}/entities/{entity}/schema      JSON Schema of an entity
+//   torque://models/{modelId}/entities/{entity}/records     most recent records
+// Subscribed sessions are notified of changes over the `GET /mcp` event stream.
+
+/// Header carrying the session created by `initialize`
+const SESSION_HEADER: &str = "mcp-session-id";
+
+const MODELS_URI: &str = "torque://models";
+
+/// Records returned by a records resource
+const RECENT_RECORDS: u64 = 20;
+
+/// Sessions without an open event stream are dropped after this long without requests
+const SESSION_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Resource subscriptions of an MCP session
+struct McpSession {
+    subscriptions: HashSet<String>,
+    last_seen: Instant,
+    /// Open event streams of the session
+    streams: usize,
+}
+
+impl McpSession {
+    fn new() -> Self {
+        Self { subscriptions: HashSet::new(), last_seen: Instant::now(), streams: 0 }
+    }
+}
+
+type Sessions = DashMap<String, McpSession>;
+
+/// MCP sessions by id, created by `initialize` when resources are enabled
+static SESSIONS: once_cell::sync::Lazy<Sessions> = once_cell::sync::Lazy::new(DashMap::new);
+
+/// Drop sessions that have no event stream and haven't been used within `ttl`
+fn expire_idle_sessions(sessions: &Sessions, ttl: Duration) {
+    sessions.retain(|_, session| session.streams > 0 || session.last_seen.elapsed() < ttl);
+}
+
+/// Counts an open event stream of a session, ending the session when its last stream
+/// is dropped, e.g. because the client disconnected
+struct StreamGuard {
+    sessions: &'static Sessions,
+    session: String,
+}
+
+impl StreamGuard {
+    /// Open a stream of an existing session
+    fn open(sessions: &'static Sessions, session: String) -> Option<Self> {
+        sessions.get_mut(&session)?.streams += 1;
+        Some(Self { sessions, session })
+    }
+}
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        self.sessions.remove_if_mut(&self.session, |_, session| {
+            session.streams = session.streams.saturating_sub(1);
+            session.streams == 0
+        });
+    }
+}
+
+enum ResourceUri<'a> {
+    Models,
+    Model(&'a str),
+    Schema(&'a str, &'a str),
+    Records(&'a str, &'a str),
+}
+
+fn parse_resource_uri(uri: &str) -> Option<ResourceUri<'_>> {
+    let path = uri.strip_prefix(MODELS_URI)?;
+    if path.is_empty() {
+        return Some(ResourceUri::Models);
+    }
+    let segments: Vec<&str> = path.strip_prefix('/')?.split('/').collect();
+    match *segments.as_slice() {
+        [model] => Some(ResourceUri::Model(model)),
+        [model, "entities", entity, "schema"] => Some(ResourceUri::Schema(model, entity)),
+        [model, "entities", entity, "records"] => Some(ResourceUri::Records(model, entity)),
+        _ => None,
+    }
+}
+
+fn model_uri(model: &TorqueModel) -> String {
+    format!("{}/{}", MODELS_URI, model.id)
+}
+
+fn entity_uri(model: &TorqueModel, entity: &ModelEntity, resource: &str) -> String {
+    format!("{}/entities/{}/{}", model_uri(model), entity.name, resource)
+}
+
+fn rpc_result(request: &Value, result: Result<Value, (i32, String)>) -> Response {
+    let response = match result {
+        Ok(result) => json!({
+            "jsonrpc": "2.0",
+            "id": request.get("id"),
+            "result": result
+        }),
+        Err((code, message)) => rpc_error(request.get("id"), code, message),
+    };
+    Json(response).into_response()
+}
+
+fn resource_not_found(uri: &str) -> (i32, String) {
+    (-32002, format!("Resource '{}' not found", uri))
+}
+
+async fn list_resources(state: &AppState) -> Result<Value, (i32, String)> {
+    let models = state.services.model_service.get_models().await
+        .map_err(|e| (-32603, format!("Failed to load models: {}", e)))?;
+
+    let mut resources = vec![json!({
+        "uri": MODELS_URI,
+        "name": "Models",
+        "description": "All models with their entities",
+        "mimeType": "application/json"
+    })];
+    for model in &models {
+        resources.push(json!({
+            "uri": model_uri(model),
+            "name": model.name,
+            "description": format!("Definition of model '{}'", model.name),
+            "mimeType": "application/json"
+        }));
+        for entity in &model.entities {
+            resources.push(json!({
+                "uri": entity_uri(model, entity, "schema"),
+                "name": format!("{} / {} schema", model.name, entity.display_name),
+                "description": format!("JSON Schema of {} records", entity.display_name),
+                "mimeType": "application/schema+json"
+            }));
+            resources.push(json!({
+                "uri": entity_uri(model, entity, "records"),
+                "name": format!("{} / {} records", model.name, entity.display_name),
+                "description": format!("The {} most recently created {} records", RECENT_RECORDS, entity.display_name),
+                "mimeType": "application/json"
+            }));
+        }
+    }
+
+    Ok(json!({ "resources": resources }))
+}
+
+fn resource_templates() -> Value {
+    json!({
+        "resourceTemplates": [
+            {
+                "uriTemplate": "torque://models/{modelId}",
+                "name": "Model definition",
+                "mimeType": "application/json"
+            },
+            {
+                "uriTemplate": "torque://models/{modelId}/entities/{entity}/schema",
+                "name": "Entity JSON Schema",
+                "mimeType": "application/schema+json"
+            },
+            {
+                "uriTemplate": "torque://models/{modelId}/entities/{entity}/records",
+                "name": "Recent entity records",
+                "mimeType": "application/json"
+            }
+        ]
+    })
+}
+
+async fn read_resource(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let uri = params.get("uri")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: uri".to_string()))?;
+    let resource = parse_resource_uri(uri).ok_or_else(|| resource_not_found(uri))?;
+
+    let (mime_type, contents) = match resource {
+        ResourceUri::Models => {
+            let models = state.services.model_service.get_models().await
+                .map_err(|e| (-32603, format!("Failed to load models: {}", e)))?;
+            let models: Vec<Value> = models.iter().map(|model| json!({
+                "id": model.id,
+                "name": model.name,
+                "description": model.description,
+                "version": model.version,
+                "uri": model_uri(model),
+                "entities": model.entities.iter().map(|e| e.name.as_str()).collect::<Vec<_>>()
+            })).collect();
+            ("application/json", json!(models))
+        }
+        ResourceUri::Model(model_id) => {
+            let id = model_id.parse::<Uuid>().map_err(|_| resource_not_found(uri))?;
+            let model = state.services.model_service.get_model(id).await
+                .map_err(|e| (-32603, format!("Failed to load model: {}", e)))?
+                .ok_or_else(|| resource_not_found(uri))?;
+            ("application/json", json!(model))
+        }
+        ResourceUri::Schema(model_id, entity) => {
+            let model = load_runtime_model(state, model_id).await.map_err(|_| resource_not_found(uri))?;
+            let entity = find_entity(&model, entity).ok_or_else(|| resource_not_found(uri))?;
+            ("application/schema+json", entity_json_schema(&model, entity))
+        }
+        ResourceUri::Records(model_id, entity) => {
+            let model = load_runtime_model(state, model_id).await.map_err(|_| resource_not_found(uri))?;
+            let entity = find_entity(&model, entity).ok_or_else(|| resource_not_found(uri))?;
+            let query = EntityListQuery { limit: RECENT_RECORDS, ..Default::default() };
+            let (records, total) = state.services.app_database_service
+                .query_entities(model.id.as_str(), &entity.name, &query)
+                .await
+                .map_err(|e| (-32603, format!("Failed to load records: {}", e)))?;
+            ("application/json", json!({ "data": records, "total": total }))
+        }
+    };
+
+    Ok(json!({
+        "contents": [
+            {
+                "uri": uri,
+                "mimeType": mime_type,
+                "text": serde_json::to_string_pretty(&contents).unwrap_or_else(|_| contents.to_string())
+            }
+        ]
+    }))
+}
+
+fn update_subscription(session: Option<&str>, params: &Value, subscribe: bool) -> Result<Value, (i32, String)> {
+    let uri = params.get("uri")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: uri".to_string()))?;
+    parse_resource_uri(uri).ok_or_else(|| resource_not_found(uri))?;
+
+    let session = session
+        .ok_or_else(|| (-32600, format!("Subscriptions need the '{}' header returned by initialize", SESSION_HEADER)))?;
+    let mut entry = SESSIONS.get_mut(session)
+        .ok_or_else(|| (-32600, format!("Unknown MCP session '{}'", session)))?;
+    if subscribe {
+        entry.subscriptions.insert(uri.to_string());
+    } else {
+        entry.subscriptions.remove(uri);
+    }
+    Ok(json!({}))
+}
+
+/// Whether an event changes the contents of a resource
+fn resource_updated(event: &ModelChangeEvent, uri: &str) -> bool {
+    let model_id = event.model_id().to_string();
+    match (event, parse_resource_uri(uri)) {
+        (
+            ModelChangeEvent::EntityDataChanged { entity_type, .. } | ModelChangeEvent::EntityDataBulkChanged { entity_type, .. },
+            Some(ResourceUri::Records(model, entity)),
+        ) => model == model_id && entity == entity_type.as_str(),
+        (
+            ModelChangeEvent::EntityDataChanged { .. }
+            | ModelChangeEvent::EntityDataBulkChanged { .. }
+            | ModelChangeEvent::FlowExecutionUpdated { .. },
+            _,
+        ) => false,
+        (
+            ModelChangeEvent::ModelCreated { .. } | ModelChangeEvent::ModelUpdated { .. } | ModelChangeEvent::ModelDeleted { .. },
+            Some(ResourceUri::Models),
+        ) => true,
+        // Collaboration events repeat edits that are also announced as definition changes
+        (
+            ModelChangeEvent::ModelCreated { .. }
+            | ModelChangeEvent::ModelUpdated { .. }
+            | ModelChangeEvent::ModelDeleted { .. }
+            | ModelChangeEvent::EntityAdded { .. }
+            | ModelChangeEvent::EntityUpdated { .. }
+            | ModelChangeEvent::EntityRemoved { .. }
+            | ModelChangeEvent::RelationshipAdded { .. }
+            | ModelChangeEvent::RelationshipUpdated { .. }
+            | ModelChangeEvent::RelationshipRemoved { .. }
+            | ModelChangeEvent::FlowAdded { .. }
+            | ModelChangeEvent::FlowUpdated { .. }
+            | ModelChangeEvent::FlowRemoved { .. }
+            | ModelChangeEvent::LayoutAdded { .. }
+            | ModelChangeEvent::LayoutUpdated { .. }
+            | ModelChangeEvent::LayoutRemoved { .. },
+            Some(ResourceUri::Model(model) | ResourceUri::Schema(model, _)),
+        ) => model == model_id,
+        _ => false,
+    }
+}
+
+/// Notifications a session should receive for an event: one per subscribed resource
+/// it changes, plus a list change when models or entities come and go
+pub fn resource_notifications(session: &str, event: &ModelChangeEvent) -> Vec<Value> {
+    let Some(entry) = SESSIONS.get(session) else {
+        return Vec::new();
+    };
+
+    let mut notifications: Vec<Value> = entry.subscriptions.iter()
+        .filter(|uri| resource_updated(event, uri))
+        .map(|uri| json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/resources/updated",
+            "params": { "uri": uri }
+        }))
+        .collect();
+    if matches!(
+        event,
+        ModelChangeEvent::ModelCreated { .. }
+            | ModelChangeEvent::ModelUpdated { .. }
+            | ModelChangeEvent::ModelDeleted { .. }
+            | ModelChangeEvent::EntityAdded { .. }
+            | ModelChangeEvent::EntityUpdated { .. }
+            | ModelChangeEvent::EntityRemoved { .. }
+    ) {
+        notifications.push(json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/resources/list_changed"
+        }));
+    }
+    notifications
+}
+
+/// Notifications telling a session that missed events to read its resources again:
+/// every subscribed resource may have changed, and so may the resource list
+fn resync_notifications(sessions: &Sessions, session: &str) -> Vec<Value> {
+    let Some(entry) = sessions.get(session) else {
+        return Vec::new();
+    };
+
+    entry.subscriptions.iter()
+        .map(|uri| json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/resources/updated",
+            "params": { "uri": uri }
+        }))
+        .chain([json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/resources/list_changed"
+        })])
+        .collect()
+}
+
+/// GET /mcp: server-sent notifications for the session in the `mcp-session-id` header
+async fn mcp_event_stream(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    let session = headers.get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or(StatusCode::BAD_REQUEST)?
+        .to_string();
+    let guard = StreamGuard::open(&SESSIONS, session).ok_or(StatusCode::NOT_FOUND)?;
+
+    let receiver = state.services.broadcast.subscribe();
+    let notifications = stream::unfold((receiver, guard), |(mut receiver, guard)| async move {
+        loop {
+            match receiver.recv().await {
+                // The stream ends with its session
+                Ok(_) if !SESSIONS.contains_key(&guard.session) => return None,
+                Ok(message) => {
+                    let notifications = resource_notifications(&guard.session, &message.event);
+                    if !notifications.is_empty() {
+                        return Some((stream::iter(notifications), (receiver, guard)));
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("MCP session {} missed {} events, asking it to resync", guard.session, skipped);
+                    let notifications = resync_notifications(&SESSIONS, &guard.session);
+                    if !notifications.is_empty() {
+                        return Some((stream::iter(notifications), (receiver, guard)));
+                    }
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+    .flatten()
+    .map(|notification| Ok(Event::default().event("message").data(notification.to_string())));
+
+    Ok(Sse::new(notifications).keep_alive(KeepAlive::default()))
+}
+
+/// DELETE /mcp: end the session in the `mcp-session-id` header
+async fn end_mcp_session(headers: HeaderMap) -> StatusCode {
+    let session = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok());
+    match session.and_then(|session| SESSIONS.remove(session)) {
+        Some(_) => StatusCode::NO_CONTENT,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+fn list_prompts() -> Value {
+    let model_id = json!({
+        "name": "modelId",
+        "description": "Model/project ID",
+        "required": true
+    });
+    json!({
+        "prompts": [
+            {
+                "name": "design_entity",
+                "description": "Design a new entity for a model and add it with the model editing tools",
+                "arguments": [
+                    model_id,
+                    {
+                        "name": "requirements",
+                        "description": "What the entity should store",
+                        "required": true
+                    }
+                ]
+            },
+            {
+                "name": "fix_verification_errors",
+                "description": "Fix the configuration errors found by verifying a model",
+                "arguments": [model_id]
+            }
+        ]
+    })
+}
+
+async fn get_prompt(state: &AppState, params: &Value) -> Result<Value, (i32, String)> {
+    let name = params.get("name")
+        .and_then(|v| v.as_str())
+        .ok_or((-32602, "Missing required parameter: name".to_string()))?;
+    let empty_args = json!({});
+    let arguments = params.get("arguments").unwrap_or(&empty_args);
+    let model = load_model(state, arguments).await.map_err(|message| (-32602, message))?;
+
+    let (description, text) = match name {
+        "design_entity" => {
+            let requirements = str_arg(arguments, "requirements").map_err(|message| (-32602, message))?;
+            (format!("Design an entity for model '{}'", model.name), design_entity_prompt(&model, requirements))
+        }
+        "fix_verification_errors" => {
+            let report = state.services.model_service.verify_model(&model).await
+                .map_err(|e| (-32603, format!("Failed to verify model: {}", e)))?;
+            (format!("Fix verification errors of model '{}'", model.name), fix_verification_errors_prompt(&model, &report))
+        }
+        _ => return Err((-32602, format!("Unknown prompt: {}", name))),
+    };
+
+    Ok(json!({
+        "description": description,
+        "messages": [
+            {
+                "role": "user",
+                "content": {
+                    "type": "text",
+                    "text": text
+                }
+            }
+        ]
+    }))
+}
+
+fn design_entity_prompt(model: &TorqueModel, requirements: &str) -> String {
+    let mut text = format!(
+        "Design a new entity for the Torque model \"{}\" ({}) that stores: {}\n\nExisting entities:\n",
+        model.name, model.id, requirements
+    );
+    if model.entities.is_empty() {
+        text.push_str("(none)\n");
+    }
+    for entity in &model.entities {
+        let fields: Vec<String> = entity.fields.iter()
+            .map(|f| format!("{} {}{}", f.name, f.field_type, if f.required { " (required)" } else { "" }))
+            .collect();
+        text.push_str(&format!("- {} (id {}): {}\n", entity.name, entity.id, fields.join(", ")));
+    }
+    text.push_str(
+        "\nPick an entity name that doesn't clash with the existing ones and give each field a type in \
+         field type notation: String, String(<max length>), Integer, Integer(<min>..<max>), Float, Boolean, \
+         DateTime, Date, Time, Json, Binary, Enum[a,b], Reference(<entity id>) or Array<type>. Link to \
+         existing entities with Reference fields using the ids above.\n\n\
+         Then create the entity with the torque_add_entity tool, connect it with torque_add_relationship \
+         and, if it needs its own screen, add a layout with torque_add_layout.",
+    );
+    text
+}
+
+fn fix_verification_errors_prompt(
+    model: &TorqueModel,
+    report: &crate::model::validation::ConfigurationErrorReport,
+) -> String {
+    if report.errors.is_empty() {
+        return format!(
+            "Verifying the Torque model \"{}\" ({}) found no configuration errors, so there is nothing to fix.",
+            model.name, model.id
+        );
+    }
+
+    let mut text = format!(
+        "Verifying the Torque model \"{}\" ({}) found {} configuration errors:\n",
+        model.name, model.id, report.total_errors
+    );
+    for (i, error) in report.errors.iter().enumerate() {
+        let (error_type, error_parameters) = error_type_and_parameters(&error.error);
+        text.push_str(&format!(
+            "\n{}. [{:?}] {}\n   {}\n   Location: {} '{}'\n   errorType: {}, errorParameters: {}\n",
+            i + 1,
+            error.severity,
+            error.title,
+            error.description,
+            error.location.component_type,
+            error.location.component_name,
+            error_type,
+            error_parameters
+        ));
+        if !error.suggested_fixes.is_empty() {
+            text.push_str(&format!("   Suggested fixes: {}\n", error.suggested_fixes.join("; ")));
+        }
+        if error.auto_fixable {
+            text.push_str("   Auto-fixable\n");
+        }
+    }
+    text.push_str(
+        "\nFix the errors in order of severity. For auto-fixable errors, call torque_get_remediation_strategies \
+         with the model ID, errorType and errorParameters, then torque_execute_auto_remediation with the \
+         chosen strategy. Fix the others with the model editing tools (torque_add_entity, torque_add_field, \
+         torque_add_relationship, torque_add_layout). Finally run torque_verify_model to confirm the model is clean.",
+    );
+    text
+}
+
+/// Variant name and fields of an error, as taken by the remediation tools
+fn error_type_and_parameters(error: &ConfigurationError) -> (String, Value) {
+    match serde_json::to_value(error) {
+        Ok(Value::String(error_type)) => (error_type, json!({})),
+        Ok(Value::Object(error)) => error.into_iter().next().unwrap_or_else(|| ("Unknown".to_string(), json!({}))),
+        _ => ("Unknown".to_string(), json!({})),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A session map of the test's own, so expiring sessions doesn't affect other tests
+    fn sessions() -> &'static Sessions {
+        Box::leak(Box::new(DashMap::new()))
+    }
+
+    #[test]
+    fn test_idle_sessions_expire_unless_streaming() {
+        let sessions = sessions();
+        let idle = Uuid::new_v4().to_string();
+        let streaming = Uuid::new_v4().to_string();
+        sessions.insert(idle.clone(), McpSession::new());
+        sessions.insert(streaming.clone(), McpSession::new());
+        let first = StreamGuard::open(sessions, streaming.clone()).unwrap();
+        let second = StreamGuard::open(sessions, streaming.clone()).unwrap();
+        assert!(StreamGuard::open(sessions, Uuid::new_v4().to_string()).is_none());
+
+        expire_idle_sessions(sessions, SESSION_IDLE_TTL);
+        assert!(sessions.contains_key(&idle));
+
+        expire_idle_sessions(sessions, Duration::ZERO);
+        assert!(!sessions.contains_key(&idle));
+        assert!(sessions.contains_key(&streaming));
+
+        // The session ends with the last of its event streams
+        drop(first);
+        assert!(sessions.contains_key(&streaming));
+        drop(second);
+        assert!(!sessions.contains_key(&streaming));
+    }
+
+    #[test]
+    fn test_lagging_sessions_are_asked_to_resync() {
+        let sessions = sessions();
+        let session = Uuid::new_v4().to_string();
+        sessions.insert(session.clone(), McpSession::new());
+        sessions.get_mut(&session).unwrap().subscriptions.insert(MODELS_URI.to_string());
+
+        let notifications = resync_notifications(sessions, &session);
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0]["method"], "notifications/resources/updated");
+        assert_eq!(notifications[0]["params"]["uri"], MODELS_URI);
+        assert_eq!(notifications[1]["method"], "notifications/resources/list_changed");
+
+        sessions.remove(&session);
+        assert!(resync_notifications(sessions, &session).is_empty());
+    }
+
+    #[test]
+    fn test_collaboration_events_dont_update_resources() {
+        let model_id = Uuid::new_v4();
+        let uri = format!("{}/{}", MODELS_URI, model_id);
+        let presence = ModelChangeEvent::presence_changed(model_id.clone(), vec![]);
+        assert!(!resource_updated(&presence, &uri));
+        assert!(!resource_updated(&presence, &format!("{}/entities/task/schema", uri)));
+
+        let entity_updated = ModelChangeEvent::entity_updated(model_id.clone(), Uuid::new_v4());
+        assert!(resource_updated(&entity_updated, &uri));
+        assert!(!resource_updated(&entity_updated, &format!("{}/{}", MODELS_URI, Uuid::new_v4())));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{Config, Result, services::ServiceRegistry};
 use axum::{
+    extract::DefaultBodyLimit,
     http::Method,
     routing::{get, post},
     Router,
@@ -24,11 +25,23 @@ pub mod mcp;
 #[derive(Clone)]
 pub struct AppState {
     pub services: Arc<ServiceRegistry>,
+    /// GraphQL schema, built once with the limits from `Config.graphql`
+    pub graphql_schema: graphql::GraphQLSchema,
+}
+
+impl AppState {
+    pub fn new(services: Arc<ServiceRegistry>) -> Self {
+        let graphql_schema = graphql::create_schema(&services.config.graphql);
+        Self { services, graphql_schema }
+    }
 }
 
 /// Create the main Axum router with all routes and middleware
 pub fn create_router(services: Arc<ServiceRegistry>) -> Router {
-    let state = AppState { services };
+    // Multipart framing adds overhead on top of the raw file size
+    let upload_body_limit = services.blob_service.max_upload_size() + 64 * 1024;
+    let state = AppState::new(services);
+    graphql::dynamic::spawn_schema_eviction(state.services.broadcast.subscribe());
 
     // Health check and status routes
     let health_routes = Router::new()
@@ -36,13 +49,17 @@ pub fn create_router(services: Arc<ServiceRegistry>) -> Router {
         .route("/metrics", get(handlers::health::metrics))
         .route("/status", get(handlers::health::status));
 
-    // API routes (placeholders for future implementation)
-    let api_routes = Router::new()
-        .route("/entities", get(handlers::entity::list_entities))
-        .route("/entities", post(handlers::entity::create_entity))
-        .route("/entities/:id", get(handlers::entity::get_entity))
-        .route("/entities/:id", post(handlers::entity::update_entity))
-        .route("/entities/:id", axum::routing::delete(handlers::entity::delete_entity));
+    // Generated REST API over app data, one collection per model entity
+    let rest_routes = Router::new()
+        .route("/models/:model/openapi.json", get(handlers::rest::get_openapi_document))
+        .route("/models/:model/:entity", get(handlers::rest::list_records).post(handlers::rest::create_record))
+        .route(
+            "/models/:model/:entity/:id",
+            get(handlers::rest::get_record)
+                .patch(handlers::rest::update_record)
+                .delete(handlers::rest::delete_record),
+        );
+    let rest_enabled = state.services.config.rest.enabled;
 
     // App Database API routes
     let app_database_routes = Router::new()
@@ -54,10 +71,36 @@ pub fn create_router(services: Arc<ServiceRegistry>) -> Router {
         .route("/models/:model_id/app-database/sync", post(handlers::app_database::sync_schema))
         .route("/models/:model_id/app-database/stats", get(handlers::app_database::get_database_stats));
 
-    // GraphQL route (placeholder)
-    let graphql_routes = Router::new()
-        .route("/graphql", post(handlers::graphql::graphql_handler))
-        .route("/graphql/playground", get(handlers::graphql::playground));
+    // JSON Schema for entity data, derived from the model
+    let schema_routes = Router::new()
+        .route("/models/:model_id/schemas/:entity", get(handlers::schema::get_entity_schema));
+
+    // Binary attachment routes for FieldType::Binary
+    let blob_routes = Router::new()
+        .route(
+            "/models/:model_id/app-database/blobs",
+            post(handlers::blob::upload_blob).layer(DefaultBodyLimit::max(upload_body_limit)),
+        )
+        .route("/models/:model_id/app-database/blobs/:blob_id", get(handlers::blob::download_blob))
+        .route("/models/:model_id/app-database/blobs/:blob_id", axum::routing::delete(handlers::blob::delete_blob))
+        .route("/models/:model_id/app-database/blobs/:blob_id/metadata", get(handlers::blob::get_blob_metadata));
+
+    // GraphQL routes; subscriptions are served over WebSocket
+    let playground_enabled = state.services.config.graphql.enable_playground;
+    let mut graphql_routes = Router::new()
+        .route("/graphql", get(handlers::graphql::graphql_get_handler).post(handlers::graphql::graphql_handler))
+        .route("/graphql/ws", get(handlers::graphql::graphql_ws_handler));
+    if playground_enabled {
+        graphql_routes = graphql_routes.route("/graphql/playground", get(handlers::graphql::playground));
+    }
+
+    // GraphQL over each model's app data, with a schema generated from the model
+    let mut model_graphql = post(handlers::graphql::model_graphql_handler);
+    if playground_enabled {
+        model_graphql = model_graphql.get(handlers::graphql::model_playground);
+    }
+    let model_graphql_routes = Router::new()
+        .route("/models/:model_id/graphql", model_graphql);
 
     // JSON-RPC route (placeholder) 
     let jsonrpc_routes = Router::new()
@@ -80,21 +123,28 @@ pub fn create_router(services: Arc<ServiceRegistry>) -> Router {
 
     // Configure CORS
     let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
         .allow_headers(Any)
         .allow_origin(Any)
         .max_age(Duration::from_secs(3600));
 
     // Build the main router
-    Router::new()
+    let mut router = Router::new()
         .nest("/health", health_routes)
-        .nest("/api/v1", api_routes)
         .nest("/api/v1", app_database_routes)
+        .nest("/api/v1", blob_routes)
+        .nest("/api/v1", schema_routes)
+        .nest("/api/v1", model_graphql_routes)
         .nest("/", graphql_routes)
         .nest("/", jsonrpc_routes)
         .nest("/", websocket_routes)
         .nest("/api/v1", mcp_routes)
-        .nest("/", frontend_routes)
+        .nest("/", frontend_routes);
+    if rest_enabled {
+        router = router.nest("/api", rest_routes);
+    }
+
+    router
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http()
@@ -152,6 +202,9 @@ pub async fn start_server(config: Config, services: Arc<ServiceRegistry>) -> Res
             cleanup_services.cache.cleanup_expired();
         }
     });
+
+    // Start background task applying model retention policies
+    let _retention_handle = services.retention_service.clone().spawn();
     
     tracing::info!("Starting axum server with router...");
     tracing::info!("Server will handle requests on {}", bound_addr);
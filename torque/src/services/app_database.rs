@@ -1,12 +1,16 @@
 use crate::{Result, Error};
 use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, PaginatorTrait, ColumnTrait, Set, QuerySelect};
 use std::sync::Arc;
-use std::collections::HashMap;
-use crate::services::{cache::CacheService, model::ModelService};
+use std::collections::{BTreeMap, HashMap};
+use crate::services::{cache::CacheService, model::ModelService, blob_store::BlobService, broadcast::BroadcastService};
+use crate::model::events::{DataChangeAction, ModelChangeEvent, RecordDiff};
+use crate::model::types::{FieldType, ModelEntity};
 use crate::database::entities::app_entities::{self, Entity as AppEntities, Model as AppEntity};
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use crate::common::Uuid;
+use crate::services::retention::not_compressed_sql;
+use sea_orm::sea_query::Expr;
 
 /// Service for managing application data using unified AppEntities table
 #[derive(Clone)]
@@ -14,8 +18,13 @@ pub struct AppDatabaseService {
     system_db: Arc<DatabaseConnection>,
     cache: Arc<CacheService>,
     model_service: Arc<ModelService>,
+    blob_service: Arc<BlobService>,
+    broadcast: Arc<BroadcastService>,
 }
 
+/// Rows compressed by the retention job are read this many at a time when matched in memory
+const COMPRESSED_BATCH_SIZE: u64 = 500;
+
 #[derive(Debug, Serialize)]
 pub struct DatabaseStatus {
     pub exists: bool,
@@ -56,6 +65,69 @@ impl Default for PaginationParams {
     }
 }
 
+/// Comparison applied by an [`EntityFilter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// Case-insensitive substring match on text values
+    Contains,
+    /// Equal to any value of a JSON array
+    In,
+}
+
+impl FilterOp {
+    pub fn parse(op: &str) -> Option<Self> {
+        match op {
+            "eq" => Some(Self::Eq),
+            "ne" => Some(Self::Ne),
+            "gt" => Some(Self::Gt),
+            "gte" => Some(Self::Gte),
+            "lt" => Some(Self::Lt),
+            "lte" => Some(Self::Lte),
+            "contains" => Some(Self::Contains),
+            "in" => Some(Self::In),
+            _ => None,
+        }
+    }
+
+    fn sql(&self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Ne => "<>",
+            Self::Gt => ">",
+            Self::Gte => ">=",
+            Self::Lt => "<",
+            Self::Lte => "<=",
+            Self::Contains => "LIKE",
+            Self::In => "IN",
+        }
+    }
+}
+
+/// Filter on a top-level field of entity data
+#[derive(Debug, Clone)]
+pub struct EntityFilter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: serde_json::Value,
+}
+
+/// Filtering, sorting and paging for [`AppDatabaseService::query_entities`]
+#[derive(Debug, Clone, Default)]
+pub struct EntityListQuery {
+    pub filters: Vec<EntityFilter>,
+    /// Data field to sort by; newest first when unset
+    pub sort_field: Option<String>,
+    pub sort_descending: bool,
+    pub limit: u64,
+    pub offset: u64,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum AppDatabaseError {
     #[error("App database not found for model: {model_id}")]
@@ -82,11 +154,15 @@ impl AppDatabaseService {
         system_db: Arc<DatabaseConnection>,
         cache: Arc<CacheService>,
         model_service: Arc<ModelService>,
+        blob_service: Arc<BlobService>,
+        broadcast: Arc<BroadcastService>,
     ) -> Self {
         Self {
             system_db,
             cache,
             model_service,
+            blob_service,
+            broadcast,
         }
     }
 
@@ -115,13 +191,9 @@ impl AppDatabaseService {
         let _model_uuid = model_id.parse::<Uuid>()
             .map_err(|_| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
         
-        // Delete all entities for this model
-        AppEntities::delete_many()
-            .filter(app_entities::Column::ModelId.eq(model_id))
-            .exec(self.get_connection())
-            .await?;
+        let removed = self.remove_all_records(model_id).await?;
 
-        tracing::info!("Dropped all entities for model: {}", model_id);
+        tracing::info!("Dropped all {} entities for model: {}", removed, model_id);
         Ok(())
     }
 
@@ -131,14 +203,33 @@ impl AppDatabaseService {
         let _model_uuid = model_id.parse::<Uuid>()
             .map_err(|_| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
         
-        // Delete all entities for this model
+        let removed = self.remove_all_records(model_id).await?;
+
+        tracing::info!("Emptied all {} entities for model: {}", removed, model_id);
+        Ok(())
+    }
+
+    /// Delete every record and attachment of a model, sending a bulk deleted event per entity type
+    async fn remove_all_records(&self, model_id: &str) -> Result<usize> {
+        let records: Vec<(String, String)> = AppEntities::find()
+            .select_only()
+            .column(app_entities::Column::EntityType)
+            .column(app_entities::Column::Id)
+            .filter(app_entities::Column::ModelId.eq(model_id))
+            .into_tuple()
+            .all(self.get_connection())
+            .await?;
+
         AppEntities::delete_many()
             .filter(app_entities::Column::ModelId.eq(model_id))
             .exec(self.get_connection())
             .await?;
 
-        tracing::info!("Emptied all entities for model: {}", model_id);
-        Ok(())
+        self.blob_service.delete_for_model(model_id).await?;
+
+        let removed = records.len();
+        broadcast_bulk_change(&self.broadcast, model_id, DataChangeAction::Deleted, records).await;
+        Ok(removed)
     }
 
     /// Synchronize database schema with model definition (no-op for unified schema)
@@ -160,14 +251,14 @@ impl AppDatabaseService {
         &self,
         model_id: &str,
         entity_type: &str,
-        entity_data: serde_json::Value,
+        mut entity_data: serde_json::Value,
     ) -> Result<AppEntity> {
-        // Validate model_id is a valid UUID format
-        let _model_uuid = model_id.parse::<Uuid>()
-            .map_err(|_| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
+        let blobs = self.check_entity_data(model_id, entity_type, &mut entity_data).await?;
+        let entity_id = uuid::Uuid::new_v4().to_string();
+        self.blob_service.attach(model_id, &entity_id, &blobs).await?;
 
         let new_entity = app_entities::ActiveModel {
-            id: Set(uuid::Uuid::new_v4().to_string()),
+            id: Set(entity_id),
             model_id: Set(model_id.to_string()),
             entity_type: Set(entity_type.to_string()),
             data: Set(entity_data.into()),
@@ -179,15 +270,84 @@ impl AppDatabaseService {
             .exec_with_returning(self.get_connection())
             .await?;
 
+        self.broadcast_change(DataChangeAction::Created, None, &entity).await;
         Ok(entity)
     }
 
+    /// Validate entity data against the JSON Schema generated for its entity type,
+    /// with the default values it would be stored with
+    pub async fn validate_entity_data(
+        &self,
+        model_id: &str,
+        entity_type: &str,
+        entity_data: &serde_json::Value,
+    ) -> Result<()> {
+        self.check_entity_data(model_id, entity_type, &mut entity_data.clone()).await.map(|_| ())
+    }
+
+    /// Fill in field defaults and validate entity data, returning the `(field_name, blob_id)`
+    /// pairs its Binary fields refer to
+    async fn check_entity_data(
+        &self,
+        model_id: &str,
+        entity_type: &str,
+        entity_data: &mut serde_json::Value,
+    ) -> Result<Vec<(String, String)>> {
+        let model_uuid = model_id.parse::<Uuid>()
+            .map_err(|_| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
+        let model = self.model_service.get_runtime_model(model_uuid, None).await?
+            .ok_or_else(|| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
+
+        let entity = crate::model::json_schema::find_entity(&model, entity_type)
+            .ok_or_else(|| Error::Validation(format!("Model has no entity '{}'", entity_type)))?;
+
+        crate::model::json_schema::apply_defaults(entity, entity_data);
+        crate::model::json_schema::validate_entity_data(&model, entity, entity_data)?;
+        Ok(blob_references(entity, entity_data))
+    }
+
+    /// Check the owner named by a blob upload: the model must exist, a record must belong
+    /// to it, and a field must be a Binary field (of the record's entity type when given)
+    pub async fn check_blob_owner(
+        &self,
+        model_id: &str,
+        entity_id: Option<&str>,
+        field_name: Option<&str>,
+    ) -> Result<()> {
+        let model_uuid = model_id.parse::<Uuid>()
+            .map_err(|_| Error::InvalidInput(format!("Invalid model ID '{}'", model_id)))?;
+        let model = self.model_service.get_runtime_model(model_uuid, None).await?
+            .ok_or_else(|| Error::ModelNotFound(model_id.to_string()))?;
+
+        let entity_type = match entity_id {
+            Some(entity_id) => Some(
+                self.get_entity(model_id, entity_id).await?
+                    .ok_or_else(|| Error::NotFound(format!("Record '{}' not found in model {}", entity_id, model_id)))?
+                    .entity_type,
+            ),
+            None => None,
+        };
+
+        if let Some(field_name) = field_name {
+            let is_binary_field = model.entities.iter()
+                .filter(|entity| entity_type.as_deref().is_none_or(|t| entity.name == t))
+                .flat_map(|entity| &entity.fields)
+                .any(|field| field.name == field_name && holds_blobs(&field.field_type));
+            if !is_binary_field {
+                return Err(Error::Validation(format!(
+                    "'{}' is not a Binary field of {}", field_name, entity_type.as_deref().unwrap_or("this model")
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Update entity instance in the unified AppEntities table
     pub async fn update_entity(
         &self,
         model_id: &str,
         entity_id: &str,
-        entity_data: serde_json::Value,
+        mut entity_data: serde_json::Value,
     ) -> Result<AppEntity> {
         // Validate model_id is a valid UUID format
         let _model_uuid = model_id.parse::<Uuid>()
@@ -199,6 +359,10 @@ impl AppDatabaseService {
             .await?
             .ok_or_else(|| Error::NotFound("Entity not found".to_string()))?;
 
+        let blobs = self.check_entity_data(model_id, &entity.entity_type, &mut entity_data).await?;
+        self.blob_service.attach(model_id, entity_id, &blobs).await?;
+
+        let previous_data = entity.data.clone();
         let mut entity: app_entities::ActiveModel = entity.into();
         entity.data = Set(entity_data.into());
         entity.updated_at = Set(chrono::Utc::now().naive_utc());
@@ -207,23 +371,105 @@ impl AppDatabaseService {
             .exec(self.get_connection())
             .await?;
 
+        self.broadcast_change(DataChangeAction::Updated, Some(previous_data), &updated_entity).await;
         Ok(updated_entity)
     }
 
+    /// Merge top-level fields into an existing record, like a PATCH. When `entity_type`
+    /// is given the record must be of that type. The merged record is validated and
+    /// announced like [`Self::update_entity`].
+    pub async fn patch_entity(
+        &self,
+        model_id: &str,
+        entity_type: Option<&str>,
+        entity_id: &str,
+        changes: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<AppEntity> {
+        let record = self.get_entity(model_id, entity_id).await?
+            .filter(|record| entity_type.is_none_or(|t| record.entity_type == t))
+            .ok_or_else(|| Error::NotFound(format!(
+                "{} record '{}' not found", entity_type.unwrap_or("Entity"), entity_id
+            )))?;
+
+        let mut data = crate::services::retention::inflate_if_compressed(record.data);
+        match data.as_object_mut() {
+            Some(object) => object.extend(changes),
+            None => data = serde_json::Value::Object(changes),
+        }
+
+        self.update_entity(model_id, entity_id, data).await
+    }
+
     /// Delete entity instance from the unified AppEntities table
     pub async fn delete_entity(&self, model_id: &str, entity_id: &str) -> Result<()> {
         // Validate model_id is a valid UUID format
         let _model_uuid = model_id.parse::<Uuid>()
             .map_err(|_| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
 
+        let existing = AppEntities::find_by_id(entity_id.to_string())
+            .filter(app_entities::Column::ModelId.eq(model_id))
+            .one(self.get_connection())
+            .await?;
+
         AppEntities::delete_by_id(entity_id.to_string())
             .filter(app_entities::Column::ModelId.eq(model_id))
             .exec(self.get_connection())
             .await?;
 
+        if let Some(existing) = existing {
+            self.broadcast_change(DataChangeAction::Deleted, None, &existing).await;
+        }
+
+        // Remove any binary attachments owned by the deleted entity
+        let removed = self.blob_service.delete_for_entity(model_id, entity_id).await?;
+        if removed > 0 {
+            tracing::debug!("Removed {} blobs for deleted entity {}", removed, entity_id);
+        }
+
         Ok(())
     }
 
+    /// Notify subscribers that a record changed. Created and updated records are sent
+    /// whole when small enough; larger updates send the changed fields instead.
+    async fn broadcast_change(&self, action: DataChangeAction, previous_data: Option<serde_json::Value>, entity: &AppEntity) {
+        let Ok(model_uuid) = Uuid::parse(&entity.model_id) else {
+            return;
+        };
+
+        let max_bytes = self.broadcast.max_record_payload_bytes();
+        let fits = |value: &serde_json::Value| serde_json::to_vec(value).is_ok_and(|bytes| bytes.len() <= max_bytes);
+        let (record, diff) = match action {
+            DataChangeAction::Deleted => (None, None),
+            _ => {
+                let record = entity_record(entity.clone());
+                if fits(&record) {
+                    (Some(record), None)
+                } else {
+                    let diff = previous_data.map(|previous| {
+                        RecordDiff::between(
+                            &crate::services::retention::inflate_if_compressed(previous),
+                            &crate::services::retention::inflate_if_compressed(entity.data.clone()),
+                        )
+                    });
+                    let diff = diff.filter(|diff| serde_json::to_value(diff).is_ok_and(|value| fits(&value)));
+                    (None, diff)
+                }
+            }
+        };
+
+        let event = ModelChangeEvent::entity_data_changed(
+            model_uuid,
+            entity.entity_type.clone(),
+            entity.id.clone(),
+            action,
+            record,
+            diff,
+        );
+        if let Err(e) = self.broadcast.broadcast_event(event).await {
+            tracing::warn!("Failed to broadcast {} change of {} record {}: {}", action.as_str(), entity.entity_type, entity.id, e);
+        }
+    }
+
     /// Get entity count for a specific entity type
     pub async fn get_entity_count(&self, model_id: &str, entity_type: &str) -> Result<u64> {
         // Validate model_id is a valid UUID format
@@ -263,21 +509,208 @@ impl AppDatabaseService {
         // Extract the JSON data from each entity
         let results: Vec<serde_json::Value> = entities
             .into_iter()
-            .map(|entity| {
-                let mut value = entity.data.clone();
-                // Add metadata
-                if let serde_json::Value::Object(ref mut map) = value {
-                    map.insert("_id".to_string(), serde_json::Value::String(entity.id));
-                    map.insert("_created_at".to_string(), serde_json::Value::String(entity.created_at.to_string()));
-                    map.insert("_updated_at".to_string(), serde_json::Value::String(entity.updated_at.to_string()));
-                }
-                value
-            })
+            .map(entity_record)
             .collect();
 
         Ok(results)
     }
 
+    /// Get a single entity instance
+    pub async fn get_entity(&self, model_id: &str, entity_id: &str) -> Result<Option<AppEntity>> {
+        let _model_uuid = model_id.parse::<Uuid>()
+            .map_err(|_| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
+
+        let entity = AppEntities::find_by_id(entity_id.to_string())
+            .filter(app_entities::Column::ModelId.eq(model_id))
+            .one(self.get_connection())
+            .await?;
+
+        Ok(entity)
+    }
+
+    /// Get an entity instance by ID, whatever model it belongs to
+    pub async fn find_entity(&self, entity_id: &str) -> Result<Option<AppEntity>> {
+        Ok(AppEntities::find_by_id(entity_id.to_string())
+            .one(self.get_connection())
+            .await?)
+    }
+
+    /// Query entities with filters on data fields, sorting and pagination.
+    /// Returns the requested page (as records with `_id` metadata) and the total match count.
+    ///
+    /// Filters run in the database against the stored JSON. The database can't look
+    /// inside rows compressed by the retention job, so when filtering or sorting those
+    /// are matched in memory, in bounded batches, and merged into the page.
+    pub async fn query_entities(
+        &self,
+        model_id: &str,
+        entity_type: &str,
+        query: &EntityListQuery,
+    ) -> Result<(Vec<serde_json::Value>, u64)> {
+        use sea_orm::{ConnectionTrait, DatabaseBackend, Statement};
+
+        let _model_uuid = model_id.parse::<Uuid>()
+            .map_err(|_| AppDatabaseError::ModelNotFound { model_id: model_id.to_string() })?;
+
+        let backend = self.get_connection().get_database_backend();
+        let postgres = backend == DatabaseBackend::Postgres;
+        let mut values: Vec<sea_orm::Value> = vec![model_id.into(), entity_type.into()];
+        let placeholder = |values: &mut Vec<sea_orm::Value>, value: sea_orm::Value| {
+            values.push(value);
+            if postgres { format!("${}", values.len()) } else { "?".to_string() }
+        };
+
+        let mut conditions = if postgres {
+            vec!["model_id = $1::uuid".to_string(), "entity_type = $2".to_string()]
+        } else {
+            vec!["model_id = ?".to_string(), "entity_type = ?".to_string()]
+        };
+
+        for filter in &query.filters {
+            let field = if postgres {
+                placeholder(&mut values, filter.field.clone().into())
+            } else {
+                placeholder(&mut values, format!("$.{}", filter.field).into())
+            };
+            let condition = match (postgres, filter.op) {
+                (_, FilterOp::In) => {
+                    let candidates: Vec<String> = filter.value.as_array().map(Vec::as_slice).unwrap_or_default()
+                        .iter()
+                        .map(|v| match postgres {
+                            true => format!("{}::jsonb", placeholder(&mut values, v.to_string().into())),
+                            false => placeholder(&mut values, sqlite_value(v)),
+                        })
+                        .collect();
+                    match (candidates.is_empty(), postgres) {
+                        (true, _) => "1 = 0".to_string(),
+                        (false, true) => format!("(data -> {}) IN ({})", field, candidates.join(", ")),
+                        (false, false) => format!("json_extract(data, {}) IN ({})", field, candidates.join(", ")),
+                    }
+                }
+                (true, FilterOp::Contains) => {
+                    let pattern = placeholder(&mut values, format!("%{}%", like_escape(&json_text(&filter.value))).into());
+                    format!("(data ->> {}) ILIKE {} ESCAPE '\\'", field, pattern)
+                }
+                (true, op) => {
+                    let value = placeholder(&mut values, filter.value.to_string().into());
+                    format!("(data -> {}) {} {}::jsonb", field, op.sql(), value)
+                }
+                (false, FilterOp::Contains) => {
+                    let pattern = placeholder(&mut values, format!("%{}%", like_escape(&json_text(&filter.value))).into());
+                    format!("json_extract(data, {}) LIKE {} ESCAPE '\\'", field, pattern)
+                }
+                (false, op) => {
+                    let value = placeholder(&mut values, sqlite_value(&filter.value));
+                    format!("json_extract(data, {}) {} {}", field, op.sql(), value)
+                }
+            };
+            conditions.push(condition);
+        }
+
+        let (compressed_matches, compressed_total) = match query.filters.is_empty() && query.sort_field.is_none() {
+            true => (Vec::new(), 0),
+            false => self.match_compressed(model_id, entity_type, query).await?,
+        };
+        if compressed_total > 0 {
+            conditions.push(not_compressed_sql(backend));
+        }
+        let where_clause = conditions.join(" AND ");
+
+        let count_row = self.get_connection().query_one(Statement::from_sql_and_values(
+            backend,
+            format!("SELECT COUNT(*) AS count FROM app_entities WHERE {}", where_clause),
+            values.clone(),
+        )).await?;
+        let total: i64 = match count_row {
+            Some(row) => row.try_get("", "count")?,
+            None => 0,
+        };
+
+        let direction = if query.sort_descending { "DESC" } else { "ASC" };
+        let order_by = match &query.sort_field {
+            Some(field) if postgres => {
+                let field = placeholder(&mut values, field.clone().into());
+                format!("(data -> {}) {}, created_at DESC", field, direction)
+            }
+            Some(field) => {
+                let field = placeholder(&mut values, format!("$.{}", field).into());
+                format!("json_extract(data, {}) {}, created_at DESC", field, direction)
+            }
+            None => "created_at DESC".to_string(),
+        };
+        // With compressed matches to merge, the page is cut after merging
+        let (limit, offset) = match compressed_total == 0 {
+            true => (query.limit, query.offset),
+            false => (query.offset + query.limit, 0),
+        };
+        let limit = placeholder(&mut values, (limit as i64).into());
+        let offset = placeholder(&mut values, (offset as i64).into());
+        let columns = if postgres {
+            "id::text AS id, model_id::text AS model_id, entity_type, data, created_at, updated_at"
+        } else {
+            "id, model_id, entity_type, data, created_at, updated_at"
+        };
+
+        let entities = AppEntities::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                backend,
+                format!(
+                    "SELECT {} FROM app_entities WHERE {} ORDER BY {} LIMIT {} OFFSET {}",
+                    columns, where_clause, order_by, limit, offset
+                ),
+                values,
+            ))
+            .all(self.get_connection())
+            .await?;
+        let mut records: Vec<serde_json::Value> = entities.into_iter().map(entity_record).collect();
+
+        if compressed_total == 0 {
+            return Ok((records, total as u64));
+        }
+        let total = total as u64 + compressed_total;
+        records.extend(compressed_matches);
+        records.sort_by(|a, b| compare_records(a, b, query));
+        let page = records.into_iter()
+            .skip(query.offset as usize)
+            .take(query.limit as usize)
+            .collect();
+        Ok((page, total))
+    }
+
+    /// Match compressed rows against a query, reading them `COMPRESSED_BATCH_SIZE` at a
+    /// time. Only the first `offset + limit` matches in query order are kept, since no
+    /// later one can reach the page. Returns those and the number of matches.
+    async fn match_compressed(
+        &self,
+        model_id: &str,
+        entity_type: &str,
+        query: &EntityListQuery,
+    ) -> Result<(Vec<serde_json::Value>, u64)> {
+        let backend = self.get_connection().get_database_backend();
+        let keep = (query.offset + query.limit) as usize;
+        let mut pages = AppEntities::find()
+            .filter(app_entities::Column::ModelId.eq(model_id))
+            .filter(app_entities::Column::EntityType.eq(entity_type))
+            .filter(Expr::cust(format!("NOT {}", not_compressed_sql(backend))))
+            .order_by_asc(app_entities::Column::Id)
+            .paginate(self.get_connection(), COMPRESSED_BATCH_SIZE);
+
+        let mut matches = Vec::new();
+        let mut total = 0;
+        while let Some(rows) = pages.fetch_and_next().await? {
+            let before = matches.len();
+            matches.extend(
+                rows.into_iter()
+                    .map(entity_record)
+                    .filter(|record| query.filters.iter().all(|filter| matches_filter(record, filter))),
+            );
+            total += (matches.len() - before) as u64;
+            matches.sort_by(|a, b| compare_records(a, b, query));
+            matches.truncate(keep);
+        }
+        Ok((matches, total))
+    }
+
     /// Get database status for a model
     pub async fn get_database_status(&self, model_id: &str) -> Result<DatabaseStatus> {
         // With unified schema, database always exists - check if model exists
@@ -352,34 +785,209 @@ impl AppDatabaseService {
         let data: serde_json::Value = serde_json::from_str(&json_content)
             .map_err(|e| Error::Serialization(e))?;
 
+        let sample_data = data.get("sample_data")
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        let total_created = self.load_records(model_id, &sample_data).await?;
+
+        tracing::info!("Loaded {} sample entities for model {}", total_created, model.name);
+        Ok(total_created)
+    }
+
+    /// Load the seed data of a model template into the database
+    pub async fn load_template_sample_data(&self, model_id: &str, template_id: &str) -> Result<u64> {
+        let template = crate::model::templates::find_template(template_id)
+            .ok_or_else(|| Error::NotFound(format!("Model template '{}' not found", template_id)))?;
+
+        let total_created = self.load_records(model_id, &template.sample_data()).await?;
+        tracing::info!("Loaded {} entities from template '{}' for model {}", total_created, template_id, model_id);
+        Ok(total_created)
+    }
+
+    /// Create entities from records keyed by entity type, skipping records that fail
+    pub async fn load_records(
+        &self,
+        model_id: &str,
+        records: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<u64> {
         let mut total_created = 0u64;
 
-        // Load sample data if it exists
-        if let Some(sample_data) = data.get("sample_data") {
-            if let Some(sample_data_obj) = sample_data.as_object() {
-                for (entity_type, entity_data) in sample_data_obj {
-                    if let Some(data_array) = entity_data.as_array() {
-                        tracing::info!("Loading {} {} entities from sample data", data_array.len(), entity_type);
-                        
-                        for entity_instance in data_array {
-                            let entity_json = serde_json::to_value(entity_instance)
-                                .map_err(|e| Error::Serialization(e))?;
-                            
-                            match self.create_entity(model_id, entity_type, entity_json).await {
-                                Ok(_) => {
-                                    total_created += 1;
-                                }
-                                Err(e) => {
-                                    tracing::warn!("Failed to create {} entity: {}", entity_type, e);
-                                }
-                            }
+        for (entity_type, entity_data) in records {
+            if let Some(data_array) = entity_data.as_array() {
+                tracing::info!("Loading {} {} entities from sample data", data_array.len(), entity_type);
+
+                for entity_instance in data_array {
+                    match self.create_entity(model_id, entity_type, entity_instance.clone()).await {
+                        Ok(_) => {
+                            total_created += 1;
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to create {} entity: {}", entity_type, e);
                         }
                     }
                 }
             }
         }
 
-        tracing::info!("Loaded {} sample entities for model {}", total_created, model.name);
         Ok(total_created)
     }
-}
\ No newline at end of file
+}
+
+/// Send one bulk change event per entity type for `(entity_type, record_id)` pairs of a model
+pub(crate) async fn broadcast_bulk_change(
+    broadcast: &BroadcastService,
+    model_id: &str,
+    action: DataChangeAction,
+    records: impl IntoIterator<Item = (String, String)>,
+) {
+    let Ok(model_uuid) = Uuid::parse(model_id) else {
+        return;
+    };
+
+    let mut by_entity: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (entity_type, record_id) in records {
+        by_entity.entry(entity_type).or_default().push(record_id);
+    }
+
+    let max_bytes = broadcast.max_record_payload_bytes();
+    for (entity_type, record_ids) in by_entity {
+        let count = record_ids.len() as u64;
+        // Subscribers reload the entity's data when the IDs are too many to send
+        let record_ids = Some(record_ids)
+            .filter(|ids| serde_json::to_vec(ids).is_ok_and(|bytes| bytes.len() <= max_bytes));
+        let event = ModelChangeEvent::entity_data_bulk_changed(model_uuid.clone(), entity_type.clone(), action, count, record_ids);
+        if let Err(e) = broadcast.broadcast_event(event).await {
+            tracing::warn!("Failed to broadcast {} {} of {} records of model {}: {}", action.as_str(), count, entity_type, model_id, e);
+        }
+    }
+}
+
+/// Readable entity data with `_id`, `_created_at` and `_updated_at` metadata
+pub fn entity_record(entity: AppEntity) -> serde_json::Value {
+    let mut value = crate::services::retention::inflate_if_compressed(entity.data);
+    if let serde_json::Value::Object(ref mut map) = value {
+        map.insert("_id".to_string(), serde_json::Value::String(entity.id));
+        map.insert("_created_at".to_string(), serde_json::Value::String(entity.created_at.to_string()));
+        map.insert("_updated_at".to_string(), serde_json::Value::String(entity.updated_at.to_string()));
+    }
+    value
+}
+
+/// Whether values of a field type are blob IDs
+fn holds_blobs(field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Binary => true,
+        FieldType::Array { element_type } => matches!(element_type.as_ref(), FieldType::Binary),
+        _ => false,
+    }
+}
+
+/// Blob IDs held by an entity's Binary fields (or arrays of them), as `(field_name, blob_id)`
+fn blob_references(entity: &ModelEntity, data: &serde_json::Value) -> Vec<(String, String)> {
+    let mut references = Vec::new();
+    for field in &entity.fields {
+        let Some(value) = data.get(&field.name).filter(|_| holds_blobs(&field.field_type)) else {
+            continue;
+        };
+        let ids = match value {
+            serde_json::Value::Array(items) => items.iter().filter_map(|item| item.as_str()).collect(),
+            other => other.as_str().into_iter().collect::<Vec<_>>(),
+        };
+        references.extend(ids.into_iter().map(|id| (field.name.clone(), id.to_string())));
+    }
+    references
+}
+
+/// Evaluate a filter against a record in memory, with SQL semantics for missing values
+fn matches_filter(record: &serde_json::Value, filter: &EntityFilter) -> bool {
+    let Some(value) = record.get(&filter.field).filter(|value| !value.is_null()) else {
+        return false;
+    };
+    let ordering = || compare_json(Some(value), Some(&filter.value));
+    match filter.op {
+        FilterOp::Eq => ordering().is_eq(),
+        FilterOp::Ne => ordering().is_ne(),
+        FilterOp::Gt => ordering().is_gt(),
+        FilterOp::Gte => ordering().is_ge(),
+        FilterOp::Lt => ordering().is_lt(),
+        FilterOp::Lte => ordering().is_le(),
+        FilterOp::Contains => json_text(value).to_lowercase().contains(&json_text(&filter.value).to_lowercase()),
+        FilterOp::In => filter.value.as_array().is_some_and(|candidates| {
+            candidates.iter().any(|candidate| compare_json(Some(value), Some(candidate)).is_eq())
+        }),
+    }
+}
+
+/// Order records the way `query_entities` orders them in SQL: by the sort field, then newest first
+fn compare_records(a: &serde_json::Value, b: &serde_json::Value, query: &EntityListQuery) -> std::cmp::Ordering {
+    let by_field = match &query.sort_field {
+        Some(field) if query.sort_descending => compare_json(b.get(field), a.get(field)),
+        Some(field) => compare_json(a.get(field), b.get(field)),
+        None => std::cmp::Ordering::Equal,
+    };
+    by_field.then_with(|| b["_created_at"].as_str().cmp(&a["_created_at"].as_str()))
+}
+
+/// Compare JSON values like SQLite compares json_extract() results:
+/// nulls first, then numbers (booleans as 0 and 1), then text
+fn compare_json(a: Option<&serde_json::Value>, b: Option<&serde_json::Value>) -> std::cmp::Ordering {
+    use serde_json::Value;
+
+    fn rank(value: Option<&Value>) -> u8 {
+        match value {
+            None | Some(Value::Null) => 0,
+            Some(Value::Bool(_)) | Some(Value::Number(_)) => 1,
+            Some(Value::String(_)) => 2,
+            Some(_) => 3,
+        }
+    }
+    fn number(value: &Value) -> f64 {
+        match value {
+            Value::Bool(b) => *b as u8 as f64,
+            other => other.as_f64().unwrap_or_default(),
+        }
+    }
+
+    match (a, b) {
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        (Some(a), Some(b)) if rank(Some(a)) == 1 && rank(Some(b)) == 1 => {
+            number(a).partial_cmp(&number(b)).unwrap_or(std::cmp::Ordering::Equal)
+        }
+        (Some(a), Some(b)) if rank(Some(a)) == 3 && rank(Some(b)) == 3 => a.to_string().cmp(&b.to_string()),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Text form of a filter value, without JSON quoting for strings
+fn json_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Escape `LIKE` wildcards so the text matches literally with `ESCAPE '\'`
+fn like_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Bind a JSON scalar the way SQLite's json_extract() returns it
+fn sqlite_value(value: &serde_json::Value) -> sea_orm::Value {
+    match value {
+        serde_json::Value::Bool(b) => (*b as i64).into(),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64().unwrap_or_default().into(),
+        },
+        serde_json::Value::String(s) => s.clone().into(),
+        other => other.to_string().into(),
+    }
+}
@@ -1,32 +1,145 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
+use sea_orm::{
+    ActiveValue::NotSet, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
 use tokio::sync::{broadcast, RwLock};
 use crate::common::{Uuid, UtcDateTime};
 use axum::extract::ws::{WebSocket, Message};
 use futures_util::{stream::SplitSink, SinkExt};
 use tracing::{info, warn, debug, error};
+use serde::{Deserialize, Serialize};
 use serde_json;
 
-use crate::model::events::{ModelChangeEvent, ModelEventMessage};
+use crate::config::EventLogConfig;
+use crate::database::entities::event_log;
+use crate::model::events::{ModelChangeEvent, ModelEventMessage, EVENT_KINDS};
 use crate::Result;
 
 /// Maximum number of queued events per broadcast channel
-const BROADCAST_CHANNEL_SIZE: usize = 1000;
+pub const BROADCAST_CHANNEL_SIZE: usize = 1000;
+
+/// Trim the event log down to the retained events every this many events
+const LOG_TRIM_INTERVAL: i64 = 100;
+
+/// Most events a [`SeqTracker`] holds past a gap before giving up on the missing ones
+const MAX_SEQS_PAST_GAP: usize = 256;
+
+/// Outcome of resuming an event stream from a sequence number
+#[derive(Debug)]
+pub enum Replay {
+    /// The missed events, oldest first
+    Events(Vec<ModelEventMessage>),
+    /// The missed events cannot be replayed; the client has to reload its state
+    ResyncRequired { latest_seq: u64 },
+}
+
+/// Tracks which logged events were seen, when they can arrive out of sequence order
+/// because of concurrent publishers or events relayed from other nodes
+#[derive(Debug, Clone)]
+pub struct SeqTracker {
+    /// Every event up to this sequence number was seen
+    contiguous: u64,
+    /// Events seen past the first gap
+    ahead: BTreeSet<u64>,
+}
+
+impl SeqTracker {
+    /// Tracker that has seen every event up to `seq`
+    pub fn new(seq: u64) -> Self {
+        Self { contiguous: seq, ahead: BTreeSet::new() }
+    }
+
+    /// Record an event, returning false when it was already seen
+    pub fn insert(&mut self, seq: u64) -> bool {
+        if seq <= self.contiguous || !self.ahead.insert(seq) {
+            return false;
+        }
+        while self.ahead.first() == Some(&(self.contiguous + 1)) {
+            self.ahead.pop_first();
+            self.contiguous += 1;
+        }
+        true
+    }
+
+    /// Sequence number up to which every event was seen, where a resumed stream has to start
+    pub fn contiguous(&self) -> u64 {
+        self.contiguous
+    }
+
+    /// Whether so many events arrived past a gap that the missing ones are not coming
+    pub fn gap_stalled(&self) -> bool {
+        self.ahead.len() > MAX_SEQS_PAST_GAP
+    }
+
+    /// Give up on the missing events and treat everything up to the latest seen event
+    /// as seen. Returns the sequence number before the first gap.
+    pub fn skip_gaps(&mut self) -> u64 {
+        let before_gap = self.contiguous;
+        if let Some(last) = self.ahead.pop_last() {
+            self.contiguous = last;
+        }
+        self.ahead.clear();
+        before_gap
+    }
+}
+
+/// Events a client subscribes to
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EventFilter {
+    /// Only events of this model
+    pub model_id: Option<Uuid>,
+    /// Only app data changes of these entity types; model definition events are not affected
+    pub entity_types: Option<Vec<String>>,
+    /// Only events of these kinds, e.g. `EntityDataChanged`
+    pub event_kinds: Option<Vec<String>>,
+}
+
+impl EventFilter {
+    /// Reject event kinds that no event has
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        match self.event_kinds.iter().flatten().find(|kind| !EVENT_KINDS.contains(&kind.as_str())) {
+            Some(kind) => Err(format!("Unknown event kind '{}'", kind)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn matches(&self, event: &ModelChangeEvent) -> bool {
+        if self.model_id.as_ref().is_some_and(|model_id| event.model_id() != *model_id) {
+            return false;
+        }
+        if self.event_kinds.as_ref().is_some_and(|kinds| !kinds.iter().any(|kind| kind == event.kind())) {
+            return false;
+        }
+        match (event, &self.entity_types) {
+            (
+                ModelChangeEvent::EntityDataChanged { entity_type, .. } | ModelChangeEvent::EntityDataBulkChanged { entity_type, .. },
+                Some(entity_types),
+            ) => {
+                entity_types.iter().any(|t| t == entity_type)
+            }
+            _ => true,
+        }
+    }
+}
 
 /// WebSocket client connection info
 #[derive(Debug, Clone)]
 pub struct WebSocketClient {
     pub client_id: String,
     pub connected_at: UtcDateTime,
-    pub model_filter: Option<Uuid>, // Optional filter for specific model
+    pub filter: EventFilter,
 }
 
 /// High-performance broadcast service for real-time model synchronization
 /// 
 /// Architecture:
-/// 1. Model service emits events to a broadcast channel
+/// 1. Model service emits events, which are numbered in the event log and sent to a broadcast channel
 /// 2. WebSocket handlers subscribe to this channel in their send_task
 /// 3. Each WebSocket handler sends messages to its own client
+/// 4. With the `postgres` feature on a Postgres database, events are also sent to other
+///    nodes via NOTIFY, and events from other nodes are fed into the channel (see `event_fanout`)
 /// 
 /// Note: The start_broadcast_loop method is NOT used to avoid duplicate sends.
 /// WebSocket handlers already handle message distribution.
@@ -37,80 +150,177 @@ pub struct BroadcastService {
     clients: Arc<RwLock<HashMap<String, WebSocketClient>>>,
     /// WebSocket senders for active connections
     websocket_senders: Arc<RwLock<HashMap<String, SplitSink<WebSocket, Message>>>>,
+    /// Database holding the event log
+    db: Arc<DatabaseConnection>,
+    config: EventLogConfig,
+    /// Marks events this node publishes to other nodes, so it can skip its own
+    node_id: Uuid,
 }
 
 impl BroadcastService {
     /// Create a new broadcast service
-    pub fn new() -> Self {
+    pub fn new(db: Arc<DatabaseConnection>, config: EventLogConfig) -> Self {
         let (event_sender, _) = broadcast::channel(BROADCAST_CHANNEL_SIZE);
         
         Self {
             event_sender,
             clients: Arc::new(RwLock::new(HashMap::new())),
             websocket_senders: Arc::new(RwLock::new(HashMap::new())),
+            db,
+            config,
+            node_id: Uuid::new_v4(),
         }
     }
 
     /// Broadcast a model change event to all connected clients
     pub async fn broadcast_event(&self, event: ModelChangeEvent) -> Result<()> {
-        let message = ModelEventMessage::new(event.clone());
-        
-        // Send to broadcast channel
+        self.publish(ModelEventMessage::new(event)).await
+    }
+
+    /// Broadcast a model change event excluding a specific client
+    pub async fn broadcast_event_excluding(&self, event: ModelChangeEvent, exclude_client: String) -> Result<()> {
+        self.publish(ModelEventMessage::with_exclusion(event, exclude_client)).await
+    }
+
+    /// Record the event in the event log and send it to the broadcast channel.
+    /// The sequence number is the event log row id. Concurrent publishers are not
+    /// serialized, so receivers can see events slightly out of sequence order.
+    async fn publish(&self, mut message: ModelEventMessage) -> Result<()> {
+        if !message.event.is_ephemeral() {
+            match self.append_to_log(&message).await {
+                Ok(seq) => message.seq = Some(seq),
+                // Still deliver live; reconnecting clients will not get this event replayed
+                Err(e) => error!("Failed to record {} in the event log: {}", message.event.description(), e),
+            }
+        }
+
+        #[cfg(feature = "postgres")]
+        if sea_orm::ConnectionTrait::get_database_backend(self.db.as_ref()) == sea_orm::DatabaseBackend::Postgres {
+            if let Err(e) = crate::services::event_fanout::notify(self.db.as_ref(), &self.node_id, &message).await {
+                warn!("Failed to notify other nodes of {}: {}", message.event.description(), e);
+            }
+        }
+
+        let description = message.event.description();
         match self.event_sender.send(message) {
             Ok(receiver_count) => {
-                if receiver_count > 0 {
-                    debug!(
-                        "Broadcasted {} to {} receivers: {}", 
-                        event.description(),
-                        receiver_count,
-                        serde_json::to_string(&event).unwrap_or_default()
-                    );
-                } else {
-                    // No receivers yet, this is normal during startup
-                    debug!("No receivers for event: {}", event.description());
-                }
+                debug!("Broadcasted {} to {} receivers", description, receiver_count);
                 Ok(())
             }
             Err(broadcast::error::SendError(_)) => {
                 // Channel has no receivers, this is normal during startup
-                debug!("No active receivers for broadcast event: {}", event.description());
+                debug!("No active receivers for broadcast event: {}", description);
                 Ok(())
             }
         }
     }
 
-    /// Broadcast a model change event excluding a specific client
-    pub async fn broadcast_event_excluding(&self, event: ModelChangeEvent, exclude_client: String) -> Result<()> {
-        let message = ModelEventMessage::with_exclusion(event.clone(), exclude_client);
-        
-        match self.event_sender.send(message) {
-            Ok(receiver_count) => {
-                if receiver_count > 0 {
-                    debug!(
-                        "Broadcasted {} to {} receivers (excluding client): {}", 
-                        event.description(),
-                        receiver_count,
-                        serde_json::to_string(&event).unwrap_or_default()
-                    );
-                } else {
-                    debug!("No receivers for event: {}", event.description());
-                }
-                Ok(())
+    async fn append_to_log(&self, message: &ModelEventMessage) -> Result<u64> {
+        let entry = event_log::ActiveModel {
+            seq: NotSet,
+            model_id: Set(message.event.model_id().to_string()),
+            event: Set(serde_json::to_value(&message.event)?),
+            exclude_client: Set(message.exclude_client.clone()),
+            node_id: Set(Some(self.node_id.to_string())),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        };
+        let seq = event_log::Entity::insert(entry).exec(self.db.as_ref()).await?.last_insert_id;
+
+        if seq % LOG_TRIM_INTERVAL == 0 && seq > self.config.retained_events as i64 {
+            let cutoff = seq - self.config.retained_events as i64;
+            if let Err(e) = event_log::Entity::delete_many()
+                .filter(event_log::Column::Seq.lte(cutoff))
+                .exec(self.db.as_ref())
+                .await
+            {
+                warn!("Failed to trim the event log: {}", e);
             }
-            Err(broadcast::error::SendError(_)) => {
-                // Channel has no receivers, this is normal during startup
-                debug!("No active receivers for broadcast event: {}", event.description());
-                Ok(())
+        }
+
+        Ok(seq as u64)
+    }
+
+    /// Largest serialized app data record sent whole with a change event
+    pub fn max_record_payload_bytes(&self) -> usize {
+        self.config.max_record_payload_bytes
+    }
+
+    /// Sequence number of the most recent logged event, 0 if the log is empty
+    pub async fn latest_seq(&self) -> Result<u64> {
+        let latest = event_log::Entity::find()
+            .order_by_desc(event_log::Column::Seq)
+            .one(self.db.as_ref())
+            .await?;
+        Ok(latest.map(|entry| entry.seq as u64).unwrap_or(0))
+    }
+
+    /// Events logged after `last_seq`, for a client resuming its stream.
+    /// Asks the client to resync when the log no longer covers the gap, or the gap
+    /// is larger than `max_replay`.
+    pub async fn replay_since(&self, last_seq: u64) -> Result<Replay> {
+        let latest_seq = self.latest_seq().await?;
+        if last_seq > latest_seq {
+            // Ahead of the log, e.g. after the database was reset
+            return Ok(Replay::ResyncRequired { latest_seq });
+        }
+
+        let oldest = event_log::Entity::find()
+            .order_by_asc(event_log::Column::Seq)
+            .one(self.db.as_ref())
+            .await?;
+        if oldest.is_some_and(|entry| entry.seq as u64 > last_seq + 1) {
+            return Ok(Replay::ResyncRequired { latest_seq });
+        }
+
+        let entries = event_log::Entity::find()
+            .filter(event_log::Column::Seq.gt(last_seq as i64))
+            .order_by_asc(event_log::Column::Seq)
+            .limit(self.config.max_replay + 1)
+            .all(self.db.as_ref())
+            .await?;
+        if entries.len() as u64 > self.config.max_replay {
+            return Ok(Replay::ResyncRequired { latest_seq });
+        }
+
+        let mut messages = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let seq = entry.seq;
+            match logged_message(entry) {
+                Ok(message) => messages.push(message),
+                Err(e) => {
+                    warn!("Event {} in the event log can no longer be read: {}", seq, e);
+                    return Ok(Replay::ResyncRequired { latest_seq });
+                }
             }
         }
+        Ok(Replay::Events(messages))
+    }
+
+    /// A single event from the event log
+    pub async fn logged_event(&self, seq: u64) -> Result<Option<ModelEventMessage>> {
+        let entry = event_log::Entity::find_by_id(seq as i64).one(self.db.as_ref()).await?;
+        Ok(entry.map(logged_message).transpose()?)
+    }
+
+    /// Identifies this server among the nodes sharing a database
+    pub fn node_id(&self) -> &Uuid {
+        &self.node_id
+    }
+
+    /// Send an event published by another node to this node's subscribers,
+    /// without logging it again
+    pub fn deliver_remote(&self, message: ModelEventMessage) {
+        if self.event_sender.send(message).is_err() {
+            debug!("No active receivers for remote event");
+        }
     }
 
     /// Register a new WebSocket client
-    pub async fn register_client(&self, client_id: String, model_filter: Option<Uuid>) -> Result<()> {
+    pub async fn register_client(&self, client_id: String, filter: EventFilter) -> Result<()> {
         let client = WebSocketClient {
             client_id: client_id.clone(),
             connected_at: UtcDateTime::now(),
-            model_filter,
+            filter,
         };
 
         let mut clients = self.clients.write().await;
@@ -120,6 +330,15 @@ impl BroadcastService {
         Ok(())
     }
 
+    /// Replace the event filter of a registered client
+    pub async fn update_client_filter(&self, client_id: &str, filter: EventFilter) -> Result<()> {
+        let mut clients = self.clients.write().await;
+        if let Some(client) = clients.get_mut(client_id) {
+            client.filter = filter;
+        }
+        Ok(())
+    }
+
     /// Unregister a WebSocket client
     pub async fn unregister_client(&self, client_id: &str) -> Result<()> {
         // Remove from clients
@@ -201,14 +420,12 @@ impl BroadcastService {
                 }
             }
 
-            // Check model filter
+            // Check the client's event filter
             let clients = self.clients.read().await;
             if let Some(client) = clients.get(client_id) {
-                if let Some(ref filter_model) = client.model_filter {
-                    if *filter_model != message.event.model_id() {
-                        debug!("Skipping client {} due to model filter", client_id);
-                        continue;
-                    }
+                if !client.filter.matches(&message.event) {
+                    debug!("Skipping client {} due to event filter", client_id);
+                    continue;
                 }
             }
 
@@ -257,8 +474,45 @@ impl BroadcastService {
     }
 }
 
-impl Default for BroadcastService {
-    fn default() -> Self {
-        Self::new()
+/// Rebuild a broadcast message from its event log entry
+fn logged_message(entry: event_log::Model) -> serde_json::Result<ModelEventMessage> {
+    Ok(ModelEventMessage {
+        seq: Some(entry.seq as u64),
+        event: serde_json::from_value(entry.event)?,
+        exclude_client: entry.exclude_client,
+        origin: entry.node_id.and_then(|node| node.parse().ok()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seq_tracker_fills_gaps_out_of_order() {
+        let mut seen = SeqTracker::new(10);
+        assert!(!seen.insert(10));
+        assert!(seen.insert(12));
+        assert!(seen.insert(13));
+        assert_eq!(seen.contiguous(), 10);
+
+        // The late event closes the gap
+        assert!(seen.insert(11));
+        assert_eq!(seen.contiguous(), 13);
+        assert!(!seen.insert(12));
+    }
+
+    #[test]
+    fn test_seq_tracker_skips_stalled_gaps() {
+        let mut seen = SeqTracker::new(0);
+        for seq in 2..=MAX_SEQS_PAST_GAP as u64 + 2 {
+            assert!(seen.insert(seq));
+        }
+        assert!(seen.gap_stalled());
+
+        assert_eq!(seen.skip_gaps(), 0);
+        assert_eq!(seen.contiguous(), MAX_SEQS_PAST_GAP as u64 + 2);
+        assert!(!seen.gap_stalled());
+        assert!(!seen.insert(1));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,207 @@
+use crate::common::{Uuid, UtcDateTime};
+use crate::model::collab::{EditOperation, Presence};
+use crate::model::events::ModelChangeEvent;
+use crate::services::{broadcast::BroadcastService, model::ModelService};
+use crate::{Error, Result};
+use dashmap::DashMap;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Number of applied operations kept per model to check concurrent edits against
+const OPERATION_LOG_SIZE: usize = 500;
+
+/// An operation in a model's edit history
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedOperation {
+    pub revision: u64,
+    pub client_id: String,
+    pub operation: EditOperation,
+}
+
+/// Result of submitting an operation
+#[derive(Debug, Clone)]
+pub enum EditOutcome {
+    /// The operation was applied as the given revision
+    Applied { revision: u64 },
+    /// The operation conflicts with edits the client hasn't seen. `operations` are the edits
+    /// applied since the client's base revision; when `resync` is set they're no longer
+    /// available and the client has to reload the model.
+    Conflict {
+        revision: u64,
+        operations: Vec<AppliedOperation>,
+        resync: bool,
+    },
+}
+
+#[derive(Default)]
+struct ModelSession {
+    revision: u64,
+    log: VecDeque<AppliedOperation>,
+    editors: HashMap<String, Presence>,
+}
+
+/// Collaborative model editing sessions.
+///
+/// Operations on a model are applied one at a time in arrival order, each producing a new
+/// revision. Clients submit operations against the last revision they've seen; an operation is
+/// rejected when an edit by another client since then touched the same property or removed
+/// the item it targets. Revisions and presence live in memory and restart when a model's last
+/// editor leaves.
+///
+/// Sessions are local to one node: editors of a model must all be connected to the same node.
+/// When several nodes share a Postgres database, other nodes see the resulting model events
+/// but not the session, so edits made through different nodes aren't checked against each
+/// other and their revisions are unrelated.
+pub struct CollaborationService {
+    model_service: Arc<ModelService>,
+    broadcast: Arc<BroadcastService>,
+    sessions: DashMap<Uuid, Arc<Mutex<ModelSession>>>,
+}
+
+impl CollaborationService {
+    pub fn new(model_service: Arc<ModelService>, broadcast: Arc<BroadcastService>) -> Self {
+        Self {
+            model_service,
+            broadcast,
+            sessions: DashMap::new(),
+        }
+    }
+
+    fn session(&self, model_id: &Uuid) -> Arc<Mutex<ModelSession>> {
+        self.sessions.entry(model_id.clone()).or_default().clone()
+    }
+
+    async fn broadcast_presence(&self, model_id: &Uuid, session: &ModelSession, exclude_client: Option<&str>) -> Result<()> {
+        let mut editors: Vec<Presence> = session.editors.values().cloned().collect();
+        editors.sort_by(|a, b| a.joined_at.cmp(&b.joined_at));
+        let event = ModelChangeEvent::presence_changed(model_id.clone(), editors);
+        match exclude_client {
+            Some(client_id) => self.broadcast.broadcast_event_excluding(event, client_id.to_string()).await,
+            None => self.broadcast.broadcast_event(event).await,
+        }
+    }
+
+    /// Join a model's session, returning its current revision and editors
+    pub async fn join(
+        &self,
+        model_id: Uuid,
+        client_id: &str,
+        user: Option<String>,
+        color: Option<String>,
+    ) -> Result<(u64, Vec<Presence>)> {
+        if self.model_service.get_model(model_id.clone()).await?.is_none() {
+            return Err(Error::NotFound(format!("Model with id {} not found", model_id)));
+        }
+
+        let session = self.session(&model_id);
+        let mut session = session.lock().await;
+        let now = UtcDateTime::now();
+        session.editors.insert(client_id.to_string(), Presence {
+            client_id: client_id.to_string(),
+            user,
+            color,
+            cursor: None,
+            joined_at: now.clone(),
+            last_seen: now,
+        });
+        self.broadcast_presence(&model_id, &session, Some(client_id)).await?;
+
+        let mut editors: Vec<Presence> = session.editors.values().cloned().collect();
+        editors.sort_by(|a, b| a.joined_at.cmp(&b.joined_at));
+        Ok((session.revision, editors))
+    }
+
+    /// Leave a model's session
+    pub async fn leave(&self, model_id: &Uuid, client_id: &str) -> Result<()> {
+        let Some(session) = self.sessions.get(model_id).map(|s| s.clone()) else {
+            return Ok(());
+        };
+        let mut guard = session.lock().await;
+        if guard.editors.remove(client_id).is_none() {
+            return Ok(());
+        }
+        if guard.editors.is_empty() {
+            drop(guard);
+            // Someone may have joined while the lock was released
+            self.sessions.remove_if(model_id, |_, s| s.try_lock().is_ok_and(|s| s.editors.is_empty()));
+            return Ok(());
+        }
+        self.broadcast_presence(model_id, &guard, None).await
+    }
+
+    /// Leave every session a disconnected client had joined
+    pub async fn leave_all(&self, client_id: &str) {
+        let model_ids: Vec<Uuid> = self.sessions.iter().map(|s| s.key().clone()).collect();
+        for model_id in model_ids {
+            if let Err(e) = self.leave(&model_id, client_id).await {
+                tracing::warn!("Failed to remove {} from session of model {}: {}", client_id, model_id, e);
+            }
+        }
+    }
+
+    /// Update an editor's cursor and share it with the other editors
+    pub async fn update_cursor(&self, model_id: &Uuid, client_id: &str, cursor: Option<Value>) -> Result<()> {
+        let session = self.session(model_id);
+        let mut session = session.lock().await;
+        let editor = session.editors.get_mut(client_id)
+            .ok_or_else(|| Error::InvalidInput(format!("Join model {} before sending cursor updates", model_id)))?;
+        editor.cursor = cursor;
+        editor.last_seen = UtcDateTime::now();
+        self.broadcast_presence(model_id, &session, Some(client_id)).await
+    }
+
+    /// Apply an operation made against `base_revision`, rebroadcasting it to the other editors
+    pub async fn apply(
+        &self,
+        model_id: &Uuid,
+        client_id: &str,
+        base_revision: u64,
+        operation: EditOperation,
+    ) -> Result<EditOutcome> {
+        let session = self.session(model_id);
+        let mut session = session.lock().await;
+        if !session.editors.contains_key(client_id) {
+            return Err(Error::InvalidInput(format!("Join model {} before sending operations", model_id)));
+        }
+
+        // Operations after the base revision must still be in the log to check against
+        let oldest = session.log.front().map_or(session.revision + 1, |a| a.revision);
+        if base_revision > session.revision || (base_revision < session.revision && base_revision + 1 < oldest) {
+            return Ok(EditOutcome::Conflict { revision: session.revision, operations: vec![], resync: true });
+        }
+
+        let unseen: Vec<&AppliedOperation> = session.log.iter().filter(|a| a.revision > base_revision).collect();
+        if unseen.iter().any(|a| a.client_id != client_id && operation.conflicts_with(&a.operation)) {
+            return Ok(EditOutcome::Conflict {
+                revision: session.revision,
+                operations: unseen.into_iter().cloned().collect(),
+                resync: false,
+            });
+        }
+
+        self.model_service.apply_edit_operation(model_id.clone(), &operation, client_id).await?;
+
+        session.revision += 1;
+        let revision = session.revision;
+        session.log.push_back(AppliedOperation {
+            revision,
+            client_id: client_id.to_string(),
+            operation: operation.clone(),
+        });
+        if session.log.len() > OPERATION_LOG_SIZE {
+            session.log.pop_front();
+        }
+        if let Some(editor) = session.editors.get_mut(client_id) {
+            editor.last_seen = UtcDateTime::now();
+        }
+
+        let event = ModelChangeEvent::operation_applied(model_id.clone(), revision, client_id.to_string(), operation);
+        self.broadcast.broadcast_event_excluding(event, client_id.to_string()).await?;
+
+        Ok(EditOutcome::Applied { revision })
+    }
+}
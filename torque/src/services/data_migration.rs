@@ -0,0 +1,640 @@
+use crate::{Result, Error};
+use crate::common::{Uuid, UtcDateTime};
+use crate::database::entities::app_entities::{self, Entity as AppEntities, Model as AppEntity};
+use crate::database::entities::{app_entities_archive, data_migration_backups, data_migration_jobs};
+use crate::model::data_migration::{apply_plan, DataMigrationPlan, OrphanStrategy, RowOutcome};
+use crate::model::events::DataChangeAction;
+use crate::services::{
+    app_database::broadcast_bulk_change, blob_store::BlobService, broadcast::BroadcastService, model::ModelService,
+    retention::inflate_if_compressed,
+};
+use dashmap::DashSet;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, SqlErr, TransactionTrait,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_FAILED: &str = "failed";
+pub const STATUS_ROLLED_BACK: &str = "rolled_back";
+
+/// Number of before/after rows included in a preview
+const PREVIEW_SAMPLES: usize = 5;
+
+/// Rows looked up per query by ID, to stay within bind parameter limits
+const ROW_LOOKUP_BATCH: usize = 500;
+
+/// How often a running job records that its node is still alive
+const JOB_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A pending or running job without a heartbeat for this long was abandoned, e.g. by a
+/// node that crashed or restarted, and no longer blocks new jobs for its model
+const JOB_LEASE: Duration = Duration::from_secs(120);
+
+/// Migrates stored app entity rows when a model's schema changes between versions
+pub struct DataMigrationService {
+    db: Arc<DatabaseConnection>,
+    model_service: Arc<ModelService>,
+    blob_service: Arc<BlobService>,
+    broadcast: Arc<BroadcastService>,
+    /// Jobs running in this process. SQLite can't record heartbeats while a job holds
+    /// the write lock, so these are never treated as abandoned.
+    running: DashSet<String>,
+}
+
+/// Rows changed by a job or its rollback, as `(entity_type, entity_id)`, announced
+/// to subscribers in bulk once the change is committed
+#[derive(Default)]
+struct ChangedRows {
+    created: Vec<(String, String)>,
+    updated: Vec<(String, String)>,
+    deleted: Vec<(String, String)>,
+}
+
+impl ChangedRows {
+    /// A row rewritten in place; moving it to another entity type removes it from the old one
+    fn rewritten(&mut self, from_type: String, to_type: String, id: String) {
+        if from_type == to_type {
+            self.updated.push((to_type, id));
+        } else {
+            self.deleted.push((from_type, id.clone()));
+            self.created.push((to_type, id));
+        }
+    }
+
+    async fn broadcast(self, broadcast: &BroadcastService, model_id: &str) {
+        broadcast_bulk_change(broadcast, model_id, DataChangeAction::Created, self.created).await;
+        broadcast_bulk_change(broadcast, model_id, DataChangeAction::Updated, self.updated).await;
+        broadcast_bulk_change(broadcast, model_id, DataChangeAction::Deleted, self.deleted).await;
+    }
+}
+
+/// Row counts produced by running a plan
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataMigrationStats {
+    pub rows_scanned: u64,
+    pub rows_updated: u64,
+    pub rows_archived: u64,
+    pub rows_deleted: u64,
+    pub coercion_failures: u64,
+}
+
+/// One row before and after the plan is applied
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataMigrationSample {
+    pub entity_id: String,
+    pub entity_type: String,
+    pub action: String,
+    pub before: serde_json::Value,
+    pub after: Option<serde_json::Value>,
+}
+
+/// Dry run of a data migration
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataMigrationPreview {
+    pub model_id: String,
+    pub plan: DataMigrationPlan,
+    pub stats: DataMigrationStats,
+    pub samples: Vec<DataMigrationSample>,
+}
+
+/// Tracked data migration job
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataMigrationJob {
+    pub id: String,
+    pub model_id: String,
+    pub from_version: i32,
+    pub to_version: i32,
+    pub status: String,
+    pub plan: serde_json::Value,
+    pub rows_updated: i64,
+    pub rows_archived: i64,
+    pub rows_deleted: i64,
+    pub coercion_failures: i64,
+    pub error: Option<String>,
+    pub created_at: UtcDateTime,
+    pub completed_at: Option<UtcDateTime>,
+    pub rolled_back_at: Option<UtcDateTime>,
+}
+
+impl From<data_migration_jobs::Model> for DataMigrationJob {
+    fn from(job: data_migration_jobs::Model) -> Self {
+        Self {
+            id: job.id,
+            model_id: job.model_id,
+            from_version: job.from_version,
+            to_version: job.to_version,
+            status: job.status,
+            plan: job.plan,
+            rows_updated: job.rows_updated,
+            rows_archived: job.rows_archived,
+            rows_deleted: job.rows_deleted,
+            coercion_failures: job.coercion_failures,
+            error: job.error,
+            created_at: UtcDateTime::from_chrono(job.created_at.and_utc()),
+            completed_at: job.completed_at.map(|t| UtcDateTime::from_chrono(t.and_utc())),
+            rolled_back_at: job.rolled_back_at.map(|t| UtcDateTime::from_chrono(t.and_utc())),
+        }
+    }
+}
+
+impl DataMigrationService {
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        model_service: Arc<ModelService>,
+        blob_service: Arc<BlobService>,
+        broadcast: Arc<BroadcastService>,
+    ) -> Self {
+        Self { db, model_service, blob_service, broadcast, running: DashSet::new() }
+    }
+
+    /// Build the plan for a model. `from_version` defaults to the target of the last
+    /// completed job (or version 1), `to_version` to the latest recorded version.
+    /// Rows of entity types missing from the target model are treated as orphans.
+    pub async fn plan(
+        &self,
+        model_id: &Uuid,
+        from_version: Option<i32>,
+        to_version: Option<i32>,
+        orphan_strategy: OrphanStrategy,
+    ) -> Result<DataMigrationPlan> {
+        let from_version = match from_version {
+            Some(from_version) => from_version,
+            None => self.last_migrated_version(model_id).await?.unwrap_or(1),
+        };
+
+        let mut plan = self.model_service
+            .plan_data_migration(model_id.clone(), from_version, to_version, orphan_strategy)
+            .await?;
+
+        let target = self.model_service.get_model_version(model_id.clone(), plan.to_version).await?.model;
+        let known: HashSet<&str> = target.entities.iter().map(|e| e.name.as_str()).collect();
+        let renamed: HashSet<String> = plan.steps.iter()
+            .filter(|step| matches!(step, crate::model::data_migration::DataMigrationStep::RenameEntityType { .. }))
+            .map(|step| step.entity_type().to_string())
+            .collect();
+
+        let stored_types: Vec<String> = AppEntities::find()
+            .select_only()
+            .column(app_entities::Column::EntityType)
+            .distinct()
+            .filter(app_entities::Column::ModelId.eq(model_id.to_string()))
+            .into_tuple()
+            .all(self.db.as_ref())
+            .await?;
+
+        for entity_type in stored_types {
+            if !known.contains(entity_type.as_str()) && !renamed.contains(&entity_type) {
+                plan.push_orphan(entity_type);
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Run the plan against the stored rows without writing anything
+    pub async fn preview(
+        &self,
+        model_id: &Uuid,
+        from_version: Option<i32>,
+        to_version: Option<i32>,
+        orphan_strategy: OrphanStrategy,
+    ) -> Result<DataMigrationPreview> {
+        let plan = self.plan(model_id, from_version, to_version, orphan_strategy).await?;
+        let rows = self.load_rows(self.db.as_ref(), model_id).await?;
+
+        let mut stats = DataMigrationStats::default();
+        let mut samples = Vec::new();
+
+        for row in rows {
+            stats.rows_scanned += 1;
+            let before = inflate_if_compressed(row.data.clone());
+            let outcome = apply_plan(&plan, &row.entity_type, &before);
+
+            let (action, after) = match outcome {
+                RowOutcome::Unchanged => continue,
+                RowOutcome::Updated { data, coercion_failures, .. } => {
+                    stats.rows_updated += 1;
+                    stats.coercion_failures += coercion_failures;
+                    ("update", Some(data))
+                }
+                RowOutcome::Archive => {
+                    stats.rows_archived += 1;
+                    ("archive", None)
+                }
+                RowOutcome::Delete => {
+                    stats.rows_deleted += 1;
+                    ("delete", None)
+                }
+            };
+
+            if samples.len() < PREVIEW_SAMPLES {
+                samples.push(DataMigrationSample {
+                    entity_id: row.id,
+                    entity_type: row.entity_type,
+                    action: action.to_string(),
+                    before,
+                    after,
+                });
+            }
+        }
+
+        Ok(DataMigrationPreview {
+            model_id: model_id.to_string(),
+            plan,
+            stats,
+            samples,
+        })
+    }
+
+    /// Record a job for the plan and run it in the background
+    pub async fn start_job(
+        self: &Arc<Self>,
+        model_id: &Uuid,
+        from_version: Option<i32>,
+        to_version: Option<i32>,
+        orphan_strategy: OrphanStrategy,
+    ) -> Result<DataMigrationJob> {
+        if let Some(active) = self.active_job(model_id.as_str()).await? {
+            return Err(already_in_progress(&active.id, model_id));
+        }
+
+        let plan = self.plan(model_id, from_version, to_version, orphan_strategy).await?;
+
+        let now = chrono::Utc::now().naive_utc();
+        let job = data_migration_jobs::ActiveModel {
+            id: Set(Uuid::new_v4().to_string()),
+            model_id: Set(model_id.to_string()),
+            from_version: Set(plan.from_version),
+            to_version: Set(plan.to_version),
+            status: Set(STATUS_PENDING.to_string()),
+            plan: Set(serde_json::to_value(&plan)?),
+            rows_updated: Set(0),
+            rows_archived: Set(0),
+            rows_deleted: Set(0),
+            coercion_failures: Set(0),
+            error: Set(None),
+            created_at: Set(now),
+            completed_at: Set(None),
+            rolled_back_at: Set(None),
+            heartbeat_at: Set(Some(now)),
+        }
+        .insert(self.db.as_ref())
+        .await;
+
+        // A job started concurrently can slip past the check above; the partial unique
+        // index on active jobs makes the second insert fail instead of running both
+        let job = match job {
+            Ok(job) => job,
+            Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                let active = self.active_job(model_id.as_str()).await?
+                    .map(|job| job.id)
+                    .unwrap_or_default();
+                return Err(already_in_progress(&active, model_id));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let service = self.clone();
+        let job_id = job.id.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.run_job(&job_id, plan).await {
+                tracing::error!("Data migration job {} failed: {}", job_id, e);
+            }
+        });
+
+        Ok(job.into())
+    }
+
+    /// Execute a recorded job. All row changes happen in one transaction,
+    /// so a failed job leaves the app data untouched.
+    pub async fn run_job(&self, job_id: &str, plan: DataMigrationPlan) -> Result<DataMigrationStats> {
+        self.running.insert(job_id.to_string());
+        let result = self.run_tracked_job(job_id, plan).await;
+        self.running.remove(job_id);
+        result
+    }
+
+    async fn run_tracked_job(&self, job_id: &str, plan: DataMigrationPlan) -> Result<DataMigrationStats> {
+        self.set_status(job_id, STATUS_RUNNING, None).await?;
+
+        // Heartbeats run on their own task, so they don't wait for the job's transaction
+        let heartbeat = tokio::spawn(send_heartbeats(self.db.clone(), job_id.to_string()));
+        let result = self.apply(job_id, &plan).await;
+        heartbeat.abort();
+
+        match result {
+            Ok(stats) => {
+                data_migration_jobs::ActiveModel {
+                    id: Set(job_id.to_string()),
+                    status: Set(STATUS_COMPLETED.to_string()),
+                    rows_updated: Set(stored_count(stats.rows_updated)?),
+                    rows_archived: Set(stored_count(stats.rows_archived)?),
+                    rows_deleted: Set(stored_count(stats.rows_deleted)?),
+                    coercion_failures: Set(stored_count(stats.coercion_failures)?),
+                    completed_at: Set(Some(chrono::Utc::now().naive_utc())),
+                    ..Default::default()
+                }
+                .update(self.db.as_ref())
+                .await?;
+
+                tracing::info!(
+                    "Data migration job {} completed: {} updated, {} archived, {} deleted, {} coercion failures",
+                    job_id, stats.rows_updated, stats.rows_archived, stats.rows_deleted, stats.coercion_failures
+                );
+                Ok(stats)
+            }
+            Err(e) => {
+                self.set_status(job_id, STATUS_FAILED, Some(e.to_string())).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Apply the plan, then remove the blobs of deleted rows and announce the changed rows.
+    /// Archived rows keep their blobs until the archive is purged.
+    async fn apply(&self, job_id: &str, plan: &DataMigrationPlan) -> Result<DataMigrationStats> {
+        let job = self.get_job(job_id).await?;
+        let txn = self.db.begin().await?;
+        let now = chrono::Utc::now().naive_utc();
+        let mut stats = DataMigrationStats::default();
+        let mut changed = ChangedRows::default();
+        let mut deleted_ids = Vec::new();
+
+        let model_id = Uuid::parse(&job.model_id)
+            .map_err(|_| Error::Internal(format!("Invalid model id on data migration job: {}", job.model_id)))?;
+
+        for row in self.load_rows(&txn, &model_id).await? {
+            stats.rows_scanned += 1;
+            let outcome = apply_plan(plan, &row.entity_type, &inflate_if_compressed(row.data.clone()));
+            if outcome == RowOutcome::Unchanged {
+                continue;
+            }
+
+            data_migration_backups::Entity::insert(data_migration_backups::ActiveModel {
+                job_id: Set(job_id.to_string()),
+                entity_id: Set(row.id.clone()),
+                model_id: Set(row.model_id.clone()),
+                entity_type: Set(row.entity_type.clone()),
+                data: Set(row.data.clone()),
+                created_at: Set(row.created_at),
+                updated_at: Set(row.updated_at),
+            })
+            .exec(&txn)
+            .await?;
+
+            match outcome {
+                RowOutcome::Unchanged => {}
+                RowOutcome::Updated { entity_type, data, coercion_failures } => {
+                    changed.rewritten(row.entity_type.clone(), entity_type.clone(), row.id.clone());
+                    // app_entities::ActiveModel's Default calls `new()`, so start from the row instead
+                    let mut active: app_entities::ActiveModel = row.into();
+                    active.entity_type = Set(entity_type);
+                    active.data = Set(data);
+                    active.updated_at = Set(now);
+                    AppEntities::update(active).exec(&txn).await?;
+                    stats.rows_updated += 1;
+                    stats.coercion_failures += coercion_failures;
+                }
+                RowOutcome::Archive => {
+                    changed.deleted.push((row.entity_type.clone(), row.id.clone()));
+                    app_entities_archive::Entity::insert(app_entities_archive::ActiveModel {
+                        id: Set(row.id.clone()),
+                        model_id: Set(row.model_id),
+                        entity_type: Set(row.entity_type),
+                        data: Set(row.data),
+                        created_at: Set(row.created_at),
+                        updated_at: Set(row.updated_at),
+                        archived_at: Set(now),
+                    })
+                    .exec(&txn)
+                    .await?;
+                    AppEntities::delete_by_id(row.id).exec(&txn).await?;
+                    stats.rows_archived += 1;
+                }
+                RowOutcome::Delete => {
+                    changed.deleted.push((row.entity_type, row.id.clone()));
+                    AppEntities::delete_by_id(row.id.clone()).exec(&txn).await?;
+                    deleted_ids.push(row.id);
+                    stats.rows_deleted += 1;
+                }
+            }
+        }
+
+        txn.commit().await?;
+
+        // The rows are gone either way, so a failed cleanup doesn't fail the job
+        for entity_id in &deleted_ids {
+            if let Err(e) = self.blob_service.delete_for_entity(&job.model_id, entity_id).await {
+                tracing::warn!("Failed to remove blobs of row {} deleted by data migration job {}: {}", entity_id, job_id, e);
+            }
+        }
+        changed.broadcast(&self.broadcast, &job.model_id).await;
+        Ok(stats)
+    }
+
+    /// Restore every row touched by a completed job to its pre-migration state.
+    /// Only the most recent completed job of a model can be rolled back, and only while
+    /// none of the rows it migrated were changed since. Rows the job deleted come back
+    /// without their attachments, which were removed with them.
+    pub async fn rollback_job(&self, job_id: &str) -> Result<DataMigrationJob> {
+        let job = self.get_job(job_id).await?;
+        if job.status != STATUS_COMPLETED {
+            return Err(Error::Validation(format!(
+                "Only completed data migration jobs can be rolled back (job {} is {})", job_id, job.status
+            )));
+        }
+
+        let latest = self.last_completed_job(&job.model_id).await?;
+        if latest.as_ref().map(|j| j.id.as_str()) != Some(job_id) {
+            return Err(Error::Validation(format!(
+                "Data migration job {} has been superseded; roll back later jobs first", job_id
+            )));
+        }
+
+        let backups = data_migration_backups::Entity::find()
+            .filter(data_migration_backups::Column::JobId.eq(job_id))
+            .all(self.db.as_ref())
+            .await?;
+        let restored = backups.len();
+
+        let txn = self.db.begin().await?;
+        let mut current: Vec<AppEntity> = Vec::new();
+        for chunk in backups.chunks(ROW_LOOKUP_BATCH) {
+            current.extend(AppEntities::find()
+                .filter(app_entities::Column::Id.is_in(chunk.iter().map(|backup| backup.entity_id.clone())))
+                .all(&txn)
+                .await?);
+        }
+
+        // Restoring would silently undo edits made after the migration
+        let completed_at = job.completed_at.as_ref().map(|t| t.as_chrono().naive_utc());
+        let edited: Vec<&str> = current.iter()
+            .filter(|row| completed_at.is_some_and(|completed_at| row.updated_at > completed_at))
+            .map(|row| row.id.as_str())
+            .collect();
+        if !edited.is_empty() {
+            return Err(Error::Validation(format!(
+                "Data migration job {} can't be rolled back: {} migrated rows were changed since ({})",
+                job_id, edited.len(), edited.iter().take(5).copied().collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        let current_types: HashMap<&str, &str> = current.iter()
+            .map(|row| (row.id.as_str(), row.entity_type.as_str()))
+            .collect();
+        let mut changed = ChangedRows::default();
+        for backup in backups {
+            match current_types.get(backup.entity_id.as_str()) {
+                Some(entity_type) => changed.rewritten(entity_type.to_string(), backup.entity_type.clone(), backup.entity_id.clone()),
+                None => changed.created.push((backup.entity_type.clone(), backup.entity_id.clone())),
+            }
+            app_entities_archive::Entity::delete_by_id(backup.entity_id.clone()).exec(&txn).await?;
+            AppEntities::delete_by_id(backup.entity_id.clone()).exec(&txn).await?;
+            AppEntities::insert(app_entities::ActiveModel {
+                id: Set(backup.entity_id),
+                model_id: Set(backup.model_id),
+                entity_type: Set(backup.entity_type),
+                data: Set(backup.data),
+                created_at: Set(backup.created_at),
+                updated_at: Set(backup.updated_at),
+            })
+            .exec(&txn)
+            .await?;
+        }
+
+        let job = data_migration_jobs::ActiveModel {
+            id: Set(job_id.to_string()),
+            status: Set(STATUS_ROLLED_BACK.to_string()),
+            rolled_back_at: Set(Some(chrono::Utc::now().naive_utc())),
+            ..Default::default()
+        }
+        .update(&txn)
+        .await?;
+        txn.commit().await?;
+        changed.broadcast(&self.broadcast, &job.model_id).await;
+
+        tracing::info!("Rolled back data migration job {} ({} rows restored)", job_id, restored);
+        Ok(job.into())
+    }
+
+    pub async fn get_job(&self, job_id: &str) -> Result<DataMigrationJob> {
+        data_migration_jobs::Entity::find_by_id(job_id.to_string())
+            .one(self.db.as_ref())
+            .await?
+            .map(DataMigrationJob::from)
+            .ok_or_else(|| Error::NotFound(format!("Data migration job {} not found", job_id)))
+    }
+
+    /// Jobs for a model, newest first
+    pub async fn list_jobs(&self, model_id: &Uuid) -> Result<Vec<DataMigrationJob>> {
+        let jobs = data_migration_jobs::Entity::find()
+            .filter(data_migration_jobs::Column::ModelId.eq(model_id.to_string()))
+            .order_by_desc(data_migration_jobs::Column::CreatedAt)
+            .all(self.db.as_ref())
+            .await?;
+        Ok(jobs.into_iter().map(DataMigrationJob::from).collect())
+    }
+
+    /// Version the app data was last migrated to
+    async fn last_migrated_version(&self, model_id: &Uuid) -> Result<Option<i32>> {
+        Ok(self.last_completed_job(model_id.as_str()).await?.map(|job| job.to_version))
+    }
+
+    /// The pending or running job of a model. A job whose heartbeat stopped is marked
+    /// failed instead, so a crash or restart doesn't block the model's migrations.
+    async fn active_job(&self, model_id: &str) -> Result<Option<data_migration_jobs::Model>> {
+        let Some(job) = data_migration_jobs::Entity::find()
+            .filter(data_migration_jobs::Column::ModelId.eq(model_id))
+            .filter(data_migration_jobs::Column::Status.is_in([STATUS_PENDING, STATUS_RUNNING]))
+            .one(self.db.as_ref())
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let last_seen = job.heartbeat_at.unwrap_or(job.created_at);
+        let abandoned = chrono::Utc::now().naive_utc() - last_seen > chrono::Duration::seconds(JOB_LEASE.as_secs() as i64);
+        if !abandoned || self.running.contains(&job.id) {
+            return Ok(Some(job));
+        }
+
+        tracing::warn!("Data migration job {} sent no heartbeat since {}; marking it failed", job.id, last_seen);
+        data_migration_jobs::Entity::update_many()
+            .col_expr(data_migration_jobs::Column::Status, STATUS_FAILED.into())
+            .col_expr(data_migration_jobs::Column::Error, Some(format!("Abandoned: no heartbeat since {}", last_seen)).into())
+            .col_expr(data_migration_jobs::Column::CompletedAt, Some(chrono::Utc::now().naive_utc()).into())
+            .filter(data_migration_jobs::Column::Id.eq(job.id))
+            .filter(data_migration_jobs::Column::Status.is_in([STATUS_PENDING, STATUS_RUNNING]))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(None)
+    }
+
+    async fn last_completed_job(&self, model_id: &str) -> Result<Option<data_migration_jobs::Model>> {
+        Ok(data_migration_jobs::Entity::find()
+            .filter(data_migration_jobs::Column::ModelId.eq(model_id))
+            .filter(data_migration_jobs::Column::Status.eq(STATUS_COMPLETED))
+            .order_by_desc(data_migration_jobs::Column::CreatedAt)
+            .one(self.db.as_ref())
+            .await?)
+    }
+
+    async fn load_rows<C: ConnectionTrait>(&self, conn: &C, model_id: &Uuid) -> Result<Vec<AppEntity>> {
+        Ok(AppEntities::find()
+            .filter(app_entities::Column::ModelId.eq(model_id.to_string()))
+            .order_by_asc(app_entities::Column::CreatedAt)
+            .all(conn)
+            .await?)
+    }
+
+    async fn set_status(&self, job_id: &str, status: &str, error: Option<String>) -> Result<()> {
+        data_migration_jobs::ActiveModel {
+            id: Set(job_id.to_string()),
+            status: Set(status.to_string()),
+            error: Set(error),
+            completed_at: Set((status == STATUS_FAILED).then(|| chrono::Utc::now().naive_utc())),
+            ..Default::default()
+        }
+        .update(self.db.as_ref())
+        .await?;
+        Ok(())
+    }
+}
+
+/// Record that a job is still alive until the task is aborted
+async fn send_heartbeats(db: Arc<DatabaseConnection>, job_id: String) {
+    let mut interval = tokio::time::interval(JOB_HEARTBEAT_INTERVAL);
+    loop {
+        interval.tick().await;
+        let heartbeat = data_migration_jobs::ActiveModel {
+            id: Set(job_id.clone()),
+            heartbeat_at: Set(Some(chrono::Utc::now().naive_utc())),
+            ..Default::default()
+        };
+        if let Err(e) = heartbeat.update(db.as_ref()).await {
+            tracing::debug!("Failed to record heartbeat of data migration job {}: {}", job_id, e);
+        }
+    }
+}
+
+fn already_in_progress(job_id: &str, model_id: &Uuid) -> Error {
+    Error::Validation(format!(
+        "Data migration job {} is already in progress for model {}", job_id, model_id
+    ))
+}
+
+/// Row counts are stored as BIGINT
+fn stored_count(count: u64) -> Result<i64> {
+    i64::try_from(count).map_err(|_| Error::Internal(format!("Row count {} does not fit in the job record", count)))
+}
@@ -0,0 +1,308 @@
+use std::sync::Arc;
+use std::time::Duration;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use tracing::{debug, error, info, warn};
+
+use crate::common::Uuid;
+use crate::model::events::ModelEventMessage;
+use crate::services::broadcast::{BroadcastService, Replay, SeqTracker};
+use crate::Result;
+
+/// Notification channel shared by all nodes
+pub const CHANNEL: &str = "torque_events";
+
+/// Postgres rejects notification payloads of 8000 bytes or more
+const MAX_PAYLOAD_BYTES: usize = 7999;
+
+/// Delay before reconnecting a lost listener connection
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Payload of a notification
+#[derive(Debug, Serialize, Deserialize)]
+struct Notice {
+    /// Node that published the event
+    node: Uuid,
+    /// Event log position of a logged event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seq: Option<u64>,
+    /// The event itself, when it is not logged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<ModelEventMessage>,
+}
+
+/// Tell the other nodes about an event published by this one. Logged events are
+/// announced by sequence number and read back from the shared event log, which keeps
+/// notifications under the payload limit; unlogged events travel inline.
+pub async fn notify(db: &DatabaseConnection, node: &Uuid, message: &ModelEventMessage) -> Result<()> {
+    let Some(payload) = encode(node, message)? else {
+        return Ok(());
+    };
+
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT pg_notify($1, $2)",
+        [CHANNEL.into(), payload.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Notification payload announcing an event, `None` when it exceeds the payload limit
+fn encode(node: &Uuid, message: &ModelEventMessage) -> Result<Option<String>> {
+    let notice = match message.seq {
+        Some(seq) => Notice { node: node.clone(), seq: Some(seq), message: None },
+        None => Notice { node: node.clone(), seq: None, message: Some(message.clone()) },
+    };
+    let payload = serde_json::to_string(&notice)?;
+    if payload.len() > MAX_PAYLOAD_BYTES {
+        warn!("Not sending {} to other nodes: {} bytes exceeds the notification limit", message.event.description(), payload.len());
+        return Ok(None);
+    }
+    Ok(Some(payload))
+}
+
+/// Listen for events published by other nodes and rebroadcast them locally,
+/// reconnecting whenever the listener connection is lost
+pub fn spawn_listener(broadcast: Arc<BroadcastService>, database_url: String) {
+    tokio::spawn(async move {
+        // Logged events this node has seen, kept across reconnects
+        let mut seen = None;
+        loop {
+            if let Err(e) = listen(&broadcast, &database_url, &mut seen).await {
+                error!("Event listener failed, reconnecting in {:?}: {}", RECONNECT_DELAY, e);
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+}
+
+async fn listen(
+    broadcast: &BroadcastService,
+    database_url: &str,
+    seen: &mut Option<SeqTracker>,
+) -> std::result::Result<(), sqlx::Error> {
+    let mut listener = PgListener::connect(database_url).await?;
+    listener.listen(CHANNEL).await?;
+    info!("Listening for events from other nodes on '{}' (node {})", CHANNEL, broadcast.node_id());
+
+    // Events notified while the listener was not connected are only in the event log
+    let seen = match seen {
+        Some(seen) => {
+            catch_up(broadcast, seen).await;
+            seen
+        }
+        None => {
+            let latest_seq = broadcast.latest_seq().await.unwrap_or_else(|e| {
+                warn!("Failed to read the event log position: {}", e);
+                0
+            });
+            seen.insert(SeqTracker::new(latest_seq))
+        }
+    };
+
+    loop {
+        let notification = listener.recv().await?;
+        dispatch(broadcast, notification.payload(), seen).await;
+    }
+}
+
+/// Deliver the events other nodes logged since the last one this node saw in sequence
+async fn catch_up(broadcast: &BroadcastService, seen: &mut SeqTracker) -> usize {
+    let messages = match broadcast.replay_since(seen.contiguous()).await {
+        Ok(Replay::Events(messages)) => messages,
+        Ok(Replay::ResyncRequired { latest_seq }) => {
+            warn!("Events from other nodes up to seq {} were missed and can no longer be replayed", latest_seq);
+            *seen = SeqTracker::new(latest_seq);
+            return 0;
+        }
+        Err(e) => {
+            warn!("Failed to replay events missed by the listener: {}", e);
+            return 0;
+        }
+    };
+
+    let mut delivered = 0;
+    for message in messages {
+        let Some(seq) = message.seq else { continue };
+        if !seen.insert(seq) || message.origin.as_ref() == Some(broadcast.node_id()) {
+            continue;
+        }
+        broadcast.deliver_remote(message);
+        delivered += 1;
+    }
+    if delivered > 0 {
+        info!("Delivered {} events from other nodes missed by the listener", delivered);
+    }
+    delivered
+}
+
+/// Decode a notification and deliver its event to this node's subscribers.
+/// Returns whether an event was delivered; events this node published itself, and
+/// logged events already delivered, are skipped.
+async fn dispatch(broadcast: &BroadcastService, payload: &str, seen: &mut SeqTracker) -> bool {
+    let notice: Notice = match serde_json::from_str(payload) {
+        Ok(notice) => notice,
+        Err(e) => {
+            warn!("Ignoring malformed event notification: {}", e);
+            return false;
+        }
+    };
+    if let Some(seq) = notice.seq {
+        if !seen.insert(seq) {
+            return false;
+        }
+        if seen.gap_stalled() {
+            warn!("Gave up waiting for events missing after seq {}", seen.skip_gaps());
+        }
+    }
+    if notice.node == *broadcast.node_id() {
+        // Already delivered locally when it was published
+        return false;
+    }
+
+    let message = match (notice.seq, notice.message) {
+        (_, Some(message)) => Some(message),
+        (Some(seq), None) => match broadcast.logged_event(seq).await {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Failed to read event {} from the event log: {}", seq, e);
+                None
+            }
+        },
+        (None, None) => None,
+    };
+    match message {
+        Some(message) => {
+            debug!("Rebroadcasting {} from node {}", message.event.description(), notice.node);
+            broadcast.deliver_remote(message);
+            true
+        }
+        None => {
+            warn!("Event notification from node {} has no readable event", notice.node);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EventLogConfig;
+    use crate::model::events::ModelChangeEvent;
+    use crate::Config;
+
+    async fn database() -> Arc<DatabaseConnection> {
+        let mut config = Config::default();
+        config.database.url = "sqlite::memory:".to_string();
+        Arc::new(crate::database::setup_database(&config).await.unwrap())
+    }
+
+    #[test]
+    fn test_notice_round_trip() {
+        let node = Uuid::new_v4();
+        let event = ModelChangeEvent::entity_added(Uuid::new_v4(), Uuid::new_v4());
+
+        // Logged events are announced by position only
+        let mut logged = ModelEventMessage::new(event.clone());
+        logged.seq = Some(42);
+        let notice: Notice = serde_json::from_str(&encode(&node, &logged).unwrap().unwrap()).unwrap();
+        assert_eq!(notice.node, node);
+        assert_eq!(notice.seq, Some(42));
+        assert!(notice.message.is_none());
+
+        let inline = ModelEventMessage::with_exclusion(event, "client-1".to_string());
+        let notice: Notice = serde_json::from_str(&encode(&node, &inline).unwrap().unwrap()).unwrap();
+        assert_eq!(notice.seq, None);
+        let message = notice.message.unwrap();
+        assert_eq!(message.event.kind(), "EntityAdded");
+        assert_eq!(message.exclude_client.as_deref(), Some("client-1"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_events_are_not_sent() {
+        let record = serde_json::json!({ "body": "x".repeat(MAX_PAYLOAD_BYTES) });
+        let message = ModelEventMessage::new(ModelChangeEvent::entity_data_changed(
+            Uuid::new_v4(),
+            "note".to_string(),
+            "1".to_string(),
+            crate::model::events::DataChangeAction::Created,
+            Some(record),
+            None,
+        ));
+        assert!(encode(&Uuid::new_v4(), &message).unwrap().is_none());
+
+        // Dropped before reaching the database, so even a non-Postgres connection succeeds
+        let db = database().await;
+        notify(db.as_ref(), &Uuid::new_v4(), &message).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_delivers_events_of_other_nodes() {
+        let db = database().await;
+        let publisher = BroadcastService::new(db.clone(), EventLogConfig::default());
+        let receiver = BroadcastService::new(db, EventLogConfig::default());
+        let mut events = receiver.subscribe();
+        let mut seen = SeqTracker::new(0);
+
+        // A logged event is read back from the shared event log
+        let model_id = Uuid::new_v4();
+        publisher.broadcast_event(ModelChangeEvent::entity_added(model_id.clone(), Uuid::new_v4())).await.unwrap();
+        let seq = publisher.latest_seq().await.unwrap();
+        let payload = serde_json::to_string(&Notice { node: publisher.node_id().clone(), seq: Some(seq), message: None }).unwrap();
+        assert!(dispatch(&receiver, &payload, &mut seen).await);
+        let delivered = events.try_recv().unwrap();
+        assert_eq!(delivered.seq, Some(seq));
+        assert_eq!(delivered.event.model_id(), model_id);
+        assert_eq!(delivered.origin.as_ref(), Some(publisher.node_id()));
+
+        // A logged event is delivered once
+        assert!(!dispatch(&receiver, &payload, &mut seen).await);
+
+        // Inline events are delivered as sent
+        let inline = ModelEventMessage::new(ModelChangeEvent::presence_changed(model_id.clone(), vec![]));
+        let payload = encode(publisher.node_id(), &inline).unwrap().unwrap();
+        assert!(dispatch(&receiver, &payload, &mut seen).await);
+        assert_eq!(events.try_recv().unwrap().event.kind(), "PresenceChanged");
+
+        // The node's own events were already delivered when published
+        let payload = encode(receiver.node_id(), &inline).unwrap().unwrap();
+        assert!(!dispatch(&receiver, &payload, &mut seen).await);
+
+        // Unreadable notifications are skipped
+        assert!(!dispatch(&receiver, "not json", &mut seen).await);
+        let payload = serde_json::to_string(&Notice { node: publisher.node_id().clone(), seq: Some(seq + 100), message: None }).unwrap();
+        assert!(!dispatch(&receiver, &payload, &mut seen).await);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_delivers_events_missed_while_disconnected() {
+        let db = database().await;
+        let publisher = BroadcastService::new(db.clone(), EventLogConfig::default());
+        let receiver = BroadcastService::new(db, EventLogConfig::default());
+        let model_id = Uuid::new_v4();
+
+        publisher.broadcast_event(ModelChangeEvent::entity_added(model_id.clone(), Uuid::new_v4())).await.unwrap();
+        let mut seen = SeqTracker::new(receiver.latest_seq().await.unwrap());
+
+        // Published while the listener was disconnected: two remote events and one of its own
+        publisher.broadcast_event(ModelChangeEvent::entity_added(model_id.clone(), Uuid::new_v4())).await.unwrap();
+        receiver.broadcast_event(ModelChangeEvent::entity_added(model_id.clone(), Uuid::new_v4())).await.unwrap();
+        publisher.broadcast_event(ModelChangeEvent::entity_added(model_id.clone(), Uuid::new_v4())).await.unwrap();
+        let latest_seq = publisher.latest_seq().await.unwrap();
+
+        let mut events = receiver.subscribe();
+        assert_eq!(catch_up(&receiver, &mut seen).await, 2);
+        assert_eq!(events.try_recv().unwrap().seq, Some(latest_seq - 2));
+        assert_eq!(events.try_recv().unwrap().seq, Some(latest_seq));
+        assert!(events.try_recv().is_err());
+        assert_eq!(seen.contiguous(), latest_seq);
+
+        // Notifications that arrive after catching up are not delivered again
+        let payload = serde_json::to_string(&Notice { node: publisher.node_id().clone(), seq: Some(latest_seq), message: None }).unwrap();
+        assert!(!dispatch(&receiver, &payload, &mut seen).await);
+        assert_eq!(catch_up(&receiver, &mut seen).await, 0);
+    }
+}
@@ -3,7 +3,8 @@ use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use std::collections::HashMap;
 use crate::services::app_database::AppDatabaseService;
-use crate::model::types::{TorqueModel, ModelEntity, ModelRelationship, RelationshipType};
+use crate::services::blob_store::{BlobService, BlobUpload};
+use crate::model::types::{FieldType, TorqueModel, ModelEntity, ModelRelationship, RelationshipType};
 use serde::{Serialize, Deserialize};
 use fake::{Fake, Faker};
 use fake::faker::*;
@@ -14,6 +15,7 @@ use ::chrono::Utc;
 #[derive(Clone)]
 pub struct FakeDataService {
     app_database_service: Arc<AppDatabaseService>,
+    blob_service: Arc<BlobService>,
     max_instances_per_entity: usize,
 }
 
@@ -58,9 +60,10 @@ pub struct SyncResponse {
 const BATCH_SIZE: usize = 100;
 
 impl FakeDataService {
-    pub fn new(app_database_service: Arc<AppDatabaseService>) -> Self {
+    pub fn new(app_database_service: Arc<AppDatabaseService>, blob_service: Arc<BlobService>) -> Self {
         Self {
             app_database_service,
+            blob_service,
             max_instances_per_entity: 5, // Default max
         }
     }
@@ -142,7 +145,8 @@ impl FakeDataService {
             let batch_size = std::cmp::min(BATCH_SIZE, count - batch * BATCH_SIZE);
             let fake_data = self.generate_batch_data(entity, batch_size);
             
-            for data in fake_data {
+            for mut data in fake_data {
+                self.add_sample_blobs(model_id, entity, &mut data).await?;
                 self.insert_record(model_id, &entity.name, data, &conn).await?;
                 records_created += 1;
             }
@@ -171,13 +175,73 @@ impl FakeDataService {
         batch
     }
 
+    /// Upload a small text blob for each Binary field so seeded records refer to real
+    /// attachments. Fields stay null when the storage config allows no text uploads.
+    async fn add_sample_blobs(
+        &self,
+        model_id: &str,
+        entity: &ModelEntity,
+        record: &mut HashMap<String, serde_json::Value>,
+    ) -> Result<()> {
+        let Some(content_type) = ["text/plain", "application/octet-stream"]
+            .into_iter()
+            .find(|content_type| self.blob_service.is_content_type_allowed(content_type))
+        else {
+            return Ok(());
+        };
+
+        for field in &entity.fields {
+            let count = match &field.field_type {
+                FieldType::Binary => 1,
+                FieldType::Array { element_type } if matches!(element_type.as_ref(), FieldType::Binary) => 2,
+                _ => continue,
+            };
+
+            let mut blob_ids = Vec::with_capacity(count);
+            for index in 0..count {
+                let blob = self.blob_service.upload(BlobUpload {
+                    model_id: model_id.to_string(),
+                    entity_id: None,
+                    field_name: Some(field.name.clone()),
+                    filename: format!("{}-{}.txt", field.name, index + 1),
+                    content_type: content_type.to_string(),
+                    data: lorem::en::Sentence(3..8).fake::<String>().into_bytes(),
+                }).await?;
+                blob_ids.push(serde_json::Value::String(blob.id));
+            }
+
+            let value = match field.field_type {
+                FieldType::Binary => blob_ids.swap_remove(0),
+                _ => serde_json::Value::Array(blob_ids),
+            };
+            record.insert(field.name.clone(), value);
+        }
+        Ok(())
+    }
+
     /// Generate fake value based on field type and name
     pub fn generate_fake_value(&self, field_type: &crate::model::types::FieldType, field_name: &str) -> serde_json::Value {
-        use crate::model::types::FieldType;
         match field_type {
-            FieldType::String { .. } => self.generate_string_value(field_name),
-            FieldType::Integer { .. } => self.generate_integer_value(field_name),
-            FieldType::Float { .. } => self.generate_float_value(field_name),
+            // Keep generated values inside the field constraints so they pass schema validation
+            FieldType::String { max_length } => {
+                let value = self.generate_string_value(field_name);
+                match (max_length, value.as_str()) {
+                    (Some(max), Some(s)) if s.chars().count() > *max => {
+                        s.chars().take(*max).collect::<String>().into()
+                    }
+                    _ => value,
+                }
+            },
+            FieldType::Integer { min, max } => {
+                let value = self.generate_integer_value(field_name).as_i64().unwrap_or(0);
+                let value = min.map_or(value, |min| value.max(min));
+                max.map_or(value, |max| value.min(max)).into()
+            },
+            FieldType::Float { min, max } => {
+                let value = self.generate_float_value(field_name).as_f64().unwrap_or(0.0);
+                let value = min.map_or(value, |min| value.max(min));
+                max.map_or(value, |max| value.min(max)).into()
+            },
             FieldType::Boolean => Faker.fake::<bool>().into(),
             FieldType::DateTime => Utc::now()
                 .format("%Y-%m-%dT%H:%M:%S%.3fZ")
@@ -198,7 +262,8 @@ impl FakeDataService {
                 obj.insert("value".to_string(), (1..100).fake::<i32>().into());
                 serde_json::Value::Object(obj)
             },
-            FieldType::Binary => serde_json::Value::String("binary_data_placeholder".to_string()),
+            // Seeding replaces this with the ID of an uploaded sample blob
+            FieldType::Binary => serde_json::Value::Null,
             FieldType::Enum { values } => {
                 if !values.is_empty() {
                     values.choose(&mut rand::thread_rng())
@@ -6,9 +6,17 @@ pub mod entity;
 pub mod cache;
 pub mod metrics;
 pub mod model;
+pub mod model_search;
 pub mod broadcast;
 pub mod app_database;
 pub mod fake_data;
+pub mod blob_store;
+pub mod retention;
+pub mod data_migration;
+pub mod sql_import;
+pub mod collaboration;
+#[cfg(feature = "postgres")]
+pub mod event_fanout;
 
 /// Core service registry for dependency injection
 #[derive(Clone)]
@@ -22,6 +30,10 @@ pub struct ServiceRegistry {
     pub broadcast: Arc<broadcast::BroadcastService>,
     pub app_database_service: Arc<app_database::AppDatabaseService>,
     pub fake_data_service: Arc<fake_data::FakeDataService>,
+    pub blob_service: Arc<blob_store::BlobService>,
+    pub retention_service: Arc<retention::RetentionService>,
+    pub data_migration_service: Arc<data_migration::DataMigrationService>,
+    pub collaboration: Arc<collaboration::CollaborationService>,
 }
 
 impl ServiceRegistry {
@@ -44,21 +56,67 @@ impl ServiceRegistry {
             cache.clone(),
         ));
 
-        // Initialize broadcast service
-        let broadcast = Arc::new(broadcast::BroadcastService::new());
+        // Initialize broadcast service with its event log
+        let broadcast = Arc::new(broadcast::BroadcastService::new(
+            db.clone(),
+            config.events.clone(),
+        ));
+
+        // Drop compiled entity validators of deleted models and entities
+        crate::model::json_schema::spawn_validator_eviction(broadcast.subscribe());
+
+        // Initialize collaborative model editing sessions
+        let collaboration = Arc::new(collaboration::CollaborationService::new(
+            model_service.clone(),
+            broadcast.clone(),
+        ));
+
+        // Initialize blob storage for Binary fields
+        let blob_service = Arc::new(blob_store::BlobService::new(
+            db.clone(),
+            &config.storage,
+        )?);
 
         // Initialize app database service
         let app_database_service = Arc::new(app_database::AppDatabaseService::new(
             db.clone(),
             cache.clone(),
             model_service.clone(),
+            blob_service.clone(),
+            broadcast.clone(),
+        ));
+
+        // Initialize retention policy job (started by the HTTP server)
+        let retention_service = Arc::new(retention::RetentionService::new(
+            db.clone(),
+            model_service.clone(),
+            metrics.clone(),
+            broadcast.clone(),
+            blob_service.clone(),
+            config.retention.clone(),
+        ));
+
+        // Initialize app data migration jobs for model schema changes
+        let data_migration_service = Arc::new(data_migration::DataMigrationService::new(
+            db.clone(),
+            model_service.clone(),
+            blob_service.clone(),
+            broadcast.clone(),
         ));
 
         // Initialize fake data service
         let fake_data_service = Arc::new(fake_data::FakeDataService::new(
             app_database_service.clone(),
+            blob_service.clone(),
         ));
 
+        // Share events with other nodes on the same Postgres database
+        #[cfg(feature = "postgres")]
+        if config.database.url.starts_with("postgres") {
+            event_fanout::spawn_listener(broadcast.clone(), config.database.url.clone());
+            tracing::info!("Collaborative editing sessions are per node; editors of a model must share a node");
+        }
+
         // Note: We don't start the broadcast loop here because WebSocket handlers
         // already subscribe to the broadcast channel and send messages to their clients.
         // Starting the loop would cause duplicate sends and "sending after closing" errors.
@@ -67,6 +125,9 @@ impl ServiceRegistry {
         // Create a channel for model events
         let (model_event_sender, mut model_event_receiver) = tokio::sync::broadcast::channel(1000);
         
+        // Keep the model search index in sync with model changes
+        model_service.spawn_search_indexer(model_event_sender.subscribe());
+
         // Set the event sender in the model service
         model_service.set_event_sender(model_event_sender).await;
 
@@ -90,6 +151,10 @@ impl ServiceRegistry {
             broadcast,
             app_database_service,
             fake_data_service,
+            blob_service,
+            retention_service,
+            data_migration_service,
+            collaboration,
         })
     }
 }
\ No newline at end of file
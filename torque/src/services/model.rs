@@ -1,18 +1,26 @@
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
 use crate::common::Uuid;
 use crate::common::UtcDateTime;
 use chrono::Utc;
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing;
 
-use sea_orm::DatabaseConnection;
+use sea_orm::{DatabaseConnection, DatabaseTransaction};
 use crate::services::cache::CacheService;
+use crate::services::model_search::{ModelSearchHit, ModelSearchIndex};
 use crate::model::types::*;
 use crate::model::events::ModelChangeEvent;
 use crate::error::Error;
-use crate::database::entities::torque_models;
+use crate::database::entities::{torque_models, torque_model_pins, torque_model_versions};
+
+/// Times a version snapshot is retried when another node takes its number first
+const VERSION_NUMBER_ATTEMPTS: usize = 5;
+
+/// Author recorded on version snapshots when the caller doesn't name one
+pub const SYSTEM_AUTHOR: &str = "system";
 
 /// Model service for managing TorqueModels with high-performance caching
 pub struct ModelService {
@@ -20,6 +28,10 @@ pub struct ModelService {
     cache: Arc<CacheService>,
     model_cache: DashMap<Uuid, CacheEntry<TorqueModel>>,
     event_sender: Arc<RwLock<Option<broadcast::Sender<ModelChangeEvent>>>>,
+    /// Serializes snapshot numbering on this node; saves from other nodes sharing the
+    /// database are handled by `record_version` retrying on a taken number
+    version_lock: Mutex<()>,
+    search_index: ModelSearchIndex,
 }
 
 #[derive(Clone)]
@@ -49,10 +61,12 @@ impl<T> CacheEntry<T> {
 impl ModelService {
     pub fn new(database: Arc<DatabaseConnection>, cache: Arc<CacheService>) -> Self {
         Self {
+            search_index: ModelSearchIndex::new(database.clone()),
             database,
             cache,
             model_cache: DashMap::new(),
             event_sender: Arc::new(RwLock::new(None)),
+            version_lock: Mutex::new(()),
         }
     }
 
@@ -80,9 +94,16 @@ impl ModelService {
         
         // Try to get from database first
         match self.get_models_from_db().await {
-            Ok(models) => {
-                // Cache the models we retrieved from database
-                for model in &models {
+            Ok(mut models) => {
+                // Cache the models we retrieved from database, unless the cache already holds
+                // a newer definition whose write had not committed when the rows were read
+                for model in models.iter_mut() {
+                    if let Some(entry) = self.model_cache.get(&model.id) {
+                        if !entry.is_expired() && entry.data.updated_at > model.updated_at {
+                            *model = entry.data.clone();
+                            continue;
+                        }
+                    }
                     self.model_cache.insert(
                         model.id.clone(),
                         CacheEntry::new(model.clone(), 3600),
@@ -142,7 +163,7 @@ impl ModelService {
     
     /// Save model to database (internal helper)
     async fn save_model_to_db(&self, model: &TorqueModel) -> Result<(), Error> {
-        use sea_orm::{EntityTrait, Set};
+        use sea_orm::{EntityTrait, Set, TransactionTrait};
         
         let model_json = serde_json::to_value(model)?;
         let schema_json = serde_json::json!({
@@ -164,9 +185,12 @@ impl ModelService {
             updated_at: Set(model.updated_at.as_chrono().naive_utc()),
         };
         
+        let txn = self.database.begin().await?;
         torque_models::Entity::insert(active_model)
-            .exec(self.database.as_ref())
+            .exec(&txn)
             .await?;
+        self.record_version(&txn, model, &model.created_by, Some("Created model".to_string())).await?;
+        txn.commit().await?;
             
         Ok(())
     }
@@ -217,7 +241,7 @@ impl ModelService {
 
     /// Get a model by name and version
     pub async fn get_model_by_name_and_version(&self, name: &str, version: &str) -> Result<TorqueModel, Error> {
-        use sea_orm::{EntityTrait, ColumnTrait, QueryFilter};
+        use sea_orm::{EntityTrait, ColumnTrait, QueryFilter, QueryOrder};
         
         let model_record = torque_models::Entity::find()
             .filter(torque_models::Column::Name.eq(name))
@@ -238,7 +262,16 @@ impl ModelService {
                 }
             }
         } else {
-            Err(Error::NotFound(format!("Model {} v{} not found", name, version)))
+            // Not the current definition; look through the version history
+            let snapshot = torque_model_versions::Entity::find()
+                .filter(torque_model_versions::Column::Name.eq(name))
+                .filter(torque_model_versions::Column::Version.eq(version))
+                .order_by_desc(torque_model_versions::Column::VersionNumber)
+                .one(self.database.as_ref())
+                .await?
+                .ok_or_else(|| Error::NotFound(format!("Model {} v{} not found", name, version)))?;
+
+            Ok(Self::version_from_record(snapshot)?.model)
         }
     }
 
@@ -296,24 +329,9 @@ impl ModelService {
         }
         model.updated_at = UtcDateTime::from_chrono(Utc::now());
 
-        // Persist to database
-        use sea_orm::{ActiveModelTrait, Set};
-        
-        let model_json = serde_json::to_value(&model)?;
-        let schema_json = serde_json::to_value(&model)?; // For now, use same as model_json
-        let active_model = torque_models::ActiveModel {
-            id: Set(model.id.to_string()),
-            name: Set(model.name.clone()),
-            description: Set(model.description.clone()),
-            version: Set(model.version.clone()),
-            model_json: Set(model_json),
-            schema_json: Set(schema_json),
-            created_at: Set(model.created_at.clone().into_chrono().naive_utc()),
-            updated_at: Set(model.updated_at.clone().into_chrono().naive_utc()),
-        };
-        
-        active_model.update(self.database.as_ref()).await?;
-        tracing::info!("Updated model '{}' in database", model.name);
+        let author = input.author.unwrap_or_else(|| SYSTEM_AUTHOR.to_string());
+        let message = input.message.unwrap_or_else(|| "Updated model properties".to_string());
+        self.persist_model_to_database(&model, &author, message).await?;
 
         // Update cache
         self.model_cache.insert(
@@ -340,6 +358,328 @@ impl ModelService {
         Ok(true)
     }
 
+    /// Record an immutable snapshot of the model as the next version number, as part of `txn`.
+    /// Another node can take the same number between reading the latest and inserting, so
+    /// each attempt runs in a savepoint and a unique violation retries with a fresh number.
+    async fn record_version(
+        &self,
+        txn: &DatabaseTransaction,
+        model: &TorqueModel,
+        author: &str,
+        message: Option<String>,
+    ) -> Result<i32, Error> {
+        use sea_orm::{EntityTrait, ColumnTrait, QueryFilter, QueryOrder, Set, SqlErr, TransactionTrait};
+
+        let _guard = self.version_lock.lock().await;
+        let model_json = serde_json::to_value(model)?;
+
+        for attempt in 1..=VERSION_NUMBER_ATTEMPTS {
+            let savepoint = txn.begin().await?;
+            let version_number = torque_model_versions::Entity::find()
+                .filter(torque_model_versions::Column::ModelId.eq(model.id.to_string()))
+                .order_by_desc(torque_model_versions::Column::VersionNumber)
+                .one(&savepoint)
+                .await?
+                .map(|v| v.version_number + 1)
+                .unwrap_or(1);
+
+            let snapshot = torque_model_versions::ActiveModel {
+                id: Set(Uuid::new_v4().to_string()),
+                model_id: Set(model.id.to_string()),
+                version_number: Set(version_number),
+                name: Set(model.name.clone()),
+                version: Set(model.version.clone()),
+                author: Set(author.to_string()),
+                message: Set(message.clone()),
+                model_json: Set(model_json.clone()),
+                created_at: Set(Utc::now().naive_utc()),
+            };
+
+            match torque_model_versions::Entity::insert(snapshot).exec(&savepoint).await {
+                Ok(_) => {
+                    savepoint.commit().await?;
+                    tracing::debug!("Recorded version {} of model '{}'", version_number, model.name);
+                    return Ok(version_number);
+                }
+                Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                    savepoint.rollback().await?;
+                    tracing::debug!(
+                        "Version {} of model '{}' was taken concurrently (attempt {})",
+                        version_number, model.name, attempt
+                    );
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(Error::Internal(format!(
+            "Could not allocate a version number for model '{}' after {} attempts",
+            model.name, VERSION_NUMBER_ATTEMPTS
+        )))
+    }
+
+    /// Get the newest recorded version number of a model
+    pub async fn latest_version_number(&self, model_id: Uuid) -> Result<Option<i32>, Error> {
+        use sea_orm::{EntityTrait, ColumnTrait, QueryFilter, QueryOrder};
+
+        let latest = torque_model_versions::Entity::find()
+            .filter(torque_model_versions::Column::ModelId.eq(model_id.to_string()))
+            .order_by_desc(torque_model_versions::Column::VersionNumber)
+            .one(self.database.as_ref())
+            .await?;
+
+        Ok(latest.map(|v| v.version_number))
+    }
+
+    /// Convert a snapshot row into a ModelVersion
+    fn version_from_record(record: torque_model_versions::Model) -> Result<ModelVersion, Error> {
+        let model = serde_json::from_value::<TorqueModel>(record.model_json)?;
+        let model_id = Uuid::parse(&record.model_id)
+            .map_err(|_| Error::Internal(format!("Invalid model id on version record: {}", record.model_id)))?;
+
+        Ok(ModelVersion {
+            info: ModelVersionInfo {
+                model_id,
+                version_number: record.version_number,
+                name: record.name,
+                version: record.version,
+                author: record.author,
+                message: record.message,
+                created_at: UtcDateTime::from_chrono(record.created_at.and_utc()),
+            },
+            model,
+        })
+    }
+
+    /// List the version history of a model, newest first
+    pub async fn list_model_versions(&self, model_id: Uuid) -> Result<Vec<ModelVersionInfo>, Error> {
+        use sea_orm::{EntityTrait, ColumnTrait, QueryFilter, QueryOrder};
+
+        let records = torque_model_versions::Entity::find()
+            .filter(torque_model_versions::Column::ModelId.eq(model_id.to_string()))
+            .order_by_desc(torque_model_versions::Column::VersionNumber)
+            .all(self.database.as_ref())
+            .await?;
+
+        records.into_iter()
+            .map(|record| Self::version_from_record(record).map(|v| v.info))
+            .collect()
+    }
+
+    /// Get a specific version snapshot of a model
+    pub async fn get_model_version(&self, model_id: Uuid, version_number: i32) -> Result<ModelVersion, Error> {
+        use sea_orm::{EntityTrait, ColumnTrait, QueryFilter};
+
+        let record = torque_model_versions::Entity::find()
+            .filter(torque_model_versions::Column::ModelId.eq(model_id.to_string()))
+            .filter(torque_model_versions::Column::VersionNumber.eq(version_number))
+            .one(self.database.as_ref())
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Version {} of model {} not found", version_number, model_id)))?;
+
+        Self::version_from_record(record)
+    }
+
+    /// Roll a model back to a previous version.
+    /// The old snapshot is restored as the current definition and recorded as a new version,
+    /// so history is never rewritten.
+    pub async fn rollback_model(
+        &self,
+        model_id: Uuid,
+        version_number: i32,
+        author: Option<String>,
+        message: Option<String>,
+    ) -> Result<TorqueModel, Error> {
+        let current = self.get_model(model_id.clone()).await?
+            .ok_or_else(|| Error::NotFound(format!("Model with id {} not found", model_id)))?;
+        let target = self.get_model_version(model_id.clone(), version_number).await?;
+
+        let mut model = target.model;
+        model.id = current.id.clone();
+        model.created_at = current.created_at.clone();
+        model.updated_at = UtcDateTime::now();
+
+        let author = author.unwrap_or_else(|| SYSTEM_AUTHOR.to_string());
+        let message = message.unwrap_or_else(|| format!("Rolled back to version {}", version_number));
+        self.persist_model_to_database(&model, &author, message).await?;
+        tracing::info!("Rolled back model '{}' to version {}", model.name, version_number);
+
+        self.model_cache.insert(
+            model.id.clone(),
+            CacheEntry::new(model.clone(), 3600),
+        );
+
+        self.emit_event(ModelChangeEvent::model_updated(model.clone()));
+
+        Ok(model)
+    }
+
+    /// Pin running apps of a model to a specific version snapshot
+    pub async fn pin_model_version(&self, model_id: Uuid, version_number: i32, pinned_by: Option<String>) -> Result<ModelVersionPin, Error> {
+        use sea_orm::{EntityTrait, Set};
+        use sea_orm::sea_query::OnConflict;
+
+        // Make sure the snapshot exists before pinning to it
+        self.get_model_version(model_id.clone(), version_number).await?;
+
+        let pinned_at = Utc::now();
+        let pinned_by = pinned_by.unwrap_or_else(|| SYSTEM_AUTHOR.to_string());
+        let pin = torque_model_pins::ActiveModel {
+            model_id: Set(model_id.to_string()),
+            version_number: Set(version_number),
+            pinned_by: Set(pinned_by.clone()),
+            pinned_at: Set(pinned_at.naive_utc()),
+        };
+
+        torque_model_pins::Entity::insert(pin)
+            .on_conflict(
+                OnConflict::column(torque_model_pins::Column::ModelId)
+                    .update_columns([
+                        torque_model_pins::Column::VersionNumber,
+                        torque_model_pins::Column::PinnedBy,
+                        torque_model_pins::Column::PinnedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec(self.database.as_ref())
+            .await?;
+
+        tracing::info!("Pinned model {} to version {}", model_id, version_number);
+
+        Ok(ModelVersionPin {
+            model_id,
+            version_number,
+            pinned_by,
+            pinned_at: UtcDateTime::from_chrono(pinned_at),
+        })
+    }
+
+    /// Remove a version pin so running apps follow the latest definition again
+    pub async fn unpin_model_version(&self, model_id: Uuid) -> Result<bool, Error> {
+        use sea_orm::{EntityTrait, ColumnTrait, QueryFilter};
+
+        let result = torque_model_pins::Entity::delete_many()
+            .filter(torque_model_pins::Column::ModelId.eq(model_id.to_string()))
+            .exec(self.database.as_ref())
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Get the version pin for a model, if any
+    pub async fn get_model_version_pin(&self, model_id: Uuid) -> Result<Option<ModelVersionPin>, Error> {
+        use sea_orm::EntityTrait;
+
+        let pin = torque_model_pins::Entity::find_by_id(model_id.to_string())
+            .one(self.database.as_ref())
+            .await?;
+
+        Ok(pin.map(|p| ModelVersionPin {
+            model_id: model_id.clone(),
+            version_number: p.version_number,
+            pinned_by: p.pinned_by,
+            pinned_at: UtcDateTime::from_chrono(p.pinned_at.and_utc()),
+        }))
+    }
+
+    /// Load a version snapshot, or the current definition when no version is given
+    async fn model_at_version(&self, model_id: Uuid, version_number: Option<i32>) -> Result<TorqueModel, Error> {
+        match version_number {
+            Some(version_number) => Ok(self.get_model_version(model_id, version_number).await?.model),
+            None => self.get_model(model_id.clone()).await?
+                .ok_or_else(|| Error::NotFound(format!("Model with id {} not found", model_id))),
+        }
+    }
+
+    /// Structural diff between two versions of a model (None = current definition)
+    pub async fn diff_model_versions(
+        &self,
+        model_id: Uuid,
+        from_version: Option<i32>,
+        to_version: Option<i32>,
+    ) -> Result<crate::model::diff::ModelDiff, Error> {
+        let from = self.model_at_version(model_id.clone(), from_version).await?;
+        let to = self.model_at_version(model_id, to_version).await?;
+
+        Ok(crate::model::diff::diff_models(&from, &to)?)
+    }
+
+    /// Build the app data migration plan between two recorded versions of a model.
+    /// `to_version` defaults to the latest recorded version.
+    pub async fn plan_data_migration(
+        &self,
+        model_id: Uuid,
+        from_version: i32,
+        to_version: Option<i32>,
+        orphan_strategy: crate::model::data_migration::OrphanStrategy,
+    ) -> Result<crate::model::data_migration::DataMigrationPlan, Error> {
+        let to_version = match to_version {
+            Some(to_version) => to_version,
+            None => self.latest_version_number(model_id.clone()).await?
+                .ok_or_else(|| Error::NotFound(format!("Model {} has no recorded versions", model_id)))?,
+        };
+
+        let from = self.get_model_version(model_id.clone(), from_version).await?.model;
+        let to = self.get_model_version(model_id, to_version).await?.model;
+
+        crate::model::data_migration::plan_data_migration(&from, &to, from_version, to_version, orphan_strategy)
+    }
+
+    /// Three-way merge an edited copy of a model into the current definition.
+    /// `base_version` is the version the edit started from. The merged model is only
+    /// saved when there are no conflicts; otherwise the conflicts are returned for resolution.
+    pub async fn merge_model(
+        &self,
+        model_id: Uuid,
+        base_version: i32,
+        incoming: TorqueModel,
+        author: Option<String>,
+        message: Option<String>,
+    ) -> Result<crate::model::diff::MergeResult, Error> {
+        let base = self.get_model_version(model_id.clone(), base_version).await?.model;
+        let current = self.get_model(model_id.clone()).await?
+            .ok_or_else(|| Error::NotFound(format!("Model with id {} not found", model_id)))?;
+
+        let mut result = crate::model::diff::merge_models(&base, &current, &incoming)?;
+        if !result.is_clean() {
+            tracing::info!("Merge into model '{}' has {} conflict(s)", current.name, result.conflicts.len());
+            return Ok(result);
+        }
+
+        result.merged.id = current.id.clone();
+        result.merged.created_at = current.created_at.clone();
+        result.merged.updated_at = UtcDateTime::now();
+
+        let author = author.unwrap_or_else(|| SYSTEM_AUTHOR.to_string());
+        let message = message.unwrap_or_else(|| format!("Merged changes based on version {}", base_version));
+        self.persist_model_to_database(&result.merged, &author, message).await?;
+
+        self.model_cache.insert(
+            result.merged.id.clone(),
+            CacheEntry::new(result.merged.clone(), 3600),
+        );
+        self.emit_event(ModelChangeEvent::model_updated(result.merged.clone()));
+
+        Ok(result)
+    }
+
+    /// Resolve the model definition a running app should use.
+    /// An explicitly requested version wins, then the model's pin, then the latest definition.
+    pub async fn get_runtime_model(&self, model_id: Uuid, requested_version: Option<i32>) -> Result<Option<TorqueModel>, Error> {
+        let version_number = match requested_version {
+            Some(version_number) => Some(version_number),
+            None => self.get_model_version_pin(model_id.clone()).await?.map(|p| p.version_number),
+        };
+
+        match version_number {
+            Some(version_number) => {
+                let snapshot = self.get_model_version(model_id.clone(), version_number).await?;
+                Ok(Some(snapshot.model))
+            }
+            None => self.get_model(model_id).await,
+        }
+    }
+
     /// Get entities for a specific model
     pub async fn get_entities(&self, model_id: Uuid) -> Result<Vec<ModelEntity>, Error> {
         if let Some(model) = self.get_model(model_id).await? {
@@ -371,7 +711,7 @@ impl ModelService {
                 field_type: f.field_type,
                 required: f.required,
                 default_value: f.default_value,
-                validation: vec![], // TODO: Convert from input
+                validation: f.validation,
                 ui_config: f.ui_config.unwrap_or_default(),
             }).collect(),
             constraints: vec![],
@@ -394,7 +734,7 @@ impl ModelService {
         self.emit_event(ModelChangeEvent::entity_added(model_id, entity.id.clone()));
 
         // Persist to database
-        self.persist_model_to_database(&model).await?;
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Added entity '{}'", entity.name)).await?;
 
         Ok(entity)
     }
@@ -461,33 +801,28 @@ impl ModelService {
         self.emit_event(ModelChangeEvent::entity_updated(model.id.clone(), entity_id));
 
         // Persist to database
-        self.persist_model_to_database(&model).await?;
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Updated entity '{}'", updated_entity.name)).await?;
 
         Ok(updated_entity)
     }
 
-    /// Delete an entity from a model
-    pub async fn delete_entity(&self, entity_id: Uuid) -> Result<bool, Error> {
-        // Find the model containing this entity
-        let mut model_with_entity = None;
-        for entry in self.model_cache.iter() {
-            let model = &entry.value().data;
-            if model.entities.iter().any(|e| e.id == entity_id) {
-                model_with_entity = Some(model.clone());
-                break;
-            }
-        }
-
-        let mut model = model_with_entity
+    /// Delete an entity from a model.
+    /// Relationships, layout components, flows and `Reference` fields that refer to the entity are
+    /// reported, and removed as well when `cleanup_references` is set.
+    pub async fn delete_entity(&self, entity_id: Uuid, cleanup_references: bool) -> Result<EntityDeletion, Error> {
+        let mut model = self.find_model_containing(|m| m.entities.iter().any(|e| e.id == entity_id)).await?
             .ok_or_else(|| Error::NotFound(format!("Entity with id {} not found", entity_id)))?;
 
-        // Remove the entity
-        let initial_count = model.entities.len();
-        model.entities.retain(|e| e.id != entity_id);
-        
-        if model.entities.len() == initial_count {
-            return Ok(false); // Entity was not found
-        }
+        let entity_name = model.entities.iter()
+            .find(|e| e.id == entity_id)
+            .map(|e| e.name.clone())
+            .unwrap_or_default();
+        let referencing_entities: Vec<Uuid> = model.entities.iter()
+            .filter(|e| e.fields.iter().any(|f| f.field_type.references_entity(&entity_id)))
+            .map(|e| e.id.clone())
+            .collect();
+        let deletion = model.remove_entity(&entity_id, cleanup_references)
+            .ok_or_else(|| Error::NotFound(format!("Entity with id {} not found", entity_id)))?;
 
         model.updated_at = UtcDateTime::now();
 
@@ -497,12 +832,39 @@ impl ModelService {
             CacheEntry::new(model.clone(), 3600),
         );
 
-        // Emit entity removed event
-        self.emit_event(ModelChangeEvent::entity_removed(model.id.clone(), entity_id));
+        // Persist to database
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Deleted entity '{}'", entity_name)).await?;
 
-        // TODO: Persist to database
+        // Emit entity removed event, followed by the references that were cleaned up
+        self.emit_event(ModelChangeEvent::entity_removed(model.id.clone(), entity_id.clone()));
+        if cleanup_references {
+            for relationship_id in &deletion.relationships {
+                self.emit_event(ModelChangeEvent::relationship_removed(model.id.clone(), relationship_id.clone()));
+            }
+            for layout_id in &deletion.layouts {
+                self.emit_event(ModelChangeEvent::layout_updated(model.id.clone(), layout_id.clone()));
+            }
+            for flow_id in &deletion.flows {
+                self.emit_event(ModelChangeEvent::flow_removed(model.id.clone(), flow_id.clone()));
+            }
+            for referencing_entity in referencing_entities {
+                self.emit_event(ModelChangeEvent::entity_updated(model.id.clone(), referencing_entity));
+            }
+        }
 
-        Ok(true)
+        Ok(deletion)
+    }
+
+    /// Find the model containing a component, checking the cache before the database
+    async fn find_model_containing(&self, predicate: impl Fn(&TorqueModel) -> bool) -> Result<Option<TorqueModel>, Error> {
+        let cached = self.model_cache.iter()
+            .find(|entry| predicate(&entry.value().data))
+            .map(|entry| entry.value().data.clone());
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        Ok(self.get_models().await?.into_iter().find(|m| predicate(m)))
     }
 
     /// Get relationships for a specific model
@@ -527,6 +889,7 @@ impl ModelService {
             .map_err(|_| Error::InvalidInput("Invalid from_entity ID format".to_string()))?;
         let to_entity = input.to_entity.parse::<Uuid>()
             .map_err(|_| Error::InvalidInput("Invalid to_entity ID format".to_string()))?;
+        ensure_entities_exist(&model, [from_entity, to_entity])?;
 
         let relationship = ModelRelationship {
             id: Uuid::new_v4(),
@@ -550,12 +913,99 @@ impl ModelService {
             CacheEntry::new(model.clone(), 3600),
         );
 
+        // Persist to database
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Added relationship '{}'", relationship.name)).await?;
+
         // Emit relationship added event
         self.emit_event(ModelChangeEvent::relationship_added(model_id.clone(), relationship.id.clone()));
 
         Ok(relationship)
     }
 
+    /// Update an existing relationship in a model
+    pub async fn update_relationship(&self, relationship_id: Uuid, input: UpdateRelationshipInput) -> Result<ModelRelationship, Error> {
+        let mut model = self.find_model_containing(|m| m.relationships.iter().any(|r| r.id == relationship_id)).await?
+            .ok_or_else(|| Error::NotFound(format!("Relationship with id {} not found", relationship_id)))?;
+
+        let from_entity = input.from_entity
+            .map(|id| id.parse::<Uuid>().map_err(|_| Error::InvalidInput("Invalid from_entity ID format".to_string())))
+            .transpose()?;
+        let to_entity = input.to_entity
+            .map(|id| id.parse::<Uuid>().map_err(|_| Error::InvalidInput("Invalid to_entity ID format".to_string())))
+            .transpose()?;
+        ensure_entities_exist(&model, from_entity.into_iter().chain(to_entity))?;
+
+        let relationship = model.relationships.iter_mut()
+            .find(|r| r.id == relationship_id)
+            .ok_or_else(|| Error::NotFound(format!("Relationship with id {} not found", relationship_id)))?;
+
+        if let Some(name) = input.name {
+            relationship.name = name;
+        }
+        if let Some(relationship_type) = input.relationship_type {
+            relationship.relationship_type = relationship_type;
+        }
+        if let Some(from_entity) = from_entity {
+            relationship.from_entity = from_entity;
+        }
+        if let Some(to_entity) = to_entity {
+            relationship.to_entity = to_entity;
+        }
+        if let Some(from_field) = input.from_field {
+            relationship.from_field = from_field;
+        }
+        if let Some(to_field) = input.to_field {
+            relationship.to_field = to_field;
+        }
+        if let Some(cascade) = input.cascade {
+            relationship.cascade = cascade;
+        }
+        if let Some(ui_config) = input.ui_config {
+            relationship.ui_config = ui_config;
+        }
+        let updated_relationship = relationship.clone();
+
+        model.updated_at = UtcDateTime::now();
+
+        // Update cache with modified model
+        self.model_cache.insert(
+            model.id.clone(),
+            CacheEntry::new(model.clone(), 3600),
+        );
+
+        // Persist to database
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Updated relationship '{}'", updated_relationship.name)).await?;
+
+        // Emit relationship updated event
+        self.emit_event(ModelChangeEvent::relationship_updated(model.id.clone(), relationship_id));
+
+        Ok(updated_relationship)
+    }
+
+    /// Delete a relationship from a model
+    pub async fn delete_relationship(&self, relationship_id: Uuid) -> Result<bool, Error> {
+        let Some(mut model) = self.find_model_containing(|m| m.relationships.iter().any(|r| r.id == relationship_id)).await? else {
+            return Ok(false);
+        };
+
+        model.relationships.retain(|r| r.id != relationship_id);
+        model.updated_at = UtcDateTime::now();
+
+        // Update cache with modified model
+        self.model_cache.insert(
+            model.id.clone(),
+            CacheEntry::new(model.clone(), 3600),
+        );
+
+        // Persist to database
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Deleted relationship {}", relationship_id)).await?;
+
+        // Emit relationship removed event
+        self.emit_event(ModelChangeEvent::relationship_removed(model.id.clone(), relationship_id));
+
+        Ok(true)
+    }
+
     /// Get flows for a specific model
     pub async fn get_flows(&self, model_id: Uuid) -> Result<Vec<ModelFlow>, Error> {
         if let Some(model) = self.get_model(model_id).await? {
@@ -599,12 +1049,93 @@ impl ModelService {
             CacheEntry::new(model.clone(), 3600),
         );
 
+        // Persist to database
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Added flow '{}'", flow.name)).await?;
+
         // Emit flow added event
         self.emit_event(ModelChangeEvent::flow_added(model_id.clone(), flow.id.clone()));
 
         Ok(flow)
     }
 
+    /// Update an existing flow in a model
+    pub async fn update_flow(&self, flow_id: Uuid, input: UpdateFlowInput) -> Result<ModelFlow, Error> {
+        let mut model = self.find_model_containing(|m| m.flows.iter().any(|f| f.id == flow_id)).await?
+            .ok_or_else(|| Error::NotFound(format!("Flow with id {} not found", flow_id)))?;
+
+        let flow = model.flows.iter_mut()
+            .find(|f| f.id == flow_id)
+            .ok_or_else(|| Error::NotFound(format!("Flow with id {} not found", flow_id)))?;
+
+        if let Some(name) = input.name {
+            flow.name = name;
+        }
+        if let Some(flow_type) = input.flow_type {
+            flow.flow_type = flow_type;
+        }
+        if let Some(trigger) = input.trigger {
+            flow.trigger = trigger;
+        }
+        if let Some(steps) = input.steps {
+            let existing = std::mem::take(&mut flow.steps);
+            flow.steps = steps.into_iter().map(|s| crate::model::types::FlowStep {
+                id: existing.iter()
+                    .find(|step| Some(&step.id) == s.id.as_ref())
+                    .or_else(|| existing.iter().find(|step| s.id.is_none() && step.name == s.name))
+                    .map(|step| step.id.clone())
+                    .unwrap_or_else(Uuid::new_v4),
+                name: s.name,
+                step_type: s.step_type,
+                condition: s.condition,
+                configuration: serde_json::from_value(s.configuration).unwrap_or_default(),
+            }).collect();
+        }
+        if let Some(error_handling) = input.error_handling {
+            flow.error_handling = error_handling;
+        }
+        let updated_flow = flow.clone();
+
+        model.updated_at = UtcDateTime::now();
+
+        // Update cache with modified model
+        self.model_cache.insert(
+            model.id.clone(),
+            CacheEntry::new(model.clone(), 3600),
+        );
+
+        // Persist to database
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Updated flow '{}'", updated_flow.name)).await?;
+
+        // Emit flow updated event
+        self.emit_event(ModelChangeEvent::flow_updated(model.id.clone(), flow_id));
+
+        Ok(updated_flow)
+    }
+
+    /// Delete a flow from a model
+    pub async fn delete_flow(&self, flow_id: Uuid) -> Result<bool, Error> {
+        let Some(mut model) = self.find_model_containing(|m| m.flows.iter().any(|f| f.id == flow_id)).await? else {
+            return Ok(false);
+        };
+
+        model.flows.retain(|f| f.id != flow_id);
+        model.updated_at = UtcDateTime::now();
+
+        // Update cache with modified model
+        self.model_cache.insert(
+            model.id.clone(),
+            CacheEntry::new(model.clone(), 3600),
+        );
+
+        // Persist to database
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Deleted flow {}", flow_id)).await?;
+
+        // Emit flow removed event
+        self.emit_event(ModelChangeEvent::flow_removed(model.id.clone(), flow_id));
+
+        Ok(true)
+    }
+
     /// Get layouts for a specific model
     pub async fn get_layouts(&self, model_id: Uuid) -> Result<Vec<ModelLayout>, Error> {
         if let Some(model) = self.get_model(model_id).await? {
@@ -660,7 +1191,7 @@ impl ModelService {
         );
 
         // Persist to database
-        self.persist_model_to_database(&model).await?;
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Added layout '{}'", layout.name)).await?;
 
         // Emit layout added event
         self.emit_event(ModelChangeEvent::layout_added(model_id.clone(), layout.id.clone()));
@@ -732,7 +1263,7 @@ impl ModelService {
         );
         
         // Persist to database
-        self.persist_model_to_database(&model).await?;
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Updated layout '{}'", updated_layout.name)).await?;
         
         // Emit layout updated event
         self.emit_event(ModelChangeEvent::layout_updated(model.id.clone(), layout_id));
@@ -773,7 +1304,7 @@ impl ModelService {
         );
 
         // Persist to database
-        self.persist_model_to_database(&model).await?;
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Deleted layout {}", layout_id)).await?;
 
         // Emit layout removed event
         self.emit_event(ModelChangeEvent::layout_removed(model.id.clone(), layout_id));
@@ -781,11 +1312,175 @@ impl ModelService {
         Ok(true)
     }
 
-    /// Search models by name or description
-    pub async fn search_models(&self, _query: String) -> Result<Vec<TorqueModel>, Error> {
-        // TODO: Implement full-text search
-        // For now, return empty results
-        Ok(vec![])
+    /// Apply a fine-grained edit from a collaborative editing session and persist the result
+    pub async fn apply_edit_operation(
+        &self,
+        model_id: Uuid,
+        operation: &crate::model::collab::EditOperation,
+        author: &str,
+    ) -> Result<TorqueModel, Error> {
+        use crate::model::collab::EditOperation as Op;
+
+        let mut model = self.get_model(model_id.clone()).await?
+            .ok_or_else(|| Error::NotFound(format!("Model with id {} not found", model_id)))?;
+
+        operation.apply(&mut model)?;
+
+        // Update cache with modified model
+        self.model_cache.insert(
+            model_id.clone(),
+            CacheEntry::new(model.clone(), 3600),
+        );
+
+        self.persist_model_to_database(&model, author, operation.description()).await?;
+
+        let event = match operation {
+            Op::SetModelProperty { .. } => ModelChangeEvent::model_updated(model.clone()),
+            Op::AddEntity { entity } => ModelChangeEvent::entity_added(model_id, entity.id.clone()),
+            Op::UpdateEntity { entity_id, .. }
+            | Op::AddField { entity_id, .. }
+            | Op::UpdateField { entity_id, .. }
+            | Op::RemoveField { entity_id, .. } => ModelChangeEvent::entity_updated(model_id, entity_id.clone()),
+            Op::RemoveEntity { entity_id } => ModelChangeEvent::entity_removed(model_id, entity_id.clone()),
+            Op::AddRelationship { relationship } => ModelChangeEvent::relationship_added(model_id, relationship.id.clone()),
+            Op::UpdateRelationship { relationship_id, .. } => ModelChangeEvent::relationship_updated(model_id, relationship_id.clone()),
+            Op::RemoveRelationship { relationship_id } => ModelChangeEvent::relationship_removed(model_id, relationship_id.clone()),
+            Op::AddLayout { layout } => ModelChangeEvent::layout_added(model_id, layout.id.clone()),
+            Op::UpdateLayout { layout_id, .. } => ModelChangeEvent::layout_updated(model_id, layout_id.clone()),
+            Op::RemoveLayout { layout_id } => ModelChangeEvent::layout_removed(model_id, layout_id.clone()),
+            Op::AddFlow { flow } => ModelChangeEvent::flow_added(model_id, flow.id.clone()),
+            Op::UpdateFlow { flow_id, .. } => ModelChangeEvent::flow_updated(model_id, flow_id.clone()),
+            Op::RemoveFlow { flow_id } => ModelChangeEvent::flow_removed(model_id, flow_id.clone()),
+        };
+        self.emit_event(event);
+
+        Ok(model)
+    }
+
+    /// Generate list, detail and form layouts for an entity from its UI configuration.
+    /// A previously generated layout with the same name is replaced, keeping its ID.
+    pub async fn generate_layouts(
+        &self,
+        model_id: Uuid,
+        entity: &str,
+        kinds: &[crate::model::layouts::LayoutKind],
+    ) -> Result<Vec<ModelLayout>, Error> {
+        let mut model = self.get_model(model_id.clone()).await?
+            .ok_or_else(|| Error::NotFound(format!("Model with id {} not found", model_id)))?;
+
+        let entity = crate::model::json_schema::find_entity(&model, entity)
+            .ok_or_else(|| Error::EntityNotFound(format!("Model has no entity '{}'", entity)))?;
+        let generated = crate::model::layouts::generate_layouts(&model, entity, kinds);
+
+        let mut layouts = Vec::with_capacity(generated.len());
+        let mut events = Vec::with_capacity(generated.len());
+        for mut layout in generated {
+            match model.layouts.iter_mut().find(|l| l.name == layout.name) {
+                Some(existing) => {
+                    layout.id = existing.id.clone();
+                    layout.created_at = existing.created_at.clone();
+                    *existing = layout.clone();
+                    events.push(ModelChangeEvent::layout_updated(model_id.clone(), layout.id.clone()));
+                }
+                None => {
+                    model.layouts.push(layout.clone());
+                    events.push(ModelChangeEvent::layout_added(model_id.clone(), layout.id.clone()));
+                }
+            }
+            layouts.push(layout);
+        }
+        model.updated_at = UtcDateTime::now();
+
+        // Update cache with modified model
+        self.model_cache.insert(
+            model_id.clone(),
+            CacheEntry::new(model.clone(), 3600),
+        );
+
+        let names: Vec<&str> = layouts.iter().map(|l| l.name.as_str()).collect();
+        self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Generated layouts {}", names.join(", "))).await?;
+
+        for event in events {
+            self.emit_event(event);
+        }
+
+        Ok(layouts)
+    }
+
+    /// Search models by name, description and component names, best match first
+    pub async fn search_models(&self, query: String) -> Result<Vec<TorqueModel>, Error> {
+        let hits = self.search_model_hits(&query, None).await?;
+
+        let mut models: Vec<TorqueModel> = Vec::new();
+        for hit in hits {
+            if models.iter().any(|m| m.id == hit.model_id) {
+                continue;
+            }
+            if let Some(model) = self.get_model(hit.model_id).await? {
+                models.push(model);
+            }
+        }
+
+        Ok(models)
+    }
+
+    /// Full-text search returning each match with its path inside the model
+    pub async fn search_model_hits(&self, query: &str, limit: Option<u64>) -> Result<Vec<ModelSearchHit>, Error> {
+        self.search_index
+            .search(query, limit.unwrap_or(crate::services::model_search::DEFAULT_SEARCH_LIMIT))
+            .await
+    }
+
+    /// Rebuild the search index and keep it up to date from model change events
+    pub fn spawn_search_indexer(
+        self: &Arc<Self>,
+        mut receiver: broadcast::Receiver<ModelChangeEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = self.clone();
+        tokio::spawn(async move {
+            service.rebuild_search_index().await;
+
+            loop {
+                match receiver.recv().await {
+                    Ok(ModelChangeEvent::ModelDeleted { model_id, .. }) => {
+                        if let Err(e) = service.search_index.remove_model(&model_id).await {
+                            tracing::error!("Failed to remove model {} from search index: {}", model_id, e);
+                        }
+                    }
+                    Ok(event) => {
+                        let model_id = event.model_id();
+                        let model = match event {
+                            ModelChangeEvent::ModelCreated { model, .. }
+                            | ModelChangeEvent::ModelUpdated { model, .. } => Ok(Some(model)),
+                            _ => service.get_model(model_id.clone()).await,
+                        };
+                        let result = match model {
+                            Ok(Some(model)) => service.search_index.index_model(&model).await,
+                            Ok(None) => service.search_index.remove_model(&model_id).await,
+                            Err(e) => Err(e),
+                        };
+                        if let Err(e) = result {
+                            tracing::error!("Failed to update search index for model {}: {}", model_id, e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Search indexer missed {} model events, rebuilding index", skipped);
+                        service.rebuild_search_index().await;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    async fn rebuild_search_index(&self) {
+        let result = match self.get_models().await {
+            Ok(models) => self.search_index.rebuild(&models).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::error!("Failed to rebuild model search index: {}", e);
+        }
     }
 
     /// Validate unique IDs across all model components
@@ -1025,8 +1720,16 @@ impl ModelService {
         self.create_model(model).await
     }
 
-    /// Replace an existing model with imported data
-    pub async fn replace_model(&self, model_id: Uuid, data: String) -> Result<TorqueModel, Error> {
+    /// Replace an existing model with imported data.
+    /// When `base_version` is given and the model has been saved since that version,
+    /// the import is three-way merged with the newer changes instead of overwriting them.
+    pub async fn replace_model(
+        &self,
+        model_id: Uuid,
+        data: String,
+        base_version: Option<i32>,
+        author: Option<String>,
+    ) -> Result<TorqueModel, Error> {
         // Parse the export format
         let export_data: serde_json::Value = serde_json::from_str(&data)
             .map_err(|e| Error::Serialization(e))?;
@@ -1042,11 +1745,6 @@ impl ModelService {
         let mut updated_model = existing_model.clone();
         updated_model.name = import_input.name;
         updated_model.description = import_input.description;
-        updated_model.version = format!("{}.0", 
-            existing_model.version.split('.').next()
-                .and_then(|v| v.parse::<u32>().ok())
-                .unwrap_or(1) + 1
-        );
         updated_model.updated_at = UtcDateTime::now();
         
         // Extract entities, relationships, layouts, flows from the converted data
@@ -1074,6 +1772,40 @@ impl ModelService {
             }
         }
 
+        // Don't overwrite edits saved after the version the import was based on
+        let mut message = "Replaced model from import".to_string();
+        if let Some(base_version) = base_version {
+            let latest = self.latest_version_number(model_id.clone()).await?;
+            if matches!(latest, Some(n) if n != base_version) {
+                let base = self.get_model_version(model_id.clone(), base_version).await?.model;
+                let result = crate::model::diff::merge_models(&base, &existing_model, &updated_model)?;
+                if !result.is_clean() {
+                    let locations: Vec<String> = result.conflicts.iter()
+                        .map(|c| match &c.property {
+                            Some(property) => format!("{}.{}", c.path, property),
+                            None => c.path.clone(),
+                        })
+                        .collect();
+                    return Err(Error::Validation(format!(
+                        "Model changed since version {} and the import conflicts with those changes: {}",
+                        base_version,
+                        locations.join(", ")
+                    )));
+                }
+
+                updated_model = result.merged;
+                updated_model.updated_at = UtcDateTime::now();
+                message = format!("Merged import based on version {}", base_version);
+            }
+        }
+
+        // Bumped after merging, so the version itself never conflicts
+        updated_model.version = format!("{}.0",
+            existing_model.version.split('.').next()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(1) + 1
+        );
+
         // Update cache
         self.model_cache.insert(
             model_id.clone(),
@@ -1084,11 +1816,20 @@ impl ModelService {
         self.emit_event(ModelChangeEvent::model_updated(updated_model.clone()));
 
         // Persist to database
-        self.persist_model_to_database(&updated_model).await?;
+        let author = author.unwrap_or_else(|| SYSTEM_AUTHOR.to_string());
+        self.persist_model_to_database(&updated_model, &author, message).await?;
 
         Ok(updated_model)
     }
 
+    /// Keep the ID of an exported item when present so re-imports can be diffed and merged by ID
+    fn export_id(item: &serde_json::Value) -> Uuid {
+        item.get("id")
+            .and_then(|v| v.as_str())
+            .and_then(|id| Uuid::parse(id).ok())
+            .unwrap_or_else(Uuid::new_v4)
+    }
+
     /// Convert export format to internal TorqueModel
     fn convert_export_to_model(&self, export_data: serde_json::Value) -> Result<CreateModelInput, Error> {
         let metadata = export_data["metadata"].as_object()
@@ -1147,7 +1888,7 @@ impl ModelService {
                     .unwrap_or(serde_json::json!([]));
 
                 model_fields.push(crate::model::types::EntityField {
-                    id: Uuid::new_v4(),
+                    id: Self::export_id(field),
                     name: field_name.to_string(),
                     display_name: field_display_name,
                     field_type,
@@ -1159,7 +1900,7 @@ impl ModelService {
             }
 
             model_entities.push(crate::model::types::ModelEntity {
-                id: Uuid::new_v4(),
+                id: Self::export_id(entity),
                 name: entity_name.to_string(),
                 display_name: entity_display_name,
                 description: Some(entity_description),
@@ -1191,7 +1932,7 @@ impl ModelService {
             for component in components {
                 if let Some(position) = component.get("position") {
                     layout_components.push(crate::model::types::LayoutComponent {
-                        id: Uuid::new_v4(),
+                        id: Self::export_id(component),
                         component_type: "DataGrid".to_string(), // Default type
                         position: crate::model::types::ComponentPosition {
                             row: position.get("row").and_then(|r| r.as_u64()).unwrap_or(0) as u32,
@@ -1210,7 +1951,7 @@ impl ModelService {
 
             let now = UtcDateTime::now();
             model_layouts.push(crate::model::types::ModelLayout {
-                id: Uuid::new_v4(),
+                id: Self::export_id(layout),
                 name: layout_name.to_string(),
                 description: layout.get("description").and_then(|d| d.as_str()).map(|s| s.to_string()),
                 layout_type: crate::model::types::LayoutType::Dashboard, // Default type
@@ -1321,25 +2062,61 @@ impl ModelService {
         Ok(())
     }
     
+    /// Create a new model from a registered template (see `crate::model::templates`)
+    pub async fn create_model_from_template(
+        &self,
+        template_id: &str,
+        name: Option<String>,
+        description: Option<String>,
+    ) -> Result<TorqueModel, Error> {
+        let template = crate::model::templates::find_template(template_id)
+            .ok_or_else(|| Error::NotFound(format!("Model template '{}' not found", template_id)))?;
+
+        let definition = template.definition(name.as_deref(), description.as_deref());
+        let model = self.model_from_sample_definition(&definition);
+        self.store_new_model(model).await
+    }
+
     /// Parse sample model from JSON (simplified format)
     async fn parse_sample_model(&self, json_content: &str) -> Result<TorqueModel, Error> {
-        use serde_json::Value;
-        
         // Parse as generic JSON first
         let data: Value = serde_json::from_str(json_content)?;
         
-        // Extract basic information
-        let name = data["name"].as_str().unwrap_or("Sample Model").to_string();
-        let description = data["description"].as_str().map(|s| s.to_string());
-        let version = data["version"].as_str().unwrap_or("1.0.0").to_string();
-        let created_by = data["created_by"].as_str().unwrap_or("system").to_string();
+        let name = data["name"].as_str().unwrap_or("Sample Model");
+        let version = data["version"].as_str().unwrap_or("1.0.0");
         
         // Check if a model with the same name and version already exists
-        if let Ok(existing_model) = self.get_model_by_name_and_version(&name, &version).await {
+        if let Ok(existing_model) = self.get_model_by_name_and_version(name, version).await {
             tracing::info!("Model {} v{} already exists, returning existing model with {} entities", name, version, existing_model.entities.len());
             return Ok(existing_model);
         }
         
+        let model = self.store_new_model(self.model_from_sample_definition(&data)).await?;
+        
+        // TODO: Load sample data - requires entity service integration
+        // For now, we just log that sample data exists
+        if let Some(sample_data) = data.get("sample_data") {
+            tracing::info!("Model {} contains sample data that needs to be loaded", model.name);
+            if let Some(sample_data_obj) = sample_data.as_object() {
+                for (entity_type, entity_data) in sample_data_obj {
+                    if let Some(data_array) = entity_data.as_array() {
+                        tracing::info!("  - {} {} entities", data_array.len(), entity_type);
+                    }
+                }
+            }
+        }
+        
+        Ok(model)
+    }
+    
+    /// Build a model from the sample model JSON format
+    fn model_from_sample_definition(&self, data: &Value) -> TorqueModel {
+        // Extract basic information
+        let name = data["name"].as_str().unwrap_or("Sample Model").to_string();
+        let description = data["description"].as_str().map(|s| s.to_string());
+        let version = data["version"].as_str().unwrap_or("1.0.0").to_string();
+        let created_by = data["created_by"].as_str().unwrap_or("system").to_string();
+        
         // Parse config
         let config = if let Some(config_data) = data.get("config") {
             serde_json::from_value(config_data.clone()).unwrap_or_default()
@@ -1385,7 +2162,7 @@ impl ModelService {
         
         // Create a basic model structure
         let now = UtcDateTime::now();
-        let model = TorqueModel {
+        TorqueModel {
             id: Uuid::new_v4(),
             name,
             description,
@@ -1399,9 +2176,11 @@ impl ModelService {
             flows,
             layouts,
             validations: vec![], // TODO: Parse validations from JSON
-        };
-        
-        // Save to database and cache
+        }
+    }
+    
+    /// Save a newly built model to the database and cache
+    pub async fn store_new_model(&self, model: TorqueModel) -> Result<TorqueModel, Error> {
         self.save_model_to_db(&model).await?;
         self.model_cache.insert(
             model.id.clone(),
@@ -1411,19 +2190,6 @@ impl ModelService {
         // Emit model created event
         self.emit_event(ModelChangeEvent::model_created(model.clone()));
         
-        // TODO: Load sample data - requires entity service integration
-        // For now, we just log that sample data exists
-        if let Some(sample_data) = data.get("sample_data") {
-            tracing::info!("Model {} contains sample data that needs to be loaded", model.name);
-            if let Some(sample_data_obj) = sample_data.as_object() {
-                for (entity_type, entity_data) in sample_data_obj {
-                    if let Some(data_array) = entity_data.as_array() {
-                        tracing::info!("  - {} {} entities", data_array.len(), entity_type);
-                    }
-                }
-            }
-        }
-        
         Ok(model)
     }
     
@@ -1596,12 +2362,16 @@ impl ModelService {
     
     /// Parse field validation from JSON
     fn parse_field_validation_from_json(&self, validation_data: &serde_json::Value) -> Result<FieldValidation, Error> {
+        let value = validation_data.get("value");
         let validation_type = match validation_data.get("type").and_then(|v| v.as_str()).unwrap_or("Required") {
             "Required" => ValidationType::Required,
-            "MinLength" => ValidationType::MinLength(2),
-            "MaxLength" => ValidationType::MaxLength(255),
-            "Pattern" => ValidationType::Pattern(".*".to_string()),
-            "Range" => ValidationType::Range { min: serde_json::Value::Number(serde_json::Number::from_f64(0.0).unwrap()), max: serde_json::Value::Number(serde_json::Number::from_f64(100.0).unwrap()) },
+            "MinLength" => ValidationType::MinLength(value.and_then(|v| v.as_u64()).map_or(2, |v| v as usize)),
+            "MaxLength" => ValidationType::MaxLength(value.and_then(|v| v.as_u64()).map_or(255, |v| v as usize)),
+            "Pattern" => ValidationType::Pattern(value.and_then(|v| v.as_str()).unwrap_or(".*").to_string()),
+            "Range" => ValidationType::Range {
+                min: value.and_then(|v| v.get("min")).cloned().unwrap_or(serde_json::Value::Null),
+                max: value.and_then(|v| v.get("max")).cloned().unwrap_or(serde_json::Value::Null),
+            },
             "Custom" => ValidationType::Custom(validation_data.get("rule").and_then(|r| r.as_str()).unwrap_or("").to_string()),
             _ => ValidationType::Required,
         };
@@ -1868,9 +2638,9 @@ impl ModelService {
         })
     }
     
-    /// Helper method to persist a model to the database
-    async fn persist_model_to_database(&self, model: &TorqueModel) -> Result<(), Error> {
-        use sea_orm::{ActiveModelTrait, Set};
+    /// Helper method to persist a model to the database and record a version snapshot
+    async fn persist_model_to_database(&self, model: &TorqueModel, author: &str, message: String) -> Result<(), Error> {
+        use sea_orm::{ActiveModelTrait, Set, TransactionTrait};
         
         let model_json = serde_json::to_value(model)?;
         let schema_json = serde_json::to_value(model)?; // For now, use same as model_json
@@ -1885,7 +2655,11 @@ impl ModelService {
             updated_at: Set(model.updated_at.clone().into_chrono().naive_utc()),
         };
         
-        active_model.save(self.database.as_ref()).await?;
+        // The definition and its version snapshot are written together or not at all
+        let txn = self.database.begin().await?;
+        active_model.update(&txn).await?;
+        self.record_version(&txn, model, author, Some(message)).await?;
+        txn.commit().await?;
         tracing::info!("Persisted model '{}' to database", model.name);
         Ok(())
     }
@@ -1981,7 +2755,7 @@ impl ModelService {
             );
             
             // Persist to database
-            self.persist_model_to_database(&model).await?;
+            self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Applied remediation strategy {}", strategy.id)).await?;
             
             // Emit model updated event
             self.emit_event(ModelChangeEvent::model_updated(model));
@@ -2070,7 +2844,7 @@ impl ModelService {
             );
             
             // Persist to database
-            self.persist_model_to_database(&model).await?;
+            self.persist_model_to_database(&model, SYSTEM_AUTHOR, format!("Applied {} remediation", strategy_type)).await?;
             
             // Emit model updated event
             self.emit_event(ModelChangeEvent::model_updated(model));
@@ -2150,6 +2924,18 @@ impl ModelService {
     }
 }
 
+/// Reject relationship endpoints that don't name an entity of the model.
+fn ensure_entities_exist(model: &TorqueModel, entity_ids: impl IntoIterator<Item = Uuid>) -> Result<(), Error> {
+    for entity_id in entity_ids {
+        if !model.entities.iter().any(|e| e.id == entity_id) {
+            return Err(Error::Validation(format!(
+                "Entity with id {} not found in model '{}'", entity_id, model.name
+            )));
+        }
+    }
+    Ok(())
+}
+
 // Input types for the service layer
 #[derive(Debug, Clone)]
 pub struct CreateModelInput {
@@ -2163,6 +2949,42 @@ pub struct UpdateModelInput {
     pub name: Option<String>,
     pub description: Option<String>,
     pub config: Option<ModelConfig>,
+    /// Recorded as the author of the version snapshot (defaults to `SYSTEM_AUTHOR`)
+    pub author: Option<String>,
+    /// Recorded as the message of the version snapshot
+    pub message: Option<String>,
+}
+
+/// Metadata for an immutable model version snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelVersionInfo {
+    pub model_id: Uuid,
+    pub version_number: i32,
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    pub message: Option<String>,
+    pub created_at: UtcDateTime,
+}
+
+/// A model version snapshot together with the model definition it captured
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelVersion {
+    #[serde(flatten)]
+    pub info: ModelVersionInfo,
+    pub model: TorqueModel,
+}
+
+/// Version a model is pinned to for running apps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelVersionPin {
+    pub model_id: Uuid,
+    pub version_number: i32,
+    pub pinned_by: String,
+    pub pinned_at: UtcDateTime,
 }
 
 #[derive(Debug, Clone)]
@@ -2195,6 +3017,7 @@ pub struct CreateFieldInput {
     pub field_type: FieldType,
     pub required: bool,
     pub default_value: Option<Value>,
+    pub validation: Vec<FieldValidation>,
     pub ui_config: Option<FieldUiConfig>,
 }
 
@@ -2211,6 +3034,18 @@ pub struct CreateRelationshipInput {
     pub ui_config: Option<RelationshipUiConfig>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct UpdateRelationshipInput {
+    pub name: Option<String>,
+    pub relationship_type: Option<RelationshipType>,
+    pub from_entity: Option<String>,
+    pub to_entity: Option<String>,
+    pub from_field: Option<String>,
+    pub to_field: Option<String>,
+    pub cascade: Option<CascadeAction>,
+    pub ui_config: Option<RelationshipUiConfig>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateFlowInput {
     pub model_id: String,
@@ -2221,8 +3056,20 @@ pub struct CreateFlowInput {
     pub error_handling: Option<ErrorHandling>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct UpdateFlowInput {
+    pub name: Option<String>,
+    pub flow_type: Option<FlowType>,
+    pub trigger: Option<FlowTrigger>,
+    pub steps: Option<Vec<CreateFlowStepInput>>,
+    pub error_handling: Option<ErrorHandling>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateFlowStepInput {
+    /// Existing step to keep when a flow's steps are replaced; steps without one
+    /// keep the ID of the existing step with the same name
+    pub id: Option<Uuid>,
     pub name: String,
     pub step_type: FlowStepType,
     pub condition: Option<String>,
@@ -0,0 +1,298 @@
+use crate::common::Uuid;
+use crate::error::Error;
+use crate::model::types::TorqueModel;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement, TransactionTrait, Value};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Default number of hits returned by a search
+pub const DEFAULT_SEARCH_LIMIT: u64 = 50;
+
+/// A search match inside a model
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSearchHit {
+    pub model_id: Uuid,
+    pub model_name: String,
+    /// Location of the match, e.g. `entities.Customer.fields.email`
+    pub path: String,
+    /// Kind of component matched: model, entity, field, flow or layout
+    pub kind: String,
+    pub name: String,
+    /// Relevance, higher is better
+    pub score: f64,
+}
+
+/// One indexed component of a model
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchDocument {
+    pub path: String,
+    pub kind: &'static str,
+    pub name: String,
+    pub content: String,
+}
+
+/// Full-text index over model, entity, field, flow and layout names.
+/// Backed by FTS5 on SQLite and a tsvector column on Postgres.
+pub struct ModelSearchIndex {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ModelSearchIndex {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    /// Replace the indexed documents of a model
+    pub async fn index_model(&self, model: &TorqueModel) -> Result<(), Error> {
+        let backend = self.db.get_database_backend();
+        let txn = self.db.begin().await?;
+
+        txn.execute(delete_statement(backend, &model.id)).await?;
+
+        let insert = match backend {
+            DatabaseBackend::Postgres => "INSERT INTO model_search_index (model_id, model_name, path, kind, name, content) \
+                 VALUES ($1::uuid, $2, $3, $4, $5, $6)",
+            _ => "INSERT INTO model_search_index (model_id, model_name, path, kind, name, content) \
+                 VALUES (?, ?, ?, ?, ?, ?)",
+        };
+        for document in documents(model) {
+            let values: Vec<Value> = vec![
+                model.id.to_string().into(),
+                model.name.clone().into(),
+                document.path.into(),
+                document.kind.into(),
+                document.name.into(),
+                document.content.into(),
+            ];
+            txn.execute(Statement::from_sql_and_values(backend, insert, values)).await?;
+        }
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Drop all indexed documents of a model
+    pub async fn remove_model(&self, model_id: &Uuid) -> Result<(), Error> {
+        let backend = self.db.get_database_backend();
+        self.db.execute(delete_statement(backend, model_id)).await?;
+        Ok(())
+    }
+
+    /// Rebuild the index from scratch
+    pub async fn rebuild(&self, models: &[TorqueModel]) -> Result<(), Error> {
+        self.db.execute(Statement::from_string(
+            self.db.get_database_backend(),
+            "DELETE FROM model_search_index".to_string(),
+        )).await?;
+
+        for model in models {
+            self.index_model(model).await?;
+        }
+
+        tracing::info!("Rebuilt model search index ({} models)", models.len());
+        Ok(())
+    }
+
+    /// Search the index. Every term must match; the last term also matches as a prefix.
+    pub async fn search(&self, query: &str, limit: u64) -> Result<Vec<ModelSearchHit>, Error> {
+        let backend = self.db.get_database_backend();
+
+        let statement = match backend {
+            DatabaseBackend::Postgres => {
+                let Some(tsquery) = postgres_query(query) else {
+                    return Ok(vec![]);
+                };
+                Statement::from_sql_and_values(
+                    backend,
+                    "SELECT model_id::text AS model_id, model_name, path, kind, name, \
+                         ts_rank(document, q)::float8 AS score \
+                     FROM model_search_index, to_tsquery('simple', $1) q \
+                     WHERE document @@ q \
+                     ORDER BY score DESC, path \
+                     LIMIT $2",
+                    vec![tsquery.into(), (limit as i64).into()],
+                )
+            }
+            _ => {
+                let Some(fts_query) = fts5_query(query) else {
+                    return Ok(vec![]);
+                };
+                // bm25() is lower for better matches, so negate it to keep "higher is better"
+                Statement::from_sql_and_values(
+                    backend,
+                    "SELECT model_id, model_name, path, kind, name, \
+                         -bm25(model_search_index, 0.0, 0.0, 0.0, 0.0, 10.0, 1.0) AS score \
+                     FROM model_search_index \
+                     WHERE model_search_index MATCH ? \
+                     ORDER BY score DESC, path \
+                     LIMIT ?",
+                    vec![fts_query.into(), (limit as i64).into()],
+                )
+            }
+        };
+
+        let rows = self.db.query_all(statement).await?;
+        let mut hits = Vec::with_capacity(rows.len());
+        for row in rows {
+            let model_id: String = row.try_get("", "model_id")?;
+            hits.push(ModelSearchHit {
+                model_id: Uuid::parse(&model_id)
+                    .map_err(|_| Error::Internal(format!("Invalid model id in search index: {}", model_id)))?,
+                model_name: row.try_get("", "model_name")?,
+                path: row.try_get("", "path")?,
+                kind: row.try_get("", "kind")?,
+                name: row.try_get("", "name")?,
+                score: row.try_get("", "score")?,
+            });
+        }
+
+        Ok(hits)
+    }
+}
+
+fn delete_statement(backend: DatabaseBackend, model_id: &Uuid) -> Statement {
+    let sql = match backend {
+        DatabaseBackend::Postgres => "DELETE FROM model_search_index WHERE model_id = $1::uuid",
+        _ => "DELETE FROM model_search_index WHERE model_id = ?",
+    };
+    Statement::from_sql_and_values(backend, sql, vec![model_id.to_string().into()])
+}
+
+/// Documents indexed for a model, one per searchable component
+pub fn documents(model: &TorqueModel) -> Vec<SearchDocument> {
+    let mut docs = vec![SearchDocument {
+        path: "name".to_string(),
+        kind: "model",
+        name: model.name.clone(),
+        content: join_content(&[search_terms(&model.name).as_str(), model.description.as_deref().unwrap_or_default()]),
+    }];
+
+    for entity in &model.entities {
+        let entity_path = format!("entities.{}", entity.name);
+        docs.push(SearchDocument {
+            path: entity_path.clone(),
+            kind: "entity",
+            name: entity.name.clone(),
+            content: join_content(&[
+                search_terms(&entity.name).as_str(),
+                entity.display_name.as_str(),
+                entity.description.as_deref().unwrap_or_default(),
+            ]),
+        });
+
+        for field in &entity.fields {
+            docs.push(SearchDocument {
+                path: format!("{}.fields.{}", entity_path, field.name),
+                kind: "field",
+                name: field.name.clone(),
+                content: join_content(&[search_terms(&field.name).as_str(), field.display_name.as_str()]),
+            });
+        }
+    }
+
+    for flow in &model.flows {
+        docs.push(SearchDocument {
+            path: format!("flows.{}", flow.name),
+            kind: "flow",
+            name: flow.name.clone(),
+            content: search_terms(&flow.name),
+        });
+    }
+
+    for layout in &model.layouts {
+        docs.push(SearchDocument {
+            path: format!("layouts.{}", layout.name),
+            kind: "layout",
+            name: layout.name.clone(),
+            content: join_content(&[search_terms(&layout.name).as_str(), layout.description.as_deref().unwrap_or_default()]),
+        });
+    }
+
+    docs
+}
+
+fn join_content(parts: &[&str]) -> String {
+    parts.iter()
+        .filter(|p| !p.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split identifiers like `customerEmail` or `order_items` into separate words
+/// so either part can be searched on its own
+pub fn search_terms(name: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_numeric();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words.join(" ")
+}
+
+/// Alphanumeric terms of a user query, lowercased
+fn query_terms(query: &str) -> Vec<String> {
+    query.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Build an FTS5 MATCH expression, quoting terms so user input can't inject query syntax
+pub fn fts5_query(query: &str) -> Option<String> {
+    let terms = query_terms(query);
+    let last = terms.len().checked_sub(1)?;
+    Some(terms.iter().enumerate()
+        .map(|(i, t)| if i == last { format!("\"{}\"*", t) } else { format!("\"{}\"", t) })
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+/// Build a Postgres tsquery expression
+pub fn postgres_query(query: &str) -> Option<String> {
+    let terms = query_terms(query);
+    let last = terms.len().checked_sub(1)?;
+    Some(terms.iter().enumerate()
+        .map(|(i, t)| if i == last { format!("{}:*", t) } else { t.clone() })
+        .collect::<Vec<_>>()
+        .join(" & "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_terms() {
+        assert_eq!(search_terms("customerEmail"), "customer email");
+        assert_eq!(search_terms("order_items"), "order items");
+        assert_eq!(search_terms("CRM Project"), "crm project");
+        assert_eq!(search_terms("API"), "api");
+    }
+
+    #[test]
+    fn test_query_builders() {
+        assert_eq!(fts5_query("cust email").as_deref(), Some("\"cust\" \"email\"*"));
+        assert_eq!(fts5_query("\"; DROP").as_deref(), Some("\"drop\"*"));
+        assert_eq!(postgres_query("Customer Ord").as_deref(), Some("customer & ord:*"));
+        assert_eq!(fts5_query("  ").as_deref(), None);
+    }
+}
@@ -0,0 +1,446 @@
+use crate::{Result, Error};
+use crate::config::RetentionJobConfig;
+use crate::database::entities::app_entities::{self, Entity as AppEntities, Model as AppEntity};
+use crate::database::entities::app_entities_archive;
+use crate::model::types::{CleanupStrategy, RetentionPolicy, TorqueModel};
+use crate::model::events::DataChangeAction;
+use crate::services::{
+    app_database::broadcast_bulk_change, blob_store::BlobService, broadcast::BroadcastService, metrics::MetricsService,
+    model::ModelService,
+};
+use base64::Engine;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sea_orm::{
+    sea_query::{Expr, SimpleExpr},
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select, Set, TransactionTrait,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Marker key identifying app entity data that has been gzip-compressed in place
+const COMPRESSED_MARKER: &str = "_compressed";
+const COMPRESSED_PAYLOAD: &str = "_payload";
+const GZIP_BASE64: &str = "gzip+base64";
+
+/// Background job that applies `ModelConfig.database.retention_policy` to app entities
+pub struct RetentionService {
+    db: Arc<DatabaseConnection>,
+    model_service: Arc<ModelService>,
+    metrics: Arc<MetricsService>,
+    broadcast: Arc<BroadcastService>,
+    blob_service: Arc<BlobService>,
+    config: RetentionJobConfig,
+}
+
+/// Outcome of applying a retention policy to one entity type
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EntityRetentionResult {
+    pub entity_type: String,
+    pub archived: u64,
+    pub deleted: u64,
+    pub compressed: u64,
+}
+
+/// Outcome of applying a model's retention policy
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelRetentionReport {
+    pub model_id: String,
+    pub model_name: String,
+    pub strategy: String,
+    pub entities: Vec<EntityRetentionResult>,
+}
+
+/// Outcome of a complete retention run across all models
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetentionRunReport {
+    pub models_processed: u64,
+    pub rows_archived: u64,
+    pub rows_deleted: u64,
+    pub rows_compressed: u64,
+    /// Archived rows removed after `archive_retention_days`
+    pub rows_purged: u64,
+    /// Blobs removed because no record refers to them
+    pub blobs_removed: u64,
+    pub errors: Vec<String>,
+    pub duration_ms: u64,
+    pub models: Vec<ModelRetentionReport>,
+}
+
+impl RetentionService {
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        model_service: Arc<ModelService>,
+        metrics: Arc<MetricsService>,
+        broadcast: Arc<BroadcastService>,
+        blob_service: Arc<BlobService>,
+        config: RetentionJobConfig,
+    ) -> Self {
+        Self {
+            db,
+            model_service,
+            metrics,
+            broadcast,
+            blob_service,
+            config,
+        }
+    }
+
+    /// Spawn the periodic retention job if enabled in configuration
+    pub fn spawn(self: Arc<Self>) -> Option<tokio::task::JoinHandle<()>> {
+        if !self.config.enabled {
+            tracing::info!("Retention job disabled by configuration");
+            return None;
+        }
+
+        let interval_seconds = self.config.interval_seconds.max(60);
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+            loop {
+                interval.tick().await;
+                match self.run_once().await {
+                    Ok(report) => tracing::info!(
+                        "Retention run complete: {} models, {} archived, {} deleted, {} compressed ({}ms)",
+                        report.models_processed,
+                        report.rows_archived,
+                        report.rows_deleted,
+                        report.rows_compressed,
+                        report.duration_ms
+                    ),
+                    Err(e) => tracing::error!("Retention run failed: {}", e),
+                }
+            }
+        }))
+    }
+
+    /// Apply retention policies for every model that defines one, then remove unreferenced blobs
+    pub async fn run_once(&self) -> Result<RetentionRunReport> {
+        let start_time = std::time::Instant::now();
+        let mut report = RetentionRunReport::default();
+
+        let models = self.model_service.get_models().await?;
+        for model in models {
+            let Some(policy) = model.config.database.retention_policy.clone() else {
+                continue;
+            };
+
+            match self.apply_policy(&model, &policy).await {
+                Ok(model_report) => {
+                    for entity in &model_report.entities {
+                        report.rows_archived += entity.archived;
+                        report.rows_deleted += entity.deleted;
+                        report.rows_compressed += entity.compressed;
+                    }
+                    self.record_model_metrics(&model_report);
+                    report.models.push(model_report);
+                    report.models_processed += 1;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to apply retention policy for model {}: {}", model.id, e);
+                    report.errors.push(format!("{}: {}", model.id, e));
+                }
+            }
+        }
+
+        if self.config.archive_retention_days > 0 {
+            match self.purge_archive().await {
+                Ok(purged) => report.rows_purged = purged,
+                Err(e) => {
+                    tracing::warn!("Failed to purge archived rows: {}", e);
+                    report.errors.push(format!("archive: {}", e));
+                }
+            }
+        }
+
+        match self.blob_service.remove_unreferenced(self.config.batch_size).await {
+            Ok(removed) => report.blobs_removed = removed,
+            Err(e) => {
+                tracing::warn!("Failed to remove unreferenced blobs: {}", e);
+                report.errors.push(format!("blobs: {}", e));
+            }
+        }
+
+        report.duration_ms = start_time.elapsed().as_millis() as u64;
+
+        self.metrics.record_metric("retention.run.duration_ms".to_string(), report.duration_ms as f64, None);
+        self.metrics.record_metric("retention.run.models_processed".to_string(), report.models_processed as f64, None);
+        self.metrics.record_metric("retention.run.errors".to_string(), report.errors.len() as f64, None);
+        self.metrics.record_metric("retention.run.rows_purged".to_string(), report.rows_purged as f64, None);
+        self.metrics.record_metric("retention.run.blobs_removed".to_string(), report.blobs_removed as f64, None);
+
+        Ok(report)
+    }
+
+    /// Apply a retention policy to all entity types of a model
+    pub async fn apply_policy(&self, model: &TorqueModel, policy: &RetentionPolicy) -> Result<ModelRetentionReport> {
+        let model_id = model.id.to_string();
+        let mut entities = Vec::with_capacity(model.entities.len());
+
+        for entity in &model.entities {
+            let candidates = self.find_expired_rows(&model_id, &entity.name, policy).await?;
+            let mut result = EntityRetentionResult {
+                entity_type: entity.name.clone(),
+                ..Default::default()
+            };
+
+            if !candidates.is_empty() {
+                match policy.cleanup_strategy {
+                    CleanupStrategy::Archive => result.archived = self.archive_rows(&model_id, candidates).await?,
+                    CleanupStrategy::Delete => result.deleted = self.delete_rows(&model_id, candidates).await?,
+                    CleanupStrategy::Compress => result.compressed = self.compress_rows(candidates).await?,
+                }
+            }
+
+            entities.push(result);
+        }
+
+        Ok(ModelRetentionReport {
+            model_id,
+            model_name: model.name.clone(),
+            strategy: format!("{:?}", policy.cleanup_strategy),
+            entities,
+        })
+    }
+
+    /// Collect rows that exceed the policy's age or record-count limits.
+    /// A `max_age_days` of 0 disables the age limit. Under the Compress
+    /// strategy rows that are already compressed are never candidates, so
+    /// they can't fill the batch and starve the rows behind them.
+    async fn find_expired_rows(&self, model_id: &str, entity_type: &str, policy: &RetentionPolicy) -> Result<Vec<AppEntity>> {
+        let batch_size = self.config.batch_size.max(1);
+        let mut rows: HashMap<String, AppEntity> = HashMap::new();
+        let candidates = || {
+            let query = AppEntities::find()
+                .filter(app_entities::Column::ModelId.eq(model_id))
+                .filter(app_entities::Column::EntityType.eq(entity_type));
+            match policy.cleanup_strategy {
+                CleanupStrategy::Compress => query.filter(self.not_compressed()),
+                _ => query,
+            }
+        };
+
+        if policy.max_age_days > 0 {
+            let cutoff = chrono::Utc::now().naive_utc() - chrono::Duration::days(policy.max_age_days as i64);
+            let expired = candidates()
+                .filter(app_entities::Column::UpdatedAt.lt(cutoff))
+                .order_by_asc(app_entities::Column::UpdatedAt)
+                .limit(batch_size)
+                .all(self.db.as_ref())
+                .await?;
+            rows.extend(expired.into_iter().map(|row| (row.id.clone(), row)));
+        }
+
+        if let Some(max_records) = policy.max_records {
+            // Everything beyond the newest `max_records` rows is over the limit,
+            // counting compressed rows since they are still kept
+            let newest = |query: Select<AppEntities>| {
+                query
+                    .order_by_desc(app_entities::Column::CreatedAt)
+                    .order_by_desc(app_entities::Column::Id)
+            };
+            let boundary = newest(
+                AppEntities::find()
+                    .filter(app_entities::Column::ModelId.eq(model_id))
+                    .filter(app_entities::Column::EntityType.eq(entity_type)),
+            )
+            .offset(max_records)
+            .one(self.db.as_ref())
+            .await?;
+
+            if let Some(boundary) = boundary {
+                let overflow = newest(candidates())
+                    .filter(
+                        app_entities::Column::CreatedAt.lt(boundary.created_at).or(app_entities::Column::CreatedAt
+                            .eq(boundary.created_at)
+                            .and(app_entities::Column::Id.lte(boundary.id))),
+                    )
+                    .limit(batch_size)
+                    .all(self.db.as_ref())
+                    .await?;
+                rows.extend(overflow.into_iter().map(|row| (row.id.clone(), row)));
+            }
+        }
+
+        Ok(rows.into_values().collect())
+    }
+
+    /// SQL condition matching rows whose data isn't marked as compressed
+    fn not_compressed(&self) -> SimpleExpr {
+        Expr::cust(not_compressed_sql(self.db.get_database_backend()))
+    }
+
+    /// Remove blobs attached to rows that no longer exist in `app_entities`
+    async fn remove_blobs(&self, model_id: &str, removed: &[(String, String)]) -> Result<()> {
+        for (_, entity_id) in removed {
+            self.blob_service.delete_for_entity(model_id, entity_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Move rows into `app_entities_archive`. Their blobs are kept until the
+    /// archived rows are purged.
+    async fn archive_rows(&self, model_id: &str, rows: Vec<AppEntity>) -> Result<u64> {
+        let txn = self.db.begin().await?;
+        let archived_at = chrono::Utc::now().naive_utc();
+        let count = rows.len() as u64;
+        let removed: Vec<(String, String)> = rows.iter().map(|row| (row.entity_type.clone(), row.id.clone())).collect();
+
+        for row in rows {
+            let archived = app_entities_archive::ActiveModel {
+                id: Set(row.id.clone()),
+                model_id: Set(row.model_id),
+                entity_type: Set(row.entity_type),
+                data: Set(row.data),
+                created_at: Set(row.created_at),
+                updated_at: Set(row.updated_at),
+                archived_at: Set(archived_at),
+            };
+            app_entities_archive::Entity::insert(archived).exec(&txn).await?;
+            AppEntities::delete_by_id(row.id).exec(&txn).await?;
+        }
+
+        txn.commit().await?;
+        broadcast_bulk_change(&self.broadcast, model_id, DataChangeAction::Deleted, removed).await;
+        Ok(count)
+    }
+
+    /// Permanently remove archived rows older than `archive_retention_days`, with their blobs
+    async fn purge_archive(&self) -> Result<u64> {
+        let cutoff = chrono::Utc::now().naive_utc() - chrono::Duration::days(self.config.archive_retention_days as i64);
+        let expired = app_entities_archive::Entity::find()
+            .filter(app_entities_archive::Column::ArchivedAt.lt(cutoff))
+            .order_by_asc(app_entities_archive::Column::ArchivedAt)
+            .limit(self.config.batch_size.max(1))
+            .all(self.db.as_ref())
+            .await?;
+
+        let ids: Vec<String> = expired.iter().map(|row| row.id.clone()).collect();
+        let result = app_entities_archive::Entity::delete_many()
+            .filter(app_entities_archive::Column::Id.is_in(ids))
+            .exec(self.db.as_ref())
+            .await?;
+        for row in &expired {
+            self.blob_service.delete_for_entity(&row.model_id, &row.id).await?;
+        }
+        Ok(result.rows_affected)
+    }
+
+    /// Permanently remove rows
+    async fn delete_rows(&self, model_id: &str, rows: Vec<AppEntity>) -> Result<u64> {
+        let removed: Vec<(String, String)> = rows.into_iter().map(|row| (row.entity_type, row.id)).collect();
+        let ids: Vec<String> = removed.iter().map(|(_, id)| id.clone()).collect();
+        let result = AppEntities::delete_many()
+            .filter(app_entities::Column::Id.is_in(ids))
+            .exec(self.db.as_ref())
+            .await?;
+        self.remove_blobs(model_id, &removed).await?;
+        broadcast_bulk_change(&self.broadcast, model_id, DataChangeAction::Deleted, removed).await;
+        Ok(result.rows_affected)
+    }
+
+    /// Gzip the JSON payload of rows in place, skipping rows already compressed
+    async fn compress_rows(&self, rows: Vec<AppEntity>) -> Result<u64> {
+        let mut compressed = 0;
+        for row in rows {
+            if is_compressed(&row.data) {
+                continue;
+            }
+            let data = compress_data(&row.data)?;
+            // Only data is set, so updated_at keeps the row's age
+            let mut active: app_entities::ActiveModel = row.into();
+            active.data = Set(data);
+            active.update(self.db.as_ref()).await?;
+            compressed += 1;
+        }
+        Ok(compressed)
+    }
+
+    fn record_model_metrics(&self, report: &ModelRetentionReport) {
+        let mut tags = HashMap::new();
+        tags.insert("model_id".to_string(), report.model_id.clone());
+        tags.insert("strategy".to_string(), report.strategy.clone());
+
+        let (archived, deleted, compressed) = report.entities.iter().fold((0, 0, 0), |acc, e| {
+            (acc.0 + e.archived, acc.1 + e.deleted, acc.2 + e.compressed)
+        });
+
+        self.metrics.record_metric("retention.rows_archived".to_string(), archived as f64, Some(tags.clone()));
+        self.metrics.record_metric("retention.rows_deleted".to_string(), deleted as f64, Some(tags.clone()));
+        self.metrics.record_metric("retention.rows_compressed".to_string(), compressed as f64, Some(tags));
+    }
+}
+
+/// SQL condition matching `app_entities` rows whose data isn't marked as compressed
+pub(crate) fn not_compressed_sql(backend: DatabaseBackend) -> String {
+    let marker = match backend {
+        DatabaseBackend::Postgres => format!("(data ->> '{}')", COMPRESSED_MARKER),
+        _ => format!("json_extract(data, '$.{}')", COMPRESSED_MARKER),
+    };
+    format!("({} IS NULL OR {} <> '{}')", marker, marker, GZIP_BASE64)
+}
+
+/// Check whether app entity data was compressed by the retention job
+pub fn is_compressed(data: &serde_json::Value) -> bool {
+    data.get(COMPRESSED_MARKER).and_then(|v| v.as_str()) == Some(GZIP_BASE64)
+}
+
+/// Gzip a JSON value and wrap it in a marker object
+pub fn compress_data(data: &serde_json::Value) -> Result<serde_json::Value> {
+    let raw = serde_json::to_vec(data)?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&raw)?;
+    let gzipped = encoder.finish()?;
+
+    Ok(serde_json::json!({
+        COMPRESSED_MARKER: GZIP_BASE64,
+        COMPRESSED_PAYLOAD: base64::engine::general_purpose::STANDARD.encode(gzipped),
+    }))
+}
+
+/// Restore a JSON value produced by `compress_data`
+pub fn decompress_data(data: &serde_json::Value) -> Result<serde_json::Value> {
+    let payload = data.get(COMPRESSED_PAYLOAD)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::Validation("Compressed entity data is missing its payload".to_string()))?;
+    let gzipped = base64::engine::general_purpose::STANDARD.decode(payload)
+        .map_err(|e| Error::Validation(format!("Invalid compressed entity payload: {}", e)))?;
+
+    let mut raw = Vec::new();
+    GzDecoder::new(gzipped.as_slice()).read_to_end(&mut raw)?;
+    Ok(serde_json::from_slice(&raw)?)
+}
+
+/// Return entity data in its readable form, inflating compressed rows
+pub fn inflate_if_compressed(data: serde_json::Value) -> serde_json::Value {
+    if !is_compressed(&data) {
+        return data;
+    }
+    match decompress_data(&data) {
+        Ok(inflated) => inflated,
+        Err(e) => {
+            tracing::warn!("Failed to decompress entity data: {}", e);
+            data
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_roundtrip() {
+        let data = serde_json::json!({"title": "Write report", "done": false, "tags": ["a", "b"]});
+
+        let compressed = compress_data(&data).unwrap();
+        assert!(is_compressed(&compressed));
+        assert!(!is_compressed(&data));
+
+        assert_eq!(decompress_data(&compressed).unwrap(), data);
+        assert_eq!(inflate_if_compressed(compressed), data);
+        assert_eq!(inflate_if_compressed(data.clone()), data);
+    }
+}
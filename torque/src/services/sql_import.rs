@@ -0,0 +1,566 @@
+use crate::common::{Uuid, UtcDateTime};
+use crate::error::Error;
+use crate::model::types::*;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, QueryResult, Statement};
+use serde_json::Value;
+
+/// Column of an introspected table
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlColumn {
+    pub name: String,
+    /// Declared type, lowercased (e.g. `varchar(100)`, `integer`, `_int4` for Postgres arrays)
+    pub data_type: String,
+    pub nullable: bool,
+    pub default: Option<String>,
+    pub max_length: Option<usize>,
+    /// Labels of a Postgres enum type
+    pub enum_values: Vec<String>,
+}
+
+/// Foreign key of an introspected table
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlForeignKey {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    /// Empty when the key references the primary key implicitly (SQLite)
+    pub referenced_columns: Vec<String>,
+    /// ON DELETE action, e.g. `cascade` or `set null`
+    pub on_delete: String,
+}
+
+/// Unique index or constraint other than the primary key
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlUniqueIndex {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+/// Structure of one table read from an existing database
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlTable {
+    pub name: String,
+    pub columns: Vec<SqlColumn>,
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<SqlForeignKey>,
+    pub unique_indexes: Vec<SqlUniqueIndex>,
+}
+
+/// Read tables, columns, keys and unique indexes from a SQLite or Postgres database.
+/// `schema` selects the Postgres schema (default `public`) and is ignored for SQLite.
+pub async fn introspect(db: &DatabaseConnection, schema: Option<&str>) -> Result<Vec<SqlTable>, Error> {
+    match db.get_database_backend() {
+        DatabaseBackend::Postgres => introspect_postgres(db, schema.unwrap_or("public")).await,
+        DatabaseBackend::Sqlite => introspect_sqlite(db).await,
+        DatabaseBackend::MySql => Err(Error::InvalidInput("Importing from MySQL is not supported".to_string())),
+    }
+}
+
+async fn query(db: &DatabaseConnection, sql: &str, values: Vec<sea_orm::Value>) -> Result<Vec<QueryResult>, Error> {
+    let backend = db.get_database_backend();
+    Ok(db.query_all(Statement::from_sql_and_values(backend, sql, values)).await?)
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',').filter(|s| !s.is_empty()).map(str::to_string).collect()
+}
+
+async fn introspect_sqlite(db: &DatabaseConnection) -> Result<Vec<SqlTable>, Error> {
+    let table_rows = query(
+        db,
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        vec![],
+    ).await?;
+
+    let mut tables = Vec::with_capacity(table_rows.len());
+    for row in table_rows {
+        let name: String = row.try_get("", "name")?;
+
+        let mut columns = Vec::new();
+        let mut primary_key: Vec<(i32, String)> = Vec::new();
+        for row in query(db, "SELECT name, type, \"notnull\" AS not_null, dflt_value, pk FROM pragma_table_info(?)", vec![name.clone().into()]).await? {
+            let column_name: String = row.try_get("", "name")?;
+            let declared: String = row.try_get("", "type")?;
+            let not_null: i32 = row.try_get("", "not_null")?;
+            let pk: i32 = row.try_get("", "pk")?;
+            if pk > 0 {
+                primary_key.push((pk, column_name.clone()));
+            }
+            columns.push(SqlColumn {
+                max_length: declared_length(&declared),
+                data_type: declared.to_lowercase(),
+                name: column_name,
+                nullable: not_null == 0,
+                default: row.try_get("", "dflt_value")?,
+                enum_values: vec![],
+            });
+        }
+        primary_key.sort();
+
+        let mut foreign_keys: Vec<SqlForeignKey> = Vec::new();
+        for row in query(
+            db,
+            "SELECT id, \"table\" AS ref_table, \"from\" AS from_column, \"to\" AS to_column, on_delete \
+             FROM pragma_foreign_key_list(?) ORDER BY id, seq",
+            vec![name.clone().into()],
+        ).await? {
+            let id: i32 = row.try_get("", "id")?;
+            let from_column: String = row.try_get("", "from_column")?;
+            let to_column: Option<String> = row.try_get("", "to_column")?;
+            let fk_name = format!("{}_fk_{}", name, id);
+            match foreign_keys.iter_mut().find(|fk| fk.name == fk_name) {
+                Some(fk) => {
+                    fk.columns.push(from_column);
+                    fk.referenced_columns.extend(to_column);
+                }
+                None => foreign_keys.push(SqlForeignKey {
+                    name: fk_name,
+                    columns: vec![from_column],
+                    referenced_table: row.try_get("", "ref_table")?,
+                    referenced_columns: to_column.into_iter().collect(),
+                    on_delete: row.try_get::<String>("", "on_delete")?.to_lowercase(),
+                }),
+            }
+        }
+
+        let mut unique_indexes = Vec::new();
+        for row in query(db, "SELECT name, \"unique\" AS is_unique, origin FROM pragma_index_list(?)", vec![name.clone().into()]).await? {
+            let is_unique: i32 = row.try_get("", "is_unique")?;
+            let origin: String = row.try_get("", "origin")?;
+            if is_unique == 0 || origin == "pk" {
+                continue;
+            }
+            let index_name: String = row.try_get("", "name")?;
+            let mut index_columns = Vec::new();
+            for row in query(db, "SELECT name FROM pragma_index_info(?) ORDER BY seqno", vec![index_name.clone().into()]).await? {
+                // Expression indexes have no column name
+                if let Some(column) = row.try_get::<Option<String>>("", "name")? {
+                    index_columns.push(column);
+                }
+            }
+            if !index_columns.is_empty() {
+                unique_indexes.push(SqlUniqueIndex { name: index_name, columns: index_columns });
+            }
+        }
+
+        tables.push(SqlTable {
+            name,
+            columns,
+            primary_key: primary_key.into_iter().map(|(_, c)| c).collect(),
+            foreign_keys,
+            unique_indexes,
+        });
+    }
+
+    Ok(tables)
+}
+
+async fn introspect_postgres(db: &DatabaseConnection, schema: &str) -> Result<Vec<SqlTable>, Error> {
+    let table_rows = query(
+        db,
+        "SELECT table_name::text AS name FROM information_schema.tables \
+         WHERE table_schema = $1 AND table_type = 'BASE TABLE' ORDER BY table_name",
+        vec![schema.into()],
+    ).await?;
+
+    let mut tables = Vec::with_capacity(table_rows.len());
+    for row in table_rows {
+        let name: String = row.try_get("", "name")?;
+
+        let mut columns = Vec::new();
+        for row in query(
+            db,
+            "SELECT c.column_name::text AS name, c.data_type::text AS data_type, c.udt_name::text AS udt_name, \
+                 c.is_nullable::text AS is_nullable, c.column_default::text AS column_default, \
+                 c.character_maximum_length::int4 AS max_length, \
+                 array_to_string(ARRAY(SELECT e.enumlabel::text FROM pg_type t JOIN pg_enum e ON e.enumtypid = t.oid \
+                       WHERE t.typname = c.udt_name ORDER BY e.enumsortorder), chr(31)) AS enum_values \
+             FROM information_schema.columns c \
+             WHERE c.table_schema = $1 AND c.table_name = $2 ORDER BY c.ordinal_position",
+            vec![schema.into(), name.clone().into()],
+        ).await? {
+            let data_type: String = row.try_get("", "data_type")?;
+            let udt_name: String = row.try_get("", "udt_name")?;
+            let is_nullable: String = row.try_get("", "is_nullable")?;
+            let max_length: Option<i32> = row.try_get("", "max_length")?;
+            columns.push(SqlColumn {
+                name: row.try_get("", "name")?,
+                // Arrays and enums are only described by their underlying type name
+                data_type: if matches!(data_type.as_str(), "ARRAY" | "USER-DEFINED") { udt_name } else { data_type.to_lowercase() },
+                nullable: is_nullable == "YES",
+                default: row.try_get("", "column_default")?,
+                max_length: max_length.and_then(|l| usize::try_from(l).ok()),
+                // Labels are joined with the ASCII unit separator since they may contain commas
+                enum_values: row.try_get::<String>("", "enum_values")?
+                    .split('\u{1f}')
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            });
+        }
+
+        let mut primary_key = Vec::new();
+        let mut foreign_keys = Vec::new();
+        let mut unique_indexes = Vec::new();
+        for row in query(
+            db,
+            "SELECT con.conname::text AS name, con.contype::text AS kind, \
+                 array_to_string(ARRAY(SELECT a.attname::text FROM unnest(con.conkey) WITH ORDINALITY k(attnum, ord) \
+                     JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum ORDER BY k.ord), ',') AS columns, \
+                 ref.relname::text AS ref_table, \
+                 array_to_string(ARRAY(SELECT a.attname::text FROM unnest(con.confkey) WITH ORDINALITY k(attnum, ord) \
+                     JOIN pg_attribute a ON a.attrelid = con.confrelid AND a.attnum = k.attnum ORDER BY k.ord), ',') AS ref_columns, \
+                 CASE con.confdeltype WHEN 'c' THEN 'cascade' WHEN 'n' THEN 'set null' WHEN 'r' THEN 'restrict' \
+                     WHEN 'd' THEN 'set default' ELSE 'no action' END AS on_delete \
+             FROM pg_constraint con \
+             JOIN pg_class rel ON rel.oid = con.conrelid \
+             JOIN pg_namespace ns ON ns.oid = rel.relnamespace \
+             LEFT JOIN pg_class ref ON ref.oid = con.confrelid \
+             WHERE ns.nspname = $1 AND rel.relname = $2 AND con.contype IN ('p', 'u', 'f') \
+             ORDER BY con.conname",
+            vec![schema.into(), name.clone().into()],
+        ).await? {
+            let constraint_name: String = row.try_get("", "name")?;
+            let kind: String = row.try_get("", "kind")?;
+            let constraint_columns = split_list(&row.try_get::<String>("", "columns")?);
+            match kind.as_str() {
+                "p" => primary_key = constraint_columns,
+                "u" => unique_indexes.push(SqlUniqueIndex { name: constraint_name, columns: constraint_columns }),
+                _ => foreign_keys.push(SqlForeignKey {
+                    name: constraint_name,
+                    columns: constraint_columns,
+                    referenced_table: row.try_get::<Option<String>>("", "ref_table")?.unwrap_or_default(),
+                    referenced_columns: split_list(&row.try_get::<String>("", "ref_columns")?),
+                    on_delete: row.try_get("", "on_delete")?,
+                }),
+            }
+        }
+
+        // Unique indexes that aren't backing a constraint
+        for row in query(
+            db,
+            "SELECT i.relname::text AS name, \
+                 array_to_string(ARRAY(SELECT a.attname::text FROM unnest(ix.indkey::int2[]) WITH ORDINALITY k(attnum, ord) \
+                     JOIN pg_attribute a ON a.attrelid = ix.indrelid AND a.attnum = k.attnum ORDER BY k.ord), ',') AS columns \
+             FROM pg_index ix \
+             JOIN pg_class i ON i.oid = ix.indexrelid \
+             JOIN pg_class t ON t.oid = ix.indrelid \
+             JOIN pg_namespace ns ON ns.oid = t.relnamespace \
+             WHERE ns.nspname = $1 AND t.relname = $2 AND ix.indisunique AND NOT ix.indisprimary \
+             ORDER BY i.relname",
+            vec![schema.into(), name.clone().into()],
+        ).await? {
+            let index_columns = split_list(&row.try_get::<String>("", "columns")?);
+            if !index_columns.is_empty() && !unique_indexes.iter().any(|u| u.columns == index_columns) {
+                unique_indexes.push(SqlUniqueIndex { name: row.try_get("", "name")?, columns: index_columns });
+            }
+        }
+
+        tables.push(SqlTable { name, columns, primary_key, foreign_keys, unique_indexes });
+    }
+
+    Ok(tables)
+}
+
+/// Length in a declared type like `VARCHAR(100)`
+fn declared_length(declared: &str) -> Option<usize> {
+    let start = declared.find('(')?;
+    let end = declared[start..].find(')')? + start;
+    declared[start + 1..end].split(',').next()?.trim().parse().ok()
+}
+
+/// Map a column type to the closest Torque field type
+pub fn field_type_for_column(column: &SqlColumn) -> FieldType {
+    if !column.enum_values.is_empty() {
+        return FieldType::Enum { values: column.enum_values.clone() };
+    }
+    // Postgres reports array columns by their element type prefixed with `_`
+    if let Some(element) = column.data_type.strip_prefix('_') {
+        let element = SqlColumn { data_type: element.to_string(), max_length: None, ..column.clone() };
+        return FieldType::Array { element_type: Box::new(field_type_for_column(&element)) };
+    }
+    if column.data_type.ends_with("[]") {
+        let element = SqlColumn {
+            data_type: column.data_type.trim_end_matches("[]").to_string(),
+            max_length: None,
+            ..column.clone()
+        };
+        return FieldType::Array { element_type: Box::new(field_type_for_column(&element)) };
+    }
+
+    let t = column.data_type.as_str();
+    if t.contains("interval") || t.contains("point") {
+        FieldType::String { max_length: None }
+    } else if t.contains("bool") {
+        FieldType::Boolean
+    } else if t.contains("int") || t.contains("serial") {
+        FieldType::Integer { min: None, max: None }
+    } else if ["real", "floa", "doub", "numeric", "decimal", "money"].iter().any(|n| t.contains(n)) {
+        FieldType::Float { min: None, max: None }
+    } else if t.contains("timestamp") || t.contains("datetime") {
+        FieldType::DateTime
+    } else if t.contains("date") {
+        FieldType::Date
+    } else if t.starts_with("time") {
+        FieldType::Time
+    } else if t.contains("json") {
+        FieldType::Json
+    } else if t.contains("blob") || t.contains("bytea") || t.contains("binary") {
+        FieldType::Binary
+    } else {
+        FieldType::String { max_length: column.max_length }
+    }
+}
+
+/// Literal column default as a JSON value; expressions like `now()` are dropped
+fn default_value(default: &str, field_type: &FieldType) -> Option<Value> {
+    // Postgres annotates literals with their type, e.g. `'active'::character varying`
+    let literal = default.split("::").next().unwrap_or(default).trim();
+    if let Some(text) = literal.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        return Some(Value::String(text.replace("''", "'")));
+    }
+    match field_type {
+        FieldType::Boolean => match literal.to_lowercase().as_str() {
+            "true" | "1" => Some(Value::Bool(true)),
+            "false" | "0" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        FieldType::Integer { .. } => literal.parse::<i64>().ok().map(Value::from),
+        FieldType::Float { .. } => literal.parse::<f64>().ok().map(Value::from),
+        _ => None,
+    }
+}
+
+/// Human readable name for a table or column, e.g. `order_items` -> `Order Items`
+fn display_name(name: &str) -> String {
+    crate::services::model_search::search_terms(name)
+        .split(' ')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().collect::<String>() + chars.as_str()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn cascade_action(on_delete: &str) -> CascadeAction {
+    match on_delete {
+        "cascade" => CascadeAction::Delete,
+        "set null" => CascadeAction::SetNull,
+        "restrict" => CascadeAction::Restrict,
+        _ => CascadeAction::None,
+    }
+}
+
+/// Build a model from introspected tables.
+///
+/// Single-column foreign keys become `Reference` fields and many-to-one relationships;
+/// primary keys, unique indexes and foreign keys are kept as entity constraints.
+pub fn model_from_tables(name: &str, tables: &[SqlTable], generate_layouts: bool) -> TorqueModel {
+    let entity_ids: Vec<(String, Uuid)> = tables.iter().map(|t| (t.name.clone(), Uuid::new_v4())).collect();
+    let entity_id = |table: &str| entity_ids.iter().find(|(t, _)| t == table).map(|(_, id)| id.clone());
+
+    let mut entities = Vec::with_capacity(tables.len());
+    let mut relationships: Vec<ModelRelationship> = Vec::new();
+
+    for table in tables {
+        let id = entity_id(&table.name).expect("every table has an entity id");
+        let single_integer_key = match table.primary_key.as_slice() {
+            [key] => table.columns.iter()
+                .any(|c| &c.name == key && matches!(field_type_for_column(c), FieldType::Integer { .. })),
+            _ => false,
+        };
+
+        let fields = table.columns.iter().map(|column| {
+            let reference = table.foreign_keys.iter()
+                .find(|fk| fk.columns.len() == 1 && fk.columns[0] == column.name)
+                .and_then(|fk| entity_id(&fk.referenced_table));
+            let field_type = match reference {
+                Some(entity_id) => FieldType::Reference { entity_id },
+                None => field_type_for_column(column),
+            };
+            let default_value = column.default.as_deref().and_then(|d| default_value(d, &field_type));
+            // Database generated keys don't have to be supplied by clients
+            let generated = single_integer_key && table.primary_key[0] == column.name;
+            EntityField {
+                id: Uuid::new_v4(),
+                name: column.name.clone(),
+                display_name: display_name(&column.name),
+                required: !column.nullable && column.default.is_none() && !generated,
+                field_type,
+                default_value,
+                validation: vec![],
+                ui_config: FieldUiConfig::default(),
+            }
+        }).collect();
+
+        let mut constraints = Vec::new();
+        let mut indexes = Vec::new();
+        if !table.primary_key.is_empty() {
+            constraints.push(EntityConstraint {
+                constraint_type: ConstraintType::PrimaryKey,
+                name: format!("{}_pkey", table.name),
+                fields: table.primary_key.clone(),
+                message: None,
+            });
+        }
+        for unique in &table.unique_indexes {
+            constraints.push(EntityConstraint {
+                constraint_type: ConstraintType::UniqueKey,
+                name: unique.name.clone(),
+                fields: unique.columns.clone(),
+                message: Some(format!("{} must be unique", unique.columns.join(", "))),
+            });
+            indexes.push(EntityIndex {
+                name: unique.name.clone(),
+                fields: unique.columns.clone(),
+                index_type: IndexType::BTree,
+                unique: true,
+            });
+        }
+
+        for fk in &table.foreign_keys {
+            let Some(target_id) = entity_id(&fk.referenced_table) else {
+                tracing::warn!("Skipping foreign key {} on {}: table {} was not imported", fk.name, table.name, fk.referenced_table);
+                continue;
+            };
+            let target_field = fk.referenced_columns.first().cloned()
+                .or_else(|| tables.iter().find(|t| t.name == fk.referenced_table).and_then(|t| t.primary_key.first().cloned()))
+                .unwrap_or_else(|| "id".to_string());
+            constraints.push(EntityConstraint {
+                constraint_type: ConstraintType::ForeignKey { reference_entity: target_id.clone(), reference_field: target_field.clone() },
+                name: fk.name.clone(),
+                fields: fk.columns.clone(),
+                message: None,
+            });
+
+            if let [column] = fk.columns.as_slice() {
+                let base = format!("{}_{}", table.name, column.strip_suffix("_id").unwrap_or(column));
+                let mut relationship_name = base.clone();
+                let mut n = 2;
+                while relationships.iter().any(|r| r.name == relationship_name) {
+                    relationship_name = format!("{}_{}", base, n);
+                    n += 1;
+                }
+                relationships.push(ModelRelationship {
+                    id: Uuid::new_v4(),
+                    name: relationship_name,
+                    relationship_type: RelationshipType::ManyToOne,
+                    from_entity: id.clone(),
+                    to_entity: target_id,
+                    from_field: column.clone(),
+                    to_field: target_field,
+                    cascade: cascade_action(&fk.on_delete),
+                    ui_config: RelationshipUiConfig::default(),
+                });
+            }
+        }
+
+        entities.push(ModelEntity {
+            id,
+            name: table.name.clone(),
+            display_name: display_name(&table.name),
+            description: None,
+            entity_type: EntityType::Data,
+            fields,
+            constraints,
+            indexes,
+            ui_config: EntityUiConfig::default(),
+            behavior: EntityBehavior::default(),
+        });
+    }
+
+    let now = UtcDateTime::now();
+    let mut model = TorqueModel {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        description: Some(format!("Imported from an existing database ({} tables)", tables.len())),
+        version: "1.0.0".to_string(),
+        created_at: now.clone(),
+        updated_at: now,
+        created_by: "import-sql".to_string(),
+        config: ModelConfig::default(),
+        entities,
+        relationships,
+        flows: vec![],
+        layouts: vec![],
+        validations: vec![],
+    };
+
+    // Layouts look up relationships, so they are generated once the model is complete
+    if generate_layouts {
+        model.layouts = model.entities.iter()
+            .flat_map(|entity| crate::model::layouts::default_layouts(&model, entity))
+            .collect();
+    }
+    model
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> SqlColumn {
+        SqlColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable,
+            default: None,
+            max_length: declared_length(data_type),
+            enum_values: vec![],
+        }
+    }
+
+    #[test]
+    fn test_column_type_mapping() {
+        assert!(matches!(field_type_for_column(&column("a", "varchar(40)", true)), FieldType::String { max_length: Some(40) }));
+        assert!(matches!(field_type_for_column(&column("a", "bigint", true)), FieldType::Integer { .. }));
+        assert!(matches!(field_type_for_column(&column("a", "double precision", true)), FieldType::Float { .. }));
+        assert!(matches!(field_type_for_column(&column("a", "timestamp with time zone", true)), FieldType::DateTime));
+        assert!(matches!(field_type_for_column(&column("a", "time without time zone", true)), FieldType::Time));
+        assert!(matches!(field_type_for_column(&column("a", "interval", true)), FieldType::String { .. }));
+        assert!(matches!(field_type_for_column(&column("a", "jsonb", true)), FieldType::Json));
+        assert!(matches!(field_type_for_column(&column("a", "_int4", true)), FieldType::Array { .. }));
+        assert_eq!(default_value("'active'::character varying", &FieldType::String { max_length: None }), Some(Value::from("active")));
+        assert_eq!(default_value("now()", &FieldType::DateTime), None);
+    }
+
+    #[test]
+    fn test_model_from_tables() {
+        let customers = SqlTable {
+            name: "customers".to_string(),
+            columns: vec![column("id", "integer", false), column("email", "text", false)],
+            primary_key: vec!["id".to_string()],
+            foreign_keys: vec![],
+            unique_indexes: vec![SqlUniqueIndex { name: "customers_email_key".to_string(), columns: vec!["email".to_string()] }],
+        };
+        let orders = SqlTable {
+            name: "orders".to_string(),
+            columns: vec![column("id", "integer", false), column("customer_id", "integer", false)],
+            primary_key: vec!["id".to_string()],
+            foreign_keys: vec![SqlForeignKey {
+                name: "orders_customer_id_fkey".to_string(),
+                columns: vec!["customer_id".to_string()],
+                referenced_table: "customers".to_string(),
+                referenced_columns: vec![],
+                on_delete: "cascade".to_string(),
+            }],
+            unique_indexes: vec![],
+        };
+
+        let model = model_from_tables("Shop", &[customers, orders], true);
+        let customer = &model.entities[0];
+        let order = &model.entities[1];
+
+        assert!(!customer.fields[0].required, "generated keys are optional");
+        assert!(customer.fields[1].required);
+        assert!(matches!(&order.fields[1].field_type, FieldType::Reference { entity_id } if *entity_id == customer.id));
+        assert_eq!(model.relationships.len(), 1);
+        assert_eq!(model.relationships[0].name, "orders_customer");
+        assert_eq!(model.relationships[0].to_field, "id");
+        assert!(customer.constraints.iter().any(|c| matches!(c.constraint_type, ConstraintType::UniqueKey)));
+        assert_eq!(model.layouts.len(), 6);
+        assert_eq!(display_name("order_items"), "Order Items");
+    }
+}
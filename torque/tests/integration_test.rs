@@ -161,4 +161,2141 @@ async fn test_performance_requirements() {
     // Should complete in under 50ms (in-memory database)
     assert!(duration < Duration::from_millis(50), 
            "Entity operations took too long: {:?}", duration);
-}
\ No newline at end of file
+}
+/// Test that model saves produce version snapshots that can be listed, fetched, rolled back and pinned,
+/// with distinct numbers when several nodes save at once
+#[tokio::test]
+async fn test_model_versioning() {
+    use torque::services::model::{CreateModelInput, UpdateModelInput};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let model_service = &services.model_service;
+
+    let model = model_service.create_model(CreateModelInput {
+        name: "Versioned".to_string(),
+        description: None,
+        config: None,
+    }).await.unwrap();
+
+    model_service.update_model(model.id.clone(), UpdateModelInput {
+        name: Some("Versioned v2".to_string()),
+        description: None,
+        config: None,
+        author: Some("alice".to_string()),
+        message: Some("Rename".to_string()),
+    }).await.unwrap();
+
+    let versions = model_service.list_model_versions(model.id.clone()).await.unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0].version_number, 2);
+    assert_eq!(versions[0].author, "alice");
+    assert_eq!(versions[0].message.as_deref(), Some("Rename"));
+
+    let first = model_service.get_model_version(model.id.clone(), 1).await.unwrap();
+    assert_eq!(first.model.name, "Versioned");
+
+    // Rolling back restores the old definition as a new version
+    let restored = model_service.rollback_model(model.id.clone(), 1, None, None).await.unwrap();
+    assert_eq!(restored.name, "Versioned");
+    let versions = model_service.list_model_versions(model.id.clone()).await.unwrap();
+    assert_eq!(versions.len(), 3);
+
+    // Pinned runtime lookups serve the snapshot rather than the latest definition
+    model_service.pin_model_version(model.id.clone(), 2, None).await.unwrap();
+    let runtime = model_service.get_runtime_model(model.id.clone(), None).await.unwrap().unwrap();
+    assert_eq!(runtime.name, "Versioned v2");
+
+    assert!(model_service.unpin_model_version(model.id.clone()).await.unwrap());
+    let runtime = model_service.get_runtime_model(model.id.clone(), None).await.unwrap().unwrap();
+    assert_eq!(runtime.name, "Versioned");
+
+    // Saves from another node sharing the database get their own version numbers
+    let other_node = torque::services::model::ModelService::new(services.db.clone(), services.cache.clone());
+    let mut saves = Vec::new();
+    for n in 0..5 {
+        for service in [model_service.as_ref(), &other_node] {
+            saves.push(service.update_model(model.id.clone(), UpdateModelInput {
+                name: None,
+                description: Some(format!("Edit {}", n)),
+                config: None,
+                author: None,
+                message: None,
+            }));
+        }
+    }
+    for result in futures::future::join_all(saves).await {
+        result.unwrap();
+    }
+    let mut numbers: Vec<i32> = model_service.list_model_versions(model.id.clone()).await.unwrap()
+        .into_iter()
+        .map(|v| v.version_number)
+        .collect();
+    numbers.sort();
+    assert_eq!(numbers, (1..=13).collect::<Vec<_>>());
+}
+
+/// An import based on an older version merges with edits saved since, when they don't overlap
+#[tokio::test]
+async fn test_replace_model_merges_stale_import() {
+    use torque::services::model::CreateModelInput;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let model_service = &services.model_service;
+
+    let model = model_service.create_model(CreateModelInput {
+        name: "Shop".to_string(),
+        description: None,
+        config: None,
+    }).await.unwrap();
+    let base_version = model_service.latest_version_number(model.id.clone()).await.unwrap().unwrap();
+
+    // Someone else imports a Customer entity first, bumping the model version
+    let import = serde_json::json!({
+        "metadata": { "name": "Shop" },
+        "entities": [{ "name": "Customer", "fields": [] }],
+    });
+    let latest = model_service.latest_version_number(model.id.clone()).await.unwrap();
+    model_service.replace_model(model.id.clone(), import.to_string(), latest, None).await.unwrap();
+
+    let import = serde_json::json!({
+        "metadata": { "name": "Store" },
+        "entities": [],
+    });
+    let replaced = model_service
+        .replace_model(model.id.clone(), import.to_string(), Some(base_version), Some("alice".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(replaced.name, "Store");
+    assert!(replaced.entities.iter().any(|e| e.name == "Customer"));
+    assert_eq!(replaced.version, "3.0");
+
+    let versions = model_service.list_model_versions(model.id.clone()).await.unwrap();
+    assert_eq!(versions[0].author, "alice");
+}
+
+/// A file written by `torque model export` loads as a full model and diffs by component id
+#[tokio::test]
+async fn test_diff_against_exported_model() {
+    use torque::model::diff::{diff_models, ChangeKind, DiffComponent};
+    use torque::model::types::TorqueModel;
+    use torque::services::model::UpdateEntityInput;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let model_service = &services.model_service;
+
+    let model = model_service.create_model_from_template("todo", None, None).await.unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("todo.json");
+    std::fs::write(&path, model_service.export_model(model.id.clone()).await.unwrap()).unwrap();
+
+    let exported: TorqueModel = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let current = model_service.get_model(model.id.clone()).await.unwrap().unwrap();
+    assert!(diff_models(&exported, &current).unwrap().is_empty());
+
+    // Renaming an entity is a modification of the same component, not a remove and add
+    let category = current.entities.iter().find(|e| e.name == "category").unwrap();
+    model_service.update_entity(category.id.clone(), UpdateEntityInput {
+        name: Some("label".to_string()),
+        display_name: None,
+        description: None,
+        entity_type: None,
+        fields: None,
+        ui_config: None,
+        behavior: None,
+    }).await.unwrap();
+    let current = model_service.get_model(model.id.clone()).await.unwrap().unwrap();
+
+    let diff = diff_models(&exported, &current).unwrap();
+    let entity_changes: Vec<_> = diff.changes.iter().filter(|c| c.component == DiffComponent::Entity).collect();
+    assert_eq!(entity_changes.len(), 1, "{}", diff);
+    assert_eq!(entity_changes[0].kind, ChangeKind::Modified);
+    assert_eq!(entity_changes[0].id, Some(category.id.clone()));
+}
+
+/// Field renames and type changes migrate existing app data, can be previewed and rolled back
+/// unless the rows were edited since, and only one job runs per model at a time
+#[tokio::test]
+async fn test_data_migration_after_field_changes() {
+    use sea_orm::EntityTrait;
+    use sea_orm::Set;
+    use torque::database::entities::{app_entities, data_migration_jobs};
+    use torque::model::data_migration::OrphanStrategy;
+    use torque::model::events::{DataChangeAction, ModelChangeEvent};
+    use torque::model::types::{EntityType, FieldType};
+    use torque::services::model::{CreateEntityInput, CreateFieldInput, CreateModelInput, UpdateEntityInput};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let model_service = &services.model_service;
+
+    let model = model_service.create_model(CreateModelInput {
+        name: "Migrating".to_string(),
+        description: None,
+        config: None,
+    }).await.unwrap();
+
+    let text_field = |name: &str| CreateFieldInput {
+        name: name.to_string(),
+        display_name: name.to_string(),
+        field_type: FieldType::String { max_length: None },
+        required: false,
+        default_value: None,
+        validation: vec![],
+        ui_config: None,
+    };
+    let entity = model_service.create_entity(CreateEntityInput {
+        model_id: model.id.to_string(),
+        name: "Customer".to_string(),
+        display_name: "Customer".to_string(),
+        description: None,
+        entity_type: EntityType::Data,
+        fields: vec![text_field("mail"), text_field("age")],
+        ui_config: None,
+        behavior: None,
+    }).await.unwrap();
+    let from_version = model_service.latest_version_number(model.id.clone()).await.unwrap().unwrap();
+
+    let row = services.app_database_service
+        .create_entity(model.id.as_str(), "Customer", serde_json::json!({"mail": "a@example.com", "age": "42"}))
+        .await.unwrap();
+
+    // Rename `mail` to `email` and turn `age` into an integer
+    let mut fields = entity.fields.clone();
+    fields[0].name = "email".to_string();
+    fields[1].field_type = FieldType::Integer { min: None, max: None };
+    model_service.update_entity(entity.id.clone(), UpdateEntityInput {
+        name: None,
+        display_name: None,
+        description: None,
+        entity_type: None,
+        fields: Some(fields),
+        ui_config: None,
+        behavior: None,
+    }).await.unwrap();
+
+    let migrations = &services.data_migration_service;
+    let preview = migrations.preview(&model.id, Some(from_version), None, OrphanStrategy::Archive).await.unwrap();
+    assert_eq!(preview.plan.steps.len(), 2);
+    assert_eq!(preview.stats.rows_updated, 1);
+    assert_eq!(preview.samples[0].after, Some(serde_json::json!({"email": "a@example.com", "age": 42})));
+
+    let finished = |job_id: String| async move {
+        let mut status = String::new();
+        for _ in 0..50 {
+            status = migrations.get_job(&job_id).await.unwrap().status;
+            if status != "pending" && status != "running" {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        status
+    };
+    let mut receiver = services.broadcast.subscribe();
+    let job = migrations.start_job(&model.id, Some(from_version), None, OrphanStrategy::Archive).await.unwrap();
+    assert_eq!(finished(job.id.clone()).await, "completed");
+
+    let migrated = app_entities::Entity::find_by_id(row.id.clone()).one(services.db.as_ref()).await.unwrap().unwrap();
+    assert_eq!(migrated.data, serde_json::json!({"email": "a@example.com", "age": 42}));
+
+    // Migrated rows are announced in one event per entity type
+    loop {
+        let message = timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+        if let ModelChangeEvent::EntityDataBulkChanged { action, entity_type, record_ids, .. } = message.event {
+            assert_eq!(action, DataChangeAction::Updated);
+            assert_eq!(entity_type, "Customer");
+            assert_eq!(record_ids, Some(vec![row.id.clone()]));
+            break;
+        }
+    }
+
+    // Rolling back restores the original row
+    let rolled_back = migrations.rollback_job(&job.id).await.unwrap();
+    assert_eq!(rolled_back.status, "rolled_back");
+    let restored = app_entities::Entity::find_by_id(row.id.clone()).one(services.db.as_ref()).await.unwrap().unwrap();
+    assert_eq!(restored.data, serde_json::json!({"mail": "a@example.com", "age": "42"}));
+
+    // Rows edited after a migration block its rollback
+    let job = migrations.start_job(&model.id, Some(from_version), None, OrphanStrategy::Archive).await.unwrap();
+    assert_eq!(finished(job.id.clone()).await, "completed");
+    let edited = serde_json::json!({"email": "b@example.com", "age": 43});
+    services.app_database_service.update_entity(model.id.as_str(), &row.id, edited.clone()).await.unwrap();
+    assert!(migrations.rollback_job(&job.id).await.is_err());
+    let kept = app_entities::Entity::find_by_id(row.id).one(services.db.as_ref()).await.unwrap().unwrap();
+    assert_eq!(kept.data, edited);
+
+    // The database allows only one pending or running job per model, even if the in-progress check races
+    let job_row = data_migration_jobs::Entity::find_by_id(job.id.clone()).one(services.db.as_ref()).await.unwrap().unwrap();
+    let pending = |id: &str| {
+        let mut active: data_migration_jobs::ActiveModel = job_row.clone().into();
+        active.id = Set(id.to_string());
+        active.status = Set("pending".to_string());
+        data_migration_jobs::Entity::insert(active).exec(services.db.as_ref())
+    };
+    pending("concurrent-1").await.unwrap();
+    assert!(pending("concurrent-2").await.is_err());
+    assert!(migrations.start_job(&model.id, Some(from_version), None, OrphanStrategy::Archive).await.is_err());
+
+    // A job left pending by a crashed node stops blocking once its heartbeat is stale
+    let stale = chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    data_migration_jobs::Entity::update(data_migration_jobs::ActiveModel {
+        id: Set("concurrent-1".to_string()),
+        heartbeat_at: Set(Some(stale)),
+        ..Default::default()
+    }).exec(services.db.as_ref()).await.unwrap();
+    let job = migrations.start_job(&model.id, Some(from_version), None, OrphanStrategy::Archive).await.unwrap();
+    assert_eq!(migrations.get_job("concurrent-1").await.unwrap().status, "failed");
+    assert_eq!(finished(job.id).await, "completed");
+}
+
+/// The retention job compresses old rows once, keeping them queryable, deletes rows over
+/// the record limit with the blobs they owned, and archives rows with their blobs until
+/// the archive is purged
+#[tokio::test]
+async fn test_retention_job_compresses_and_deletes_overflow() {
+    use sea_orm::{ConnectionTrait, EntityTrait, Statement};
+    use torque::database::entities::app_entities;
+    use torque::model::types::{CleanupStrategy, RetentionPolicy};
+    use torque::services::app_database::{EntityFilter, EntityListQuery, FilterOp};
+    use torque::services::blob_store::BlobUpload;
+    use torque::services::model::UpdateModelInput;
+    use torque::services::retention::{inflate_if_compressed, is_compressed};
+
+    let blobs = tempfile::tempdir().unwrap();
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    config.storage.local_path = blobs.path().to_path_buf();
+    config.retention.batch_size = 1;
+    config.retention.archive_retention_days = 30;
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+
+    let model = services.model_service.create_model_from_template("todo", None, None).await.unwrap();
+    let model_id = model.id.to_string();
+    let mut ids = Vec::new();
+    for (id, name) in ["Work", "Home", "Errands"].into_iter().enumerate() {
+        let data = serde_json::json!({ "id": id + 1, "name": name, "color": "#3B82F6", "active": true });
+        ids.push(services.app_database_service.create_entity(&model_id, "category", data).await.unwrap().id);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    let set_policy = |cleanup_strategy| {
+        let mut config = model.config.clone();
+        config.database.retention_policy = Some(RetentionPolicy { max_age_days: 0, max_records: Some(1), cleanup_strategy });
+        services.model_service.update_model(model.id.clone(), UpdateModelInput {
+            name: None,
+            description: None,
+            config: Some(config),
+            author: None,
+            message: None,
+        })
+    };
+    let compressed = |id: String| {
+        let db = services.db.clone();
+        async move {
+            let row = app_entities::Entity::find_by_id(id).one(db.as_ref()).await.unwrap();
+            row.map(|row| is_compressed(&row.data))
+        }
+    };
+
+    // One row per run: rows compressed earlier don't fill later batches
+    set_policy(CleanupStrategy::Compress).await.unwrap();
+    let retention = &services.retention_service;
+    assert_eq!(retention.run_once().await.unwrap().rows_compressed, 1);
+    assert_eq!(retention.run_once().await.unwrap().rows_compressed, 1);
+    assert_eq!(retention.run_once().await.unwrap().rows_compressed, 0);
+    assert_eq!(compressed(ids[0].clone()).await, Some(true));
+    assert_eq!(compressed(ids[1].clone()).await, Some(true));
+    assert_eq!(compressed(ids[2].clone()).await, Some(false));
+    let row = services.app_database_service.get_entity(&model_id, &ids[0]).await.unwrap().unwrap();
+    assert_eq!(inflate_if_compressed(row.data)["name"], "Work");
+
+    // Compressed rows still match filters and sort together with the others
+    let filtered = EntityListQuery {
+        filters: vec![EntityFilter { field: "name".to_string(), op: FilterOp::Eq, value: serde_json::json!("Work") }],
+        limit: 10,
+        ..Default::default()
+    };
+    let (records, total) = services.app_database_service.query_entities(&model_id, "category", &filtered).await.unwrap();
+    assert_eq!(total, 1);
+    assert_eq!(records[0]["_id"], ids[0].as_str());
+    let sorted = EntityListQuery { sort_field: Some("name".to_string()), limit: 2, ..Default::default() };
+    let (records, total) = services.app_database_service.query_entities(&model_id, "category", &sorted).await.unwrap();
+    assert_eq!(total, 3);
+    let names: Vec<_> = records.iter().map(|r| r["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["Errands", "Home"]);
+
+    // Deleting overflow rows also removes their attachments
+    let mut attachments = Vec::new();
+    for id in &ids[..2] {
+        let blob = services.blob_service.upload(BlobUpload {
+            model_id: model_id.clone(),
+            entity_id: Some(id.clone()),
+            field_name: Some("icon".to_string()),
+            filename: "icon.png".to_string(),
+            content_type: "image/png".to_string(),
+            data: vec![0x89, 0x50, 0x4e, 0x47],
+        }).await.unwrap();
+        attachments.push(blob.id);
+    }
+    set_policy(CleanupStrategy::Delete).await.unwrap();
+    assert_eq!(retention.run_once().await.unwrap().rows_deleted, 1);
+    assert_eq!(retention.run_once().await.unwrap().rows_deleted, 1);
+    assert_eq!(retention.run_once().await.unwrap().rows_deleted, 0);
+    assert_eq!(compressed(ids[0].clone()).await, None);
+    assert_eq!(compressed(ids[1].clone()).await, None);
+    assert_eq!(compressed(ids[2].clone()).await, Some(false));
+    for blob_id in &attachments {
+        assert!(services.blob_service.get_info(&model_id, blob_id).await.is_err());
+    }
+    assert_eq!(std::fs::read_dir(blobs.path().join(&model_id)).map(|dir| dir.count()).unwrap_or(0), 0);
+
+    // Archived rows keep their attachments until they are purged from the archive
+    let data = serde_json::json!({ "id": 4, "name": "Garden", "color": "#22C55E", "active": true });
+    services.app_database_service.create_entity(&model_id, "category", data).await.unwrap();
+    let blob = services.blob_service.upload(BlobUpload {
+        model_id: model_id.clone(),
+        entity_id: Some(ids[2].clone()),
+        field_name: Some("icon".to_string()),
+        filename: "icon.png".to_string(),
+        content_type: "image/png".to_string(),
+        data: vec![0x89, 0x50, 0x4e, 0x47],
+    }).await.unwrap();
+    set_policy(CleanupStrategy::Archive).await.unwrap();
+    let report = retention.run_once().await.unwrap();
+    assert_eq!((report.rows_archived, report.rows_purged), (1, 0));
+    assert_eq!(compressed(ids[2].clone()).await, None);
+    assert!(services.blob_service.get_info(&model_id, &blob.id).await.is_ok());
+
+    services.db.execute(Statement::from_string(
+        services.db.get_database_backend(),
+        "UPDATE app_entities_archive SET archived_at = '2000-01-01 00:00:00'".to_string(),
+    )).await.unwrap();
+    assert_eq!(retention.run_once().await.unwrap().rows_purged, 1);
+    assert!(services.blob_service.get_info(&model_id, &blob.id).await.is_err());
+}
+
+/// Blob uploads over HTTP must name an existing record and Binary field of the model and
+/// download intact. Records can only refer to their own or unattached blobs, and blobs no
+/// record refers to are removed by the retention job once they are old enough. Seeding
+/// fills Binary fields with uploaded sample blobs.
+#[tokio::test]
+async fn test_blob_upload_and_download_endpoints() {
+    use axum::body::Body;
+    use axum::http::{header, Request, StatusCode};
+    use sea_orm::{ConnectionTrait, Statement};
+    use tower::ServiceExt;
+    use torque::model::types::{EntityType, FieldType};
+    use torque::services::blob_store::BlobUpload;
+    use torque::services::fake_data::SeedRequest;
+    use torque::services::model::{CreateEntityInput, CreateFieldInput, CreateModelInput};
+
+    let blobs = tempfile::tempdir().unwrap();
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    config.storage.local_path = blobs.path().to_path_buf();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+
+    let model = services.model_service.create_model(CreateModelInput {
+        name: "Documents".to_string(),
+        description: None,
+        config: None,
+    }).await.unwrap();
+    let field = |name: &str, field_type: FieldType| CreateFieldInput {
+        name: name.to_string(),
+        display_name: name.to_string(),
+        field_type,
+        required: false,
+        default_value: None,
+        validation: vec![],
+        ui_config: None,
+    };
+    services.model_service.create_entity(CreateEntityInput {
+        model_id: model.id.to_string(),
+        name: "Document".to_string(),
+        display_name: "Document".to_string(),
+        description: None,
+        entity_type: EntityType::Data,
+        fields: vec![field("title", FieldType::String { max_length: None }), field("file", FieldType::Binary)],
+        ui_config: None,
+        behavior: None,
+    }).await.unwrap();
+    let model_id = model.id.to_string();
+    let app_database = &services.app_database_service;
+    let record = app_database.create_entity(&model_id, "Document", serde_json::json!({ "title": "Report" })).await.unwrap();
+
+    let router = server::create_router(services.clone());
+    let upload = |model_id: &str, owner: &[(&str, &str)]| {
+        let boundary = "torque-test-boundary";
+        let mut body = String::new();
+        for (name, value) in owner {
+            body.push_str(&format!("--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"));
+        }
+        body.push_str(&format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"report.txt\"\r\n\
+             Content-Type: text/plain\r\n\r\nquarterly numbers\r\n--{boundary}--\r\n"
+        ));
+        let request = Request::post(format!("/api/v1/models/{}/app-database/blobs", model_id))
+            .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={boundary}"))
+            .body(Body::from(body))
+            .unwrap();
+        router.clone().oneshot(request)
+    };
+    let json = |response: axum::response::Response| async move {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+    };
+
+    // The owner must exist in this model and name a Binary field
+    let unknown_model = torque::common::Uuid::new_v4().to_string();
+    assert_eq!(upload(&unknown_model, &[]).await.unwrap().status(), StatusCode::NOT_FOUND);
+    let response = upload(&model_id, &[("entity_id", "missing"), ("field_name", "file")]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = upload(&model_id, &[("entity_id", record.id.as_str()), ("field_name", "title")]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = upload(&model_id, &[("entity_id", record.id.as_str()), ("field_name", "file")]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let attached = json(response).await;
+    assert_eq!(attached["entity_id"], record.id.as_str());
+    let attached_id = attached["id"].as_str().unwrap().to_string();
+
+    let request = Request::get(format!("/api/v1/models/{}/app-database/blobs/{}", model_id, attached_id))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"quarterly numbers");
+
+    let response = upload(&model_id, &[]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let unattached_id = json(response).await["id"].as_str().unwrap().to_string();
+
+    // Writing a blob into a record attaches it; another record can't take it over
+    let data = serde_json::json!({ "title": "Report", "file": attached_id });
+    app_database.update_entity(&model_id, &record.id, data).await.unwrap();
+    let other = serde_json::json!({ "title": "Copy", "file": attached_id });
+    assert!(app_database.create_entity(&model_id, "Document", other).await.is_err());
+    let unknown = serde_json::json!({ "title": "Copy", "file": "no-such-blob" });
+    assert!(app_database.create_entity(&model_id, "Document", unknown).await.is_err());
+
+    let age_blobs = || {
+        services.db.execute(Statement::from_string(
+            services.db.get_database_backend(),
+            "UPDATE app_blobs SET created_at = '2000-01-01 00:00:00'".to_string(),
+        ))
+    };
+    let blob_service = &services.blob_service;
+
+    // Fresh uploads are kept until they are old enough to be considered abandoned
+    assert_eq!(services.retention_service.run_once().await.unwrap().blobs_removed, 0);
+    age_blobs().await.unwrap();
+    assert_eq!(services.retention_service.run_once().await.unwrap().blobs_removed, 1);
+    assert!(blob_service.get_info(&model_id, &unattached_id).await.is_err());
+    assert!(blob_service.get_info(&model_id, &attached_id).await.is_ok());
+
+    // A blob replaced in its record's Binary field is removed as well
+    let replacement = blob_service.upload(BlobUpload {
+        model_id: model_id.clone(),
+        entity_id: None,
+        field_name: None,
+        filename: "report-v2.txt".to_string(),
+        content_type: "text/plain".to_string(),
+        data: b"revised numbers".to_vec(),
+    }).await.unwrap();
+    let data = serde_json::json!({ "title": "Report", "file": replacement.id });
+    app_database.update_entity(&model_id, &record.id, data).await.unwrap();
+    age_blobs().await.unwrap();
+    assert_eq!(services.retention_service.run_once().await.unwrap().blobs_removed, 1);
+    assert!(blob_service.get_info(&model_id, &attached_id).await.is_err());
+    let info = blob_service.get_info(&model_id, &replacement.id).await.unwrap();
+    assert_eq!(info.entity_id.as_deref(), Some(record.id.as_str()));
+
+    // Seeded records refer to sample blobs attached to them
+    let seed = SeedRequest { max_instances_per_entity: Some(2), specific_entities: None, preserve_existing: true };
+    let stored = services.model_service.get_model(model.id.clone()).await.unwrap().unwrap();
+    services.fake_data_service.seed_model_data(&model_id, &stored, &seed).await.unwrap();
+    let records = app_database.get_entities(&model_id, "Document", 10, 0).await.unwrap();
+    assert_eq!(records.len(), 3);
+    for seeded in records.iter().filter(|r| r["_id"] != record.id.as_str()) {
+        let info = blob_service.get_info(&model_id, seeded["file"].as_str().unwrap()).await.unwrap();
+        assert_eq!(info.entity_id.as_deref(), seeded["_id"].as_str());
+    }
+}
+
+/// Relationships can be updated partially and are removed with the entities they connect
+#[tokio::test]
+async fn test_relationship_crud_and_entity_cleanup() {
+    use torque::model::types::{CascadeAction, EntityType, RelationshipType};
+    use torque::services::model::{CreateEntityInput, CreateModelInput, CreateRelationshipInput, UpdateRelationshipInput};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let model_service = &services.model_service;
+
+    let model = model_service.create_model(CreateModelInput {
+        name: "Relations".to_string(),
+        description: None,
+        config: None,
+    }).await.unwrap();
+
+    let mut entity_ids = Vec::new();
+    for name in ["Customer", "Order"] {
+        let entity = model_service.create_entity(CreateEntityInput {
+            model_id: model.id.to_string(),
+            name: name.to_string(),
+            display_name: name.to_string(),
+            description: None,
+            entity_type: EntityType::Data,
+            fields: vec![],
+            ui_config: None,
+            behavior: None,
+        }).await.unwrap();
+        entity_ids.push(entity.id);
+    }
+
+    let relationship = model_service.create_relationship(CreateRelationshipInput {
+        model_id: model.id.to_string(),
+        name: "customer_orders".to_string(),
+        relationship_type: RelationshipType::OneToMany,
+        from_entity: entity_ids[0].to_string(),
+        to_entity: entity_ids[1].to_string(),
+        from_field: "id".to_string(),
+        to_field: "customer_id".to_string(),
+        cascade: CascadeAction::None,
+        ui_config: None,
+    }).await.unwrap();
+
+    let updated = model_service.update_relationship(relationship.id.clone(), UpdateRelationshipInput {
+        name: Some("orders".to_string()),
+        cascade: Some(CascadeAction::Delete),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(updated.name, "orders");
+    assert_eq!(updated.to_field, "customer_id");
+
+    // Endpoints must name entities of the model, on create and on update
+    let missing = uuid::Uuid::new_v4().to_string();
+    let created = model_service.create_relationship(CreateRelationshipInput {
+        model_id: model.id.to_string(),
+        name: "dangling".to_string(),
+        relationship_type: RelationshipType::OneToMany,
+        from_entity: entity_ids[0].to_string(),
+        to_entity: missing.clone(),
+        from_field: "id".to_string(),
+        to_field: "customer_id".to_string(),
+        cascade: CascadeAction::None,
+        ui_config: None,
+    }).await;
+    assert!(matches!(created, Err(torque::Error::Validation(_))));
+    let retargeted = model_service.update_relationship(relationship.id.clone(), UpdateRelationshipInput {
+        to_entity: Some(missing),
+        ..Default::default()
+    }).await;
+    assert!(matches!(retargeted, Err(torque::Error::Validation(_))));
+    let stored = model_service.get_model(model.id.clone()).await.unwrap().unwrap();
+    assert_eq!(stored.relationships.len(), 1);
+    assert_eq!(stored.relationships[0].to_entity, entity_ids[1]);
+
+    // Deleting the entity with cleanup removes the relationship that referenced it
+    let deletion = model_service.delete_entity(entity_ids[0].clone(), true).await.unwrap();
+    assert_eq!(deletion.relationships, vec![relationship.id.clone()]);
+    let stored = model_service.get_model(model.id.clone()).await.unwrap().unwrap();
+    assert_eq!(stored.entities.len(), 1);
+    assert!(stored.relationships.is_empty());
+
+    assert!(!model_service.delete_relationship(relationship.id).await.unwrap());
+}
+
+/// Models are found by name, description, entity and field names
+#[tokio::test]
+async fn test_model_search_index() {
+    use torque::model::types::{EntityType, FieldType};
+    use torque::services::model::{CreateEntityInput, CreateFieldInput, CreateModelInput};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let model_service = &services.model_service;
+
+    let model = model_service.create_model(CreateModelInput {
+        name: "Sales CRM".to_string(),
+        description: Some("Pipeline tracking".to_string()),
+        config: None,
+    }).await.unwrap();
+
+    model_service.create_entity(CreateEntityInput {
+        model_id: model.id.to_string(),
+        name: "Customer".to_string(),
+        display_name: "Customer".to_string(),
+        description: None,
+        entity_type: EntityType::Data,
+        fields: vec![CreateFieldInput {
+            name: "primaryEmail".to_string(),
+            display_name: "Primary Email".to_string(),
+            field_type: FieldType::String { max_length: None },
+            required: false,
+            default_value: None,
+            validation: vec![],
+            ui_config: None,
+        }],
+        ui_config: None,
+        behavior: None,
+    }).await.unwrap();
+
+    // The index is updated asynchronously from model change events
+    let mut hits = Vec::new();
+    for _ in 0..50 {
+        hits = model_service.search_model_hits("emai", None).await.unwrap();
+        if !hits.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].path, "entities.Customer.fields.primaryEmail");
+    assert_eq!(hits[0].model_id, model.id);
+
+    let models = model_service.search_models("pipeline".to_string()).await.unwrap();
+    assert_eq!(models.len(), 1);
+}
+
+/// Models created from a template come with the template's sample data
+#[tokio::test]
+async fn test_create_model_from_template() {
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+
+    let template = torque::model::templates::find_template("todo").unwrap();
+    let model = services.model_service
+        .create_model_from_template("todo", Some("My Tasks".to_string()), None)
+        .await
+        .unwrap();
+    assert_eq!(model.name, "My Tasks");
+    assert!(!model.entities.is_empty());
+
+    let records = services.app_database_service
+        .load_template_sample_data(model.id.as_str(), "todo")
+        .await
+        .unwrap();
+    assert_eq!(records as usize, template.info().sample_record_count);
+
+    let unknown = services.model_service.create_model_from_template("unknown", None, None).await;
+    assert!(unknown.is_err());
+}
+
+/// Applied migrations are recorded with checksums, can be rolled back and reapplied,
+/// and a tampered checksum blocks further migrations
+#[tokio::test]
+async fn test_migration_ledger() {
+    use sea_orm::{ConnectionTrait, Statement};
+    use torque::database::migrations::{self, MigrationState};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+
+    let statuses = migrations::migration_status(&db).await.unwrap();
+    assert_eq!(statuses.len(), migrations::migrations().len());
+    assert!(statuses.iter().all(|s| s.state == MigrationState::Applied));
+
+    // Rolling back and re-applying the latest migration round-trips
+    let rolled_back = migrations::migrate_down(&db).await.unwrap().unwrap();
+    let statuses = migrations::migration_status(&db).await.unwrap();
+    assert_eq!(statuses.last().unwrap().state, MigrationState::Pending);
+    let applied = migrations::migrate_up(&db).await.unwrap();
+    assert_eq!(applied.len(), 1);
+    assert_eq!(applied[0].version, rolled_back.version);
+
+    // Dropping the event log's node column keeps the logged events
+    let backend = db.get_database_backend();
+    db.execute(Statement::from_string(
+        backend,
+        "INSERT INTO event_log (model_id, event) VALUES ('model', '{}')".to_string(),
+    )).await.unwrap();
+    let mut rolled_back = Vec::new();
+    while migrations::migration_status(&db).await.unwrap().iter().any(|s| s.version >= 10 && s.state == MigrationState::Applied) {
+        rolled_back.push(migrations::migrate_down(&db).await.unwrap().unwrap().version);
+    }
+    let events = db.query_one(Statement::from_string(backend, "SELECT COUNT(*) AS count FROM event_log".to_string()))
+        .await.unwrap().unwrap();
+    assert_eq!(events.try_get::<i64>("", "count").unwrap(), 1);
+    assert_eq!(migrations::migrate_up(&db).await.unwrap().len(), rolled_back.len());
+
+    // A tampered checksum blocks further migrations
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        "UPDATE schema_migrations SET checksum = 'edited' WHERE version = 1".to_string(),
+    )).await.unwrap();
+    let statuses = migrations::migration_status(&db).await.unwrap();
+    assert_eq!(statuses[0].state, MigrationState::ChecksumMismatch);
+    assert!(migrations::migrate_up(&db).await.is_err());
+}
+
+/// App data queries filter, sort and page records; contains filters match their text literally
+#[tokio::test]
+async fn test_query_entities_filters_and_pages() {
+    use serde_json::json;
+    use torque::services::app_database::{EntityFilter, EntityListQuery, FilterOp};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+    let model_id = model.id.to_string();
+    services.app_database_service.load_template_sample_data(&model_id, "todo").await.unwrap();
+
+    let filter = |field: &str, op, value| EntityFilter { field: field.to_string(), op, value };
+
+    let query = EntityListQuery {
+        filters: vec![filter("project_id", FilterOp::Eq, json!(1))],
+        limit: 50,
+        ..Default::default()
+    };
+    let (_, total) = services.app_database_service.query_entities(&model_id, "task", &query).await.unwrap();
+    assert_eq!(total, 4);
+
+    let query = EntityListQuery {
+        filters: vec![
+            filter("estimated_hours", FilterOp::Gte, json!(10.0)),
+            filter("title", FilterOp::Contains, json!("E")),
+        ],
+        sort_field: Some("estimated_hours".to_string()),
+        sort_descending: true,
+        limit: 2,
+        offset: 0,
+    };
+    let (records, total) = services.app_database_service.query_entities(&model_id, "task", &query).await.unwrap();
+    assert_eq!(total, 3);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["estimated_hours"], json!(16.0));
+    assert_eq!(records[1]["estimated_hours"], json!(12.0));
+    assert!(records.iter().all(|r| r["_id"].is_string()));
+
+    // LIKE wildcards and the escape character in a contains filter match literally
+    for (id, name) in ["100% done", "1000 done", "snake_case", "snakeXcase", "back\\slash"].into_iter().enumerate() {
+        let data = json!({ "id": 100 + id, "name": name, "color": "#3B82F6", "active": true });
+        services.app_database_service.create_entity(&model_id, "category", data).await.unwrap();
+    }
+    for (needle, expected) in [("0%", "100% done"), ("e_c", "snake_case"), ("\\", "back\\slash")] {
+        let query = EntityListQuery {
+            filters: vec![filter("name", FilterOp::Contains, json!(needle))],
+            limit: 50,
+            ..Default::default()
+        };
+        let (records, total) = services.app_database_service.query_entities(&model_id, "category", &query).await.unwrap();
+        assert_eq!(total, 1, "{}", needle);
+        assert_eq!(records[0]["name"], expected);
+    }
+}
+
+/// Generated clients have a type per entity and a client class for the model
+#[tokio::test]
+async fn test_model_client_codegen() {
+    use torque::model::codegen::{generate_client, CodegenLanguage};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+
+    let model = services.model_service
+        .create_model_from_template("crm", Some("Shop".to_string()), None)
+        .await
+        .unwrap();
+
+    let ts = generate_client(&model, CodegenLanguage::TypeScript);
+    assert!(ts.contains("export interface Customer {"));
+    assert!(ts.contains("export class ShopClient {"));
+    assert!(ts.contains("createOrder(data: Order)"));
+    assert!(ts.contains("async customerOrders(record: CustomerRecord)"));
+    assert!(ts.contains(&model.id.to_string()));
+
+    let rust = generate_client(&model, CodegenLanguage::Rust);
+    assert!(rust.contains("pub struct Order {"));
+    assert!(rust.contains("pub async fn list_customer("));
+    assert!(rust.contains("pub async fn customer_orders_inverse("));
+
+    // Relationship helpers follow every page instead of stopping after the first
+    assert!(ts.contains("return collectPages(page => this.listOrder(page, RELATIONSHIP_PAGE_SIZE, filters))"));
+    assert!(!ts.contains("(1, 100,"));
+    assert!(rust.contains("self.list_order(page, RELATIONSHIP_PAGE_SIZE, filters.clone()).await?"));
+    assert!(rust.contains("let done = !result.pagination.has_next_page || result.data.is_empty();"));
+    assert!(!rust.contains("(1, 100,"));
+}
+
+/// Drive the JSON-RPC methods with the requests a generated client sends and decode
+/// the responses into the generated types' shapes
+#[tokio::test]
+async fn test_model_client_round_trip() {
+    use serde_json::json;
+    use torque::model::codegen::{generate_client, CodegenLanguage};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+    let model_id = model.id.to_string();
+
+    let rust = generate_client(&model, CodegenLanguage::Rust);
+    for call in [
+        r#"self.call("loadEntityData", json!({ "modelId": self.model_id, "entityName": "category", "page": page, "limit": limit, "filters": filters }))"#,
+        r#"self.call("createEntity", json!({ "modelId": self.model_id, "entityName": "category", "data": data }))"#,
+        r#"self.call("updateEntity", json!({ "entityId": entity_id, "data": data }))"#,
+        r#"self.call("deleteEntity", json!({ "entityId": entity_id }))"#,
+    ] {
+        assert!(rust.contains(call), "generated client no longer sends {}", call);
+    }
+
+    let call = |method: &'static str, params: serde_json::Value| {
+        let state = state.clone();
+        async move {
+            let response = torque::jsonrpc::handle_request(&state, &json!({
+                "jsonrpc": "2.0", "id": 1, "method": method, "params": params
+            })).await;
+            assert!(response.get("error").is_none(), "{} failed: {}", method, response);
+            response["result"].clone()
+        }
+    };
+
+    // Mutation<T> { id, data }
+    let created = call("createEntity", json!({
+        "modelId": model_id, "entityName": "category",
+        "data": { "id": 1, "name": "Dev", "color": "#3B82F6", "active": true }
+    })).await;
+    let id = created["id"].as_str().unwrap().to_string();
+    assert_eq!(created["data"]["name"], "Dev");
+
+    // Partial updates keep the fields that were left out
+    let updated = call("updateEntity", json!({ "entityId": id, "data": { "name": "Ops" } })).await;
+    assert_eq!(updated["id"], id.as_str());
+    assert_eq!(updated["data"]["name"], "Ops");
+    assert_eq!(updated["data"]["color"], "#3B82F6");
+
+    // Page<T> { data: Record<T>[], pagination }
+    let page = call("loadEntityData", json!({
+        "modelId": model_id, "entityName": "category", "page": 1, "limit": 20, "filters": { "name": "Ops" }
+    })).await;
+    assert_eq!(page["pagination"]["total"], 1);
+    assert_eq!(page["data"][0]["_id"], id.as_str());
+    assert_eq!(page["data"][0]["active"], true);
+
+    // Deletion { id, deleted }
+    let deleted = call("deleteEntity", json!({ "entityId": id })).await;
+    assert_eq!(deleted["id"], id.as_str());
+    assert_eq!(deleted["deleted"], true);
+    let page = call("loadEntityData", json!({
+        "modelId": model_id, "entityName": "category", "page": 1, "limit": 20, "filters": {}
+    })).await;
+    assert_eq!(page["pagination"]["total"], 0);
+
+    // Relationship helpers read pages until hasNextPage is false
+    for id in 0..101 {
+        let data = json!({ "id": id, "name": format!("Category {}", id), "color": "#3B82F6", "active": true });
+        services.app_database_service.create_entity(&model_id, "category", data).await.unwrap();
+    }
+    let mut seen = 0;
+    for page in 1.. {
+        let result = call("loadEntityData", json!({
+            "modelId": model_id, "entityName": "category", "page": page, "limit": 100, "filters": { "active": true }
+        })).await;
+        seen += result["data"].as_array().unwrap().len();
+        if result["pagination"]["hasNextPage"] != true {
+            assert_eq!(page, 2);
+            break;
+        }
+    }
+    assert_eq!(seen, 101);
+}
+
+/// Tables, constraints and foreign keys of an existing database become a model
+#[tokio::test]
+async fn test_import_model_from_sql_schema() {
+    use sea_orm::{ConnectionTrait, Statement};
+    use torque::model::types::{ConstraintType, FieldType};
+    use torque::services::sql_import;
+
+    let legacy = database::connect("sqlite::memory:", false).await.unwrap();
+    for sql in [
+        "CREATE TABLE customers (id INTEGER PRIMARY KEY, email VARCHAR(120) NOT NULL UNIQUE, active BOOLEAN DEFAULT 1)",
+        "CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_id INTEGER NOT NULL REFERENCES customers(id) ON DELETE CASCADE, \
+         total DECIMAL(10, 2), placed_at DATETIME)",
+    ] {
+        legacy.execute(Statement::from_string(legacy.get_database_backend(), sql.to_string())).await.unwrap();
+    }
+
+    let tables = sql_import::introspect(&legacy, None).await.unwrap();
+    assert_eq!(tables.len(), 2);
+    let model = sql_import::model_from_tables("Legacy Shop", &tables, true);
+
+    let customers = model.entities.iter().find(|e| e.name == "customers").unwrap();
+    let orders = model.entities.iter().find(|e| e.name == "orders").unwrap();
+    let email = customers.fields.iter().find(|f| f.name == "email").unwrap();
+    assert!(email.required);
+    assert!(matches!(email.field_type, FieldType::String { max_length: Some(120) }));
+    assert!(customers.constraints.iter().any(|c| matches!(c.constraint_type, ConstraintType::UniqueKey) && c.fields == ["email"]));
+    assert!(matches!(orders.fields.iter().find(|f| f.name == "customer_id").unwrap().field_type, FieldType::Reference { .. }));
+    assert!(matches!(orders.fields.iter().find(|f| f.name == "placed_at").unwrap().field_type, FieldType::DateTime));
+    assert_eq!(model.relationships.len(), 1);
+    assert_eq!(model.layouts.len(), 6);
+
+    // The imported model can be stored like any other
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let stored = services.model_service.store_new_model(model).await.unwrap();
+    assert_eq!(services.model_service.get_model(stored.id).await.unwrap().unwrap().entities.len(), 2);
+}
+
+/// Generated list, detail and form layouts are stored on the model and keep their IDs when regenerated
+#[tokio::test]
+async fn test_generate_entity_layouts() {
+    use torque::model::layouts::LayoutKind;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+
+    let model = services.model_service
+        .create_model_from_template("crm", Some("Shop".to_string()), None)
+        .await
+        .unwrap();
+
+    let layouts = services.model_service
+        .generate_layouts(model.id.clone(), "customer", &LayoutKind::ALL)
+        .await
+        .unwrap();
+    assert_eq!(layouts.len(), 3);
+    let detail = layouts.iter().find(|l| l.name == "customer_detail").unwrap();
+    assert!(detail.components.iter().any(|c| c.component_type == "DataGrid" && c.properties["entityType"] == "order"));
+
+    // The template's own customer_form and any earlier output are replaced in place
+    assert!(model.layouts.iter().any(|l| l.name == "customer_form" && l.id == layouts[2].id));
+    let regenerated = services.model_service
+        .generate_layouts(model.id.clone(), "customer", &[LayoutKind::List])
+        .await
+        .unwrap();
+    assert_eq!(regenerated[0].id, layouts[0].id);
+    let stored = services.model_service.get_model(model.id.clone()).await.unwrap().unwrap();
+    assert_eq!(stored.layouts.len(), model.layouts.len() + 2);
+
+    assert!(services.model_service.generate_layouts(model.id, "missing", &LayoutKind::ALL).await.is_err());
+}
+
+/// Concurrent edit operations merge or conflict, and are recorded with their author
+#[tokio::test]
+async fn test_collaborative_edit_operations() {
+    use torque::model::collab::EditOperation;
+    use torque::services::collaboration::EditOutcome;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+
+    let model = services.model_service
+        .create_model_from_template("crm", Some("Shop".to_string()), None)
+        .await
+        .unwrap();
+    let customer = model.entities.iter().find(|e| e.name == "customer").unwrap();
+    let field = &customer.fields[0];
+    let update = |changes: serde_json::Value| -> EditOperation {
+        serde_json::from_value(serde_json::json!({
+            "op": "updateField",
+            "entityId": customer.id,
+            "fieldId": field.id,
+            "changes": changes,
+        })).unwrap()
+    };
+
+    let collaboration = &services.collaboration;
+    let (revision, _) = collaboration.join(model.id.clone(), "alice", Some("Alice".to_string()), None).await.unwrap();
+    let (_, editors) = collaboration.join(model.id.clone(), "bob", None, None).await.unwrap();
+    assert_eq!(editors.len(), 2);
+
+    // Concurrent edits of different properties both apply, in arrival order
+    let first = collaboration.apply(&model.id, "alice", revision, update(serde_json::json!({ "required": true }))).await.unwrap();
+    assert!(matches!(first, EditOutcome::Applied { revision: 1 }));
+    let second = collaboration.apply(&model.id, "bob", revision, update(serde_json::json!({ "display_name": "Label" }))).await.unwrap();
+    assert!(matches!(second, EditOutcome::Applied { revision: 2 }));
+
+    // An edit of a property changed since the client's base revision is rejected
+    let conflict = collaboration.apply(&model.id, "bob", revision, update(serde_json::json!({ "required": false }))).await.unwrap();
+    match conflict {
+        EditOutcome::Conflict { revision, operations, resync } => {
+            assert_eq!(revision, 2);
+            assert_eq!(operations.len(), 2);
+            assert!(!resync);
+        }
+        other => panic!("expected a conflict, got {:?}", other),
+    }
+
+    let stored = services.model_service.get_model(model.id.clone()).await.unwrap().unwrap();
+    let stored_field = &stored.entities.iter().find(|e| e.name == "customer").unwrap().fields[0];
+    assert!(stored_field.required);
+    assert_eq!(stored_field.display_name, "Label");
+
+    // Each applied edit is a version snapshot credited to its editor
+    let versions = services.model_service.list_model_versions(model.id.clone()).await.unwrap();
+    let authors: Vec<&str> = versions.iter().take(2).map(|v| v.author.as_str()).collect();
+    assert_eq!(authors, vec!["bob", "alice"]);
+
+    // Editors must join before editing
+    assert!(collaboration.apply(&model.id, "carol", 2, update(serde_json::json!({ "required": false }))).await.is_err());
+    collaboration.leave_all("alice").await;
+    collaboration.leave_all("bob").await;
+}
+
+/// GraphQL subscribers receive changes of a model's app data
+#[tokio::test]
+async fn test_graphql_entity_data_subscription() {
+    use futures_util::StreamExt;
+    use torque::server::graphql::create_schema;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+
+    let schema = create_schema(&Config::default().graphql);
+    let query = format!(
+        r#"subscription {{ entityDataChanged(modelId: "{}", entityType: "category") {{ action recordId }} }}"#,
+        model.id
+    );
+    let mut stream = schema.execute_stream(async_graphql::Request::new(query).data(state));
+
+    // The subscription only starts listening once polled
+    let next = tokio::spawn(async move { stream.next().await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let record = services.app_database_service
+        .create_entity(
+            model.id.as_str(),
+            "category",
+            serde_json::json!({ "id": 1, "name": "Development", "color": "#3B82F6", "active": true }),
+        )
+        .await
+        .unwrap();
+
+    let response = timeout(Duration::from_secs(5), next).await.unwrap().unwrap().unwrap();
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["entityDataChanged"]["action"], "created");
+    assert_eq!(data["entityDataChanged"]["recordId"], record.id);
+}
+
+/// Flow execution updates published to the broadcast service reach GraphQL subscribers
+#[tokio::test]
+async fn test_graphql_flow_execution_subscription() {
+    use futures_util::StreamExt;
+    use torque::common::Uuid;
+    use torque::model::events::ModelChangeEvent;
+    use torque::server::graphql::create_schema;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let (model_id, flow_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let schema = create_schema(&Config::default().graphql);
+    let query = format!(
+        r#"subscription {{ flowExecutionUpdated(flowId: "{}") {{ modelId status }} }}"#,
+        flow_id
+    );
+    let mut stream = schema.execute_stream(async_graphql::Request::new(query).data(state));
+    let next = tokio::spawn(async move { stream.next().await });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Other flows are filtered out
+    let broadcast = &services.broadcast;
+    let other = ModelChangeEvent::flow_execution_updated(model_id.clone(), Uuid::new_v4(), Uuid::new_v4(), "running".to_string());
+    broadcast.broadcast_event(other).await.unwrap();
+    let event = ModelChangeEvent::flow_execution_updated(model_id.clone(), flow_id, Uuid::new_v4(), "completed".to_string());
+    broadcast.broadcast_event(event).await.unwrap();
+
+    let response = timeout(Duration::from_secs(5), next).await.unwrap().unwrap().unwrap();
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["flowExecutionUpdated"]["modelId"], model_id.to_string());
+    assert_eq!(data["flowExecutionUpdated"]["status"], "completed");
+}
+
+/// GraphQL subscribers that fall behind the broadcast channel are told to resync, then keep receiving events
+#[tokio::test]
+async fn test_graphql_subscription_resync_after_lag() {
+    use futures_util::StreamExt;
+    use torque::common::Uuid;
+    use torque::model::events::ModelChangeEvent;
+    use torque::server::graphql::create_schema;
+    use torque::services::broadcast::BROADCAST_CHANNEL_SIZE;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let (model_id, flow_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let schema = create_schema(&Config::default().graphql);
+    let query = format!(r#"subscription {{ flowExecutionUpdated(flowId: "{}") {{ status }} }}"#, flow_id);
+    let mut stream = schema.execute_stream(async_graphql::Request::new(query).data(state));
+    let broadcast = services.broadcast.clone();
+    let update = |flow_id: &Uuid, status: &str| {
+        ModelChangeEvent::flow_execution_updated(model_id.clone(), flow_id.clone(), Uuid::new_v4(), status.to_string())
+    };
+
+    let next = tokio::spawn(async move { (stream.next().await, stream) });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    broadcast.broadcast_event(update(&flow_id, "running")).await.unwrap();
+    let (response, mut stream) = timeout(Duration::from_secs(5), next).await.unwrap().unwrap();
+    assert!(response.unwrap().errors.is_empty());
+
+    // Overflow the channel while the subscriber isn't reading
+    let other_flow = Uuid::new_v4();
+    for _ in 0..=BROADCAST_CHANNEL_SIZE {
+        broadcast.broadcast_event(update(&other_flow, "running")).await.unwrap();
+    }
+    broadcast.broadcast_event(update(&flow_id, "completed")).await.unwrap();
+
+    let response = timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap();
+    assert_eq!(response.errors.len(), 1);
+    let extensions = serde_json::to_value(&response.errors[0].extensions).unwrap();
+    assert_eq!(extensions["code"], "RESYNC_REQUIRED");
+
+    let response = timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap();
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    assert_eq!(response.data.into_json().unwrap()["flowExecutionUpdated"]["status"], "completed");
+}
+
+/// Query, relate and edit app data through a model's generated GraphQL schema
+#[tokio::test]
+async fn test_model_graphql_schema() {
+    use torque::server::graphql::dynamic;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+    services.app_database_service
+        .load_template_sample_data(model.id.as_str(), "todo")
+        .await
+        .unwrap();
+
+    let execute = |query: &str| {
+        let (state, model) = (state.clone(), model.clone());
+        let request = async_graphql::Request::new(query);
+        async move {
+            let response = dynamic::execute(&state, &model, request).await.unwrap();
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            response.data.into_json().unwrap()
+        }
+    };
+
+    // Filtered, sorted and paginated list with batched relationship fields
+    let data = execute(r#"{
+        projectList(filter: { id: { in: [1, 2] } }, sort: { field: id, direction: DESC }, first: 1) {
+            totalCount
+            pageInfo { hasNextPage endCursor }
+            nodes { id name projectTasks { id projectTasksInverse { id } } }
+        }
+    }"#).await;
+    let list = &data["projectList"];
+    assert_eq!(list["totalCount"], 2);
+    assert_eq!(list["pageInfo"]["hasNextPage"], true);
+    assert_eq!(list["nodes"][0]["id"], 2);
+    let tasks = list["nodes"][0]["projectTasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0]["projectTasksInverse"][0]["id"], 2);
+
+    let data = execute(&format!(
+        r#"{{ projectList(after: "{}", sort: {{ field: id, direction: DESC }}, filter: {{ id: {{ in: [1, 2] }} }}) {{ nodes {{ id }} pageInfo {{ hasNextPage hasPreviousPage }} }} }}"#,
+        list["pageInfo"]["endCursor"].as_str().unwrap()
+    )).await;
+    assert_eq!(data["projectList"]["nodes"][0]["id"], 1);
+    assert_eq!(data["projectList"]["pageInfo"]["hasNextPage"], false);
+    assert_eq!(data["projectList"]["pageInfo"]["hasPreviousPage"], true);
+
+    // References stored as strings relate to integer keys too
+    let task = serde_json::json!({
+        "id": 99, "project_id": "2", "title": "Stored by an API client", "status": "Todo", "priority": "Low",
+        "created_at": "2024-02-01T09:00:00Z", "updated_at": "2024-02-01T09:00:00Z"
+    });
+    services.app_database_service.create_entity(model.id.as_str(), "task", task).await.unwrap();
+    let data = execute(r#"{ projectList(filter: { id: { eq: 2 } }) { nodes { projectTasks { id } } } }"#).await;
+    let ids: Vec<i64> = data["projectList"]["nodes"][0]["projectTasks"].as_array().unwrap().iter()
+        .map(|t| t["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 3, "{:?}", ids);
+    assert!(ids.contains(&99));
+
+    // Enum fields with valid GraphQL names get enum types
+    let data = execute(r#"{ taskList(filter: { status: { eq: "Todo" } }) { nodes { status priority projectId: project_id } } }"#).await;
+    for task in data["taskList"]["nodes"].as_array().unwrap() {
+        assert_eq!(task["status"], "Todo");
+        assert!(task["projectId"].is_string());
+    }
+
+    // Create, update and delete
+    let data = execute(r##"mutation { createCategory(data: { id: 7, name: "Ops", color: "#000000", active: true }) { _id name } }"##).await;
+    let id = data["createCategory"]["_id"].as_str().unwrap().to_string();
+    let data = execute(&format!(r#"mutation {{ updateCategory(id: "{}", data: {{ name: "Operations" }}) {{ name color }} }}"#, id)).await;
+    assert_eq!(data["updateCategory"]["name"], "Operations");
+    assert_eq!(data["updateCategory"]["color"], "#000000");
+    let data = execute(&format!(r#"mutation {{ deleteCategory(id: "{}") }}"#, id)).await;
+    assert_eq!(data["deleteCategory"], true);
+    let data = execute(&format!(r#"{{ category(id: "{}") {{ name }} }}"#, id)).await;
+    assert!(data["category"].is_null());
+
+    // The schema follows changes to the model
+    let task = model.entities.iter().find(|e| e.name == "task").unwrap();
+    let before = dynamic::model_schema(&model, &services.config.graphql).unwrap().sdl();
+    assert!(before.contains("type Task"));
+    let mut changed = model.clone();
+    changed.entities.retain(|e| e.id != task.id);
+    let after = dynamic::model_schema(&changed, &services.config.graphql).unwrap().sdl();
+    assert!(!after.contains("type Task"));
+    assert!(!after.contains("projectTasks"));
+
+    // Entities whose type names would clash or aren't valid names are renamed, not dropped
+    for name in ["Task", "2fa codes"] {
+        let mut entity = task.clone();
+        entity.id = uuid::Uuid::new_v4().into();
+        entity.name = name.to_string();
+        changed.entities.push(entity);
+    }
+    changed.entities.push(task.clone());
+    let renamed = dynamic::model_schema(&changed, &services.config.graphql).unwrap().sdl();
+    for expected in ["type Task {", "type Entity2faCodes {", "type Task2 {", "task2(id: ID!): Task2", "entity2faCodesList("] {
+        assert!(renamed.contains(expected), "missing {}", expected);
+    }
+
+    // Enum types are numbered when they'd clash with an entity's types or another entity
+    let mut clashing = model.clone();
+    let task = clashing.entities.iter_mut().find(|e| e.name == "task").unwrap();
+    let template = task.fields[0].clone();
+    let mut task_state = task.clone();
+    for name in ["state", "sort"] {
+        let mut field = template.clone();
+        field.id = uuid::Uuid::new_v4().into();
+        field.name = name.to_string();
+        field.field_type = torque::model::types::FieldType::Enum { values: vec!["Open".to_string(), "Done".to_string()] };
+        task.fields.push(field);
+    }
+    task_state.id = uuid::Uuid::new_v4().into();
+    task_state.name = "task_state".to_string();
+    clashing.entities.push(task_state);
+    let sdl = dynamic::model_schema(&clashing, &services.config.graphql).unwrap().sdl();
+    for expected in ["type TaskState {", "enum TaskState2 {", "state: TaskState2", "input TaskSort {", "enum TaskSort2 {", "sort: TaskSort2"] {
+        assert!(sdl.contains(expected), "missing {}", expected);
+    }
+
+    // Deleting the model drops its cached schema
+    dynamic::spawn_schema_eviction(services.broadcast.subscribe());
+    let mut receiver = services.broadcast.subscribe();
+    services.model_service.delete_model(model.id.clone()).await.unwrap();
+    timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!dynamic::forget_model_schema(&model.id));
+}
+
+/// The shared GraphQL schema applies the limits, introspection toggle and persisted queries from config
+#[tokio::test]
+async fn test_graphql_limits_and_persisted_queries() {
+    use sha2::{Digest, Sha256};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    config.graphql.max_query_depth = 2;
+    config.graphql.enable_introspection = false;
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+    let schema = state.graphql_schema.clone();
+    let execute = |request: async_graphql::Request| schema.execute(request.data(state.clone()));
+
+    let response = execute(async_graphql::Request::new("{ models { name } }")).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let response = execute(async_graphql::Request::new("{ models { entities { fields { name } } } }")).await;
+    assert!(response.errors.iter().any(|e| e.message.contains("too deep")), "{:?}", response.errors);
+    let response = execute(async_graphql::Request::new("{ __schema { queryType { name } } }")).await;
+    assert!(!response.errors.is_empty());
+
+    // Automatic Persisted Queries: the hash alone works once the query has been sent with it
+    let query = "{ models { id } }";
+    let hash = format!("{:x}", Sha256::digest(query.as_bytes()));
+    let persisted = |query: Option<&str>| -> async_graphql::Request {
+        serde_json::from_value(serde_json::json!({
+            "query": query.unwrap_or_default(),
+            "extensions": { "persistedQuery": { "version": 1, "sha256Hash": hash } },
+        }))
+        .unwrap()
+    };
+    let response = execute(persisted(None)).await;
+    assert!(response.errors.iter().any(|e| e.message == "PersistedQueryNotFound"), "{:?}", response.errors);
+    let response = execute(persisted(Some(query))).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let response = execute(persisted(None)).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+}
+
+/// Field types, validation rules and flow triggers written through GraphQL read back unchanged
+#[tokio::test]
+async fn test_graphql_structured_definitions_round_trip() {
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+    let schema = state.graphql_schema.clone();
+    let execute = |query: String| {
+        let request = async_graphql::Request::new(query).data(state.clone());
+        let schema = schema.clone();
+        async move {
+            let response = schema.execute(request).await;
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            response.data.into_json().unwrap()
+        }
+    };
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+    let project = model.entities.iter().find(|e| e.name == "project").unwrap();
+
+    let data = execute(format!(
+        r#"mutation {{ createEntity(input: {{
+            modelId: "{model}", name: "ticket", displayName: "Ticket", entityType: "Lookup",
+            fields: [
+                {{ name: "status", displayName: "Status", required: true,
+                   fieldTypeSpec: {{ kind: ENUM, values: ["open", "in progress, blocked"] }},
+                   validationRules: [{{ kind: REQUIRED, message: "Status is required", severity: ERROR }}] }},
+                {{ name: "projects", displayName: "Projects", required: false,
+                   fieldTypeSpec: {{ kind: ARRAY, elementType: {{ kind: REFERENCE, entityId: "{project}" }} }} }},
+                {{ name: "title", displayName: "Title", required: true, fieldType: "String(80)",
+                   validationRules: [
+                       {{ kind: MIN_LENGTH, length: 3, message: "Too short", severity: WARNING }},
+                       {{ kind: RANGE, max: 10, message: "Out of range", severity: INFO }}
+                   ] }}
+            ] }}) {{
+            entityType
+            fields {{ name fieldType fieldTypeSpec {{ kind values elementType {{ kind entityId }} }}
+                      validationRules {{ kind length min max message severity }} }}
+        }} }}"#,
+        model = model.id,
+        project = project.id,
+    )).await;
+    let entity = &data["createEntity"];
+    assert_eq!(entity["entityType"], "Lookup");
+    let fields = entity["fields"].as_array().unwrap();
+    assert_eq!(fields[0]["fieldType"], r#"Enum["open","in progress, blocked"]"#);
+    assert_eq!(fields[0]["fieldTypeSpec"]["values"], serde_json::json!(["open", "in progress, blocked"]));
+    assert_eq!(fields[0]["validationRules"][0]["kind"], "REQUIRED");
+    assert_eq!(fields[1]["fieldType"], format!("Array<Reference({})>", project.id));
+    assert_eq!(fields[1]["fieldTypeSpec"]["elementType"]["entityId"], project.id.to_string());
+    assert_eq!(fields[2]["fieldType"], "String(80)");
+    assert_eq!(fields[2]["validationRules"][0]["length"], 3);
+    assert_eq!(fields[2]["validationRules"][0]["severity"], "WARNING");
+    assert!(fields[2]["validationRules"][1]["min"].is_null());
+    assert_eq!(fields[2]["validationRules"][1]["max"], 10);
+
+    // Notation and structured input are exclusive, and properties must match the kind
+    let response = schema.execute(async_graphql::Request::new(format!(
+        r#"mutation {{ createEntity(input: {{ modelId: "{}", name: "bad", displayName: "Bad", fields: [
+            {{ name: "x", displayName: "X", required: false, fieldType: "Integer", fieldTypeSpec: {{ kind: INTEGER }} }}
+        ] }}) {{ id }} }}"#,
+        model.id
+    )).data(state.clone())).await;
+    assert!(response.errors.iter().any(|e| e.message.contains("not both")), "{:?}", response.errors);
+    let response = schema.execute(async_graphql::Request::new(format!(
+        r#"mutation {{ createEntity(input: {{ modelId: "{}", name: "bad", displayName: "Bad", fields: [
+            {{ name: "x", displayName: "X", required: false, fieldTypeSpec: {{ kind: BOOLEAN, maxLength: 5 }} }}
+        ] }}) {{ id }} }}"#,
+        model.id
+    )).data(state.clone())).await;
+    assert!(response.errors.iter().any(|e| e.message.contains("maxLength")), "{:?}", response.errors);
+
+    let data = execute(format!(
+        r#"mutation {{ createFlow(input: {{
+            modelId: "{model}", name: "Notify owner", flowType: "Automation",
+            triggerSpec: {{ kind: ENTITY_EVENT, entityId: "{project}", event: AFTER_CREATE }},
+            steps: [{{ name: "Ping", stepType: "Custom", customType: "slack", configuration: {{}} }}]
+        }}) {{ id trigger triggerSpec {{ kind entityId event }} steps {{ id stepType customType }} }} }}"#,
+        model = model.id,
+        project = project.id,
+    )).await;
+    let flow = &data["createFlow"];
+    assert_eq!(flow["triggerSpec"]["kind"], "ENTITY_EVENT");
+    assert_eq!(flow["triggerSpec"]["entityId"], project.id.to_string());
+    assert_eq!(flow["triggerSpec"]["event"], "AFTER_CREATE");
+    assert_eq!(flow["trigger"]["EntityEvent"]["event"], "AfterCreate");
+    assert_eq!(flow["steps"][0]["stepType"], "Custom");
+    assert_eq!(flow["steps"][0]["customType"], "slack");
+    let flow_id = flow["id"].as_str().unwrap().to_string();
+    let step_id = flow["steps"][0]["id"].as_str().unwrap().to_string();
+
+    // Replacing steps keeps the IDs of steps that are still there
+    let data = execute(format!(
+        r#"mutation {{ updateFlow(id: "{flow}", input: {{ steps: [
+            {{ id: "{step}", name: "Ping owner", stepType: "Custom", customType: "slack", configuration: {{}} }},
+            {{ name: "Record", stepType: "Custom", customType: "audit", configuration: {{}} }}
+        ] }}) {{ steps {{ id name }} }} }}"#,
+        flow = flow_id,
+        step = step_id,
+    )).await;
+    let steps = data["updateFlow"]["steps"].as_array().unwrap();
+    assert_eq!(steps[0]["id"], step_id.as_str());
+    assert_eq!(steps[0]["name"], "Ping owner");
+    let record_id = steps[1]["id"].as_str().unwrap().to_string();
+    assert_ne!(record_id, step_id);
+
+    let data = execute(format!(
+        r#"mutation {{ updateFlow(id: "{}", input: {{ name: "Notify", steps: [
+            {{ name: "Record", stepType: "Custom", customType: "audit", configuration: {{}} }}
+        ] }}) {{ name steps {{ id }} }} }}"#,
+        flow_id,
+    )).await;
+    assert_eq!(data["updateFlow"]["name"], "Notify");
+    assert_eq!(data["updateFlow"]["steps"][0]["id"], record_id.as_str());
+
+    let data = execute(format!(r#"mutation {{ deleteFlow(id: "{}") }}"#, flow_id)).await;
+    assert_eq!(data["deleteFlow"], true);
+    let data = execute(format!(r#"{{ flows(modelId: "{}") {{ id }} }}"#, model.id)).await;
+    assert!(data["flows"].as_array().unwrap().iter().all(|f| f["id"] != flow_id.as_str()));
+
+    // Fields left out of a relationship update keep their values
+    let relationship = &model.relationships[0];
+    execute(format!(
+        r#"mutation {{ updateRelationship(id: "{}", input: {{ uiConfig: {{
+            display_in_form: true, display_in_list: false, component_type: "cards", custom_props: {{}}
+        }} }}) {{ id }} }}"#,
+        relationship.id,
+    )).await;
+    let data = execute(format!(
+        r#"mutation {{ updateRelationship(id: "{}", input: {{ name: "project_tickets" }}) {{ name fromEntity toEntity toField uiConfig }} }}"#,
+        relationship.id,
+    )).await;
+    let updated = &data["updateRelationship"];
+    assert_eq!(updated["name"], "project_tickets");
+    assert_eq!(updated["fromEntity"], relationship.from_entity.to_string());
+    assert_eq!(updated["toEntity"], relationship.to_entity.to_string());
+    assert_eq!(updated["toField"], relationship.to_field.as_str());
+    assert_eq!(updated["uiConfig"]["component_type"], "cards");
+}
+
+/// Events missed by a reconnecting client are replayed from the event log, or a resync
+/// is requested when the log can't cover the gap
+#[tokio::test]
+async fn test_event_log_replay() {
+    use torque::common::{Uuid, UtcDateTime};
+    use torque::model::events::ModelChangeEvent;
+    use torque::services::broadcast::Replay;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    config.events.max_replay = 3;
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let broadcast = &services.broadcast;
+
+    let start = broadcast.latest_seq().await.unwrap();
+    for _ in 0..4 {
+        let event = ModelChangeEvent::ModelDeleted { model_id: Uuid::new_v4(), timestamp: UtcDateTime::now() };
+        broadcast.broadcast_event(event).await.unwrap();
+    }
+    let latest = broadcast.latest_seq().await.unwrap();
+    assert_eq!(latest, start + 4);
+
+    // Missed events are replayed in sequence order
+    match broadcast.replay_since(start + 2).await.unwrap() {
+        Replay::Events(events) => {
+            let seqs: Vec<_> = events.iter().map(|message| message.seq).collect();
+            assert_eq!(seqs, vec![Some(start + 3), Some(start + 4)]);
+        }
+        other => panic!("expected events, got {:?}", other),
+    }
+    assert!(matches!(broadcast.replay_since(latest).await.unwrap(), Replay::Events(events) if events.is_empty()));
+
+    // Gaps larger than max_replay and positions ahead of the log require a resync
+    assert!(matches!(broadcast.replay_since(start).await.unwrap(), Replay::ResyncRequired { latest_seq } if latest_seq == latest));
+    assert!(matches!(broadcast.replay_since(latest + 5).await.unwrap(), Replay::ResyncRequired { .. }));
+}
+
+/// App data changes are broadcast with the record, or a diff once the record is too large
+#[tokio::test]
+async fn test_app_data_change_events() {
+    use torque::model::events::{DataChangeAction, ModelChangeEvent};
+    use torque::services::broadcast::EventFilter;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    config.events.max_record_payload_bytes = 200;
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+    let model_id = model.id.to_string();
+    let mut receiver = services.broadcast.subscribe();
+    async fn next_data_change(
+        receiver: &mut tokio::sync::broadcast::Receiver<torque::model::events::ModelEventMessage>,
+    ) -> ModelChangeEvent {
+        loop {
+            let message = timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+            if matches!(message.event, ModelChangeEvent::EntityDataChanged { .. } | ModelChangeEvent::EntityDataBulkChanged { .. }) {
+                return message.event;
+            }
+        }
+    }
+
+    // Records created over JSON-RPC are announced, and small ones are sent whole
+    let response = torque::jsonrpc::handle_request(&state, &serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "createEntity",
+        "params": {
+            "modelId": model_id,
+            "entityName": "category",
+            "data": { "id": 2, "name": "QA", "color": "#F00", "active": true }
+        }
+    })).await;
+    let small_id = response["result"]["id"].as_str().unwrap().to_string();
+    match next_data_change(&mut receiver).await {
+        ModelChangeEvent::EntityDataChanged { action, record_id, record, diff, .. } => {
+            assert_eq!(action, DataChangeAction::Created);
+            assert_eq!(record_id, small_id);
+            let record = record.unwrap();
+            assert_eq!(record["name"], "QA");
+            assert_eq!(record["_id"], small_id.as_str());
+            assert!(diff.is_none());
+        }
+        _ => unreachable!(),
+    }
+
+    let long_name = "Development ".repeat(4);
+    let long = serde_json::json!({ "id": 1, "name": long_name, "color": "#3B82F6", "active": true });
+    let created = services.app_database_service.create_entity(&model_id, "category", long).await.unwrap();
+    match next_data_change(&mut receiver).await {
+        ModelChangeEvent::EntityDataChanged { action, record_id, record, diff, .. } => {
+            assert_eq!(action, DataChangeAction::Created);
+            assert_eq!(record_id, created.id);
+            // Too large to send whole, and a new record has nothing to diff against
+            assert!(record.is_none() && diff.is_none());
+        }
+        _ => unreachable!(),
+    }
+
+    let renamed_name = "Engineering ".repeat(4);
+    let renamed = serde_json::json!({ "id": 1, "name": renamed_name, "color": "#3B82F6", "active": true });
+    services.app_database_service.update_entity(&model_id, &created.id, renamed).await.unwrap();
+    let event = next_data_change(&mut receiver).await;
+    match &event {
+        ModelChangeEvent::EntityDataChanged { action, record, diff, .. } => {
+            assert_eq!(*action, DataChangeAction::Updated);
+            assert!(record.is_none());
+            let diff = diff.as_ref().unwrap();
+            assert_eq!(diff.set.get("name"), Some(&serde_json::json!(renamed_name)));
+            assert_eq!(diff.set.len(), 1);
+            assert!(diff.removed.is_empty());
+        }
+        _ => unreachable!(),
+    }
+
+    // Removing all of a model's data announces the removed records in one event per entity type
+    services.app_database_service.empty_app_database(&model_id).await.unwrap();
+    match next_data_change(&mut receiver).await {
+        ModelChangeEvent::EntityDataBulkChanged { action, entity_type, count, record_ids, .. } => {
+            assert_eq!(action, DataChangeAction::Deleted);
+            assert_eq!(entity_type, "category");
+            assert_eq!(count, 2);
+            let mut removed = record_ids.unwrap();
+            removed.sort();
+            let mut expected = vec![small_id, created.id.clone()];
+            expected.sort();
+            assert_eq!(removed, expected);
+        }
+        _ => unreachable!(),
+    }
+
+    // Entity type filters only narrow app data events
+    let tasks_only = EventFilter {
+        model_id: Some(model.id.clone()),
+        entity_types: Some(vec!["task".to_string()]),
+        ..Default::default()
+    };
+    assert!(!tasks_only.matches(&event));
+    assert!(tasks_only.matches(&ModelChangeEvent::model_deleted(model.id.clone())));
+    let categories = EventFilter { entity_types: Some(vec!["category".to_string()]), ..Default::default() };
+    assert!(categories.matches(&event));
+}
+
+/// Removing more records than the broadcast channel holds sends one event, so
+/// subscribers don't lag behind
+#[tokio::test]
+async fn test_bulk_removal_does_not_flood_subscribers() {
+    use sea_orm::{EntityTrait, Set};
+    use torque::database::entities::app_entities;
+    use torque::model::events::{DataChangeAction, ModelChangeEvent};
+    use torque::services::broadcast::BROADCAST_CHANNEL_SIZE;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let model = services.model_service.create_model_from_template("todo", None, None).await.unwrap();
+    let model_id = model.id.to_string();
+
+    let total = BROADCAST_CHANNEL_SIZE + 200;
+    let now = chrono::Utc::now().naive_utc();
+    let rows: Vec<_> = (0..total)
+        .map(|i| app_entities::ActiveModel {
+            id: Set(uuid::Uuid::new_v4().to_string()),
+            model_id: Set(model_id.clone()),
+            entity_type: Set("category".to_string()),
+            data: Set(serde_json::json!({ "id": i, "name": format!("Category {}", i) })),
+            created_at: Set(now),
+            updated_at: Set(now),
+        })
+        .collect();
+    for chunk in rows.chunks(100) {
+        app_entities::Entity::insert_many(chunk.to_vec()).exec(services.db.as_ref()).await.unwrap();
+    }
+
+    let mut receiver = services.broadcast.subscribe();
+    services.app_database_service.empty_app_database(&model_id).await.unwrap();
+
+    match receiver.try_recv().unwrap().event {
+        ModelChangeEvent::EntityDataBulkChanged { action, entity_type, count, record_ids, .. } => {
+            assert_eq!(action, DataChangeAction::Deleted);
+            assert_eq!(entity_type, "category");
+            assert_eq!(count, total as u64);
+            // Too many IDs for one event; subscribers reload instead
+            assert!(record_ids.is_none());
+        }
+        other => panic!("expected a bulk change, got {:?}", other),
+    }
+    assert!(matches!(receiver.try_recv(), Err(tokio::sync::broadcast::error::TryRecvError::Empty)));
+}
+
+/// JSON-RPC requests over the event WebSocket, including runtime filter changes
+#[tokio::test]
+async fn test_websocket_jsonrpc_channel() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = server::create_router(services.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws?client_id=rpc-test", addr)).await.unwrap();
+    async fn next_message<S>(socket: &mut S, predicate: fn(&serde_json::Value) -> bool) -> serde_json::Value
+    where
+        S: futures_util::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+    {
+        loop {
+            let message = timeout(Duration::from_secs(5), socket.next()).await.unwrap().unwrap().unwrap();
+            if let Message::Text(text) = message {
+                let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if predicate(&value) {
+                    return value;
+                }
+            }
+        }
+    }
+    let connected = next_message(&mut socket, |m| m["type"] == "connected").await;
+    assert_eq!(connected["clientId"], "rpc-test");
+
+    let request = |id: u64, method: &str, params: serde_json::Value| {
+        Message::Text(serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string())
+    };
+
+    socket.send(request(1, "ping", serde_json::json!({}))).await.unwrap();
+    let response = next_message(&mut socket, |m| m["id"] == 1).await;
+    assert_eq!(response["result"]["status"], "ok");
+
+    socket.send(request(2, "setEventFilter", serde_json::json!({ "eventKinds": ["Bogus"] }))).await.unwrap();
+    let response = next_message(&mut socket, |m| m["id"] == 2).await;
+    assert_eq!(response["error"]["code"], -32602);
+
+    // Unknown kinds in the connection's query are rejected the same way
+    match tokio_tungstenite::connect_async(format!("ws://{}/ws?event_kinds=ModelCreated,Bogus", addr)).await {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => assert_eq!(response.status(), 400),
+        other => panic!("expected the upgrade to be refused, got {:?}", other.map(|_| ())),
+    }
+
+    // More requests than may run at once are all answered
+    for id in 100..140 {
+        socket.send(request(id, "ping", serde_json::json!({}))).await.unwrap();
+    }
+    let mut answered = std::collections::HashSet::new();
+    while answered.len() < 40 {
+        let response = next_message(&mut socket, |m| m["id"].as_u64().is_some_and(|id| id >= 100)).await;
+        answered.insert(response["id"].as_u64().unwrap());
+    }
+
+    let filter = serde_json::json!({ "modelId": model.id, "entityTypes": ["category"], "eventKinds": ["EntityDataChanged"] });
+    socket.send(request(3, "setEventFilter", filter)).await.unwrap();
+    let response = next_message(&mut socket, |m| m["id"] == 3).await;
+    assert_eq!(response["result"]["entityTypes"], serde_json::json!(["category"]));
+
+    socket.send(request(4, "getEventFilter", serde_json::json!({}))).await.unwrap();
+    let response = next_message(&mut socket, |m| m["id"] == 4).await;
+    assert_eq!(response["result"]["eventKinds"], serde_json::json!(["EntityDataChanged"]));
+
+    // Only events passing the new filter are delivered
+    let data = serde_json::json!({ "id": 1, "name": "Development", "color": "#3B82F6", "active": true });
+    services.app_database_service.create_entity(model.id.as_str(), "category", data).await.unwrap();
+    let event = next_message(&mut socket, |m| m.get("event").is_some()).await;
+    assert_eq!(event["event"]["type"], "EntityDataChanged");
+    assert_eq!(event["event"]["data"]["entity_type"], "category");
+}
+
+/// Events from other nodes are read back from the shared event log and delivered locally
+#[tokio::test]
+async fn test_remote_event_delivery() {
+    use torque::common::{Uuid, UtcDateTime};
+    use torque::model::events::ModelChangeEvent;
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = ServiceRegistry::new(db, config).await.unwrap();
+    let broadcast = &services.broadcast;
+
+    let model_id = Uuid::new_v4();
+    let event = ModelChangeEvent::ModelDeleted { model_id: model_id.clone(), timestamp: UtcDateTime::now() };
+    broadcast.broadcast_event(event).await.unwrap();
+    let seq = broadcast.latest_seq().await.unwrap();
+
+    let logged = broadcast.logged_event(seq).await.unwrap().unwrap();
+    assert_eq!(logged.seq, Some(seq));
+    assert_eq!(logged.event.model_id(), model_id);
+    assert!(broadcast.logged_event(seq + 1).await.unwrap().is_none());
+
+    let mut receiver = broadcast.subscribe();
+    broadcast.deliver_remote(logged);
+    let delivered = timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+    assert_eq!(delivered.seq, Some(seq));
+    // Remote events are not logged a second time
+    assert_eq!(broadcast.latest_seq().await.unwrap(), seq);
+}
+
+/// App data CRUD and model editing through MCP tools, with typed per-model tools
+#[tokio::test]
+async fn test_mcp_app_data_tools() {
+    use axum::extract::{Path, State};
+    use axum::Json;
+    use torque::server::mcp::{mcp_handler, model_mcp_handler};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+
+    async fn call(state: &server::AppState, model_id: Option<String>, method: &str, params: serde_json::Value) -> serde_json::Value {
+        let request = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let headers = axum::http::HeaderMap::new();
+        let response = match model_id {
+            Some(model_id) => model_mcp_handler(Path(model_id), State(state.clone()), headers, Json(request)).await,
+            None => mcp_handler(State(state.clone()), headers, Json(request)).await,
+        };
+        let body = axum::body::to_bytes(response.unwrap().into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+    fn tool_output(response: &serde_json::Value) -> serde_json::Value {
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        serde_json::from_str(text).unwrap_or_else(|_| serde_json::json!(text))
+    }
+
+    // Typed tools take their argument schema from the entity definition
+    let scoped = Some(model.id.to_string());
+    let list = call(&state, scoped.clone(), "tools/list", serde_json::json!({})).await;
+    let tools = list["result"]["tools"].as_array().unwrap();
+    let create = tools.iter().find(|t| t["name"] == "create_category").unwrap();
+    let data_schema = &create["inputSchema"]["properties"]["data"];
+    assert_eq!(data_schema["type"], "object");
+    assert!(data_schema["properties"]["name"].is_object());
+    assert!(create["inputSchema"]["properties"].get("modelId").is_none());
+    let update = tools.iter().find(|t| t["name"] == "update_category").unwrap();
+    assert!(update["inputSchema"]["properties"]["data"].get("required").is_none());
+    assert!(tools.iter().any(|t| t["name"] == "torque_add_field"));
+
+    let arguments = serde_json::json!({ "data": { "id": 1, "name": "Development", "color": "#3B82F6", "active": true } });
+    let response = call(&state, scoped.clone(), "tools/call", serde_json::json!({ "name": "create_category", "arguments": arguments })).await;
+    assert_eq!(response["result"]["isError"], false, "{}", response);
+    let record_id = tool_output(&response)["_id"].as_str().unwrap().to_string();
+
+    // Invalid data is reported as a tool error the agent can act on
+    let arguments = serde_json::json!({ "data": { "name": 42 } });
+    let response = call(&state, scoped.clone(), "tools/call", serde_json::json!({ "name": "create_category", "arguments": arguments })).await;
+    assert_eq!(response["result"]["isError"], true);
+
+    let arguments = serde_json::json!({ "id": record_id, "data": { "name": "Research" } });
+    let response = call(&state, scoped.clone(), "tools/call", serde_json::json!({ "name": "update_category", "arguments": arguments })).await;
+    let updated = tool_output(&response);
+    assert_eq!(updated["name"], "Research");
+    assert_eq!(updated["color"], "#3B82F6");
+
+    let arguments = serde_json::json!({
+        "modelId": model.id,
+        "entityType": "category",
+        "filters": [{ "field": "name", "op": "contains", "value": "search" }]
+    });
+    let response = call(&state, None, "tools/call", serde_json::json!({ "name": "torque_query_records", "arguments": arguments })).await;
+    let result = tool_output(&response);
+    assert_eq!(result["total"], 1);
+    assert_eq!(result["data"][0]["_id"], record_id.as_str());
+
+    // Model edits show up in the typed tools
+    let arguments = serde_json::json!({ "modelId": model.id, "entity": "category", "name": "priority", "fieldType": "Integer(1..5)" });
+    let response = call(&state, None, "tools/call", serde_json::json!({ "name": "torque_add_field", "arguments": arguments })).await;
+    assert_eq!(response["result"]["isError"], false, "{}", response);
+    let list = call(&state, scoped.clone(), "tools/list", serde_json::json!({})).await;
+    let create = list["result"]["tools"].as_array().unwrap().iter().find(|t| t["name"] == "create_category").unwrap().clone();
+    assert_eq!(create["inputSchema"]["properties"]["data"]["properties"]["priority"]["maximum"], 5);
+
+    // With a pinned version, record tools follow the pin while editing tools list the latest entities
+    let version = services.model_service.latest_version_number(model.id.clone()).await.unwrap().unwrap();
+    services.model_service.pin_model_version(model.id.clone(), version, None).await.unwrap();
+    let arguments = serde_json::json!({ "modelId": model.id, "name": "label", "fields": [{ "name": "title", "fieldType": "String" }] });
+    let response = call(&state, None, "tools/call", serde_json::json!({ "name": "torque_add_entity", "arguments": arguments })).await;
+    assert_eq!(response["result"]["isError"], false, "{}", response);
+    let list = call(&state, scoped.clone(), "tools/list", serde_json::json!({})).await;
+    let tools = list["result"]["tools"].as_array().unwrap();
+    assert!(!tools.iter().any(|t| t["name"] == "create_label"));
+    let add_field = tools.iter().find(|t| t["name"] == "torque_add_field").unwrap();
+    let entity_names = add_field["inputSchema"]["properties"]["entity"]["enum"].as_array().unwrap();
+    assert!(entity_names.iter().any(|name| name == "label"), "{:?}", entity_names);
+    services.model_service.unpin_model_version(model.id.clone()).await.unwrap();
+
+    let arguments = serde_json::json!({ "id": record_id });
+    let response = call(&state, scoped, "tools/call", serde_json::json!({ "name": "delete_category", "arguments": arguments })).await;
+    assert_eq!(tool_output(&response)["deleted"], true);
+}
+
+/// MCP resources with change notifications, and prompts built from model state
+#[tokio::test]
+async fn test_mcp_resources_and_prompts() {
+    use axum::extract::State;
+    use axum::http::HeaderMap;
+    use axum::Json;
+    use torque::server::mcp::{mcp_handler, resource_notifications};
+
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    let db = database::setup_database(&config).await.unwrap();
+    let services = Arc::new(ServiceRegistry::new(db, config).await.unwrap());
+    let state = server::AppState::new(services.clone());
+
+    let model = services.model_service
+        .create_model_from_template("todo", None, None)
+        .await
+        .unwrap();
+
+    async fn call(state: &server::AppState, session: Option<&str>, method: &str, params: serde_json::Value) -> (HeaderMap, serde_json::Value) {
+        let request = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut headers = HeaderMap::new();
+        if let Some(session) = session {
+            headers.insert("mcp-session-id", session.parse().unwrap());
+        }
+        let response = mcp_handler(State(state.clone()), headers, Json(request)).await.unwrap();
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (headers, serde_json::from_slice(&body).unwrap())
+    }
+
+    let (headers, response) = call(&state, None, "initialize", serde_json::json!({})).await;
+    let capabilities = &response["result"]["capabilities"];
+    assert_eq!(capabilities["resources"]["subscribe"], true);
+    assert!(capabilities["prompts"].is_object());
+    let session = headers["mcp-session-id"].to_str().unwrap().to_string();
+
+    let records_uri = format!("torque://models/{}/entities/category/records", model.id);
+    let schema_uri = format!("torque://models/{}/entities/category/schema", model.id);
+    let (_, response) = call(&state, None, "resources/list", serde_json::json!({})).await;
+    let uris: Vec<&str> = response["result"]["resources"].as_array().unwrap().iter()
+        .map(|r| r["uri"].as_str().unwrap())
+        .collect();
+    assert!(uris.contains(&"torque://models"));
+    assert!(uris.contains(&records_uri.as_str()));
+
+    let (_, response) = call(&state, None, "resources/read", serde_json::json!({ "uri": schema_uri })).await;
+    let content = &response["result"]["contents"][0];
+    assert_eq!(content["mimeType"], "application/schema+json");
+    let schema: serde_json::Value = serde_json::from_str(content["text"].as_str().unwrap()).unwrap();
+    assert!(schema["properties"]["name"].is_object());
+
+    let (_, response) = call(&state, None, "resources/read", serde_json::json!({ "uri": "torque://models/nope" })).await;
+    assert_eq!(response["error"]["code"], -32002);
+
+    // Subscriptions need the session from initialize
+    let (_, response) = call(&state, None, "resources/subscribe", serde_json::json!({ "uri": records_uri })).await;
+    assert!(response.get("error").is_some());
+    let (_, response) = call(&state, Some(&session), "resources/subscribe", serde_json::json!({ "uri": records_uri })).await;
+    assert!(response.get("result").is_some(), "{}", response);
+
+    let mut receiver = services.broadcast.subscribe();
+    let data = serde_json::json!({ "id": 1, "name": "Development", "color": "#3B82F6", "active": true });
+    services.app_database_service.create_entity(model.id.as_str(), "category", data).await.unwrap();
+    let message = timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+    let notifications = resource_notifications(&session, &message.event);
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["method"], "notifications/resources/updated");
+    assert_eq!(notifications[0]["params"]["uri"], records_uri.as_str());
+
+    let (_, response) = call(&state, None, "resources/read", serde_json::json!({ "uri": records_uri })).await;
+    let records: serde_json::Value = serde_json::from_str(response["result"]["contents"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(records["total"], 1);
+
+    let (_, response) = call(&state, Some(&session), "resources/unsubscribe", serde_json::json!({ "uri": records_uri })).await;
+    assert!(response.get("result").is_some());
+    assert!(resource_notifications(&session, &message.event).is_empty());
+
+    // Renaming a model changes the resource list
+    services.model_service.update_model(model.id.clone(), torque::services::model::UpdateModelInput {
+        name: Some("Renamed".to_string()),
+        description: None,
+        config: None,
+        author: None,
+        message: None,
+    }).await.unwrap();
+    let message = timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+    let notifications = resource_notifications(&session, &message.event);
+    assert_eq!(notifications.last().unwrap()["method"], "notifications/resources/list_changed");
+
+    let (_, response) = call(&state, None, "prompts/list", serde_json::json!({})).await;
+    let names: Vec<&str> = response["result"]["prompts"].as_array().unwrap().iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["design_entity", "fix_verification_errors"]);
+
+    let arguments = serde_json::json!({ "modelId": model.id, "requirements": "tasks with a due date" });
+    let (_, response) = call(&state, None, "prompts/get", serde_json::json!({ "name": "design_entity", "arguments": arguments })).await;
+    let text = response["result"]["messages"][0]["content"]["text"].as_str().unwrap();
+    assert!(text.contains("tasks with a due date"));
+    assert!(text.contains("category"));
+
+    let arguments = serde_json::json!({ "modelId": model.id });
+    let (_, response) = call(&state, None, "prompts/get", serde_json::json!({ "name": "fix_verification_errors", "arguments": arguments })).await;
+    let text = response["result"]["messages"][0]["content"]["text"].as_str().unwrap();
+    assert!(text.contains("Renamed"));
+
+    // Without resources there is nothing to keep per session
+    let mut config = Config::default();
+    config.database.url = "sqlite::memory:".to_string();
+    config.mcp.enable_resources = false;
+    let db = database::setup_database(&config).await.unwrap();
+    let state = server::AppState::new(Arc::new(ServiceRegistry::new(db, config).await.unwrap()));
+    let (headers, response) = call(&state, None, "initialize", serde_json::json!({})).await;
+    assert!(response["result"]["capabilities"].get("resources").is_none());
+    assert!(headers.get("mcp-session-id").is_none());
+}
+
+/// Events published by one node reach the subscribers of another node sharing the Postgres
+/// database, exactly once. Needs a database in DATABASE_URL, run with
+/// `cargo test --features postgres -- --ignored`.
+#[cfg(feature = "postgres")]
+#[tokio::test]
+#[ignore]
+async fn test_event_fanout_between_nodes() {
+    use torque::config::EventLogConfig;
+    use torque::model::events::ModelChangeEvent;
+    use torque::services::{broadcast::BroadcastService, event_fanout};
+
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must point at a Postgres database");
+    let mut config = Config::default();
+    config.database.url = database_url.clone();
+    let db = Arc::new(database::setup_database(&config).await.unwrap());
+
+    let first = Arc::new(BroadcastService::new(db.clone(), EventLogConfig::default()));
+    let second = Arc::new(BroadcastService::new(db, EventLogConfig::default()));
+    event_fanout::spawn_listener(first.clone(), database_url.clone());
+    event_fanout::spawn_listener(second.clone(), database_url);
+    let mut first_events = first.subscribe();
+    let mut second_events = second.subscribe();
+
+    // Give the listeners time to connect before publishing
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let model_id = torque::common::Uuid::new_v4();
+    first.broadcast_event(ModelChangeEvent::entity_added(model_id.clone(), torque::common::Uuid::new_v4())).await.unwrap();
+    let presence = ModelChangeEvent::presence_changed(model_id.clone(), vec![]);
+    first.broadcast_event(presence).await.unwrap();
+
+    // The logged event is read back from the shared log, the ephemeral one arrives inline
+    let logged = timeout(Duration::from_secs(5), second_events.recv()).await.unwrap().unwrap();
+    assert_eq!(logged.event.kind(), "EntityAdded");
+    assert_eq!(logged.event.model_id(), model_id);
+    assert!(logged.seq.is_some());
+    let inline = timeout(Duration::from_secs(5), second_events.recv()).await.unwrap().unwrap();
+    assert_eq!(inline.event.kind(), "PresenceChanged");
+
+    // The publishing node delivers its events once, ignoring its own notifications
+    assert_eq!(first_events.recv().await.unwrap().event.kind(), "EntityAdded");
+    assert_eq!(first_events.recv().await.unwrap().event.kind(), "PresenceChanged");
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(first_events.try_recv().is_err());
+}